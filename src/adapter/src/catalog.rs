@@ -49,7 +49,7 @@ use mz_pgrepr::oid::FIRST_USER_OID;
 use mz_repr::{explain::ExprHumanizer, Diff, GlobalId, RelationDesc, ScalarType};
 use mz_secrets::InMemorySecretsController;
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::Expr;
+use mz_sql::ast::{Expr, Ident};
 use mz_sql::catalog::{
     CatalogCluster, CatalogDatabase, CatalogError as SqlCatalogError,
     CatalogItem as SqlCatalogItem, CatalogItemType as SqlCatalogItemType, CatalogItemType,
@@ -63,14 +63,15 @@ use mz_sql::names::{
 };
 use mz_sql::plan::{
     CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
-    Plan, PlanContext, SourceSinkClusterConfig as PlanStorageClusterConfig, StatementDesc,
+    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
+    ManagedClusterConfig, Params, Plan, PlanContext,
+    SourceSinkClusterConfig as PlanStorageClusterConfig, StatementDesc,
 };
 use mz_sql::{plan, DEFAULT_SCHEMA};
 use mz_sql_parser::ast::{CreateSinkOption, CreateSourceOption, Statement, WithOptionValue};
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_stash::{Stash, StashFactory};
-use mz_storage_client::controller::IntrospectionType;
+use mz_storage_client::controller::{CollectionDescription, CollectionLayout, IntrospectionType};
 use mz_storage_client::types::parameters::StorageParameters;
 use mz_storage_client::types::sinks::{
     SinkEnvelope, StorageSinkConnection, StorageSinkConnectionBuilder,
@@ -688,6 +689,7 @@ impl CatalogState {
         id: ClusterId,
         name: String,
         linked_object_id: Option<GlobalId>,
+        managed: Option<ManagedClusterConfig>,
         introspection_source_indexes: Vec<(&'static BuiltinLog, GlobalId)>,
     ) {
         let mut log_indexes = BTreeMap::new();
@@ -750,6 +752,7 @@ impl CatalogState {
                 name: name.clone(),
                 id,
                 linked_object_id,
+                managed,
                 bound_objects: BTreeSet::new(),
                 log_indexes,
                 replica_id_by_name: BTreeMap::new(),
@@ -1269,6 +1272,166 @@ impl CatalogState {
         tx.insert_storage_usage_event(details);
         Ok(())
     }
+
+    fn for_sessionless_user(&self, user: User) -> ConnCatalog {
+        ConnCatalog {
+            state: Cow::Borrowed(self),
+            conn_id: SYSTEM_CONN_ID,
+            cluster: "default".into(),
+            database: self
+                .resolve_database(DEFAULT_DATABASE_NAME)
+                .ok()
+                .map(|db| db.id()),
+            search_path: Vec::new(),
+            user,
+            prepared_statements: None,
+        }
+    }
+
+    fn for_system_session(&self) -> ConnCatalog {
+        self.for_sessionless_user(SYSTEM_USER.clone())
+    }
+
+    // Parses the given SQL string into a `CatalogItem`.
+    #[tracing::instrument(level = "info", skip(self, pcx))]
+    fn parse_item(
+        &self,
+        id: GlobalId,
+        create_sql: String,
+        pcx: Option<&PlanContext>,
+    ) -> Result<CatalogItem, AdapterError> {
+        let session_catalog = self.for_system_session();
+        let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
+        let (stmt, depends_on) = mz_sql::names::resolve(&session_catalog, stmt)?;
+        let depends_on = depends_on.into_iter().collect();
+        let plan = mz_sql::plan::plan(pcx, &session_catalog, stmt, &Params::empty())?;
+        Ok(match plan {
+            Plan::CreateTable(CreateTablePlan { table, .. }) => CatalogItem::Table(Table {
+                create_sql: table.create_sql,
+                desc: table.desc,
+                storage_desc: table.storage_desc,
+                layout: Some(table.layout),
+                defaults: table.defaults,
+                constraints: table.constraints,
+                expire_rows_after: table.expire_rows_after,
+                conn_id: None,
+                depends_on,
+                custom_logical_compaction_window: None,
+                is_retained_metrics_relation: false,
+            }),
+            Plan::CreateSource(CreateSourcePlan {
+                source,
+                timeline,
+                cluster_config,
+                ..
+            }) => CatalogItem::Source(Source {
+                create_sql: source.create_sql,
+                data_source: match source.data_source {
+                    mz_sql::plan::DataSourceDesc::Ingestion(ingestion) => {
+                        DataSourceDesc::Ingestion(Ingestion {
+                            desc: ingestion.desc,
+                            source_imports: ingestion.source_imports,
+                            subsource_exports: ingestion.subsource_exports,
+                            cluster_id: match cluster_config {
+                                plan::SourceSinkClusterConfig::Existing { id } => id,
+                                plan::SourceSinkClusterConfig::Linked { .. }
+                                | plan::SourceSinkClusterConfig::Undefined => {
+                                    self.clusters_by_linked_object_id[&id]
+                                }
+                            },
+                        })
+                    }
+                    mz_sql::plan::DataSourceDesc::Progress => {
+                        unreachable!("progress subsources error in purification")
+                    }
+                    mz_sql::plan::DataSourceDesc::Source => DataSourceDesc::Source,
+                },
+                desc: source.desc,
+                timeline,
+                depends_on,
+                custom_logical_compaction_window: None,
+                is_retained_metrics_relation: false,
+            }),
+            Plan::CreateView(CreateViewPlan { view, .. }) => {
+                let optimizer = Optimizer::logical_optimizer();
+                let optimized_expr = optimizer.optimize(view.expr)?;
+                let desc = RelationDesc::new(optimized_expr.typ(), view.column_names);
+                CatalogItem::View(View {
+                    create_sql: view.create_sql,
+                    optimized_expr,
+                    desc,
+                    conn_id: None,
+                    depends_on,
+                })
+            }
+            Plan::CreateMaterializedView(CreateMaterializedViewPlan {
+                materialized_view, ..
+            }) => {
+                let optimizer = Optimizer::logical_optimizer();
+                let optimized_expr = optimizer.optimize(materialized_view.expr)?;
+                let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
+                CatalogItem::MaterializedView(MaterializedView {
+                    create_sql: materialized_view.create_sql,
+                    optimized_expr,
+                    desc,
+                    depends_on,
+                    cluster_id: materialized_view.cluster_id,
+                })
+            }
+            Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
+                create_sql: index.create_sql,
+                on: index.on,
+                keys: index.keys,
+                conn_id: None,
+                depends_on,
+                cluster_id: index.cluster_id,
+            }),
+            Plan::CreateSink(CreateSinkPlan {
+                sink,
+                with_snapshot,
+                cluster_config,
+                ..
+            }) => CatalogItem::Sink(Sink {
+                create_sql: sink.create_sql,
+                from: sink.from,
+                connection: StorageSinkConnectionState::Pending(sink.connection_builder),
+                envelope: sink.envelope,
+                with_snapshot,
+                depends_on,
+                cluster_id: match cluster_config {
+                    plan::SourceSinkClusterConfig::Existing { id } => id,
+                    plan::SourceSinkClusterConfig::Linked { .. }
+                    | plan::SourceSinkClusterConfig::Undefined => {
+                        self.clusters_by_linked_object_id[&id]
+                    }
+                },
+            }),
+            Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
+                create_sql: typ.create_sql,
+                details: CatalogTypeDetails {
+                    array_id: None,
+                    typ: typ.inner,
+                },
+                depends_on,
+            }),
+            Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
+                create_sql: secret.create_sql,
+            }),
+            Plan::CreateConnection(CreateConnectionPlan { connection, .. }) => {
+                CatalogItem::Connection(Connection {
+                    create_sql: connection.create_sql,
+                    connection: connection.connection,
+                    depends_on,
+                })
+            }
+            _ => {
+                return Err(Error::new(ErrorKind::Corruption {
+                    detail: "catalog entry generated inappropriate plan".to_string(),
+                })
+                .into())
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -1384,6 +1547,9 @@ pub struct Cluster {
     pub id: ClusterId,
     pub log_indexes: BTreeMap<LogVariant, GlobalId>,
     pub linked_object_id: Option<GlobalId>,
+    /// The configuration of the cluster, if its replicas are managed by
+    /// Materialize.
+    pub managed: Option<ManagedClusterConfig>,
     /// Objects bound to this cluster. Does not include introspection source
     /// indexes.
     pub bound_objects: BTreeSet<GlobalId>,
@@ -1459,8 +1625,21 @@ pub enum CatalogItem {
 pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
+    /// Describes the rows stored for the table, which retain the columns
+    /// removed by `ALTER TABLE ... DROP COLUMN`.
+    pub storage_desc: RelationDesc,
+    /// How rows described by `storage_desc` map to rows described by `desc`,
+    /// for tables whose columns can be altered.
+    pub layout: Option<CollectionLayout>,
     #[serde(skip)]
     pub defaults: Vec<Expr<Aug>>,
+    /// The constraints enforced on writes to the table.
+    #[serde(skip)]
+    pub constraints: Vec<plan::Constraint>,
+    /// Computes the time at which each row expires, for tables created with
+    /// `EXPIRE ROWS AFTER`.
+    #[serde(skip)]
+    pub expire_rows_after: Option<MirScalarExpr>,
     pub conn_id: Option<ConnectionId>,
     pub depends_on: Vec<GlobalId>,
     pub custom_logical_compaction_window: Option<Duration>,
@@ -1475,6 +1654,14 @@ impl Table {
     pub fn timeline(&self) -> Timeline {
         Timeline::EpochMilliseconds
     }
+
+    /// Describes the storage collection that holds the table's rows.
+    pub fn collection_description(&self) -> CollectionDescription<mz_repr::Timestamp> {
+        CollectionDescription {
+            layout: self.layout.clone(),
+            ..self.storage_desc.clone().into()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1613,6 +1800,8 @@ impl Sink {
         match &self.envelope {
             SinkEnvelope::Debezium => Some("debezium"),
             SinkEnvelope::Upsert => Some("upsert"),
+            SinkEnvelope::None => Some("none"),
+            SinkEnvelope::CdcV2 => Some("materialize"),
         }
     }
 
@@ -2371,7 +2560,11 @@ impl Catalog {
                             CatalogItem::Table(Table {
                                 create_sql: CREATE_SQL_TODO.to_string(),
                                 desc: table.desc.clone(),
+                                storage_desc: table.desc.clone(),
+                                layout: None,
                                 defaults: vec![Expr::null(); table.desc.arity()],
+                                constraints: vec![],
+                                expire_rows_after: None,
                                 conn_id: None,
                                 depends_on: vec![],
                                 custom_logical_compaction_window: table
@@ -2447,7 +2640,7 @@ impl Catalog {
         }
 
         let clusters = catalog.storage().await.load_clusters().await?;
-        for (id, name, linked_object_id) in clusters {
+        for (id, name, linked_object_id, managed) in clusters {
             let introspection_source_index_gids = catalog
                 .storage()
                 .await
@@ -2481,7 +2674,7 @@ impl Catalog {
 
             catalog
                 .state
-                .insert_cluster(id, name, linked_object_id, all_indexes);
+                .insert_cluster(id, name, linked_object_id, managed, all_indexes);
         }
 
         let replicas = catalog.storage().await.load_cluster_replicas().await?;
@@ -3412,24 +3605,13 @@ impl Catalog {
     }
 
     pub fn for_sessionless_user(&self, user: User) -> ConnCatalog {
-        ConnCatalog {
-            state: Cow::Borrowed(&self.state),
-            conn_id: SYSTEM_CONN_ID,
-            cluster: "default".into(),
-            database: self
-                .resolve_database(DEFAULT_DATABASE_NAME)
-                .ok()
-                .map(|db| db.id()),
-            search_path: Vec::new(),
-            user,
-            prepared_statements: None,
-        }
+        self.state.for_sessionless_user(user)
     }
 
     // Leaving the system's search path empty allows us to catch issues
     // where catalog object names have not been normalized correctly.
     pub fn for_system_session(&self) -> ConnCatalog {
-        self.for_sessionless_user(SYSTEM_USER.clone())
+        self.state.for_system_session()
     }
 
     async fn storage<'a>(&'a self) -> MutexGuard<'a, storage::Connection> {
@@ -3995,35 +4177,11 @@ impl Catalog {
                 availability_zone,
                 az_user_specified,
             } => {
-                let cluster_replica_sizes = &self.state.cluster_replica_sizes;
-
-                if !cluster_replica_sizes.0.contains_key(&size)
-                    || (!allowed_sizes.is_empty() && !allowed_sizes.contains(&size))
-                {
-                    let mut entries = cluster_replica_sizes.0.iter().collect::<Vec<_>>();
-
-                    if !allowed_sizes.is_empty() {
-                        let allowed_sizes = BTreeSet::<&String>::from_iter(allowed_sizes.iter());
-                        entries.retain(|(name, _)| allowed_sizes.contains(name));
-                    }
-
-                    entries.sort_by_key(
-                        |(
-                            _name,
-                            ReplicaAllocation {
-                                scale, cpu_limit, ..
-                            },
-                        )| (scale, cpu_limit),
-                    );
-
-                    return Err(AdapterError::InvalidClusterReplicaSize {
-                        size,
-                        expected: entries.into_iter().map(|(name, _)| name.clone()).collect(),
-                    });
-                }
-
+                self.ensure_valid_replica_size(&size, allowed_sizes)?;
                 ReplicaLocation::Managed(ManagedReplicaLocation {
-                    allocation: cluster_replica_sizes
+                    allocation: self
+                        .state
+                        .cluster_replica_sizes
                         .0
                         .get(&size)
                         .expect("catalog out of sync")
@@ -4037,6 +4195,42 @@ impl Catalog {
         Ok(location)
     }
 
+    /// Returns an error if `size` is not a known cluster replica size, or is
+    /// not among `allowed_sizes` when any are specified.
+    pub fn ensure_valid_replica_size(
+        &self,
+        size: &String,
+        allowed_sizes: &Vec<String>,
+    ) -> Result<(), AdapterError> {
+        let cluster_replica_sizes = &self.state.cluster_replica_sizes;
+
+        if !cluster_replica_sizes.0.contains_key(size)
+            || (!allowed_sizes.is_empty() && !allowed_sizes.contains(size))
+        {
+            let mut entries = cluster_replica_sizes.0.iter().collect::<Vec<_>>();
+
+            if !allowed_sizes.is_empty() {
+                let allowed_sizes = BTreeSet::<&String>::from_iter(allowed_sizes.iter());
+                entries.retain(|(name, _)| allowed_sizes.contains(name));
+            }
+
+            entries.sort_by_key(
+                |(
+                    _name,
+                    ReplicaAllocation {
+                        scale, cpu_limit, ..
+                    },
+                )| (scale, cpu_limit),
+            );
+
+            return Err(AdapterError::InvalidClusterReplicaSize {
+                size: size.clone(),
+                expected: entries.into_iter().map(|(name, _)| name.clone()).collect(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn cluster_replica_sizes(&self) -> &ClusterReplicaSizeMap {
         &self.state.cluster_replica_sizes
     }
@@ -4144,6 +4338,7 @@ impl Catalog {
                 id: ClusterId,
                 name: String,
                 linked_object_id: Option<GlobalId>,
+                managed: Option<ManagedClusterConfig>,
                 arranged_introspection_sources: Vec<(&'static BuiltinLog, GlobalId)>,
             },
             CreateClusterReplica {
@@ -4181,9 +4376,18 @@ impl Catalog {
                 to_name: QualifiedObjectName,
                 to_item: CatalogItem,
             },
+            /// Like `UpdateItem`, but allows the item's dependencies to change.
+            ReplaceItemDefinition {
+                id: GlobalId,
+                to_item: CatalogItem,
+            },
             UpdateClusterReplicaStatus {
                 event: ClusterEvent,
             },
+            UpdateManagedCluster {
+                id: ClusterId,
+                config: ManagedClusterConfig,
+            },
             UpdateSystemConfiguration {
                 name: String,
                 value: String,
@@ -4215,6 +4419,28 @@ impl Catalog {
 
         for op in ops {
             match op {
+                Op::AlterCluster { id, config } => {
+                    let name = state.get_cluster(id).name.clone();
+                    tx.update_managed_cluster(id, config.clone())?;
+                    state.add_to_audit_log(
+                        oracle_write_ts,
+                        session,
+                        tx,
+                        builtin_table_updates,
+                        audit_events,
+                        EventType::Alter,
+                        ObjectType::Cluster,
+                        EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
+                            id: id.to_string(),
+                            name,
+                        }),
+                    )?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateManagedCluster { id, config },
+                    )?;
+                }
                 Op::AlterSink { id, cluster_config } => {
                     use mz_sql::ast::Value;
                     use mz_sql_parser::ast::CreateSinkOptionName::*;
@@ -4421,6 +4647,82 @@ impl Catalog {
                         },
                     )?;
                 }
+                Op::AlterTable { id, table } => {
+                    let entry = state.get_entry(&id);
+                    let name = entry.name().clone();
+
+                    let ser = Self::serialize_item(&table);
+                    tx.update_item(id, &name.item, &ser)?;
+
+                    // NB: this will be re-incremented by the action below.
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
+
+                    state.add_to_audit_log(
+                        oracle_write_ts,
+                        session,
+                        tx,
+                        builtin_table_updates,
+                        audit_events,
+                        EventType::Alter,
+                        ObjectType::Table,
+                        EventDetails::IdFullNameV1(IdFullNameV1 {
+                            id: id.to_string(),
+                            name: Self::full_name_detail(&state.resolve_full_name(
+                                &name,
+                                session.map(|session| session.conn_id()),
+                            )),
+                        }),
+                    )?;
+
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateItem {
+                            id,
+                            to_name: name,
+                            to_item: table,
+                        },
+                    )?;
+                }
+                Op::AlterMaterializedView {
+                    id,
+                    materialized_view,
+                } => {
+                    let entry = state.get_entry(&id);
+                    let name = entry.name().clone();
+
+                    let ser = Self::serialize_item(&materialized_view);
+                    tx.update_item(id, &name.item, &ser)?;
+
+                    // NB: this will be re-incremented by the action below.
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
+
+                    state.add_to_audit_log(
+                        oracle_write_ts,
+                        session,
+                        tx,
+                        builtin_table_updates,
+                        audit_events,
+                        EventType::Alter,
+                        ObjectType::MaterializedView,
+                        EventDetails::IdFullNameV1(IdFullNameV1 {
+                            id: id.to_string(),
+                            name: Self::full_name_detail(&state.resolve_full_name(
+                                &name,
+                                session.map(|session| session.conn_id()),
+                            )),
+                        }),
+                    )?;
+
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::ReplaceItemDefinition {
+                            id,
+                            to_item: materialized_view,
+                        },
+                    )?;
+                }
                 Op::CreateDatabase {
                     name,
                     oid,
@@ -4553,6 +4855,7 @@ impl Catalog {
                     id,
                     name,
                     linked_object_id,
+                    managed,
                     arranged_introspection_sources,
                 } => {
                     if is_reserved_name(&name) {
@@ -4564,6 +4867,7 @@ impl Catalog {
                         id,
                         &name,
                         linked_object_id,
+                        managed.clone(),
                         &arranged_introspection_sources,
                     )?;
                     state.add_to_audit_log(
@@ -4586,6 +4890,7 @@ impl Catalog {
                             id,
                             name,
                             linked_object_id,
+                            managed,
                             arranged_introspection_sources,
                         },
                     )?;
@@ -5264,6 +5569,7 @@ impl Catalog {
                     id,
                     name,
                     linked_object_id,
+                    managed,
                     arranged_introspection_sources,
                 } => {
                     info!("create cluster {}", name);
@@ -5276,6 +5582,7 @@ impl Catalog {
                         id,
                         name.clone(),
                         linked_object_id,
+                        managed,
                         arranged_introspection_sources,
                     );
                     builtin_table_updates.push(state.pack_cluster_update(&name, 1));
@@ -5436,6 +5743,34 @@ impl Catalog {
                     builtin_table_updates.extend(state.pack_item_update(id, 1));
                 }
 
+                Action::ReplaceItemDefinition { id, to_item } => {
+                    let old_entry = state.entry_by_id.remove(&id).expect("catalog out of sync");
+                    info!(
+                        "replace definition of {} {} ({})",
+                        old_entry.item_type(),
+                        state.resolve_full_name(&old_entry.name, old_entry.conn_id()),
+                        id
+                    );
+                    for u in old_entry.uses() {
+                        if let Some(dep_metadata) = state.entry_by_id.get_mut(u) {
+                            dep_metadata.used_by.retain(|u| *u != id)
+                        }
+                    }
+                    for u in to_item.uses() {
+                        match state.entry_by_id.get_mut(u) {
+                            Some(metadata) => metadata.used_by.push(id),
+                            None => panic!(
+                                "Catalog: missing dependent catalog item {} while replacing {}",
+                                &u, id
+                            ),
+                        }
+                    }
+                    let mut new_entry = old_entry;
+                    new_entry.item = to_item;
+                    state.entry_by_id.insert(id, new_entry);
+                    builtin_table_updates.extend(state.pack_item_update(id, 1));
+                }
+
                 Action::UpdateClusterReplicaStatus { event } => {
                     builtin_table_updates.push(state.pack_cluster_replica_status_update(
                         event.cluster_id,
@@ -5459,6 +5794,14 @@ impl Catalog {
                         1,
                     ));
                 }
+                Action::UpdateManagedCluster { id, config } => {
+                    let cluster = state
+                        .clusters_by_id
+                        .get_mut(&id)
+                        .expect("catalog out of sync");
+                    info!("update cluster {} to {:?}", cluster.name, config);
+                    cluster.managed = Some(config);
+                }
                 Action::UpdateSystemConfiguration { name, value } => {
                     state.insert_system_configuration(&name, &value)?;
                 }
@@ -5547,140 +5890,122 @@ impl Catalog {
     }
 
     // Parses the given SQL string into a `CatalogItem`.
-    #[tracing::instrument(level = "info", skip(self, pcx))]
     fn parse_item(
         &self,
         id: GlobalId,
         create_sql: String,
         pcx: Option<&PlanContext>,
     ) -> Result<CatalogItem, AdapterError> {
-        let session_catalog = self.for_system_session();
-        let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
-        let (stmt, depends_on) = mz_sql::names::resolve(&session_catalog, stmt)?;
-        let depends_on = depends_on.into_iter().collect();
-        let plan = mz_sql::plan::plan(pcx, &session_catalog, stmt, &Params::empty())?;
-        Ok(match plan {
-            Plan::CreateTable(CreateTablePlan { table, .. }) => CatalogItem::Table(Table {
-                create_sql: table.create_sql,
-                desc: table.desc,
-                defaults: table.defaults,
-                conn_id: None,
-                depends_on,
-                custom_logical_compaction_window: None,
-                is_retained_metrics_relation: false,
-            }),
-            Plan::CreateSource(CreateSourcePlan {
-                source,
-                timeline,
-                cluster_config,
-                ..
-            }) => CatalogItem::Source(Source {
-                create_sql: source.create_sql,
-                data_source: match source.data_source {
-                    mz_sql::plan::DataSourceDesc::Ingestion(ingestion) => {
-                        DataSourceDesc::Ingestion(Ingestion {
-                            desc: ingestion.desc,
-                            source_imports: ingestion.source_imports,
-                            subsource_exports: ingestion.subsource_exports,
-                            cluster_id: match cluster_config {
-                                plan::SourceSinkClusterConfig::Existing { id } => id,
-                                plan::SourceSinkClusterConfig::Linked { .. }
-                                | plan::SourceSinkClusterConfig::Undefined => {
-                                    self.state.clusters_by_linked_object_id[&id]
-                                }
-                            },
-                        })
-                    }
-                    mz_sql::plan::DataSourceDesc::Progress => {
-                        unreachable!("progress subsources error in purification")
-                    }
-                    mz_sql::plan::DataSourceDesc::Source => DataSourceDesc::Source,
-                },
-                desc: source.desc,
-                timeline,
-                depends_on,
-                custom_logical_compaction_window: None,
-                is_retained_metrics_relation: false,
-            }),
-            Plan::CreateView(CreateViewPlan { view, .. }) => {
-                let optimizer = Optimizer::logical_optimizer();
-                let optimized_expr = optimizer.optimize(view.expr)?;
-                let desc = RelationDesc::new(optimized_expr.typ(), view.column_names);
-                CatalogItem::View(View {
-                    create_sql: view.create_sql,
-                    optimized_expr,
-                    desc,
-                    conn_id: None,
-                    depends_on,
-                })
-            }
-            Plan::CreateMaterializedView(CreateMaterializedViewPlan {
-                materialized_view, ..
-            }) => {
-                let optimizer = Optimizer::logical_optimizer();
-                let optimized_expr = optimizer.optimize(materialized_view.expr)?;
-                let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
-                CatalogItem::MaterializedView(MaterializedView {
-                    create_sql: materialized_view.create_sql,
-                    optimized_expr,
-                    desc,
-                    depends_on,
-                    cluster_id: materialized_view.cluster_id,
-                })
+        self.state.parse_item(id, create_sql, pcx)
+    }
+
+    /// Plans the new definitions of the table `id` and of the items that
+    /// directly depend on it after an `ALTER TABLE` that changes the table's
+    /// definition to `create_sql`.
+    ///
+    /// The rows already stored for the table are reinterpreted rather than
+    /// rewritten, and the table's dependents must continue to produce the same
+    /// columns. Views and materialized views whose definitions would pick up
+    /// added columns, e.g. through `SELECT *`, have their references to the
+    /// table pinned to its previous columns. Indexes on the table are planned
+    /// anew, as the positions of the columns they reference can change.
+    pub fn plan_altered_table(
+        &self,
+        id: GlobalId,
+        create_sql: String,
+    ) -> Result<(CatalogItem, Vec<(GlobalId, CatalogItem)>), AdapterError> {
+        let entry = self.get_entry(&id);
+        let table_name = self.resolve_full_name(entry.name(), entry.conn_id());
+        let old_table = match entry.item() {
+            CatalogItem::Table(table) => table,
+            other => coord_bail!("ALTER TABLE entry was not a table: {}", other.typ()),
+        };
+        let mut table = match self.state.parse_item(id, create_sql, None)? {
+            CatalogItem::Table(table) => table,
+            _ => unreachable!("tables are created by CREATE TABLE"),
+        };
+        if table.depends_on != old_table.depends_on {
+            return Err(AdapterError::Unsupported(
+                "ALTER TABLE ADD COLUMN with a user-defined type",
+            ));
+        }
+        table.conn_id = old_table.conn_id;
+        table.custom_logical_compaction_window = old_table.custom_logical_compaction_window;
+        table.is_retained_metrics_relation = old_table.is_retained_metrics_relation;
+        let table = CatalogItem::Table(table);
+
+        // Plan the dependents against a catalog that reflects the table's new
+        // definition.
+        let mut state = self.state.clone();
+        state.entry_by_id.get_mut(&id).expect("table exists").item = table.clone();
+        let old_columns: Vec<_> = old_table
+            .desc
+            .iter_names()
+            .map(|name| Ident::new(name.as_str()))
+            .collect();
+
+        let mut dependents = vec![];
+        for dependent_id in entry.used_by() {
+            let dependent = self.get_entry(dependent_id);
+            let dependency_err = |cause: String| {
+                AdapterError::Catalog(Error::new(ErrorKind::AlterTableDependency {
+                    table: table_name.to_string(),
+                    depender: self
+                        .resolve_full_name(dependent.name(), dependent.conn_id())
+                        .to_string(),
+                    cause,
+                }))
+            };
+            if dependent.conn_id().is_some() {
+                return Err(dependency_err("the dependent is temporary".into()));
             }
-            Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
-                create_sql: index.create_sql,
-                on: index.on,
-                keys: index.keys,
-                conn_id: None,
-                depends_on,
-                cluster_id: index.cluster_id,
-            }),
-            Plan::CreateSink(CreateSinkPlan {
-                sink,
-                with_snapshot,
-                cluster_config,
-                ..
-            }) => CatalogItem::Sink(Sink {
-                create_sql: sink.create_sql,
-                from: sink.from,
-                connection: StorageSinkConnectionState::Pending(sink.connection_builder),
-                envelope: sink.envelope,
-                with_snapshot,
-                depends_on,
-                cluster_id: match cluster_config {
-                    plan::SourceSinkClusterConfig::Existing { id } => id,
-                    plan::SourceSinkClusterConfig::Linked { .. }
-                    | plan::SourceSinkClusterConfig::Undefined => {
-                        self.state.clusters_by_linked_object_id[&id]
+            let create_sql = dependent.create_sql().to_string();
+            let item = match dependent.item() {
+                CatalogItem::Index(_) => state
+                    .parse_item(*dependent_id, create_sql, None)
+                    .map_err(|e| dependency_err(e.to_string()))?,
+                CatalogItem::View(View { desc, .. })
+                | CatalogItem::MaterializedView(MaterializedView { desc, .. }) => {
+                    let same_columns = |item: &CatalogItem| {
+                        let new_desc = match item {
+                            CatalogItem::View(view) => &view.desc,
+                            CatalogItem::MaterializedView(mview) => &mview.desc,
+                            _ => return false,
+                        };
+                        new_desc.iter_names().eq(desc.iter_names())
+                            && new_desc.typ().column_types == desc.typ().column_types
+                    };
+                    match state.parse_item(*dependent_id, create_sql.clone(), None) {
+                        Ok(item) if same_columns(&item) => item,
+                        _ => {
+                            let mut stmt = mz_sql::parse::parse(&create_sql)?.into_element();
+                            mz_sql::ast::transform::create_stmt_pin_table_columns(
+                                &mut stmt,
+                                id,
+                                &old_columns,
+                            );
+                            let item = state
+                                .parse_item(*dependent_id, stmt.to_ast_string_stable(), None)
+                                .map_err(|e| dependency_err(e.to_string()))?;
+                            if !same_columns(&item) {
+                                return Err(dependency_err(
+                                    "the columns of the dependent would change".into(),
+                                ));
+                            }
+                            item
+                        }
                     }
-                },
-            }),
-            Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
-                create_sql: typ.create_sql,
-                details: CatalogTypeDetails {
-                    array_id: None,
-                    typ: typ.inner,
-                },
-                depends_on,
-            }),
-            Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
-                create_sql: secret.create_sql,
-            }),
-            Plan::CreateConnection(CreateConnectionPlan { connection, .. }) => {
-                CatalogItem::Connection(Connection {
-                    create_sql: connection.create_sql,
-                    connection: connection.connection,
-                    depends_on,
-                })
-            }
-            _ => {
-                return Err(Error::new(ErrorKind::Corruption {
-                    detail: "catalog entry generated inappropriate plan".to_string(),
-                })
-                .into())
-            }
-        })
+                }
+                other => {
+                    return Err(dependency_err(format!(
+                        "{}s cannot depend on altered tables",
+                        other.typ()
+                    )))
+                }
+            };
+            dependents.push((*dependent_id, item));
+        }
+        Ok((table, dependents))
     }
 
     pub fn uses_tables(&self, id: GlobalId) -> bool {
@@ -5857,6 +6182,9 @@ impl Catalog {
         let config = self.system_config();
         ComputeParameters {
             max_result_size: Some(config.max_result_size()),
+            max_dataflow_arrangement_records: Some(u64::cast_from(
+                config.max_dataflow_arrangement_records(),
+            )),
             persist: self.persist_config(),
         }
     }
@@ -5873,6 +6201,8 @@ impl Catalog {
         PersistParameters {
             blob_target_size: Some(config.persist_blob_target_size()),
             compaction_minimum_timeout: Some(config.persist_compaction_minimum_timeout()),
+            stats_collection_enabled: Some(config.persist_stats_collection_enabled()),
+            stats_filter_enabled: Some(config.persist_stats_filter_enabled()),
         }
     }
 }
@@ -5885,6 +6215,11 @@ pub fn is_reserved_name(name: &str) -> bool {
 
 #[derive(Debug, Clone)]
 pub enum Op {
+    /// Replaces the configuration of the managed cluster `id` with `config`.
+    AlterCluster {
+        id: ClusterId,
+        config: ManagedClusterConfig,
+    },
     AlterSink {
         id: GlobalId,
         cluster_config: plan::SourceSinkClusterConfig,
@@ -5893,6 +6228,18 @@ pub enum Op {
         id: GlobalId,
         cluster_config: plan::SourceSinkClusterConfig,
     },
+    /// Replaces the definition of the table `id` with `table`, as planned by
+    /// [`Catalog::plan_altered_table`].
+    AlterTable {
+        id: GlobalId,
+        table: CatalogItem,
+    },
+    /// Replaces the definition of the materialized view `id` with
+    /// `materialized_view`, which may depend on different objects.
+    AlterMaterializedView {
+        id: GlobalId,
+        materialized_view: CatalogItem,
+    },
     CreateDatabase {
         name: String,
         oid: u32,
@@ -5911,6 +6258,7 @@ pub enum Op {
         id: ClusterId,
         name: String,
         linked_object_id: Option<GlobalId>,
+        managed: Option<ManagedClusterConfig>,
         arranged_introspection_sources: Vec<(&'static BuiltinLog, GlobalId)>,
     },
     CreateClusterReplica {
@@ -6226,6 +6574,10 @@ impl SessionCatalog for ConnCatalog<'_> {
         &self.user.name
     }
 
+    fn is_planning_stored_definitions(&self) -> bool {
+        self.conn_id == SYSTEM_CONN_ID
+    }
+
     fn get_prepared_statement_desc(&self, name: &str) -> Option<&StatementDesc> {
         self.prepared_statements
             .as_ref()
@@ -6433,6 +6785,10 @@ impl mz_sql::catalog::CatalogCluster<'_> for Cluster {
     fn replicas(&self) -> &BTreeMap<String, ReplicaId> {
         &self.replica_id_by_name
     }
+
+    fn managed(&self) -> Option<&ManagedClusterConfig> {
+        self.managed.as_ref()
+    }
 }
 
 impl mz_sql::catalog::CatalogItem for CatalogEntry {
@@ -6494,6 +6850,10 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn cluster_id(&self) -> Option<ClusterId> {
+        self.item().cluster_id()
+    }
+
     fn table_details(&self) -> Option<&[Expr<Aug>]> {
         if let CatalogItem::Table(Table { defaults, .. }) = self.item() {
             Some(defaults)
@@ -6841,7 +7201,12 @@ mod tests {
                         desc: RelationDesc::empty()
                             .with_column("a", ScalarType::Int32.nullable(true))
                             .with_key(vec![0]),
+                        storage_desc: RelationDesc::empty()
+                            .with_column("a", ScalarType::Int32.nullable(true)),
+                        layout: None,
                         defaults: vec![Expr::null(); 1],
+                        constraints: vec![],
+                        expire_rows_after: None,
                         conn_id: None,
                         depends_on: vec![],
                         custom_logical_compaction_window: None,
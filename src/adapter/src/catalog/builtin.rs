@@ -1222,6 +1222,24 @@ pub const MZ_RAW_PEEK_DURATIONS: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::PeekDuration),
 };
 
+pub const MZ_LIR_MAPPING: BuiltinLog = BuiltinLog {
+    name: "mz_lir_mapping",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::LirMapping),
+};
+
+pub const MZ_ARRANGEMENT_SPILL: BuiltinLog = BuiltinLog {
+    name: "mz_arrangement_spill",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::ArrangementSpill),
+};
+
+pub const MZ_COMPUTE_EXPORT_ERRORS: BuiltinLog = BuiltinLog {
+    name: "mz_compute_export_errors",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::ExportErrors),
+};
+
 pub const MZ_MESSAGE_COUNTS_RECEIVED_INTERNAL: BuiltinLog = BuiltinLog {
     name: "mz_message_counts_received_internal",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1730,6 +1748,7 @@ pub static MZ_SOURCE_STATISTICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSourc
     data_source: Some(IntrospectionType::StorageSourceStatistics),
     desc: RelationDesc::empty()
         .with_column("id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::UInt64.nullable(false))
         .with_column("worker_id", ScalarType::UInt64.nullable(false))
         .with_column("snapshot_committed", ScalarType::Bool.nullable(false))
         .with_column("messages_received", ScalarType::UInt64.nullable(false))
@@ -1744,6 +1763,7 @@ pub static MZ_SINK_STATISTICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource
     data_source: Some(IntrospectionType::StorageSinkStatistics),
     desc: RelationDesc::empty()
         .with_column("id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::UInt64.nullable(false))
         .with_column("worker_id", ScalarType::UInt64.nullable(false))
         .with_column("messages_staged", ScalarType::UInt64.nullable(false))
         .with_column("messages_committed", ScalarType::UInt64.nullable(false))
@@ -1762,6 +1782,31 @@ pub static MZ_STORAGE_SHARDS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     is_retained_metrics_relation: false,
 });
 
+pub static MZ_STATEMENT_EXECUTION_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_statement_execution_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::StatementExecutionHistory),
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::Uuid.nullable(false))
+        .with_column("connection_id", ScalarType::UInt32.nullable(false))
+        .with_column("user", ScalarType::String.nullable(false))
+        .with_column("application_name", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("cluster_name", ScalarType::String.nullable(true))
+        .with_column("sql_hash", ScalarType::Bytes.nullable(false))
+        .with_column("sample_rate", ScalarType::Float64.nullable(false))
+        .with_column(
+            "execution_timestamp",
+            ScalarType::MzTimestamp.nullable(true),
+        )
+        .with_column("began_at", ScalarType::TimestampTz.nullable(false))
+        .with_column("finished_at", ScalarType::TimestampTz.nullable(false))
+        .with_column("finished_status", ScalarType::String.nullable(false))
+        .with_column("error_message", ScalarType::String.nullable(true))
+        .with_column("rows_returned", ScalarType::Int64.nullable(true)),
+    is_retained_metrics_relation: false,
+});
+
 pub static MZ_STORAGE_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_storage_usage",
     schema: MZ_CATALOG_SCHEMA,
@@ -2963,6 +3008,9 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_WORKER_COMPUTE_FRONTIERS),
         Builtin::Log(&MZ_WORKER_COMPUTE_IMPORT_FRONTIERS),
         Builtin::Log(&MZ_RAW_WORKER_COMPUTE_DELAYS),
+        Builtin::Log(&MZ_LIR_MAPPING),
+        Builtin::Log(&MZ_ARRANGEMENT_SPILL),
+        Builtin::Log(&MZ_COMPUTE_EXPORT_ERRORS),
         Builtin::Table(&MZ_VIEW_KEYS),
         Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
         Builtin::Table(&MZ_KAFKA_SINKS),
@@ -3056,6 +3104,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::Source(&MZ_SOURCE_STATISTICS),
         Builtin::Source(&MZ_SINK_STATISTICS),
+        Builtin::Source(&MZ_STATEMENT_EXECUTION_HISTORY),
         Builtin::View(&MZ_STORAGE_USAGE),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
         Builtin::Index(&MZ_SHOW_SCHEMAS_IND),
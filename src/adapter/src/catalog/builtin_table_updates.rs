@@ -966,6 +966,7 @@ impl CatalogState {
                         cpu_limit,
                         scale,
                         workers,
+                        arrangement_memory_limit: _,
                     },
                 )| {
                     // Just invent something when the limits are `None`,
@@ -98,6 +98,7 @@ impl Default for ClusterReplicaSizeMap {
                         cpu_limit: None,
                         scale: 1,
                         workers,
+                        arrangement_memory_limit: None,
                     },
                 )
             })
@@ -112,6 +113,7 @@ impl Default for ClusterReplicaSizeMap {
                     cpu_limit: None,
                     scale,
                     workers: 1,
+                    arrangement_memory_limit: None,
                 },
             );
 
@@ -122,6 +124,7 @@ impl Default for ClusterReplicaSizeMap {
                     cpu_limit: None,
                     scale,
                     workers: scale.into(),
+                    arrangement_memory_limit: None,
                 },
             );
         }
@@ -133,6 +136,7 @@ impl Default for ClusterReplicaSizeMap {
                 cpu_limit: None,
                 scale: 2,
                 workers: 4,
+                arrangement_memory_limit: None,
             },
         );
         Self(inner)
@@ -68,6 +68,12 @@ pub enum ErrorKind {
     AmbiguousRename(#[from] AmbiguousRename),
     #[error("cannot rename type: {0}")]
     TypeRename(String),
+    #[error("cannot alter table '{table}' because '{depender}' depends on it")]
+    AlterTableDependency {
+        table: String,
+        depender: String,
+        cause: String,
+    },
     #[error("cannot migrate from catalog version {last_seen_version} to version {this_version} (earlier versions might still work): {cause}")]
     FailedMigration {
         last_seen_version: String,
@@ -105,6 +111,7 @@ impl Error {
             ErrorKind::ReservedClusterName(_) => {
                 Some("The prefixes \"mz_\" and \"pg_\" are reserved for system clusters.".into())
             }
+            ErrorKind::AlterTableDependency { cause, .. } => Some(cause.clone()),
             _ => None,
         }
     }
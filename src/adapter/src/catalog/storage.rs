@@ -27,6 +27,7 @@ use mz_sql::names::{
     DatabaseId, ObjectQualifiers, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId, SchemaId,
     SchemaSpecifier,
 };
+use mz_sql::plan::ManagedClusterConfig;
 use mz_stash::{AppendBatch, Stash, StashError, TableTransaction, TypedCollection};
 use mz_storage_client::types::sources::Timeline;
 
@@ -279,6 +280,7 @@ async fn migrate(
             let default_cluster = ClusterValue {
                 name: "default".into(),
                 linked_object_id: None,
+                managed: None,
             };
             let default_replica = ClusterReplicaValue {
                 cluster_id: DEFAULT_USER_CLUSTER_ID,
@@ -672,12 +674,20 @@ impl Connection {
     #[tracing::instrument(level = "info", skip_all)]
     pub async fn load_clusters(
         &mut self,
-    ) -> Result<Vec<(ClusterId, String, Option<GlobalId>)>, Error> {
+    ) -> Result<
+        Vec<(
+            ClusterId,
+            String,
+            Option<GlobalId>,
+            Option<ManagedClusterConfig>,
+        )>,
+        Error,
+    > {
         Ok(COLLECTION_CLUSTERS
             .peek_one(&mut self.stash)
             .await?
             .into_iter()
-            .map(|(k, v)| (k.id, v.name, v.linked_object_id))
+            .map(|(k, v)| (k.id, v.name, v.linked_object_id, v.managed))
             .collect())
     }
 
@@ -1203,12 +1213,14 @@ impl<'a> Transaction<'a> {
         cluster_id: ClusterId,
         cluster_name: &str,
         linked_object_id: Option<GlobalId>,
+        managed: Option<ManagedClusterConfig>,
         introspection_source_indexes: &Vec<(&'static BuiltinLog, GlobalId)>,
     ) -> Result<(), Error> {
         self.insert_cluster(
             cluster_id,
             cluster_name,
             linked_object_id,
+            managed,
             introspection_source_indexes,
         )
     }
@@ -1220,7 +1232,13 @@ impl<'a> Transaction<'a> {
         cluster_name: &str,
         introspection_source_indexes: &Vec<(&'static BuiltinLog, GlobalId)>,
     ) -> Result<(), Error> {
-        self.insert_cluster(cluster_id, cluster_name, None, introspection_source_indexes)
+        self.insert_cluster(
+            cluster_id,
+            cluster_name,
+            None,
+            None,
+            introspection_source_indexes,
+        )
     }
 
     fn insert_cluster(
@@ -1228,6 +1246,7 @@ impl<'a> Transaction<'a> {
         cluster_id: ClusterId,
         cluster_name: &str,
         linked_object_id: Option<GlobalId>,
+        managed: Option<ManagedClusterConfig>,
         introspection_source_indexes: &Vec<(&'static BuiltinLog, GlobalId)>,
     ) -> Result<(), Error> {
         if let Err(_) = self.clusters.insert(
@@ -1235,6 +1254,7 @@ impl<'a> Transaction<'a> {
             ClusterValue {
                 name: cluster_name.to_string(),
                 linked_object_id,
+                managed,
             },
         ) {
             return Err(Error::new(ErrorKind::ClusterAlreadyExists(
@@ -1481,6 +1501,33 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Updates the configuration of the managed cluster with the given ID.
+    ///
+    /// Returns an error if `id` is not found.
+    pub fn update_managed_cluster(
+        &mut self,
+        id: ClusterId,
+        config: ManagedClusterConfig,
+    ) -> Result<(), Error> {
+        let n = self.clusters.update(|k, v| {
+            if k.id == id {
+                Some(ClusterValue {
+                    name: v.name.clone(),
+                    linked_object_id: v.linked_object_id,
+                    managed: Some(config.clone()),
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownCluster(id.to_string()).into())
+        }
+    }
+
     /// Updates all items with ids matching the keys of `items` in the transaction, to the
     /// corresponding value in `items`.
     ///
@@ -1838,6 +1885,10 @@ pub struct ClusterKey {
 pub struct ClusterValue {
     name: String,
     linked_object_id: Option<GlobalId>,
+    /// The configuration of the cluster, if it is a managed cluster. Absent
+    /// for clusters created before managed clusters existed.
+    #[serde(default)]
+    managed: Option<ManagedClusterConfig>,
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
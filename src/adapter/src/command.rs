@@ -18,7 +18,9 @@ use std::sync::Arc;
 
 use derivative::Derivative;
 use enum_kinds::EnumKind;
+use futures::FutureExt;
 use mz_sql::plan::PlanKind;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
 
@@ -30,6 +32,7 @@ use mz_sql::plan::ExecuteTimeout;
 
 use crate::client::ConnectionId;
 use crate::coord::peek::PeekResponseUnary;
+use crate::coord::{Message, StatementEndedExecutionReason, StatementLoggingId};
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, RowBatchStream, Session};
 use crate::util::Transmittable;
@@ -224,6 +227,8 @@ pub enum ExecuteResponse {
     CreatedMaterializedView,
     /// The requested type was created.
     CreatedType,
+    /// The requested savepoint was established.
+    CreatedSavepoint,
     /// The requested prepared statement was removed.
     Deallocate { all: bool },
     /// The requested cursor was declared.
@@ -279,6 +284,10 @@ pub enum ExecuteResponse {
     Prepare,
     /// A user-requested warning was raised.
     Raised,
+    /// The requested savepoint was released.
+    ReleasedSavepoint,
+    /// The active transaction rolled back to the requested savepoint.
+    RolledBackToSavepoint,
     /// Rows will be delivered via the specified future.
     SendingRows {
         #[derivative(Debug = "ignore")]
@@ -332,6 +341,7 @@ impl ExecuteResponse {
             CreatedViews { .. } => Some("CREATE VIEWS".into()),
             CreatedMaterializedView { .. } => Some("CREATE MATERIALIZED VIEW".into()),
             CreatedType => Some("CREATE TYPE".into()),
+            CreatedSavepoint => Some("SAVEPOINT".into()),
             Deallocate { all } => Some(format!("DEALLOCATE{}", if *all { " ALL" } else { "" })),
             DeclaredCursor => Some("DECLARE CURSOR".into()),
             Deleted(n) => Some(format!("DELETE {}", n)),
@@ -365,6 +375,8 @@ impl ExecuteResponse {
             }
             Prepare => Some("PREPARE".into()),
             Raised => Some("RAISE".into()),
+            ReleasedSavepoint => Some("RELEASE".into()),
+            RolledBackToSavepoint => Some("ROLLBACK".into()),
             SendingRows { .. } => None,
             SetVariable { reset: true, .. } => Some("RESET".into()),
             SetVariable { reset: false, .. } => Some("SET".into()),
@@ -384,9 +396,11 @@ impl ExecuteResponse {
 
         match plan {
             AbortTransaction => vec![TransactionRolledBack],
-            AlterItemRename | AlterNoop | AlterSecret | AlterSink | AlterSource | RotateKeys => {
+            AlterCluster | AlterItemRename | AlterNoop | AlterSecret | AlterSink | AlterSource
+            | AlterTable | RotateKeys => {
                 vec![AlteredObject]
             }
+            AlterMaterializedView => vec![AlteredObject, CreatedMaterializedView, Canceled],
             AlterIndexSetOptions | AlterIndexResetOptions => {
                 vec![AlteredObject, AlteredIndexLogicalCompaction]
             }
@@ -439,6 +453,9 @@ impl ExecuteResponse {
             Insert => vec![Inserted, SendingRows],
             PlanKind::Prepare => vec![ExecuteResponseKind::Prepare],
             PlanKind::Raise => vec![ExecuteResponseKind::Raised],
+            ReleaseSavepoint => vec![ReleasedSavepoint],
+            RollbackToSavepoint => vec![RolledBackToSavepoint],
+            Savepoint => vec![CreatedSavepoint],
             PlanKind::SetVariable | ResetVariable => vec![ExecuteResponseKind::SetVariable],
             PlanKind::Subscribe => vec![Subscribing, CopyTo],
             StartTransaction => vec![StartedTransaction],
@@ -454,6 +471,64 @@ impl Transmittable for ExecuteResponse {
     fn to_allowed(&self) -> Self::Allowed {
         ExecuteResponseKind::from(self)
     }
+
+    /// Statements that send rows finish once their rows are ready, rather
+    /// than when their response is sent.
+    fn end_logged_statement(
+        result: Result<Self, AdapterError>,
+        id: StatementLoggingId,
+        internal_cmd_tx: &UnboundedSender<Message>,
+    ) -> Result<Self, AdapterError> {
+        let end = |reason| {
+            // If sending fails, the coordinator has shut down.
+            let _ = internal_cmd_tx.send(Message::StatementExecutionEnded { id, reason });
+        };
+        match result {
+            Ok(ExecuteResponse::SendingRows { future, span }) => {
+                let internal_cmd_tx = internal_cmd_tx.clone();
+                let future = future.map(move |response| {
+                    let reason = match &response {
+                        PeekResponseUnary::Rows(rows) => StatementEndedExecutionReason::Success {
+                            rows_returned: Some(rows.len()),
+                        },
+                        PeekResponseUnary::Error(error) => StatementEndedExecutionReason::Errored {
+                            error: error.clone(),
+                        },
+                        PeekResponseUnary::Canceled => StatementEndedExecutionReason::Canceled,
+                    };
+                    let _ = internal_cmd_tx.send(Message::StatementExecutionEnded { id, reason });
+                    response
+                });
+                Ok(ExecuteResponse::SendingRows {
+                    future: Box::pin(future),
+                    span,
+                })
+            }
+            Ok(ExecuteResponse::CopyTo { format, resp }) => {
+                let resp = Self::end_logged_statement(Ok(*resp), id, internal_cmd_tx)?;
+                Ok(ExecuteResponse::CopyTo {
+                    format,
+                    resp: Box::new(resp),
+                })
+            }
+            Ok(ExecuteResponse::Canceled) => {
+                end(StatementEndedExecutionReason::Canceled);
+                Ok(ExecuteResponse::Canceled)
+            }
+            Ok(resp) => {
+                end(StatementEndedExecutionReason::Success {
+                    rows_returned: None,
+                });
+                Ok(resp)
+            }
+            Err(e) => {
+                end(StatementEndedExecutionReason::Errored {
+                    error: e.to_string(),
+                });
+                Err(e)
+            }
+        }
+    }
 }
 
 /// The state of a cancellation request.
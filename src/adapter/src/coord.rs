@@ -128,11 +128,17 @@ use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, PendingWriteTxn}
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::PendingPeek;
 use crate::coord::read_policy::ReadCapability;
+use crate::coord::shared_arrangements::SharedArrangements;
+use crate::coord::statement_logging::StatementLogging;
+pub(crate) use crate::coord::statement_logging::{
+    StatementEndedExecutionReason, StatementLoggingId,
+};
+use crate::coord::statistics::CollectionStatisticsOracle;
 use crate::coord::timeline::{TimelineContext, TimelineState, WriteTimestamp};
 use crate::coord::timestamp_selection::TimestampContext;
 use crate::error::AdapterError;
 use crate::metrics::Metrics;
-use crate::session::{EndTransactionAction, Session};
+use crate::session::{EndTransactionAction, Session, WriteOp};
 use crate::subscribe::ActiveSubscribe;
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ComputeSinkId, ResultExt};
 use crate::AdapterNotice;
@@ -150,7 +156,10 @@ mod indexes;
 mod message_handler;
 mod read_policy;
 mod sequencer;
+mod shared_arrangements;
 mod sql;
+mod statement_logging;
+mod statistics;
 
 // TODO: We can have only two consts here, instead of three, once there exists a `const` way to
 // convert between a `Timestamp` and a `Duration`, and unwrap a result in const contexts. Currently
@@ -203,6 +212,28 @@ pub enum Message<T = mz_repr::Timestamp> {
     LinearizeReads(Vec<PendingReadTxn>),
     StorageUsageFetch,
     StorageUsageUpdate(BTreeMap<Option<ShardId>, u64>),
+    /// Starts removing the expired rows of tables created with `EXPIRE ROWS
+    /// AFTER`.
+    ExpireTableRows,
+    /// Delivers the retractions of the expired rows of tables created with
+    /// `EXPIRE ROWS AFTER`, to be committed with the write lock held since
+    /// the rows were read.
+    TableRowsExpired {
+        writes: Vec<WriteOp>,
+        write_lock_guard: OwnedMutexGuard<()>,
+    },
+    /// Writes out the finished statement executions that are being logged.
+    FlushStatementLog,
+    /// Refreshes the statistics of storage collections used by the optimizer.
+    RefreshStatistics,
+    /// Delivers the refreshed statistics of storage collections, or `None` if
+    /// the refresh timed out.
+    StatisticsRefreshed(Option<CollectionStatisticsOracle>),
+    /// Reports that a logged statement execution finished.
+    StatementExecutionEnded {
+        id: StatementLoggingId,
+        reason: StatementEndedExecutionReason,
+    },
     RealTimeRecencyTimestamp {
         conn_id: ConnectionId,
         transient_revision: u64,
@@ -326,6 +357,45 @@ pub struct ReplicaMetadata {
     pub write_frontiers: Vec<(GlobalId, mz_repr::Timestamp)>,
 }
 
+/// A resize of a managed cluster that is waiting for the replicas provisioned
+/// with the new size to hydrate before retiring the replicas of the old size.
+#[derive(Debug)]
+pub struct PendingClusterReconfiguration {
+    /// The replicas provisioned with the new size.
+    new_replicas: BTreeSet<ReplicaId>,
+    /// The replicas to drop once the new replicas have hydrated.
+    old_replicas: BTreeSet<ReplicaId>,
+    /// For each compute collection on the cluster, the time that the write
+    /// frontier of each new replica must reach before the new replica counts
+    /// as hydrated.
+    targets: BTreeMap<GlobalId, mz_repr::Timestamp>,
+    /// For each storage collection ingested on the cluster, the time that the
+    /// write frontier of each new replica must reach before the new replica
+    /// counts as hydrated.
+    storage_targets: BTreeMap<GlobalId, mz_repr::Timestamp>,
+}
+
+/// A new definition of a materialized view whose dataflow is hydrating
+/// alongside the dataflow of the view's current definition.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct PendingMaterializedViewReplacement {
+    /// The materialized view with its new definition.
+    materialized_view: CatalogItem,
+    /// The cluster that maintains the materialized view.
+    cluster_id: ClusterId,
+    /// The read-only compute sink of the new dataflow.
+    sink_id: GlobalId,
+    /// Whether the replacement was requested by `CREATE OR REPLACE`, rather
+    /// than `ALTER MATERIALIZED VIEW`.
+    or_replace: bool,
+    /// The session that requested the replacement, which is waiting for the
+    /// replacement to take effect.
+    session: Session,
+    #[derivative(Debug = "ignore")]
+    tx: ClientTransmitter<ExecuteResponse>,
+}
+
 /// Metadata about an active connection.
 struct ConnMeta {
     /// A watch channel shared with the client to inform the client of
@@ -485,6 +555,13 @@ pub struct Coordinator {
     write_lock_wait_group: VecDeque<Deferred>,
     /// Pending writes waiting for a group commit.
     pending_writes: Vec<PendingWriteTxn>,
+    /// The timestamp of the latest write to each user table, against which
+    /// the reads of read-write transactions are validated when they commit.
+    table_write_timestamps: BTreeMap<GlobalId, Timestamp>,
+    /// For each user table with `PRIMARY KEY` or `UNIQUE` constraints, the
+    /// number of rows with each value of the columns of each of those
+    /// constraints, against which writes to the table are checked.
+    table_unique_keys: BTreeMap<GlobalId, Vec<BTreeMap<Row, Diff>>>,
 
     /// Handle to secret manager that can create and delete secrets from
     /// an arbitrary secret storage engine.
@@ -504,6 +581,30 @@ pub struct Coordinator {
     /// dropped and for which no further updates should be recorded.
     transient_replica_metadata: BTreeMap<ReplicaId, Option<ReplicaMetadata>>,
 
+    /// Managed clusters that are being resized, keyed by cluster ID.
+    pending_cluster_reconfigurations: BTreeMap<ClusterId, PendingClusterReconfiguration>,
+
+    /// Materialized views whose new definitions are hydrating, keyed by the
+    /// ID of the materialized view.
+    pending_materialized_view_replacements: BTreeMap<GlobalId, PendingMaterializedViewReplacement>,
+    /// The compute sinks that maintain materialized views whose definitions
+    /// have been replaced since the coordinator started, keyed by the ID of
+    /// the materialized view. All other materialized views are maintained by
+    /// a compute sink that shares their ID.
+    materialized_view_sinks: BTreeMap<GlobalId, GlobalId>,
+
+    /// Arrangements shared across the dataflows of each cluster. Empty unless
+    /// `enable_arrangement_sharing` is on.
+    shared_arrangements: SharedArrangements,
+
+    /// The sampled statement executions that are being recorded in
+    /// `mz_statement_execution_history`.
+    statement_logging: StatementLogging,
+
+    /// Statistics about storage collections, used by the optimizer to order
+    /// joins. Empty unless `enable_cardinality_estimates` is on.
+    collection_statistics: CollectionStatisticsOracle,
+
     /// Persist client for fetching storage metadata such as size metrics.
     storage_usage_client: StorageUsageClient,
     /// The interval at which to collect storage usage information.
@@ -696,6 +797,7 @@ impl Coordinator {
                 data_source,
                 since: None,
                 status_collection_id,
+                layout: None,
             }
         }
 
@@ -707,7 +809,7 @@ impl Coordinator {
         for entry in &entries {
             match entry.item() {
                 CatalogItem::Table(table) => {
-                    let collection_desc = table.desc.clone().into();
+                    let collection_desc = table.collection_description();
                     collections_to_create.push((entry.id(), collection_desc));
                 }
                 // User sources can have dependencies, so do avoid them in the
@@ -939,6 +1041,12 @@ impl Coordinator {
             self.initialize_read_policies(&policies, Some(ts)).await;
         }
 
+        info!("coordinator init: resuming cluster reconfigurations");
+        self.resume_cluster_reconfigurations();
+
+        info!("coordinator init: loading statement execution history");
+        self.bootstrap_statement_logging().await;
+
         info!("coordinator init: announcing completion of initialization to controller");
         // Announce the completion of initialization.
         self.controller.initialization_complete();
@@ -1049,6 +1157,9 @@ impl Coordinator {
             builtin_table_updates.extend(retractions);
         }
 
+        info!("coordinator init: loading the unique keys of user tables");
+        self.bootstrap_table_write_state(read_ts).await;
+
         info!("coordinator init: sending builtin table updates");
         self.send_builtin_table_updates(builtin_table_updates, BuiltinTableUpdateSource::DDL)
             .await;
@@ -1122,6 +1233,9 @@ impl Coordinator {
         });
 
         self.schedule_storage_usage_collection();
+        self.schedule_table_expiration();
+        self.schedule_statement_log_flush();
+        self.schedule_statistics_refresh();
 
         loop {
             // Before adding a branch to this select loop, please ensure that the branch is
@@ -1327,10 +1441,18 @@ pub async fn serve(
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
                 pending_writes: Vec::new(),
+                table_write_timestamps: BTreeMap::new(),
+                table_unique_keys: BTreeMap::new(),
                 secrets_controller,
                 cloud_resource_controller,
                 connection_context,
                 transient_replica_metadata: BTreeMap::new(),
+                pending_cluster_reconfigurations: BTreeMap::new(),
+                pending_materialized_view_replacements: BTreeMap::new(),
+                materialized_view_sinks: BTreeMap::new(),
+                shared_arrangements: SharedArrangements::default(),
+                statement_logging: StatementLogging::default(),
+                collection_statistics: CollectionStatisticsOracle::default(),
                 storage_usage_client,
                 storage_usage_collection_interval,
                 segment_client,
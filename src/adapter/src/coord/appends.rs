@@ -9,7 +9,7 @@
 
 //! Logic and types for all appends executed by the [`Coordinator`].
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -17,16 +17,18 @@ use derivative::Derivative;
 use tokio::sync::OwnedMutexGuard;
 use tracing::warn;
 
+use mz_expr::{BinaryFunc, MapFilterProject, MirScalarExpr};
 use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
-use mz_sql::plan::Plan;
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, ScalarType, Timestamp};
+use mz_sql::plan::{Constraint, Plan};
 use mz_storage_client::client::Update;
 
-use crate::catalog::BuiltinTableUpdate;
+use crate::catalog::{BuiltinTableUpdate, CatalogItem, Table};
 use crate::coord::timeline::WriteTimestamp;
 use crate::coord::{Coordinator, Message, PendingTxn};
-use crate::session::{Session, WriteOp};
+use crate::error::AdapterError;
+use crate::session::{EndTransactionAction, Session, WriteOp};
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ResultExt};
 use crate::ExecuteResponse;
 
@@ -62,6 +64,9 @@ pub(crate) enum PendingWriteTxn {
     User {
         /// List of all write operations within the transaction.
         writes: Vec<WriteOp>,
+        /// For read-write transactions, the timestamp at which the transaction
+        /// read and the user tables it read from.
+        table_reads: Option<(Timestamp, BTreeSet<GlobalId>)>,
         /// Holds the coordinator's write lock.
         write_lock_guard: Option<OwnedMutexGuard<()>>,
         /// Inner transaction.
@@ -72,6 +77,14 @@ pub(crate) enum PendingWriteTxn {
         updates: Vec<BuiltinTableUpdate>,
         source: BuiltinTableUpdateSource,
     },
+    /// Removal of the expired rows of tables created with `EXPIRE ROWS AFTER`.
+    Expiration {
+        /// The retractions of the expired rows.
+        writes: Vec<WriteOp>,
+        /// Holds the coordinator's write lock, which was acquired before the
+        /// expired rows were read.
+        write_lock_guard: Option<OwnedMutexGuard<()>>,
+    },
 }
 
 impl PendingWriteTxn {
@@ -79,6 +92,9 @@ impl PendingWriteTxn {
         match self {
             PendingWriteTxn::User {
                 write_lock_guard, ..
+            }
+            | PendingWriteTxn::Expiration {
+                write_lock_guard, ..
             } => std::mem::take(write_lock_guard),
             PendingWriteTxn::System { .. } => None,
         }
@@ -88,7 +104,7 @@ impl PendingWriteTxn {
     /// asynchronously.
     fn should_block(&self) -> bool {
         match self {
-            PendingWriteTxn::User { .. } => false,
+            PendingWriteTxn::User { .. } | PendingWriteTxn::Expiration { .. } => false,
             PendingWriteTxn::System { source, .. } => match source {
                 BuiltinTableUpdateSource::DDL => true,
                 BuiltinTableUpdateSource::Background => false,
@@ -233,10 +249,15 @@ impl Coordinator {
         let mut appends: BTreeMap<GlobalId, Vec<(Row, Diff)>> = BTreeMap::new();
         let mut responses = Vec::with_capacity(self.pending_writes.len());
         let should_block = pending_writes.iter().any(|write| write.should_block());
+        // Whether a transaction writes to multiple tables, and so needs its
+        // writes committed atomically.
+        let mut atomic = false;
+        let mut unique_keys = std::mem::take(&mut self.table_unique_keys);
         for pending_write_txn in pending_writes {
             match pending_write_txn {
                 PendingWriteTxn::User {
                     writes,
+                    table_reads,
                     write_lock_guard: _,
                     pending_txn:
                         PendingTxn {
@@ -246,6 +267,27 @@ impl Coordinator {
                             action,
                         },
                 } => {
+                    let result = match &table_reads {
+                        Some((txn_read_ts, table_reads)) => {
+                            self.check_table_reads(*txn_read_ts, table_reads)
+                        }
+                        None => Ok(()),
+                    };
+                    let result = result
+                        .and_then(|()| self.check_table_constraints(&writes, &mut unique_keys));
+                    if let Err(err) = result {
+                        // None of the transaction's writes are applied, so
+                        // it's rolled back and we can respond immediately.
+                        CompletedClientTransmitter::new(
+                            client_transmitter,
+                            Err(err),
+                            session,
+                            EndTransactionAction::Rollback,
+                        )
+                        .send();
+                        continue;
+                    }
+                    atomic |= writes.iter().any(|op| op.id != writes[0].id);
                     for WriteOp { id, rows } in writes {
                         // If the table that some write was targeting has been deleted while the
                         // write was waiting, then the write will be ignored and we respond to the
@@ -253,6 +295,10 @@ impl Coordinator {
                         // and the delete were concurrent. Therefore, we are free to order the
                         // write before the delete without violating any consistency guarantees.
                         if self.catalog.try_get_entry(&id).is_some() {
+                            // Read-write transactions that read from the table
+                            // before `timestamp`, including those later in this
+                            // group commit, can no longer commit.
+                            self.table_write_timestamps.insert(id, timestamp);
                             appends.entry(id).or_default().extend(rows);
                         }
                     }
@@ -271,9 +317,27 @@ impl Coordinator {
                             .push((update.row, update.diff));
                     }
                 }
+                PendingWriteTxn::Expiration { writes, .. } => {
+                    // Retractions can't violate constraints, but this also
+                    // updates the tracked unique keys, and catches tables
+                    // altered since their expired rows were read. Those are
+                    // left for the next removal of expired rows.
+                    if let Err(err) = self.check_table_constraints(&writes, &mut unique_keys) {
+                        warn!("unable to remove expired table rows: {err}");
+                        continue;
+                    }
+                    for WriteOp { id, rows } in writes {
+                        if self.catalog.try_get_entry(&id).is_some() {
+                            self.table_write_timestamps.insert(id, timestamp);
+                            appends.entry(id).or_default().extend(rows);
+                        }
+                    }
+                }
             }
         }
 
+        self.table_unique_keys = unique_keys;
+
         for (_, updates) in &mut appends {
             differential_dataflow::consolidation::consolidate(updates);
         }
@@ -296,11 +360,12 @@ impl Coordinator {
             })
             .collect();
 
-        let append_fut = self
-            .controller
-            .storage
-            .append(appends)
-            .expect("invalid updates");
+        let append_fut = if atomic {
+            self.controller.storage.append_atomic(appends)
+        } else {
+            self.controller.storage.append(appends)
+        }
+        .expect("invalid updates");
         if should_block {
             // We may panic here if the storage controller has shut down, because we cannot
             // correctly return control, nor can we simply hang here.
@@ -364,6 +429,297 @@ impl Coordinator {
             .expect("sending to self.internal_cmd_tx cannot fail");
     }
 
+    /// Checks that none of `table_reads` were written to after `read_ts`, so
+    /// that a read-write transaction that read from them at `read_ts` can
+    /// commit serializably.
+    fn check_table_reads(
+        &self,
+        read_ts: Timestamp,
+        table_reads: &BTreeSet<GlobalId>,
+    ) -> Result<(), AdapterError> {
+        for id in table_reads {
+            let Some(write_ts) = self.table_write_timestamps.get(id) else {
+                continue;
+            };
+            if *write_ts > read_ts {
+                let table = match self.catalog.try_get_entry(id) {
+                    Some(entry) => entry.name().item.clone(),
+                    None => id.to_string(),
+                };
+                return Err(AdapterError::ConcurrentTableWrite { table });
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts removing the expired rows of the tables created with `EXPIRE
+    /// ROWS AFTER`, like a `DELETE` of the rows whose expiration time is at or
+    /// before the current read timestamp would.
+    ///
+    /// The write lock is held from the read until the retractions commit, so
+    /// that no other writes to user tables can occur in between. If it isn't
+    /// available, the rows are removed during the next round instead. The
+    /// tables are read off the coordinator's main loop, keeping only their
+    /// expired rows, which are then reported with [`Message::TableRowsExpired`].
+    pub(crate) async fn expire_table_rows(&mut self) {
+        let tables: Vec<_> = self
+            .catalog
+            .entries()
+            .filter_map(|entry| match entry.item() {
+                CatalogItem::Table(table) => table
+                    .expire_rows_after
+                    .clone()
+                    .map(|expr| (entry.id(), table.desc.arity(), expr)),
+                _ => None,
+            })
+            .collect();
+        let write_lock_guard = match Arc::clone(&self.write_lock).try_lock_owned() {
+            Ok(guard) if !tables.is_empty() => guard,
+            _ => {
+                self.schedule_table_expiration();
+                return;
+            }
+        };
+
+        let read_ts = self.get_local_read_ts();
+        let mut snapshots = Vec::new();
+        for (id, arity, expr) in tables {
+            let expired = expr.call_binary(
+                MirScalarExpr::literal_ok(Datum::MzTimestamp(read_ts), ScalarType::MzTimestamp),
+                BinaryFunc::Lte,
+            );
+            let mfp = MapFilterProject::new(arity)
+                .filter(Some(expired))
+                .into_plan()
+                .expect("valid filter")
+                .into_nontemporal()
+                .expect("expiration expressions are immutable");
+            match self
+                .controller
+                .storage
+                .snapshot_and_map_filter(id, read_ts, mfp)
+                .await
+            {
+                Ok(snapshot) => snapshots.push((id, snapshot)),
+                Err(e) => warn!("unable to read table {id} to remove its expired rows: {e}"),
+            }
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "table_expiration_read", async move {
+            let mut writes = Vec::new();
+            for (id, snapshot) in snapshots {
+                let mut rows = match snapshot.await {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        warn!("unable to read table {id} to remove its expired rows: {e}");
+                        continue;
+                    }
+                };
+                differential_dataflow::consolidation::consolidate(&mut rows);
+                let rows: Vec<_> = rows
+                    .into_iter()
+                    .filter(|(_, diff)| *diff > 0)
+                    .map(|(row, diff)| (row, -diff))
+                    .collect();
+                if !rows.is_empty() {
+                    writes.push(WriteOp { id, rows });
+                }
+            }
+            // It is not an error for this task to be running after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::TableRowsExpired {
+                writes,
+                write_lock_guard,
+            });
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Checks that `writes` don't violate the `CHECK`, `PRIMARY KEY`, or
+    /// `UNIQUE` constraints of the tables they target, and that they still
+    /// match the columns of those tables, which a concurrent `ALTER TABLE` may
+    /// have changed since the writes were planned.
+    ///
+    /// Uniqueness is checked against `unique_keys`, which tracks, for each
+    /// table with unique constraints, the number of rows with each value of
+    /// each of those constraints' columns, including the writes of the
+    /// transactions earlier in the same group commit. It is only updated if
+    /// all of `writes` pass.
+    fn check_table_constraints(
+        &self,
+        writes: &[WriteOp],
+        unique_keys: &mut BTreeMap<GlobalId, Vec<BTreeMap<Row, Diff>>>,
+    ) -> Result<(), AdapterError> {
+        let mut rows_by_table: BTreeMap<GlobalId, Vec<&(Row, Diff)>> = BTreeMap::new();
+        for WriteOp { id, rows } in writes {
+            rows_by_table.entry(*id).or_default().extend(rows);
+        }
+
+        let arena = RowArena::new();
+        let mut key_deltas = Vec::new();
+        for (id, rows) in rows_by_table {
+            let Some(entry) = self.catalog.try_get_entry(&id) else {
+                // The table was dropped concurrently, so the write is ignored.
+                continue;
+            };
+            let CatalogItem::Table(table) = entry.item() else {
+                continue;
+            };
+            let column_types = &table.desc.typ().column_types;
+            let matches_columns = |row: &Row| {
+                row.iter().count() == column_types.len()
+                    && row
+                        .iter()
+                        .zip(column_types)
+                        .all(|(d, ty)| d.is_instance_of(ty))
+            };
+            if !rows.iter().all(|(row, _)| matches_columns(row)) {
+                return Err(AdapterError::TableAlteredConcurrently {
+                    table: entry.name().item.clone(),
+                });
+            }
+
+            for constraint in &table.constraints {
+                if let Constraint::Check { name, expr } = constraint {
+                    // Removing rows can't violate a check constraint.
+                    for (row, _) in rows.iter().filter(|(_, diff)| *diff > 0) {
+                        let datums = row.unpack();
+                        if expr.eval(&datums, &arena)? == Datum::False {
+                            return Err(AdapterError::CheckViolation {
+                                table: entry.name().item.clone(),
+                                constraint: name.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+            let unique_constraints: Vec<_> = unique_constraints(&table.constraints).collect();
+            if unique_constraints.is_empty() {
+                continue;
+            }
+
+            let counts = unique_keys
+                .get(&id)
+                .expect("unique keys are tracked for all tables with unique constraints");
+            let mut deltas = vec![BTreeMap::new(); unique_constraints.len()];
+            for (row, diff) in rows {
+                for ((_, columns), delta) in unique_constraints.iter().zip(deltas.iter_mut()) {
+                    if let Some(key) = unique_key(row, columns) {
+                        *delta.entry(key).or_insert(0) += diff;
+                    }
+                }
+            }
+            for (((name, columns), counts), delta) in
+                unique_constraints.iter().zip(counts).zip(&deltas)
+            {
+                for (key, diff) in delta {
+                    if counts.get(key).copied().unwrap_or(0) + diff > 1 {
+                        return Err(AdapterError::UniqueViolation {
+                            constraint: name.to_string(),
+                            columns: columns
+                                .iter()
+                                .map(|i| table.desc.get_name(*i).as_str().to_string())
+                                .collect(),
+                            values: key
+                                .iter()
+                                .map(|datum| match datum {
+                                    Datum::String(s) => s.to_string(),
+                                    datum => datum.to_string(),
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+            }
+            key_deltas.push((id, deltas));
+        }
+
+        for (id, deltas) in key_deltas {
+            let counts = unique_keys.get_mut(&id).expect("checked above");
+            for (counts, delta) in counts.iter_mut().zip(deltas) {
+                for (key, diff) in delta {
+                    let count = counts.entry(key).or_insert(0);
+                    *count += diff;
+                }
+                counts.retain(|_, count| *count != 0);
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts tracking the unique keys of the user tables that have `PRIMARY
+    /// KEY` or `UNIQUE` constraints, from their contents as of `read_ts`.
+    ///
+    /// Called once during bootstrap, so that group commits don't have to read
+    /// the tables to check their constraints. Tables created later start out
+    /// empty, see [`Coordinator::initialize_table_write_state`].
+    pub(crate) async fn bootstrap_table_write_state(&mut self, read_ts: Timestamp) {
+        let tables: Vec<_> = self
+            .catalog
+            .entries()
+            .filter(|entry| entry.id().is_user())
+            .filter_map(|entry| match entry.item() {
+                CatalogItem::Table(table)
+                    if unique_constraints(&table.constraints).next().is_some() =>
+                {
+                    Some((entry.id(), table.constraints.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        for (id, constraints) in tables {
+            let contents = self
+                .controller
+                .storage
+                .snapshot(id, read_ts)
+                .await
+                .unwrap_or_terminate("cannot fail to fetch snapshot");
+            let mut counts = vec![BTreeMap::new(); unique_constraints(&constraints).count()];
+            update_unique_keys(&mut counts, &constraints, contents.iter());
+            self.table_unique_keys.insert(id, counts);
+        }
+    }
+
+    /// Starts tracking the unique keys of the newly created, and thus empty,
+    /// table `id`, if it has `PRIMARY KEY` or `UNIQUE` constraints.
+    pub(crate) fn initialize_table_write_state(&mut self, id: GlobalId, table: &Table) {
+        let unique_constraint_count = unique_constraints(&table.constraints).count();
+        if unique_constraint_count > 0 {
+            self.table_unique_keys
+                .insert(id, vec![BTreeMap::new(); unique_constraint_count]);
+        }
+    }
+
+    /// Adapts the state tracked for writes to table `id` after an `ALTER
+    /// TABLE` changed its definition from `old_table`.
+    ///
+    /// `ALTER TABLE` can only remove unique constraints, and the values of the
+    /// columns of the remaining ones are unchanged, so their keys carry over.
+    pub(crate) fn alter_table_write_state(&mut self, id: GlobalId, old_table: &Table) {
+        let CatalogItem::Table(table) = self.catalog.get_entry(&id).item() else {
+            return;
+        };
+
+        if let Some(counts) = self.table_unique_keys.remove(&id) {
+            let mut counts_by_name: BTreeMap<_, _> = unique_constraints(&old_table.constraints)
+                .map(|(name, _)| name.clone())
+                .zip(counts)
+                .collect();
+            let counts: Vec<_> = unique_constraints(&table.constraints)
+                .map(|(name, _)| {
+                    counts_by_name
+                        .remove(name)
+                        .expect("ALTER TABLE cannot add unique constraints")
+                })
+                .collect();
+            if !counts.is_empty() {
+                self.table_unique_keys.insert(id, counts);
+            }
+        }
+    }
+
     /// Submit a write to be executed during the next group commit.
     pub(crate) fn submit_write(&mut self, pending_write_txn: PendingWriteTxn) {
         self.internal_cmd_tx
@@ -423,3 +779,47 @@ impl Coordinator {
         })
     }
 }
+
+/// Returns the name and columns of each `PRIMARY KEY` or `UNIQUE` constraint in
+/// `constraints`.
+fn unique_constraints(
+    constraints: &[Constraint],
+) -> impl Iterator<Item = (&String, &Vec<usize>)> + '_ {
+    constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::Unique { name, columns } => Some((name, columns)),
+            Constraint::Check { .. } => None,
+        })
+}
+
+/// Adds `rows` to `counts`, the number of rows with each value of the columns
+/// of each of the unique constraints in `constraints`.
+fn update_unique_keys<'a>(
+    counts: &mut [BTreeMap<Row, Diff>],
+    constraints: &[Constraint],
+    rows: impl Iterator<Item = &'a (Row, Diff)>,
+) {
+    for (row, diff) in rows {
+        for ((_, columns), counts) in unique_constraints(constraints).zip(counts.iter_mut()) {
+            if let Some(key) = unique_key(row, columns) {
+                let count = counts.entry(key).or_insert(0);
+                *count += diff;
+            }
+        }
+    }
+    for counts in counts {
+        counts.retain(|_, count| *count != 0);
+    }
+}
+
+/// Returns the values of `columns` in `row`, or `None` if any of them are null
+/// (and so can't conflict with any other row).
+fn unique_key(row: &Row, columns: &[usize]) -> Option<Row> {
+    let datums = row.unpack();
+    let key = columns.iter().map(|i| datums[*i]);
+    if key.clone().any(|datum| datum.is_null()) {
+        return None;
+    }
+    Some(Row::pack(key))
+}
@@ -21,7 +21,7 @@ use mz_compute_client::protocol::response::PeekResponse;
 use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::ScalarType;
-use mz_sql::ast::{InsertSource, Query, Raw, SetExpr, Statement};
+use mz_sql::ast::{Raw, Statement};
 use mz_sql::catalog::SessionCatalog as _;
 use mz_sql::plan::{CreateRolePlan, Params};
 
@@ -231,7 +231,7 @@ impl Coordinator {
         &mut self,
         portal_name: String,
         mut session: Session,
-        tx: ClientTransmitter<ExecuteResponse>,
+        mut tx: ClientTransmitter<ExecuteResponse>,
     ) {
         if session.vars().emit_trace_id_notice() {
             let span_context = tracing::Span::current()
@@ -266,6 +266,10 @@ impl Coordinator {
             .with_label_values(&[session_type, stmt_type])
             .inc();
 
+        if let Some(id) = self.begin_statement_execution(&session, &stmt) {
+            tx.set_logged_statement(id);
+        }
+
         let params = portal.parameters.clone();
         self.handle_execute_inner(stmt, params, session, tx).await
     }
@@ -330,18 +334,24 @@ impl Coordinator {
             TransactionStatus::InTransactionImplicit(_) | TransactionStatus::InTransaction(_) => {
                 match stmt {
                     // Statements that are safe in a transaction. We still need to verify that we
-                    // don't interleave reads and writes since we can't perform those serializably.
+                    // only interleave reads and writes when we can perform them serializably,
+                    // which `add_transaction_ops` does.
                     Statement::Close(_)
                     | Statement::Commit(_)
                     | Statement::Copy(_)
                     | Statement::Deallocate(_)
                     | Statement::Declare(_)
+                    | Statement::Delete(_)
                     | Statement::Discard(_)
                     | Statement::Execute(_)
                     | Statement::Explain(_)
+                    | Statement::ExplainAnalyze(_)
                     | Statement::Fetch(_)
+                    | Statement::Insert(_)
                     | Statement::Prepare(_)
+                    | Statement::ReleaseSavepoint(_)
                     | Statement::Rollback(_)
+                    | Statement::Savepoint(_)
                     | Statement::Select(_)
                     | Statement::SetTransaction(_)
                     | Statement::Show(_)
@@ -349,29 +359,20 @@ impl Coordinator {
                     | Statement::ResetVariable(_)
                     | Statement::StartTransaction(_)
                     | Statement::Subscribe(_)
+                    | Statement::Update(_)
                     | Statement::Raise(_) => {
                         // Always safe.
                     }
 
-                    Statement::Insert(ref insert_statement)
-                        if matches!(
-                            insert_statement.source,
-                            InsertSource::Query(Query {
-                                body: SetExpr::Values(..),
-                                ..
-                            }) | InsertSource::DefaultValues
-                        ) =>
-                    {
-                        // Inserting from default? values statements
-                        // is always safe.
-                    }
-
                     // Statements below must by run singly (in Started).
-                    Statement::AlterConnection(_)
+                    Statement::AlterCluster(_)
+                    | Statement::AlterConnection(_)
                     | Statement::AlterIndex(_)
                     | Statement::AlterSecret(_)
                     | Statement::AlterSink(_)
                     | Statement::AlterSource(_)
+                    | Statement::AlterTable(_)
+                    | Statement::AlterMaterializedView(_)
                     | Statement::AlterObjectRename(_)
                     | Statement::AlterSystemSet(_)
                     | Statement::AlterSystemReset(_)
@@ -391,15 +392,12 @@ impl Coordinator {
                     | Statement::CreateType(_)
                     | Statement::CreateView(_)
                     | Statement::CreateMaterializedView(_)
-                    | Statement::Delete(_)
                     | Statement::DropDatabase(_)
                     | Statement::DropSchema(_)
                     | Statement::DropObjects(_)
                     | Statement::DropRoles(_)
                     | Statement::DropClusters(_)
-                    | Statement::DropClusterReplicas(_)
-                    | Statement::Insert(_)
-                    | Statement::Update(_) => {
+                    | Statement::DropClusterReplicas(_) => {
                         return tx.send(
                             Err(AdapterError::OperationProhibitsTransaction(
                                 stmt.to_string(),
@@ -542,6 +540,17 @@ impl Coordinator {
             // Inform the target session (if it asks) about the cancellation.
             let _ = conn_meta.cancel_tx.send(Canceled::Canceled);
 
+            // Cancel replacements of materialized view definitions. There is
+            // at most one per session.
+            if let Some(id) = self
+                .pending_materialized_view_replacements
+                .iter()
+                .find(|(_, pending)| pending.session.conn_id() == conn_id)
+                .map(|(id, _)| *id)
+            {
+                self.cancel_materialized_view_replacement(id, Ok(ExecuteResponse::Canceled));
+            }
+
             for PendingPeek {
                 sender: rows_tx,
                 conn_id: _,
@@ -572,5 +581,6 @@ impl Coordinator {
             .dec();
         self.active_conns.remove(&session.conn_id());
         self.cancel_pending_peeks(&session.conn_id());
+        self.end_session_statement_executions(session.conn_id());
     }
 }
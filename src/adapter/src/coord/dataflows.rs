@@ -41,6 +41,7 @@ use mz_repr::{Datum, GlobalId, Row, Timestamp};
 use crate::catalog::{CatalogItem, CatalogState, DataSourceDesc, MaterializedView, Source, View};
 use crate::coord::ddl::CatalogTxn;
 use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::statistics::CollectionStatisticsOracle;
 use crate::coord::{Coordinator, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS};
 use crate::session::{Session, SERVER_MAJOR_VERSION, SERVER_MINOR_VERSION};
 use crate::util::ResultExt;
@@ -54,6 +55,8 @@ pub struct DataflowBuilder<'a, T> {
     /// This can also be used to grab a handle to the storage abstraction, through
     /// its `storage_mut()` method.
     pub compute: ComputeInstanceRef<'a, T>,
+    /// Statistics about storage collections, used to order joins.
+    pub statistics: &'a CollectionStatisticsOracle,
     recursion_guard: RecursionGuard,
 }
 
@@ -86,6 +89,7 @@ impl Coordinator {
         DataflowBuilder {
             catalog: self.catalog.state(),
             compute,
+            statistics: &self.collection_statistics,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
@@ -106,14 +110,22 @@ impl Coordinator {
 
     /// Finalizes a list of dataflows and then broadcasts it to all workers.
     ///
+    /// If `enable_arrangement_sharing` is on, the dataflows first import the
+    /// arrangements they share with other dataflows on `instance`.
+    ///
     /// # Panics
     ///
     /// Panics if any of the dataflows fail to ship.
     async fn must_ship_dataflows(
         &mut self,
-        dataflows: Vec<DataflowDesc>,
+        mut dataflows: Vec<DataflowDesc>,
         instance: ComputeInstanceId,
     ) {
+        if self.catalog.system_config().enable_arrangement_sharing() {
+            for dataflow in &mut dataflows {
+                self.share_arrangements(dataflow, instance).await;
+            }
+        }
         self.ship_dataflows(dataflows, instance)
             .await
             .expect("failed to ship dataflows");
@@ -277,6 +289,7 @@ impl CatalogTxn<'_, mz_repr::Timestamp> {
         DataflowBuilder {
             catalog: self.catalog,
             compute,
+            statistics: self.statistics,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
@@ -413,7 +426,7 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         dataflow.export_index(id, index_description, on_type);
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(&mut dataflow, &self.index_oracle())?;
+        mz_transform::optimize_dataflow(&mut dataflow, &self.index_oracle(), self.statistics)?;
 
         Ok(dataflow)
     }
@@ -450,7 +463,7 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         dataflow.export_sink(id, sink_description);
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(dataflow, &self.index_oracle())?;
+        mz_transform::optimize_dataflow(dataflow, &self.index_oracle(), self.statistics)?;
 
         Ok(())
     }
@@ -476,6 +489,48 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         };
 
         let name = mview_entry.name().to_string();
+        self.build_materialized_view_dataflow_inner(name, mview, id, id, as_of, internal_view_id)
+    }
+
+    /// Builds a dataflow description for `mview`, a new definition of the materialized view
+    /// specified by `id`.
+    ///
+    /// The dataflow exports the sink `sink_id`, which writes to the storage collection of the
+    /// materialized view, but is created read-only. This allows the dataflow to hydrate while the
+    /// materialized view's current dataflow keeps writing to the collection.
+    pub fn build_materialized_view_replacement_dataflow(
+        &mut self,
+        id: GlobalId,
+        mview: &MaterializedView,
+        sink_id: GlobalId,
+        as_of: Antichain<Timestamp>,
+        internal_view_id: GlobalId,
+    ) -> Result<DataflowDesc, AdapterError> {
+        let name = self.catalog.get_entry(&id).name().to_string();
+        self.build_materialized_view_dataflow_inner(
+            name,
+            mview,
+            sink_id,
+            id,
+            as_of,
+            internal_view_id,
+        )
+    }
+
+    /// Builds a dataflow description that writes `mview` to the storage collection `storage_id`,
+    /// through the sink `sink_id`.
+    ///
+    /// Sinks that do not share the ID of their storage collection are replacing another sink, so
+    /// they are created read-only.
+    fn build_materialized_view_dataflow_inner(
+        &mut self,
+        name: String,
+        mview: &MaterializedView,
+        sink_id: GlobalId,
+        storage_id: GlobalId,
+        as_of: Antichain<Timestamp>,
+        internal_view_id: GlobalId,
+    ) -> Result<DataflowDesc, AdapterError> {
         let mut dataflow = DataflowDesc::new(name);
 
         self.import_view_into_dataflow(&internal_view_id, &mview.optimized_expr, &mut dataflow)?;
@@ -488,7 +543,9 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
             from_desc: mview.desc.clone(),
             connection: ComputeSinkConnection::Persist(PersistSinkConnection {
                 value_desc: mview.desc.clone(),
+                storage_id,
                 storage_metadata: (),
+                read_only: sink_id != storage_id,
             }),
             as_of: SinkAsOf {
                 frontier: as_of,
@@ -496,7 +553,7 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
             },
             up_to: Antichain::default(),
         };
-        self.build_sink_dataflow_into(&mut dataflow, id, sink_description)?;
+        self.build_sink_dataflow_into(&mut dataflow, sink_id, sink_description)?;
 
         Ok(dataflow)
     }
@@ -13,6 +13,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
+use anyhow::anyhow;
 use fail::fail_point;
 use serde_json::json;
 use timely::progress::Antichain;
@@ -37,6 +38,7 @@ use crate::catalog::{
 };
 use crate::client::ConnectionId;
 use crate::coord::appends::BuiltinTableUpdateSource;
+use crate::coord::statistics::CollectionStatisticsOracle;
 use crate::coord::Coordinator;
 use crate::session::vars::SystemVars;
 use crate::session::Session;
@@ -48,6 +50,7 @@ use crate::{catalog, AdapterError, AdapterNotice};
 pub struct CatalogTxn<'a, T> {
     pub(crate) dataflow_client: &'a mz_controller::Controller<T>,
     pub(crate) catalog: &'a CatalogState,
+    pub(crate) statistics: &'a CollectionStatisticsOracle,
 }
 
 impl Coordinator {
@@ -97,8 +100,10 @@ impl Coordinator {
         let mut clusters_to_drop = vec![];
         let mut cluster_replicas_to_drop = vec![];
 
+        let mut dropped_items = BTreeSet::new();
         for op in &ops {
             if let catalog::Op::DropItem(id) = op {
+                dropped_items.insert(*id);
                 match self.catalog.get_entry(id).item() {
                     CatalogItem::Table(_) => {
                         tables_to_drop.push(*id);
@@ -272,6 +277,7 @@ impl Coordinator {
                 f(CatalogTxn {
                     dataflow_client: &self.controller,
                     catalog,
+                    statistics: &self.collection_statistics,
                 })
             })
             .await?;
@@ -289,6 +295,10 @@ impl Coordinator {
                 self.drop_sources(log_sources_to_drop.into_iter().map(|(_, id)| id).collect());
             }
             if !tables_to_drop.is_empty() {
+                for id in &tables_to_drop {
+                    self.table_write_timestamps.remove(id);
+                    self.table_unique_keys.remove(id);
+                }
                 self.drop_sources(tables_to_drop);
             }
             if !storage_sinks_to_drop.is_empty() {
@@ -316,6 +326,9 @@ impl Coordinator {
             if !materialized_views_to_drop.is_empty() {
                 self.drop_materialized_views(materialized_views_to_drop);
             }
+            if !dropped_items.is_empty() {
+                self.cancel_materialized_view_replacements(&dropped_items);
+            }
             if !secrets_to_drop.is_empty() {
                 self.drop_secrets(secrets_to_drop).await;
             }
@@ -330,6 +343,7 @@ impl Coordinator {
             }
             if !clusters_to_drop.is_empty() {
                 for cluster_id in clusters_to_drop {
+                    self.shared_arrangements.remove_cluster(cluster_id);
                     self.controller.drop_cluster(cluster_id);
                 }
             }
@@ -404,6 +418,10 @@ impl Coordinator {
                 tracing::error!("Instructed to drop a compute sink that isn't one");
             }
         }
+        for (cluster_id, ids) in &mut by_cluster {
+            let unused = self.release_shared_arrangements(*cluster_id, ids);
+            ids.extend(unused);
+        }
         let mut compute = self.controller.active_compute();
         for (cluster_id, ids) in by_cluster {
             // A cluster could have been dropped, so verify it exists.
@@ -434,6 +452,10 @@ impl Coordinator {
                 tracing::error!("Instructed to drop a non-index index");
             }
         }
+        for (cluster_id, ids) in &mut by_cluster {
+            let unused = self.release_shared_arrangements(*cluster_id, ids);
+            ids.extend(unused);
+        }
         let mut compute = self.controller.active_compute();
         for (cluster_id, ids) in by_cluster {
             // A cluster could have been dropped, so verify it exists.
@@ -449,13 +471,18 @@ impl Coordinator {
         let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
         let mut source_ids = Vec::new();
         for (cluster_id, id) in mviews {
-            if self.drop_compute_read_policy(&id) {
-                by_cluster.entry(cluster_id).or_default().push(id);
+            let sink_id = self.materialized_view_sinks.remove(&id).unwrap_or(id);
+            if self.drop_compute_read_policy(&sink_id) {
+                by_cluster.entry(cluster_id).or_default().push(sink_id);
                 source_ids.push(id);
             } else {
                 tracing::error!("Instructed to drop a materialized view that isn't one");
             }
         }
+        for (cluster_id, ids) in &mut by_cluster {
+            let unused = self.release_shared_arrangements(*cluster_id, ids);
+            ids.extend(unused);
+        }
 
         // Drop compute sinks.
         let mut compute = self.controller.active_compute();
@@ -472,6 +499,32 @@ impl Coordinator {
         self.drop_sources(source_ids)
     }
 
+    /// Abandons the pending replacements of the definitions of materialized
+    /// views that are dropped or whose new definitions depend on dropped
+    /// items.
+    fn cancel_materialized_view_replacements(&mut self, dropped_items: &BTreeSet<GlobalId>) {
+        let canceled: Vec<_> = self
+            .pending_materialized_view_replacements
+            .iter()
+            .filter(|(id, pending)| {
+                dropped_items.contains(id)
+                    || pending
+                        .materialized_view
+                        .uses()
+                        .iter()
+                        .any(|dep| dropped_items.contains(dep))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for id in canceled {
+            let err = AdapterError::Unstructured(anyhow!(
+                "materialized view or one of its new dependencies was dropped before the new \
+                definition took effect"
+            ));
+            self.cancel_materialized_view_replacement(id, Err(err));
+        }
+    }
+
     async fn drop_secrets(&mut self, secrets: Vec<GlobalId>) {
         fail_point!("drop_secrets");
         for secret in secrets {
@@ -768,8 +821,11 @@ impl Coordinator {
                         | CatalogItem::Func(_) => {}
                     }
                 }
-                Op::AlterSink { .. }
+                Op::AlterCluster { .. }
+                | Op::AlterSink { .. }
                 | Op::AlterSource { .. }
+                | Op::AlterTable { .. }
+                | Op::AlterMaterializedView { .. }
                 | Op::DropTimeline(_)
                 | Op::RenameItem { .. }
                 | Op::UpdateClusterReplicaStatus { .. }
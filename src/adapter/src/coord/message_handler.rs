@@ -29,7 +29,7 @@ use mz_storage_client::controller::CollectionMetadata;
 
 use crate::client::ConnectionId;
 use crate::command::{Command, ExecuteResponse};
-use crate::coord::appends::{BuiltinTableUpdateSource, Deferred};
+use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, PendingWriteTxn};
 use crate::coord::timestamp_selection::TimestampContext;
 use crate::coord::{
     Coordinator, CreateSourceStatementReady, Message, PendingReadTxn, RealTimeRecencyContext,
@@ -86,6 +86,34 @@ impl Coordinator {
             Message::StorageUsageUpdate(sizes) => {
                 self.storage_usage_update(sizes).await;
             }
+            Message::ExpireTableRows => {
+                self.expire_table_rows().await;
+            }
+            Message::TableRowsExpired {
+                writes,
+                write_lock_guard,
+            } => {
+                if !writes.is_empty() {
+                    self.submit_write(PendingWriteTxn::Expiration {
+                        writes,
+                        write_lock_guard: Some(write_lock_guard),
+                    });
+                }
+                self.schedule_table_expiration();
+            }
+            Message::FlushStatementLog => {
+                self.flush_statement_log().await;
+                self.schedule_statement_log_flush();
+            }
+            Message::RefreshStatistics => {
+                self.refresh_statistics();
+            }
+            Message::StatisticsRefreshed(statistics) => {
+                self.install_statistics(statistics);
+            }
+            Message::StatementExecutionEnded { id, reason } => {
+                self.end_statement_execution(id, reason);
+            }
             Message::RealTimeRecencyTimestamp {
                 conn_id,
                 transient_revision,
@@ -128,6 +156,7 @@ impl Coordinator {
                     // `mz_storage_usage_by_shard` table.
                     persist_location: _,
                     relation_desc: _,
+                    layout: _,
                 } = &collection.collection_metadata;
                 [*data_shard, *remap_shard].into_iter().chain(*status_shard)
             })
@@ -236,6 +265,19 @@ impl Coordinator {
         });
     }
 
+    /// Schedules the next removal of the expired rows of tables created with
+    /// `EXPIRE ROWS AFTER`, after the `table_expiration_interval`.
+    pub fn schedule_table_expiration(&self) {
+        let interval = self.catalog.system_config().table_expiration_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "table_expiration", async move {
+            tokio::time::sleep(interval).await;
+            if internal_cmd_tx.send(Message::ExpireTableRows).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn message_command(&mut self, cmd: Command) {
         event!(Level::TRACE, cmd = format!("{:?}", cmd));
@@ -361,6 +403,12 @@ impl Coordinator {
                     BuiltinTableUpdateSource::Background,
                 )
                 .await;
+
+                // Resized clusters may have finished hydrating their new
+                // replicas, and replaced materialized views their new
+                // dataflows.
+                self.advance_cluster_reconfigurations().await;
+                self.advance_materialized_view_replacements().await;
             }
         }
     }
@@ -9,8 +9,9 @@
 
 //! Logic and types for creating, executing, and tracking peeks.
 //!
-//! This module determines if a dataflow can be short-cut, by returning constant values
-//! or by reading out of existing arrangements, and implements the appropriate plan.
+//! This module determines if a dataflow can be short-cut, by returning constant values,
+//! by reading out of existing arrangements, or by reading the persist shard of a
+//! materialized view, and implements the appropriate plan.
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
@@ -36,6 +37,7 @@ use mz_repr::explain::text::{fmt_text_constant_rows, separated_text, DisplayText
 use mz_repr::explain::{CompactScalarSeq, ExprHumanizer, Indices};
 use mz_repr::{Diff, GlobalId, RelationType, Row};
 
+use crate::catalog::CatalogState;
 use crate::client::ConnectionId;
 use crate::coord::timestamp_selection::TimestampContext;
 use crate::util::{send_immediate_rows, ResultExt};
@@ -77,6 +79,9 @@ pub enum FastPathPlan {
     Constant(Result<Vec<(Row, Diff)>, EvalError>, RelationType),
     /// The view can be read out of an existing arrangement.
     PeekExisting(GlobalId, Option<Vec<Row>>, mz_expr::SafeMfpPlan),
+    /// The view can be read directly out of the persist shard of a
+    /// materialized view.
+    PeekPersist(GlobalId, mz_expr::SafeMfpPlan),
 }
 
 impl<'a, C> DisplayText<C> for FastPathPlan
@@ -105,33 +110,58 @@ where
             }
             FastPathPlan::PeekExisting(id, literal_constraints, mfp) => {
                 ctx.as_mut().set();
-                let (map, filter, project) = mfp.as_map_filter_project();
-                if project.len() != mfp.input_arity + map.len()
-                    || !project.iter().enumerate().all(|(i, o)| i == *o)
-                {
-                    let outputs = Indices(&project);
-                    writeln!(f, "{}Project ({})", ctx.as_mut(), outputs)?;
-                    *ctx.as_mut() += 1;
-                }
-                if !filter.is_empty() {
-                    let predicates = separated_text(" AND ", filter);
-                    writeln!(f, "{}Filter {}", ctx.as_mut(), predicates)?;
-                    *ctx.as_mut() += 1;
-                }
-                if !map.is_empty() {
-                    let scalars = CompactScalarSeq(&map);
-                    writeln!(f, "{}Map ({})", ctx.as_mut(), scalars)?;
-                    *ctx.as_mut() += 1;
-                }
+                fmt_text_mfp(f, ctx, mfp)?;
                 MirRelationExpr::fmt_indexed_filter(f, ctx, id, literal_constraints.clone())?;
                 ctx.as_mut().reset();
                 Ok(())
             }
+            FastPathPlan::PeekPersist(id, mfp) => {
+                ctx.as_mut().set();
+                fmt_text_mfp(f, ctx, mfp)?;
+                let humanized_id = ctx
+                    .as_ref()
+                    .humanize_id(*id)
+                    .unwrap_or_else(|| id.to_string());
+                writeln!(f, "{}ReadStorage {}", ctx.as_mut(), humanized_id)?;
+                ctx.as_mut().reset();
+                Ok(())
+            }
         }?;
         Ok(())
     }
 }
 
+/// Writes the `Project`, `Filter`, and `Map` operators that `mfp` applies to
+/// the rows read by a [`FastPathPlan`], indenting `ctx` once for each.
+fn fmt_text_mfp<'a, C>(
+    f: &mut fmt::Formatter<'_>,
+    ctx: &mut C,
+    mfp: &mz_expr::SafeMfpPlan,
+) -> fmt::Result
+where
+    C: AsMut<Indent> + AsRef<&'a dyn ExprHumanizer>,
+{
+    let (map, filter, project) = mfp.as_map_filter_project();
+    if project.len() != mfp.input_arity + map.len()
+        || !project.iter().enumerate().all(|(i, o)| i == *o)
+    {
+        let outputs = Indices(&project);
+        writeln!(f, "{}Project ({})", ctx.as_mut(), outputs)?;
+        *ctx.as_mut() += 1;
+    }
+    if !filter.is_empty() {
+        let predicates = separated_text(" AND ", filter);
+        writeln!(f, "{}Filter {}", ctx.as_mut(), predicates)?;
+        *ctx.as_mut() += 1;
+    }
+    if !map.is_empty() {
+        let scalars = CompactScalarSeq(&map);
+        writeln!(f, "{}Map ({})", ctx.as_mut(), scalars)?;
+        *ctx.as_mut() += 1;
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct PlannedPeek {
     pub plan: PeekPlan,
@@ -150,33 +180,38 @@ pub enum PeekPlan<T = mz_repr::Timestamp> {
     SlowPath(PeekDataflowPlan<T>),
 }
 
-fn permute_oneshot_mfp_around_index(
-    mfp: mz_expr::MapFilterProject,
-    key: &[MirScalarExpr],
-) -> Result<mz_expr::SafeMfpPlan, AdapterError> {
-    // Convert `mfp` to an executable, non-temporal plan.
+/// Converts `mfp` to an executable, non-temporal plan.
+fn oneshot_mfp_plan(mfp: mz_expr::MapFilterProject) -> Result<mz_expr::SafeMfpPlan, AdapterError> {
     // It should be non-temporal, as OneShot preparation populates `mz_now`.
-    let mut safe_mfp = mfp
-        .clone()
-        .into_plan()
+    mfp.into_plan()
         .map_err(|e| AdapterError::Unstructured(::anyhow::anyhow!(e)))?
         .into_nontemporal()
         .map_err(|_e| {
             AdapterError::Unstructured(::anyhow::anyhow!("OneShot plan has temporal constraints"))
-        })?;
-    let (permute, thinning) = mz_expr::permutation_for_arrangement(key, mfp.input_arity);
+        })
+}
+
+fn permute_oneshot_mfp_around_index(
+    mfp: mz_expr::MapFilterProject,
+    key: &[MirScalarExpr],
+) -> Result<mz_expr::SafeMfpPlan, AdapterError> {
+    let input_arity = mfp.input_arity;
+    let mut safe_mfp = oneshot_mfp_plan(mfp)?;
+    let (permute, thinning) = mz_expr::permutation_for_arrangement(key, input_arity);
     safe_mfp.permute(permute, key.len() + thinning.len());
     Ok(safe_mfp)
 }
 
 /// Determine if the dataflow plan can be implemented without an actual dataflow.
 ///
-/// If the optimized plan is a `Constant` or a `Get` of a maintained arrangement,
-/// we can avoid building a dataflow (and either just return the results, or peek
-/// out of the arrangement, respectively).
+/// If the optimized plan is a `Constant`, a `Get` of a maintained arrangement, or
+/// a `Get` of a materialized view, we can avoid building a dataflow (and either
+/// just return the results, peek out of the arrangement, or read the persist shard
+/// of the materialized view, respectively).
 pub fn create_fast_path_plan<T: timely::progress::Timestamp>(
     dataflow_plan: &mut DataflowDescription<mz_expr::OptimizedMirRelationExpr, (), T>,
     view_id: GlobalId,
+    catalog: &CatalogState,
 ) -> Result<Option<FastPathPlan>, AdapterError> {
     // At this point, `dataflow_plan` contains our best optimized dataflow.
     // We will check the plan to see if there is a fast path to escape full dataflow construction.
@@ -202,7 +237,6 @@ pub fn create_fast_path_plan<T: timely::progress::Timestamp>(
             match mir {
                 mz_expr::MirRelationExpr::Get { id, .. } => {
                     // Just grab any arrangement
-                    // Nothing to be done if an arrangement does not exist
                     for (index_id, (desc, _typ, _monotonic)) in dataflow_plan.index_imports.iter() {
                         if Id::Global(desc.on_id) == *id {
                             return Ok(Some(FastPathPlan::PeekExisting(
@@ -212,6 +246,18 @@ pub fn create_fast_path_plan<T: timely::progress::Timestamp>(
                             )));
                         }
                     }
+                    // If an arrangement does not exist, a materialized view can
+                    // still be read directly out of its persist shard.
+                    if let Id::Global(id) = id {
+                        if dataflow_plan.source_imports.contains_key(id)
+                            && catalog.get_entry(id).is_materialized_view()
+                        {
+                            return Ok(Some(FastPathPlan::PeekPersist(
+                                *id,
+                                oneshot_mfp_plan(mfp)?,
+                            )));
+                        }
+                    }
                 }
                 mz_expr::MirRelationExpr::Join { implementation, .. } => {
                     if let mz_expr::JoinImplementation::IndexedFilter(id, key, vals) =
@@ -258,7 +304,14 @@ impl crate::coord::Coordinator {
         thinned_arity: usize,
     ) -> Result<PeekPlan, AdapterError> {
         // try to produce a `FastPathPlan`
-        let fast_path_plan = create_fast_path_plan(&mut dataflow, view_id)?;
+        let fast_path_plan = create_fast_path_plan(&mut dataflow, view_id, self.catalog.state())?;
+        // Reading a persist shard at a time that is not yet complete would wait for the
+        // materialized view to catch up, which must not block the coordinator, so we only
+        // read the shard directly if its contents at the `as_of` are available.
+        let fast_path_plan = match fast_path_plan {
+            Some(FastPathPlan::PeekPersist(id, _)) if !self.is_readable(id, &dataflow) => None,
+            fast_path_plan => fast_path_plan,
+        };
         // derive a PeekPlan from the optional FastPathPlan
         let peek_plan = fast_path_plan.map_or_else(
             // finalize the dataflow and produce a PeekPlan::SlowPath as a default
@@ -287,6 +340,24 @@ impl crate::coord::Coordinator {
         Ok(peek_plan)
     }
 
+    /// Reports whether the contents of the storage collection `id` are available at the
+    /// `as_of` of `dataflow`.
+    fn is_readable(
+        &self,
+        id: GlobalId,
+        dataflow: &DataflowDescription<OptimizedMirRelationExpr>,
+    ) -> bool {
+        let Some(as_of) = dataflow.as_of.as_ref() else {
+            return false;
+        };
+        match self.controller.storage.collection(id) {
+            Ok(collection) => as_of
+                .iter()
+                .all(|time| !collection.write_frontier.less_equal(time)),
+            Err(_) => false,
+        }
+    }
+
     /// Implements a peek plan produced by `create_plan` above.
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn implement_peek_plan(
@@ -341,6 +412,51 @@ impl crate::coord::Coordinator {
 
         let timestamp = timestamp_context.timestamp_or_default();
 
+        // If the view can be read out of the persist shard of a materialized view, we read it
+        // without involving the compute layer. Only validating the read happens here; the
+        // contents are fetched and filtered once the response is awaited.
+        if let PeekPlan::FastPath(FastPathPlan::PeekPersist(id, map_filter_project)) = fast_path {
+            let snapshot = self
+                .controller
+                .storage
+                .snapshot_and_map_filter(id, timestamp, map_filter_project)
+                .await?;
+            let max_result_size = self.catalog.system_config().max_result_size();
+            let rows_rx = async move {
+                let rows = match snapshot.await {
+                    Ok(rows) => rows,
+                    Err(e) => return PeekResponseUnary::Error(e.to_string()),
+                };
+                let mut results = Vec::new();
+                for (row, count) in consolidate_constant_updates(rows) {
+                    if count < 0 {
+                        return PeekResponseUnary::Error(format!(
+                            "Invalid data in source, saw retractions ({}) for row that does not exist: {:?}",
+                            -count, row,
+                        ));
+                    }
+                    if count > 0 {
+                        let count = usize::cast_from(
+                            u64::try_from(count).expect("known to be positive from check above"),
+                        );
+                        results.push((
+                            row,
+                            NonZeroUsize::new(count)
+                                .expect("known to be non-zero from check above"),
+                        ));
+                    }
+                }
+                match finishing.finish(results, max_result_size) {
+                    Ok(rows) => PeekResponseUnary::Rows(rows),
+                    Err(e) => PeekResponseUnary::Error(e),
+                }
+            };
+            return Ok(crate::ExecuteResponse::SendingRows {
+                future: Box::pin(rows_rx),
+                span: tracing::Span::current(),
+            });
+        }
+
         // The remaining cases are a peek into a maintained arrangement, or building a dataflow.
         // In both cases we will want to peek, and the main difference is that we might want to
         // build a dataflow and drop it once the peek is issued. The peeks are also constructed
@@ -599,6 +715,18 @@ mod tests {
                 .into_nontemporal()
                 .expect("invalid nontemporal"),
         );
+        let persist = FastPathPlan::PeekPersist(
+            GlobalId::User(12),
+            MapFilterProject::new(2)
+                .filter(Some(
+                    MirScalarExpr::column(1).call_unary(UnaryFunc::IsNull(IsNull)),
+                ))
+                .project([0])
+                .into_plan()
+                .expect("invalid plan")
+                .into_nontemporal()
+                .expect("invalid nontemporal"),
+        );
 
         let humanizer = DummyHumanizer;
         let ctx_gen = || RenderingContext::new(Indent::default(), &humanizer);
@@ -606,10 +734,12 @@ mod tests {
         let constant_err_exp = "Error \"division by zero\"\n";
         let no_lookup_exp = "Project (#1, #4)\n  Map ((#0 OR #2))\n    ReadExistingIndex u10\n";
         let lookup_exp = "Filter (#0) IS NULL\n  ReadExistingIndex u11 lookup_value=(5)\n";
+        let persist_exp = "Project (#0)\n  Filter (#1) IS NULL\n    ReadStorage u12\n";
 
         assert_eq!(text_string_at(&constant_err, ctx_gen), constant_err_exp);
         assert_eq!(text_string_at(&no_lookup, ctx_gen), no_lookup_exp);
         assert_eq!(text_string_at(&lookup, ctx_gen), lookup_exp);
+        assert_eq!(text_string_at(&persist, ctx_gen), persist_exp);
 
         let mut constant_rows = vec![
             (Row::pack(Some(Datum::String("hello"))), 1),
@@ -18,6 +18,7 @@ use futures::future::BoxFuture;
 
 use maplit::btreeset;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
+use timely::PartialOrder;
 use tokio::sync::{mpsc, oneshot, OwnedMutexGuard};
 use tracing::{event, warn, Level};
 
@@ -31,34 +32,38 @@ use mz_controller::clusters::{
     ClusterConfig, ClusterId, ReplicaAllocation, ReplicaConfig, ReplicaId, ReplicaLogging,
     DEFAULT_REPLICA_LOGGING_INTERVAL_MICROS,
 };
+use mz_expr::visit::Visit;
 use mz_expr::{
-    permutation_for_arrangement, CollectionPlan, MirRelationExpr, MirScalarExpr,
+    permutation_for_arrangement, CollectionPlan, Id, MirRelationExpr, MirScalarExpr,
     OptimizedMirRelationExpr, RowSetFinishing,
 };
+use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::explain::{ExplainFormat, Explainee};
-use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, RowArena, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, Timestamp};
 use mz_sql::ast::{ExplainStage, IndexOptionName, ObjectType};
 use mz_sql::catalog::CatalogItem as SqlCatalogItem;
 use mz_sql::catalog::{CatalogCluster, CatalogError, CatalogItemType, CatalogTypeDetails};
 use mz_sql::names::QualifiedObjectName;
 use mz_sql::plan::{
-    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
-    AlterOptionParameter, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan,
-    AlterSystemResetPlan, AlterSystemSetPlan, CopyFormat, CreateClusterPlan,
-    CreateClusterReplicaPlan, CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan,
-    CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
-    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DropClusterReplicasPlan,
-    DropClustersPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan,
-    ExplainPlan, FetchPlan, IndexOption, InsertPlan, MaterializedView, MutationKind,
-    OptimizerConfig, PeekPlan, Plan, PlanKind, QueryWhen, RaisePlan, ReadThenWritePlan,
-    ResetVariablePlan, RotateKeysPlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
-    SourceSinkClusterConfig, SubscribeFrom, SubscribePlan, View,
+    AlterClusterPlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
+    AlterMaterializedViewPlan, AlterOptionParameter, AlterSecretPlan, AlterSinkPlan,
+    AlterSourcePlan, AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan,
+    AlterTablePlan, CopyFormat, CreateClusterPlan, CreateClusterReplicaPlan, CreateConnectionPlan,
+    CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan,
+    CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, DropClusterReplicasPlan, DropClustersPlan, DropDatabasePlan,
+    DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan, FetchPlan, IndexOption,
+    InsertPlan, MaterializedView, MutationKind, OptimizerConfig, PeekPlan, Plan, PlanKind,
+    QueryWhen, RaisePlan, ReadThenWritePlan, ResetVariablePlan, RotateKeysPlan, SendDiffsPlan,
+    SetVariablePlan, ShowVariablePlan, SourceSinkClusterConfig, SubscribeFrom, SubscribePlan, View,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{CollectionDescription, DataSource, ReadPolicy, StorageError};
 use mz_storage_client::types::sinks::StorageSinkConnectionBuilder;
 use mz_storage_client::types::sources::{IngestionDescription, SourceExport};
+use mz_transform::StatisticsOracle;
 
 use crate::catalog::builtin::{
     INFORMATION_SCHEMA, MZ_CATALOG_SCHEMA, MZ_INTERNAL_SCHEMA, MZ_INTROSPECTION_ROLE,
@@ -72,11 +77,13 @@ use crate::command::{Command, ExecuteResponse, Response};
 use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, DeferredPlan, PendingWriteTxn};
 use crate::coord::dataflows::{prep_relation_expr, prep_scalar_expr, ExprPrepStyle};
 use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::statistics::OneShotStatisticsOracle;
 use crate::coord::timeline::TimelineContext;
 use crate::coord::timestamp_selection::TimestampContext;
 use crate::coord::{
-    peek, Coordinator, Message, PendingReadTxn, PendingTxn, RealTimeRecencyContext, SendDiffs,
-    SinkConnectionReady, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
+    peek, Coordinator, Message, PendingClusterReconfiguration, PendingMaterializedViewReplacement,
+    PendingReadTxn, PendingTxn, RealTimeRecencyContext, SendDiffs, SinkConnectionReady,
+    DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
 };
 use crate::error::AdapterError;
 use crate::explain::optimizer_trace::OptimizerTrace;
@@ -282,6 +289,21 @@ impl Coordinator {
                 }
                 self.sequence_end_transaction(tx, session, action);
             }
+            Plan::Savepoint(plan) => {
+                let result = session.create_savepoint(plan.name);
+                tx.send(result.map(|()| ExecuteResponse::CreatedSavepoint), session);
+            }
+            Plan::ReleaseSavepoint(plan) => {
+                let result = session.release_savepoint(&plan.name);
+                tx.send(result.map(|()| ExecuteResponse::ReleasedSavepoint), session);
+            }
+            Plan::RollbackToSavepoint(plan) => {
+                let result = session.rollback_to_savepoint(&plan.name);
+                tx.send(
+                    result.map(|()| ExecuteResponse::RolledBackToSavepoint),
+                    session,
+                );
+            }
             Plan::Peek(plan) => {
                 self.sequence_peek_begin(tx, session, plan).await;
             }
@@ -338,12 +360,22 @@ impl Coordinator {
             Plan::AlterSecret(plan) => {
                 tx.send(self.sequence_alter_secret(&session, plan).await, session);
             }
+            Plan::AlterCluster(plan) => {
+                tx.send(self.sequence_alter_cluster(&session, plan).await, session);
+            }
             Plan::AlterSink(plan) => {
                 tx.send(self.sequence_alter_sink(&session, plan).await, session);
             }
             Plan::AlterSource(plan) => {
                 tx.send(self.sequence_alter_source(&session, plan).await, session);
             }
+            Plan::AlterTable(plan) => {
+                tx.send(self.sequence_alter_table(&session, plan).await, session);
+            }
+            Plan::AlterMaterializedView(plan) => {
+                self.sequence_alter_materialized_view(tx, session, plan, depends_on)
+                    .await;
+            }
             Plan::AlterSystemSet(plan) => {
                 tx.send(
                     self.sequence_alter_system_set(&session, plan).await,
@@ -596,6 +628,7 @@ impl Coordinator {
                                 data_source,
                                 since: None,
                                 status_collection_id,
+                                layout: None,
                             },
                         )])
                         .await
@@ -796,10 +829,22 @@ impl Coordinator {
     async fn sequence_create_cluster(
         &mut self,
         session: &Session,
-        CreateClusterPlan { name, replicas }: CreateClusterPlan,
+        CreateClusterPlan {
+            name,
+            replicas,
+            managed,
+        }: CreateClusterPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
         tracing::debug!("sequence_create_cluster");
 
+        if let Some(managed) = &managed {
+            // Validate the size even if the cluster has no replicas.
+            self.catalog.ensure_valid_replica_size(
+                &managed.size,
+                self.catalog.system_config().allowed_cluster_replica_sizes(),
+            )?;
+        }
+
         let id = self.catalog.allocate_user_cluster_id().await?;
         // The catalog items for the arranged introspection sources are shared between all replicas
         // of a compute instance, so we create them unconditionally during instance creation.
@@ -811,6 +856,7 @@ impl Coordinator {
             id,
             name: name.clone(),
             linked_object_id: None,
+            managed,
             arranged_introspection_sources,
         }];
 
@@ -1145,7 +1191,11 @@ impl Coordinator {
         let table = catalog::Table {
             create_sql: table.create_sql,
             desc: table.desc,
+            storage_desc: table.storage_desc,
+            layout: Some(table.layout),
             defaults: table.defaults,
+            constraints: table.constraints,
+            expire_rows_after: table.expire_rows_after,
             conn_id,
             depends_on,
             custom_logical_compaction_window: None,
@@ -1163,7 +1213,7 @@ impl Coordinator {
                 // Determine the initial validity for the table.
                 let since_ts = self.peek_local_write_ts();
 
-                let collection_desc = table.desc.clone().into();
+                let collection_desc = table.collection_description();
                 self.controller
                     .storage
                     .create_collections(vec![(table_id, collection_desc)])
@@ -1180,6 +1230,7 @@ impl Coordinator {
                     Some(DEFAULT_LOGICAL_COMPACTION_WINDOW_TS),
                 )
                 .await;
+                self.initialize_table_write_state(table_id, &table);
 
                 // Advance the new table to a timestamp higher than the current read timestamp so
                 // that the table is immediately readable.
@@ -1573,6 +1624,7 @@ impl Coordinator {
                             data_source: DataSource::Other,
                             since: Some(as_of),
                             status_collection_id: None,
+                            layout: None,
                         },
                     )])
                     .await
@@ -1999,6 +2051,7 @@ impl Coordinator {
             Ok((Some(TransactionOps::Writes(writes)), write_lock_guard)) => {
                 self.submit_write(PendingWriteTxn::User {
                     writes,
+                    table_reads: None,
                     write_lock_guard,
                     pending_txn: PendingTxn {
                         client_transmitter: tx,
@@ -2009,10 +2062,36 @@ impl Coordinator {
                 });
                 return;
             }
-            Ok((Some(TransactionOps::Peeks(timestamp_context)), _))
-                if session.vars().transaction_isolation()
-                    == &IsolationLevel::StrictSerializable =>
-            {
+            Ok((
+                Some(TransactionOps::ReadWrite {
+                    timestamp_context,
+                    table_reads,
+                    writes,
+                }),
+                write_lock_guard,
+            )) => {
+                let read_ts = *timestamp_context
+                    .timestamp()
+                    .expect("read-write transactions read at a timestamp");
+                self.submit_write(PendingWriteTxn::User {
+                    writes,
+                    table_reads: Some((read_ts, table_reads)),
+                    write_lock_guard,
+                    pending_txn: PendingTxn {
+                        client_transmitter: tx,
+                        response,
+                        session,
+                        action,
+                    },
+                });
+                return;
+            }
+            Ok((
+                Some(TransactionOps::Peeks {
+                    timestamp_context, ..
+                }),
+                _,
+            )) if session.vars().transaction_isolation() == &IsolationLevel::StrictSerializable => {
                 self.strict_serializable_reads_tx
                     .send(PendingReadTxn::Read {
                         txn: PendingTxn {
@@ -2048,7 +2127,9 @@ impl Coordinator {
 
         if let EndTransactionAction::Commit = action {
             if let (Some(mut ops), write_lock_guard) = txn.into_ops_and_lock_guard() {
-                if let TransactionOps::Writes(writes) = &mut ops {
+                if let TransactionOps::Writes(writes) | TransactionOps::ReadWrite { writes, .. } =
+                    &mut ops
+                {
                     for WriteOp { id, .. } in &mut writes.iter() {
                         // Re-verify this id exists.
                         let _ = self.catalog.try_get_entry(id).ok_or_else(|| {
@@ -2059,6 +2140,18 @@ impl Coordinator {
                     // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
                     writes.retain(|WriteOp { rows, .. }| !rows.is_empty());
                 }
+                // A read-write transaction without any writes left only read.
+                let ops = match ops {
+                    TransactionOps::ReadWrite {
+                        timestamp_context,
+                        table_reads,
+                        writes,
+                    } if writes.is_empty() => TransactionOps::Peeks {
+                        timestamp_context,
+                        table_reads: Some(table_reads),
+                    },
+                    ops => ops,
+                };
                 return Ok((Some(ops), write_lock_guard));
             }
         }
@@ -2225,6 +2318,8 @@ impl Coordinator {
 
         check_no_invalid_log_reads(&self.catalog, cluster, &source_ids, &mut target_replica)?;
 
+        let source = self.overlay_transaction_writes(session, source, &source_ids)?;
+
         let id_bundle = self
             .index_oracle(cluster.id)
             .sufficient_collections(&source_ids);
@@ -2291,8 +2386,18 @@ impl Coordinator {
         if matches!(session.transaction(), &TransactionStatus::InTransaction(_))
             || when == QueryWhen::Immediately
         {
-            session
-                .add_transaction_ops(TransactionOps::Peeks(peek_plan.timestamp_context.clone()))?;
+            // Only reads at the transaction's timestamp can be validated
+            // against concurrent writes, so only they can be part of a
+            // read-write transaction.
+            let table_reads = if when == QueryWhen::Immediately {
+                table_read_dependencies(&self.catalog, source_ids.iter().copied())
+            } else {
+                None
+            };
+            session.add_transaction_ops(TransactionOps::Peeks {
+                timestamp_context: peek_plan.timestamp_context.clone(),
+                table_reads,
+            })?;
         }
 
         let timestamp = peek_plan.timestamp_context.timestamp().cloned();
@@ -2375,6 +2480,9 @@ impl Coordinator {
             )?
             .timestamp_context
         };
+        if let Some(timestamp) = timestamp_context.timestamp() {
+            self.set_statement_execution_timestamp(conn_id, *timestamp);
+        }
 
         if in_immediate_multi_stmt_txn {
             // If there are no `txn_reads`, then this must be the first query in the transaction
@@ -2467,7 +2575,11 @@ impl Coordinator {
         );
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(&mut dataflow, &builder.index_oracle())?;
+        mz_transform::optimize_dataflow(
+            &mut dataflow,
+            &builder.index_oracle(),
+            &OneShotStatisticsOracle(builder.statistics),
+        )?;
 
         // At this point, `dataflow_plan` contains our best optimized dataflow.
         // We will check the plan to see if there is a fast path to escape full dataflow construction.
@@ -2564,6 +2676,9 @@ impl Coordinator {
                 .determine_timestamp(session, &id_bundle, &when, cluster_id, timeline, None)?
                 .timestamp_context;
             let frontier_ts = frontier.timestamp_or_default();
+            if frontier.contains_timestamp() {
+                coord.set_statement_execution_timestamp(session.conn_id(), frontier_ts);
+            }
 
             let up_to = up_to
                 .map(|expr| coord.evaluate_when(expr, session))
@@ -2763,7 +2878,17 @@ impl Coordinator {
                     },
                 )?;
 
-                mz_transform::optimize_dataflow(&mut dataflow, &self.index_oracle(cluster))?;
+                // Queries are explained as they would be peeked.
+                let one_shot_statistics = OneShotStatisticsOracle(&self.collection_statistics);
+                let statistics: &dyn StatisticsOracle = match explainee {
+                    Explainee::Query => &one_shot_statistics,
+                    Explainee::Dataflow(_) => &self.collection_statistics,
+                };
+                mz_transform::optimize_dataflow(
+                    &mut dataflow,
+                    &self.index_oracle(cluster),
+                    statistics,
+                )?;
 
                 let used_indexes = dataflow
                     .index_imports
@@ -2772,9 +2897,11 @@ impl Coordinator {
                     .collect::<Vec<GlobalId>>();
 
                 let fast_path_plan = match explainee {
-                    Explainee::Query => {
-                        peek::create_fast_path_plan(&mut dataflow, GlobalId::Explain)?
-                    }
+                    Explainee::Query => peek::create_fast_path_plan(
+                        &mut dataflow,
+                        GlobalId::Explain,
+                        self.catalog.state(),
+                    )?,
                     _ => None,
                 };
 
@@ -3050,6 +3177,15 @@ impl Coordinator {
             returning = plan.returning.len(),
         );
 
+        // A transaction's writes to multiple tables must become visible
+        // atomically, which not every storage layer can do.
+        if !self.controller.storage.supports_atomic_appends() {
+            if let Some(txn) = session.transaction().inner() {
+                if txn.ops.writes().iter().any(|op| op.id != plan.id) {
+                    return Err(AdapterError::MultiTableWriteTransaction);
+                }
+            }
+        }
         session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
             id: plan.id,
             rows: plan.updates,
@@ -3216,7 +3352,14 @@ impl Coordinator {
         mut session: Session,
         plan: ReadThenWritePlan,
     ) {
-        guard_write_critical_section!(self, tx, session, Plan::ReadThenWrite(plan));
+        // In a multi-statement transaction, the read and the write are part of
+        // a read-write transaction, whose reads are validated against
+        // concurrent writes when it commits. Otherwise, we hold the write lock
+        // so that no writes can occur between the read and the write.
+        let in_multi_stmt_txn = session.transaction().is_in_multi_statement_transaction();
+        if !in_multi_stmt_txn {
+            guard_write_critical_section!(self, tx, session, Plan::ReadThenWrite(plan));
+        }
 
         let ReadThenWritePlan {
             id,
@@ -3251,43 +3394,25 @@ impl Coordinator {
         // Ensure all objects `selection` depends on are valid for
         // `ReadThenWrite` operations, i.e. they do not refer to any objects
         // whose notion of time moves differently than that of user tables.
-        // `true` indicates they're all valid; `false` there are > 0 invalid
-        // dependencies.
         //
         // This limitation is meant to ensure no writes occur between this read
         // and the subsequent write.
-        fn validate_read_dependencies(catalog: &Catalog, id: &GlobalId) -> bool {
-            use CatalogItemType::*;
-            match catalog.try_get_entry(id) {
-                Some(entry) => match entry.item().typ() {
-                    typ @ (Func | View | MaterializedView) => {
-                        let valid_id = id.is_user() || matches!(typ, Func);
-                        valid_id
-                            && (
-                                // empty `uses` indicates either system func or
-                                // view created from constants
-                                entry.uses().is_empty()
-                                    || entry
-                                        .uses()
-                                        .iter()
-                                        .all(|id| validate_read_dependencies(catalog, id))
-                            )
-                    }
-                    Source | Secret | Connection => false,
-                    // Cannot select from sinks or indexes
-                    Sink | Index => unreachable!(),
-                    Table => id.is_user(),
-                    Type => true,
-                },
-                None => false,
-            }
+        if table_read_dependencies(&self.catalog, selection.depends_on()).is_none() {
+            tx.send(Err(AdapterError::InvalidTableMutationSelection), session);
+            return;
         }
 
-        for id in selection.depends_on() {
-            if !validate_read_dependencies(&self.catalog, &id) {
-                tx.send(Err(AdapterError::InvalidTableMutationSelection), session);
-                return;
-            }
+        // Calls to mz_now in `selection`, e.g. in the `WHERE` clause of a
+        // `DELETE`, are evaluated at the timestamp of the read. Assignments
+        // are evaluated outside of any dataflow, where mz_now has no value.
+        if assignments.values().any(|expr| expr.contains_temporal()) {
+            tx.send(
+                Err(AdapterError::Unsupported(
+                    "calls to mz_now in write statements",
+                )),
+                session,
+            );
+            return;
         }
 
         let (peek_tx, peek_rx) = oneshot::channel();
@@ -3297,7 +3422,13 @@ impl Coordinator {
             session,
             PeekPlan {
                 source: selection,
-                when: QueryWhen::Freshest,
+                // Reads in a transaction happen at the transaction's
+                // timestamp.
+                when: if in_multi_stmt_txn {
+                    QueryWhen::Immediately
+                } else {
+                    QueryWhen::Freshest
+                },
                 finishing,
                 copy_to: None,
             },
@@ -3448,9 +3579,13 @@ impl Coordinator {
                 diffs
             };
 
-            // We need to clear out the timestamp context so the write doesn't fail due to a
-            // read only transaction.
-            let timestamp_context = session.take_transaction_timestamp_context();
+            // Outside of a multi-statement transaction, we need to clear out the timestamp
+            // context so the write doesn't fail due to a read only transaction.
+            let timestamp_context = if in_multi_stmt_txn {
+                None
+            } else {
+                session.take_transaction_timestamp_context()
+            };
             // No matter what isolation level the client is using, we must linearize this
             // read. The write will be performed right after this, as part of a single
             // transaction, so the write must have a timestamp greater than or equal to the
@@ -3500,6 +3635,60 @@ impl Coordinator {
         });
     }
 
+    /// Adds the uncommitted writes of the session's transaction to the reads
+    /// of the tables they target in `source`, so that the transaction observes
+    /// its own writes.
+    ///
+    /// Returns an error if `source` reads a written table through a view or
+    /// materialized view, whose contents cannot reflect the writes.
+    fn overlay_transaction_writes(
+        &self,
+        session: &Session,
+        mut source: MirRelationExpr,
+        source_ids: &BTreeSet<GlobalId>,
+    ) -> Result<MirRelationExpr, AdapterError> {
+        let mut writes: BTreeMap<GlobalId, Vec<(Row, Diff)>> = BTreeMap::new();
+        for WriteOp { id, rows } in session.transaction_writes() {
+            writes.entry(*id).or_default().extend(rows.iter().cloned());
+        }
+        if writes.is_empty() {
+            return Ok(source);
+        }
+
+        for id in source_ids {
+            if self.catalog.get_entry(id).is_table() {
+                continue;
+            }
+            let reads_written_table = table_read_dependencies(&self.catalog, [*id])
+                .unwrap_or_default()
+                .iter()
+                .any(|table| writes.contains_key(table));
+            if reads_written_table {
+                return Err(AdapterError::Unsupported(
+                    "reads of views of tables written to earlier in the same transaction",
+                ));
+            }
+        }
+
+        source.visit_mut_post(&mut |expr| {
+            if let MirRelationExpr::Get {
+                id: Id::Global(id),
+                typ,
+            } = expr
+            {
+                if let Some(rows) = writes.get(id) {
+                    // The writes needn't respect the keys of the table.
+                    let writes = MirRelationExpr::Constant {
+                        rows: Ok(rows.clone()),
+                        typ: RelationType::new(typ.column_types.clone()),
+                    };
+                    *expr = expr.take_dangerous().union(writes);
+                }
+            }
+        })?;
+        Ok(source)
+    }
+
     async fn sequence_alter_item_rename(
         &mut self,
         session: &Session,
@@ -3584,6 +3773,167 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Secret))
     }
 
+    async fn sequence_alter_cluster(
+        &mut self,
+        session: &Session,
+        AlterClusterPlan {
+            id,
+            name,
+            config,
+            replica_config,
+        }: AlterClusterPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        if self.pending_cluster_reconfigurations.contains_key(&id) {
+            coord_bail!(
+                "cluster {} is still being resized; try again once its old replicas have been \
+                dropped",
+                name.quoted()
+            );
+        }
+        let mz_sql::plan::ReplicaConfig::Managed { size, compute, .. } = replica_config else {
+            coord_bail!("replicas of managed clusters must be managed");
+        };
+        // Validate the size even if no replicas of the new size are created.
+        self.catalog.ensure_valid_replica_size(
+            &size,
+            self.catalog.system_config().allowed_cluster_replica_sizes(),
+        )?;
+
+        let cluster = self.catalog.get_cluster(id);
+        let current = cluster
+            .managed
+            .clone()
+            .expect("only managed clusters can be altered");
+        let replication_factor = usize::cast_from(config.replication_factor);
+
+        // The replicas that remain part of the cluster, oldest first. When the
+        // size changes, all existing replicas are retired once the replicas of
+        // the new size have hydrated. When only the replication factor
+        // shrinks, the newest replicas are dropped right away.
+        let mut retained: Vec<ReplicaId> = cluster.replicas_by_id.keys().copied().collect();
+        let mut retired = vec![];
+        if config.size != current.size {
+            retired = std::mem::take(&mut retained);
+        }
+        let mut dropped = vec![];
+        if retained.len() > replication_factor {
+            dropped = retained.split_off(replication_factor);
+        }
+        let new_names: Vec<_> = (1..)
+            .map(|i| format!("r{i}"))
+            .filter(|name| !cluster.replica_id_by_name.contains_key(name))
+            .take(replication_factor - retained.len())
+            .collect();
+        if new_names.is_empty() {
+            // There are no new replicas to wait for.
+            dropped.append(&mut retired);
+        }
+        // Sources that cannot be ingested by several replicas at once must
+        // move to the new replicas without any overlap.
+        let supports_overlap =
+            cluster
+                .bound_objects
+                .iter()
+                .all(|id| match self.catalog.get_entry(id).source_desc() {
+                    Ok(Some(desc)) => desc.connection.supports_active_replication(),
+                    _ => true,
+                });
+        if !supports_overlap {
+            dropped.append(&mut retired);
+        }
+
+        // Spread the new replicas across the availability zones that are
+        // least used by the remaining replicas.
+        let azs = self.catalog.state().availability_zones();
+        let mut n_replicas_per_az = azs
+            .iter()
+            .map(|s| (s.clone(), 0))
+            .collect::<BTreeMap<_, _>>();
+        for replica_id in &retained {
+            let replica = &cluster.replicas_by_id[replica_id];
+            if let Some(az) = replica.config.location.availability_zone() {
+                *n_replicas_per_az.get_mut(az).expect("unknown AZ") += 1;
+            }
+        }
+
+        let dropped: Vec<_> = dropped.into_iter().map(|r| (id, r)).collect();
+        let mut ops = vec![catalog::Op::AlterCluster { id, config }];
+        ops.extend(
+            self.catalog
+                .drop_cluster_replica_ops(&dropped, &mut BTreeSet::new()),
+        );
+
+        let mut new_replicas = BTreeSet::new();
+        for replica_name in new_names {
+            let availability_zone = Self::choose_az(&n_replicas_per_az);
+            *n_replicas_per_az
+                .get_mut(&availability_zone)
+                .expect("availability zone does not exist") += 1;
+            let location = SerializedReplicaLocation::Managed {
+                size: size.clone(),
+                availability_zone,
+                az_user_specified: false,
+            };
+
+            let logging = if let Some(config) = compute.introspection {
+                let sources = self
+                    .catalog
+                    .allocate_persisted_introspection_sources()
+                    .await;
+                let views = self.catalog.allocate_persisted_introspection_views().await;
+                ReplicaLogging {
+                    log_logging: config.debugging,
+                    interval: Some(config.interval),
+                    sources,
+                    views,
+                }
+            } else {
+                ReplicaLogging::default()
+            };
+
+            let config = ReplicaConfig {
+                location: self.catalog.concretize_replica_location(
+                    location,
+                    self.catalog.system_config().allowed_cluster_replica_sizes(),
+                )?,
+                compute: ComputeReplicaConfig {
+                    logging,
+                    idle_arrangement_merge_effort: compute.idle_arrangement_merge_effort,
+                },
+            };
+
+            let replica_id = self.catalog.allocate_replica_id().await?;
+            new_replicas.insert(replica_id);
+            ops.push(catalog::Op::CreateClusterReplica {
+                cluster_id: id,
+                id: replica_id,
+                name: replica_name,
+                config,
+            });
+        }
+
+        self.catalog_transact(Some(session), ops).await?;
+
+        let replicas: Vec<_> = new_replicas.iter().map(|r| (id, *r)).collect();
+        self.create_cluster_replicas(&replicas).await;
+
+        if !retired.is_empty() {
+            let targets = self.cluster_hydration_targets(id);
+            let storage_targets = self.cluster_storage_hydration_targets(id);
+            self.pending_cluster_reconfigurations.insert(
+                id,
+                PendingClusterReconfiguration {
+                    new_replicas,
+                    old_replicas: retired.into_iter().collect(),
+                    targets,
+                    storage_targets,
+                },
+            );
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+    }
+
     async fn sequence_alter_sink(
         &mut self,
         session: &Session,
@@ -3635,6 +3985,214 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
     }
 
+    async fn sequence_alter_table(
+        &mut self,
+        session: &Session,
+        AlterTablePlan { id, table }: AlterTablePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let (table, dependents) = self.catalog.plan_altered_table(id, table.create_sql)?;
+        let old_table = match self.catalog.get_entry(&id).item() {
+            CatalogItem::Table(table) => table.clone(),
+            _ => unreachable!("plan_altered_table checked that the entry is a table"),
+        };
+        let (storage_desc, layout) = match &table {
+            CatalogItem::Table(table) => (
+                table.storage_desc.clone(),
+                table.layout.clone().expect("user tables have a layout"),
+            ),
+            _ => unreachable!("plan_altered_table produces a table"),
+        };
+
+        let mut ops = vec![catalog::Op::AlterTable { id, table }];
+        // The dataflows of the indexes on the table arrange its rows as they
+        // were before the alteration, so the indexes are replaced with new
+        // ones of the same name.
+        let mut indexes = vec![];
+        for (dependent_id, item) in dependents {
+            let name = self.catalog.get_entry(&dependent_id).name().clone();
+            match item {
+                CatalogItem::Index(index) => {
+                    let index_id = self.catalog.allocate_user_id().await?;
+                    let oid = self.catalog.allocate_oid()?;
+                    indexes.push((index_id, index.cluster_id));
+                    ops.push(catalog::Op::DropItem(dependent_id));
+                    ops.push(catalog::Op::CreateItem {
+                        id: index_id,
+                        oid,
+                        name,
+                        item: CatalogItem::Index(index),
+                    });
+                }
+                item => ops.push(catalog::Op::UpdateItem {
+                    id: dependent_id,
+                    name,
+                    to_item: item,
+                }),
+            }
+        }
+
+        let dataflows = self
+            .catalog_transact_with(Some(session), ops, |txn| {
+                indexes
+                    .into_iter()
+                    .map(|(index_id, cluster_id)| {
+                        let mut builder = txn.dataflow_builder(cluster_id);
+                        Ok((builder.build_index_dataflow(index_id)?, cluster_id))
+                    })
+                    .collect::<Result<Vec<_>, AdapterError>>()
+            })
+            .await?;
+
+        self.alter_table_write_state(id, &old_table);
+
+        // Dataflows created from now on read the table's rows with its new
+        // layout. Existing dataflows continue to read them with the old one.
+        self.controller
+            .storage
+            .alter_collection_desc(id, storage_desc, layout)
+            .unwrap_or_terminate("cannot fail to alter collection");
+        for (dataflow, cluster_id) in dataflows {
+            self.must_ship_dataflow(dataflow, cluster_id).await;
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Table))
+    }
+
+    /// Starts replacing the definition of a materialized view.
+    ///
+    /// The client is not responded to until the dataflow of the new definition
+    /// has caught up with the dataflow of the current definition, at which
+    /// point [`Coordinator::advance_materialized_view_replacements`] swaps them.
+    async fn sequence_alter_materialized_view(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        plan: AlterMaterializedViewPlan,
+        depends_on: Vec<GlobalId>,
+    ) {
+        let id = plan.id;
+        let or_replace = plan.or_replace;
+        match self
+            .start_materialized_view_replacement(plan, depends_on)
+            .await
+        {
+            Ok((materialized_view, cluster_id, sink_id)) => {
+                self.pending_materialized_view_replacements.insert(
+                    id,
+                    PendingMaterializedViewReplacement {
+                        materialized_view,
+                        cluster_id,
+                        sink_id,
+                        or_replace,
+                        session,
+                        tx,
+                    },
+                );
+            }
+            Err(err) => tx.send(Err(err), session),
+        }
+    }
+
+    /// Ships the read-only dataflow of a materialized view's new definition.
+    ///
+    /// Returns the materialized view with its new definition, the cluster that
+    /// maintains it, and the ID of the new dataflow's compute sink.
+    async fn start_materialized_view_replacement(
+        &mut self,
+        AlterMaterializedViewPlan {
+            id,
+            materialized_view:
+                MaterializedView {
+                    create_sql,
+                    expr: view_expr,
+                    column_names: _,
+                    cluster_id,
+                },
+            or_replace: _,
+        }: AlterMaterializedViewPlan,
+        depends_on: Vec<GlobalId>,
+    ) -> Result<(CatalogItem, ClusterId, GlobalId), AdapterError> {
+        let entry = self.catalog.get_entry(&id);
+        let name = entry.name().item.clone();
+        let desc = match entry.item() {
+            CatalogItem::MaterializedView(mview) => mview.desc.clone(),
+            _ => unreachable!("planned against a materialized view"),
+        };
+        if self
+            .pending_materialized_view_replacements
+            .contains_key(&id)
+        {
+            coord_bail!(
+                "materialized view {} is still being replaced; try again once its current \
+                replacement has taken effect",
+                name.quoted()
+            );
+        }
+
+        // Without replicas, the new definition would never catch up, and the client would wait
+        // for the replacement forever.
+        let cluster = self.catalog.get_cluster(cluster_id);
+        if cluster.replicas_by_id.is_empty() {
+            return Err(AdapterError::NoClusterReplicasAvailable(
+                cluster.name.clone(),
+            ));
+        }
+
+        // Objects that depend on the materialized view read it in the
+        // timeline of its current definition.
+        let timeline_context = self.validate_timeline_context(depends_on.clone())?;
+        if timeline_context != self.get_timeline_context(id) {
+            coord_bail!(
+                "cannot replace materialized view {}: new definition must use the same timeline",
+                name.quoted()
+            );
+        }
+
+        let log_names = depends_on
+            .iter()
+            .flat_map(|id| self.catalog.arranged_introspection_dependencies(*id))
+            .map(|id| self.catalog.get_entry(&id).name().item.clone())
+            .collect::<Vec<_>>();
+        if !log_names.is_empty() {
+            return Err(AdapterError::InvalidLogDependency {
+                object_type: "materialized view".into(),
+                log_names,
+            });
+        }
+
+        let sink_id = self.allocate_transient_id()?;
+        let internal_view_id = self.allocate_transient_id()?;
+
+        let optimized_expr = self.view_optimizer.optimize(view_expr)?;
+        // The storage collection retains the materialized view's current
+        // schema, with which the new definition has been checked to be
+        // compatible.
+        let mview = catalog::MaterializedView {
+            create_sql,
+            optimized_expr,
+            desc,
+            depends_on,
+            cluster_id,
+        };
+
+        let id_bundle = self
+            .index_oracle(cluster_id)
+            .sufficient_collections(&mview.depends_on);
+        let as_of = self.least_valid_read(&id_bundle);
+        let df = self
+            .dataflow_builder(cluster_id)
+            .build_materialized_view_replacement_dataflow(
+                id,
+                &mview,
+                sink_id,
+                as_of,
+                internal_view_id,
+            )?;
+        self.must_ship_dataflow(df, cluster_id).await;
+
+        Ok((CatalogItem::MaterializedView(mview), cluster_id, sink_id))
+    }
+
     fn extract_secret(
         &mut self,
         session: &Session,
@@ -3858,6 +4416,9 @@ impl Coordinator {
             | Plan::StartTransaction(_)
             | Plan::CommitTransaction
             | Plan::AbortTransaction
+            | Plan::Savepoint(_)
+            | Plan::ReleaseSavepoint(_)
+            | Plan::RollbackToSavepoint(_)
             | Plan::EmptyQuery
             | Plan::Declare(_)
             | Plan::Fetch(_)
@@ -3891,10 +4452,13 @@ impl Coordinator {
             | Plan::SendDiffs(_)
             | Plan::Insert(_)
             | Plan::AlterNoop(_)
+            | Plan::AlterCluster(_)
             | Plan::AlterIndexSetOptions(_)
             | Plan::AlterIndexResetOptions(_)
             | Plan::AlterSink(_)
             | Plan::AlterSource(_)
+            | Plan::AlterTable(_)
+            | Plan::AlterMaterializedView(_)
             | Plan::AlterItemRename(_)
             | Plan::AlterSecret(_)
             | Plan::AlterSystemSet(_)
@@ -3956,6 +4520,7 @@ impl Coordinator {
             id,
             name: name.clone(),
             linked_object_id: Some(linked_object_id),
+            managed: None,
             arranged_introspection_sources,
         });
         self.create_linked_cluster_replica_op(id, size, ops).await?;
@@ -4091,6 +4656,269 @@ impl Coordinator {
             self.create_cluster_replicas(&replicas).await;
         }
     }
+    /// Returns, for each compute collection maintained on the given cluster,
+    /// the time that a replica's write frontier must reach before the replica
+    /// counts as hydrated: the collection's current write frontier, but at
+    /// least one tick beyond its since, so that the replica must have
+    /// computed the collection's initial snapshot.
+    fn cluster_hydration_targets(&self, cluster_id: ClusterId) -> BTreeMap<GlobalId, Timestamp> {
+        let cluster = self.catalog.get_cluster(cluster_id);
+        let Ok(instance) = self.controller.compute.instance_ref(cluster_id) else {
+            return BTreeMap::new();
+        };
+        cluster
+            .bound_objects
+            .iter()
+            .filter_map(|id| {
+                // Storage objects bound to the cluster are not compute
+                // collections.
+                let collection = instance.collection(*id).ok()?;
+                let since = collection
+                    .read_capability()
+                    .iter()
+                    .map(|t| t.step_forward());
+                let upper = collection.write_frontier().iter().copied();
+                since.chain(upper).max().map(|target| (*id, target))
+            })
+            .collect()
+    }
+
+    /// Returns, for each storage collection ingested on the given cluster, the
+    /// time that a replica's write frontier must reach before the replica
+    /// counts as hydrated: one tick beyond the collection's current write
+    /// frontier, so that the replica must have ingested data that arrived
+    /// after the resize began.
+    ///
+    /// Sinks are exported by only one replica at a time, and move to a new
+    /// replica once the old replicas are dropped, so there is nothing to wait
+    /// for.
+    fn cluster_storage_hydration_targets(
+        &self,
+        cluster_id: ClusterId,
+    ) -> BTreeMap<GlobalId, Timestamp> {
+        let cluster = self.catalog.get_cluster(cluster_id);
+        let mut targets = BTreeMap::new();
+        for id in &cluster.bound_objects {
+            let Ok(collection) = self.controller.storage.collection(*id) else {
+                continue;
+            };
+            let DataSource::Ingestion(ingestion) = &collection.description.data_source else {
+                continue;
+            };
+            for export_id in ingestion.source_exports.keys() {
+                let Ok(export) = self.controller.storage.collection(*export_id) else {
+                    continue;
+                };
+                if let Some(upper) = export.write_frontier.as_option() {
+                    targets.insert(*export_id, upper.step_forward());
+                }
+            }
+        }
+        targets
+    }
+
+    /// Resumes the resizes of managed clusters that were interrupted by a
+    /// restart. Such clusters still have replicas whose size differs from the
+    /// cluster's configured size.
+    pub(crate) fn resume_cluster_reconfigurations(&mut self) {
+        let mut resumed = vec![];
+        for cluster in self.catalog.user_clusters() {
+            let Some(config) = &cluster.managed else {
+                continue;
+            };
+            let mut new_replicas = BTreeSet::new();
+            let mut old_replicas = BTreeSet::new();
+            for (replica_id, replica) in &cluster.replicas_by_id {
+                if replica.config.location.size() == Some(config.size.as_str()) {
+                    new_replicas.insert(*replica_id);
+                } else {
+                    old_replicas.insert(*replica_id);
+                }
+            }
+            if !new_replicas.is_empty() && !old_replicas.is_empty() {
+                resumed.push((cluster.id, new_replicas, old_replicas));
+            }
+        }
+        for (cluster_id, new_replicas, old_replicas) in resumed {
+            let targets = self.cluster_hydration_targets(cluster_id);
+            let storage_targets = self.cluster_storage_hydration_targets(cluster_id);
+            self.pending_cluster_reconfigurations.insert(
+                cluster_id,
+                PendingClusterReconfiguration {
+                    new_replicas,
+                    old_replicas,
+                    targets,
+                    storage_targets,
+                },
+            );
+        }
+    }
+
+    /// Drops the old replicas of every resized managed cluster whose new
+    /// replicas have hydrated, as determined by the write frontiers that the
+    /// new replicas report for the cluster's compute and storage collections.
+    ///
+    /// If all new replicas of a cluster have been dropped, its old replicas
+    /// are kept, so that the cluster is not left without replicas.
+    pub(crate) async fn advance_cluster_reconfigurations(&mut self) {
+        let mut finished = vec![];
+        for (cluster_id, pending) in &self.pending_cluster_reconfigurations {
+            let (Some(cluster), Ok(instance)) = (
+                self.catalog.try_get_cluster(*cluster_id),
+                self.controller.compute.instance_ref(*cluster_id),
+            ) else {
+                // The cluster has been dropped.
+                finished.push((*cluster_id, vec![]));
+                continue;
+            };
+            let new_replicas: Vec<_> = pending
+                .new_replicas
+                .iter()
+                .filter(|replica_id| cluster.replicas_by_id.contains_key(replica_id))
+                .collect();
+            if new_replicas.is_empty() {
+                warn!(
+                    "all new replicas of cluster {cluster_id} were dropped; keeping its old replicas"
+                );
+                finished.push((*cluster_id, vec![]));
+                continue;
+            }
+            let hydrated = new_replicas.into_iter().all(|replica_id| {
+                let compute_hydrated = pending.targets.iter().all(|(id, target)| {
+                    match instance.collection(*id) {
+                        Ok(collection) => collection
+                            .replica_write_frontier(*replica_id)
+                            .map_or(false, |frontier| !frontier.less_than(target)),
+                        // The collection has been dropped.
+                        Err(_) => true,
+                    }
+                });
+                let storage_hydrated = pending.storage_targets.iter().all(|(id, target)| {
+                    match self.controller.storage.collection(*id) {
+                        Ok(collection) => collection
+                            .replica_write_frontiers
+                            .get(replica_id)
+                            .map_or(false, |frontier| !frontier.less_than(target)),
+                        // The collection has been dropped.
+                        Err(_) => true,
+                    }
+                });
+                compute_hydrated && storage_hydrated
+            });
+            if hydrated {
+                let old_replicas = pending
+                    .old_replicas
+                    .iter()
+                    .filter(|replica_id| cluster.replicas_by_id.contains_key(replica_id))
+                    .map(|replica_id| (*cluster_id, *replica_id))
+                    .collect();
+                finished.push((*cluster_id, old_replicas));
+            }
+        }
+
+        for (cluster_id, old_replicas) in finished {
+            self.pending_cluster_reconfigurations.remove(&cluster_id);
+            if old_replicas.is_empty() {
+                continue;
+            }
+            let ops = self
+                .catalog
+                .drop_cluster_replica_ops(&old_replicas, &mut BTreeSet::new());
+            if let Err(err) = self.catalog_transact(None, ops).await {
+                warn!("failed to drop old replicas of cluster {cluster_id}: {err:?}");
+            }
+        }
+    }
+
+    /// Swaps in the new definition of every materialized view whose new
+    /// dataflow has caught up with the storage collection that the dataflow of
+    /// its current definition writes to.
+    pub(crate) async fn advance_materialized_view_replacements(&mut self) {
+        let mut caught_up = vec![];
+        for (id, pending) in &self.pending_materialized_view_replacements {
+            let (Ok(sink), Ok(collection)) = (
+                self.controller
+                    .compute
+                    .collection(pending.cluster_id, pending.sink_id),
+                self.controller.storage.collection(*id),
+            ) else {
+                continue;
+            };
+            if PartialOrder::less_equal(&collection.write_frontier.borrow(), &sink.write_frontier())
+            {
+                caught_up.push(*id);
+            }
+        }
+
+        for id in caught_up {
+            let PendingMaterializedViewReplacement {
+                materialized_view,
+                cluster_id,
+                sink_id,
+                or_replace,
+                session,
+                tx,
+            } = self
+                .pending_materialized_view_replacements
+                .remove(&id)
+                .expect("known to be pending");
+
+            let ops = vec![catalog::Op::AlterMaterializedView {
+                id,
+                materialized_view,
+            }];
+            if let Err(err) = self.catalog_transact(Some(&session), ops).await {
+                self.drop_compute_sinks([ComputeSinkId {
+                    cluster_id,
+                    global_id: sink_id,
+                }]);
+                tx.send(Err(err), session);
+                continue;
+            }
+
+            // Retire the dataflow of the previous definition before the new
+            // dataflow starts writing, so that the storage collection never
+            // has two writers.
+            let old_sink_id = self
+                .materialized_view_sinks
+                .insert(id, sink_id)
+                .unwrap_or(id);
+            self.remove_compute_ids_from_timeline([(cluster_id, old_sink_id)]);
+            self.drop_compute_sinks([ComputeSinkId {
+                cluster_id,
+                global_id: old_sink_id,
+            }]);
+            self.controller
+                .active_compute()
+                .allow_writes(cluster_id, sink_id)
+                .unwrap_or_terminate("cannot fail to allow writes");
+
+            let response = if or_replace {
+                ExecuteResponse::CreatedMaterializedView
+            } else {
+                ExecuteResponse::AlteredObject(ObjectType::MaterializedView)
+            };
+            tx.send(Ok(response), session);
+        }
+    }
+
+    /// Abandons the pending replacement of the definition of the materialized
+    /// view `id`, if any, and responds to the client that requested it with
+    /// `response`.
+    pub(crate) fn cancel_materialized_view_replacement(
+        &mut self,
+        id: GlobalId,
+        response: Result<ExecuteResponse, AdapterError>,
+    ) {
+        if let Some(pending) = self.pending_materialized_view_replacements.remove(&id) {
+            self.drop_compute_sinks([ComputeSinkId {
+                cluster_id: pending.cluster_id,
+                global_id: pending.sink_id,
+            }]);
+            pending.tx.send(response, pending.session);
+        }
+    }
+
     /// Returns an error if the given cluster is a linked cluster
     fn ensure_cluster_is_not_linked(&self, cluster_id: ClusterId) -> Result<(), AdapterError> {
         let cluster = self.catalog.get_cluster(cluster_id);
@@ -4119,6 +4947,44 @@ impl Coordinator {
     }
 }
 
+/// Returns the user tables that reading from `ids` reads from, or `None` if
+/// any of `ids` depends on an object whose notion of time moves differently
+/// than that of user tables.
+fn table_read_dependencies(
+    catalog: &Catalog,
+    ids: impl IntoIterator<Item = GlobalId>,
+) -> Option<BTreeSet<GlobalId>> {
+    fn collect(catalog: &Catalog, id: &GlobalId, tables: &mut BTreeSet<GlobalId>) -> bool {
+        use CatalogItemType::*;
+        match catalog.try_get_entry(id) {
+            Some(entry) => match entry.item().typ() {
+                typ @ (Func | View | MaterializedView) => {
+                    let valid_id = id.is_user() || matches!(typ, Func);
+                    valid_id && entry.uses().iter().all(|id| collect(catalog, id, tables))
+                }
+                Source | Secret | Connection => false,
+                // Cannot select from sinks or indexes
+                Sink | Index => unreachable!(),
+                Table if id.is_user() => {
+                    tables.insert(*id);
+                    true
+                }
+                Table => false,
+                Type => true,
+            },
+            None => false,
+        }
+    }
+
+    let mut tables = BTreeSet::new();
+    for id in ids {
+        if !collect(catalog, &id, &mut tables) {
+            return None;
+        }
+    }
+    Some(tables)
+}
+
 fn check_no_invalid_log_reads(
     catalog: &Catalog,
     cluster: &Cluster,
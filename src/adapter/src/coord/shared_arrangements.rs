@@ -0,0 +1,405 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sharing of arrangements across the dataflows of a cluster.
+//!
+//! Common subexpression elimination deduplicates arrangements within a single
+//! dataflow, but separate indexes and materialized views that arrange the same
+//! expression over the same sources each build their own copy of it. When
+//! `enable_arrangement_sharing` is on, the coordinator looks for such
+//! arrangements in the dataflows of new indexes and materialized views. The
+//! first time it sees one on a cluster, it installs a dataflow that maintains
+//! the arrangement as an internal index. The dataflow that asked for the
+//! arrangement, and every later dataflow on the cluster that asks for the same
+//! arrangement, imports the internal index instead of building its own.
+//!
+//! Internal indexes have transient IDs and are not recorded in the catalog.
+//! An internal index is dropped along with the last dataflow that imports it,
+//! and is installed again on demand when the coordinator restarts.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use timely::progress::Antichain;
+use timely::PartialOrder;
+
+use mz_compute_client::types::dataflows::{DataflowDesc, IndexDesc};
+use mz_compute_client::types::sources::SourceInstanceDesc;
+use mz_controller::clusters::ClusterId;
+use mz_expr::visit::Visit;
+use mz_expr::{CollectionPlan, Id, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr};
+use mz_ore::stack::maybe_grow;
+use mz_repr::{GlobalId, RelationType, Timestamp};
+
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::{Coordinator, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS};
+use crate::util::ResultExt;
+
+/// The source imports of a dataflow, with their monotonicity.
+type SourceImports = BTreeMap<GlobalId, (SourceInstanceDesc<()>, bool)>;
+
+/// The arrangements shared by the dataflows of each cluster.
+#[derive(Debug, Default)]
+pub struct SharedArrangements {
+    by_cluster: BTreeMap<ClusterId, Vec<SharedArrangement>>,
+}
+
+/// An arrangement maintained by an internal index on behalf of the dataflows
+/// that import it.
+#[derive(Debug)]
+struct SharedArrangement {
+    /// The sources read by `input`, as imported by the dataflows that arrange
+    /// it.
+    sources: SourceImports,
+    /// The arranged expression.
+    input: MirRelationExpr,
+    /// The key by which `input` is arranged.
+    key: Vec<MirScalarExpr>,
+    /// The ID under which `input` is built.
+    build_id: GlobalId,
+    /// The ID of the internal index.
+    index_id: GlobalId,
+    /// The exports of the dataflows that import the internal index.
+    users: BTreeSet<GlobalId>,
+}
+
+impl SharedArrangements {
+    /// Returns the arrangement of `input` by `key` shared on `cluster_id`, if
+    /// there is one.
+    fn get_mut(
+        &mut self,
+        cluster_id: ClusterId,
+        sources: &SourceImports,
+        input: &MirRelationExpr,
+        key: &[MirScalarExpr],
+    ) -> Option<&mut SharedArrangement> {
+        self.by_cluster
+            .get_mut(&cluster_id)?
+            .iter_mut()
+            .find(|shared| {
+                &shared.input == input && shared.key == key && &shared.sources == sources
+            })
+    }
+
+    /// Removes `users` from the users of the arrangements shared on
+    /// `cluster_id`, and forgets the arrangements that are left without users.
+    ///
+    /// Returns the IDs of the internal indexes of the forgotten arrangements.
+    fn release(&mut self, cluster_id: ClusterId, users: &[GlobalId]) -> Vec<GlobalId> {
+        let Some(shared) = self.by_cluster.get_mut(&cluster_id) else {
+            return vec![];
+        };
+        let mut unused = vec![];
+        shared.retain_mut(|shared| {
+            let mut released = false;
+            for user in users {
+                released |= shared.users.remove(user);
+            }
+            if !released || !shared.users.is_empty() {
+                return true;
+            }
+            unused.push(shared.index_id);
+            false
+        });
+        if shared.is_empty() {
+            self.by_cluster.remove(&cluster_id);
+        }
+        unused
+    }
+
+    /// Forgets the arrangements shared on `cluster_id`.
+    pub(crate) fn remove_cluster(&mut self, cluster_id: ClusterId) {
+        self.by_cluster.remove(&cluster_id);
+    }
+}
+
+impl Coordinator {
+    /// Rewrites `dataflow` to import the arrangements it would otherwise build
+    /// from the internal indexes that share them on `cluster_id`, installing
+    /// the internal indexes that do not exist yet.
+    ///
+    /// Only arrangements of expressions that read nothing but the sources
+    /// imported by `dataflow` are shared, and only if the internal index can
+    /// be read at the `as_of` of `dataflow`.
+    pub(crate) async fn share_arrangements(
+        &mut self,
+        dataflow: &mut DataflowDesc,
+        cluster_id: ClusterId,
+    ) {
+        let mut candidates = vec![];
+        for build in &dataflow.objects_to_build {
+            collect_shareable(&build.plan.0, &dataflow.source_imports, &mut candidates);
+        }
+        if candidates.is_empty() {
+            return;
+        }
+
+        let users: BTreeSet<_> = dataflow.export_ids().collect();
+        let readable_at_as_of = |since: &Antichain<Timestamp>| match &dataflow.as_of {
+            Some(as_of) => PartialOrder::less_equal(since, as_of),
+            None => true,
+        };
+        let mut replacements = vec![];
+        let mut new_dataflows = vec![];
+        for (input, key) in candidates {
+            let sources: SourceImports = input
+                .depends_on()
+                .into_iter()
+                .map(|id| (id, dataflow.source_imports[&id].clone()))
+                .collect();
+            let typ = input.typ();
+
+            if let Some(shared) = self
+                .shared_arrangements
+                .get_mut(cluster_id, &sources, &input, &key)
+            {
+                let (build_id, index_id) = (shared.build_id, shared.index_id);
+                let mut id_bundle = CollectionIdBundle::default();
+                id_bundle
+                    .compute_ids
+                    .insert(cluster_id, BTreeSet::from([index_id]));
+                if !readable_at_as_of(&self.least_valid_read(&id_bundle)) {
+                    continue;
+                }
+                self.shared_arrangements
+                    .get_mut(cluster_id, &sources, &input, &key)
+                    .expect("known to exist")
+                    .users
+                    .extend(users.iter().copied());
+                replacements.push((input, key, build_id, index_id, typ));
+                continue;
+            }
+
+            let id_bundle = CollectionIdBundle {
+                storage_ids: sources.keys().copied().collect(),
+                compute_ids: BTreeMap::new(),
+            };
+            if !readable_at_as_of(&self.least_valid_read(&id_bundle)) {
+                continue;
+            }
+            let (Ok(build_id), Ok(index_id)) =
+                (self.allocate_transient_id(), self.allocate_transient_id())
+            else {
+                continue;
+            };
+            let mut shared_dataflow = DataflowDesc::new(format!("shared-arrangement-{index_id}"));
+            shared_dataflow.source_imports = sources.clone();
+            shared_dataflow.insert_plan(build_id, OptimizedMirRelationExpr(input.clone()));
+            shared_dataflow.export_index(
+                index_id,
+                IndexDesc {
+                    on_id: build_id,
+                    key: key.clone(),
+                },
+                typ.clone(),
+            );
+            new_dataflows.push(shared_dataflow);
+            self.shared_arrangements
+                .by_cluster
+                .entry(cluster_id)
+                .or_default()
+                .push(SharedArrangement {
+                    sources,
+                    input: input.clone(),
+                    key: key.clone(),
+                    build_id,
+                    index_id,
+                    users: users.clone(),
+                });
+            replacements.push((input, key, build_id, index_id, typ));
+        }
+
+        // The internal indexes must exist before `dataflow` is finalized, so
+        // that its `as_of` accounts for their read frontiers.
+        if !new_dataflows.is_empty() {
+            let index_ids: Vec<_> = new_dataflows
+                .iter()
+                .flat_map(|dataflow| dataflow.export_ids())
+                .collect();
+            let dataflow_plans: Vec<_> = new_dataflows
+                .into_iter()
+                .map(|dataflow| self.must_finalize_dataflow(dataflow, cluster_id))
+                .collect();
+            self.controller
+                .active_compute()
+                .create_dataflows(cluster_id, dataflow_plans)
+                .unwrap_or_terminate("dataflow creation cannot fail");
+            self.initialize_compute_read_policies(
+                index_ids,
+                cluster_id,
+                Some(DEFAULT_LOGICAL_COMPACTION_WINDOW_TS),
+            )
+            .await;
+        }
+
+        for (input, key, build_id, index_id, typ) in replacements {
+            import_shared(dataflow, &input, &key, build_id, index_id, typ);
+        }
+
+        // Drop the imports of the sources that are now only read by internal
+        // indexes.
+        let mut used = BTreeSet::new();
+        for build in &dataflow.objects_to_build {
+            build.plan.depends_on_into(&mut used);
+        }
+        used.extend(dataflow.sink_exports.values().map(|sink| sink.from));
+        used.extend(dataflow.index_exports.values().map(|(desc, _)| desc.on_id));
+        dataflow.source_imports.retain(|id, _| used.contains(id));
+    }
+
+    /// Removes `users` from the users of the arrangements shared on
+    /// `cluster_id`, and drops the read policies of the internal indexes that
+    /// are left without users.
+    ///
+    /// Returns the IDs of those internal indexes, which the caller must drop.
+    pub(crate) fn release_shared_arrangements(
+        &mut self,
+        cluster_id: ClusterId,
+        users: &[GlobalId],
+    ) -> Vec<GlobalId> {
+        let unused = self.shared_arrangements.release(cluster_id, users);
+        for id in &unused {
+            self.drop_compute_read_policy(id);
+        }
+        unused
+    }
+}
+
+/// Collects into `out` the arrangements in `expr` that can be shared, given
+/// the sources imported by its dataflow.
+///
+/// Arrangements nested within the input of a shareable arrangement are not
+/// collected, as they become part of the dataflow of the internal index.
+fn collect_shareable(
+    expr: &MirRelationExpr,
+    source_imports: &SourceImports,
+    out: &mut Vec<(MirRelationExpr, Vec<MirScalarExpr>)>,
+) {
+    maybe_grow(|| {
+        if let MirRelationExpr::ArrangeBy { input, keys } = expr {
+            if let [key] = &keys[..] {
+                if is_shareable(input, source_imports) {
+                    let candidate = ((**input).clone(), key.clone());
+                    if !out.contains(&candidate) {
+                        out.push(candidate);
+                    }
+                    return;
+                }
+            }
+        }
+        for child in expr.children() {
+            collect_shareable(child, source_imports, out);
+        }
+    })
+}
+
+/// Reports whether `input` reads at least one collection, and only reads
+/// collections among `source_imports`.
+fn is_shareable(input: &MirRelationExpr, source_imports: &SourceImports) -> bool {
+    let mut reads = false;
+    let mut shareable = true;
+    let visited = input.visit_pre(&mut |expr| match expr {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } => {
+            reads = true;
+            shareable &= source_imports.contains_key(id);
+        }
+        MirRelationExpr::Get {
+            id: Id::Local(_), ..
+        }
+        | MirRelationExpr::Let { .. }
+        | MirRelationExpr::LetRec { .. } => shareable = false,
+        _ => (),
+    });
+    visited.is_ok() && reads && shareable
+}
+
+/// Rewrites `dataflow` to read the arrangement of `input` by `key` from the
+/// internal index `index_id` on `build_id`.
+fn import_shared(
+    dataflow: &mut DataflowDesc,
+    input: &MirRelationExpr,
+    key: &[MirScalarExpr],
+    build_id: GlobalId,
+    index_id: GlobalId,
+    typ: RelationType,
+) {
+    for build in &mut dataflow.objects_to_build {
+        // The visit cannot exceed the recursion limit, as `collect_shareable`
+        // has already visited the expression.
+        let _ = build.plan.0.visit_mut_pre(&mut |expr| {
+            if let MirRelationExpr::ArrangeBy {
+                input: arranged,
+                keys,
+            } = expr
+            {
+                if &**arranged == input && matches!(&keys[..], [keys] if keys == key) {
+                    **arranged = MirRelationExpr::Get {
+                        id: Id::Global(build_id),
+                        typ: typ.clone(),
+                    };
+                }
+            }
+        });
+    }
+    dataflow.import_index(
+        index_id,
+        IndexDesc {
+            on_id: build_id,
+            key: key.to_vec(),
+        },
+        typ,
+        false,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{ColumnType, ScalarType};
+
+    use super::*;
+
+    fn shared(index_id: u64, users: &[u64]) -> SharedArrangement {
+        let typ = RelationType::new(vec![ColumnType {
+            scalar_type: ScalarType::Int64,
+            nullable: false,
+        }]);
+        SharedArrangement {
+            sources: BTreeMap::new(),
+            input: MirRelationExpr::global_get(GlobalId::User(1), typ),
+            key: vec![MirScalarExpr::column(0)],
+            build_id: GlobalId::Transient(index_id - 1),
+            index_id: GlobalId::Transient(index_id),
+            users: users.iter().map(|id| GlobalId::User(*id)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_release() {
+        let cluster_id = ClusterId::User(1);
+        let mut arrangements = SharedArrangements::default();
+        arrangements
+            .by_cluster
+            .insert(cluster_id, vec![shared(2, &[10, 11]), shared(4, &[11])]);
+
+        assert_eq!(
+            arrangements.release(cluster_id, &[GlobalId::User(11)]),
+            vec![GlobalId::Transient(4)]
+        );
+        assert_eq!(
+            arrangements.release(cluster_id, &[GlobalId::User(12)]),
+            vec![]
+        );
+        assert_eq!(
+            arrangements.release(cluster_id, &[GlobalId::User(10)]),
+            vec![GlobalId::Transient(2)]
+        );
+        assert!(arrangements.by_cluster.is_empty());
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logic for recording a sample of statement executions in
+//! `mz_internal.mz_statement_execution_history`.
+//!
+//! When a statement begins executing, the coordinator decides, according to
+//! the `statement_logging_sample_percent` system variable, whether to log it.
+//! A logged execution is finished by the [`ClientTransmitter`] that sends its
+//! response, or, for responses that stream rows, once those rows are ready.
+//! Finished executions are buffered and written out periodically, at which
+//! point entries older than `statement_logging_retention` are retracted. The
+//! coordinator keeps the entries it may need to retract in memory, so the
+//! oldest entries are also retracted early whenever their total size exceeds
+//! `statement_logging_max_retained_bytes`.
+//!
+//! [`ClientTransmitter`]: crate::util::ClientTransmitter
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use timely::progress::Timestamp as TimelyTimestamp;
+use tracing::warn;
+use uuid::Uuid;
+
+use mz_controller::clusters::ClusterId;
+use mz_ore::cast::CastFrom;
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_ore::task;
+use mz_repr::{Datum, Diff, Row, Timestamp};
+use mz_sql::ast::{Raw, Statement};
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_storage_client::controller::IntrospectionType;
+
+use crate::catalog::builtin::MZ_STATEMENT_EXECUTION_HISTORY;
+use crate::client::ConnectionId;
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+
+/// How often finished statement executions are written out.
+const STATEMENT_LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The column of `mz_statement_execution_history` that holds the time at which
+/// an execution finished.
+const FINISHED_AT_COLUMN: usize = 10;
+
+/// Identifies a logged statement execution.
+pub type StatementLoggingId = Uuid;
+
+/// How a logged statement execution finished.
+#[derive(Debug, Clone)]
+pub enum StatementEndedExecutionReason {
+    /// The statement succeeded. `rows_returned` is set for statements that
+    /// return rows to the client.
+    Success { rows_returned: Option<usize> },
+    /// The statement was canceled by the user.
+    Canceled,
+    /// The statement failed.
+    Errored { error: String },
+    /// The session ended before the statement finished.
+    Aborted,
+}
+
+impl StatementEndedExecutionReason {
+    fn status(&self) -> &'static str {
+        match self {
+            StatementEndedExecutionReason::Success { .. } => "success",
+            StatementEndedExecutionReason::Canceled => "canceled",
+            StatementEndedExecutionReason::Errored { .. } => "error",
+            StatementEndedExecutionReason::Aborted => "aborted",
+        }
+    }
+}
+
+/// A logged statement execution that has not yet finished.
+#[derive(Debug)]
+struct BegunStatementExecution {
+    conn_id: ConnectionId,
+    user: String,
+    application_name: String,
+    cluster_id: Option<ClusterId>,
+    cluster_name: Option<String>,
+    sql_hash: [u8; 32],
+    sample_rate: f64,
+    began_at: EpochMillis,
+    /// The timestamp chosen by timestamp selection, if the statement read at
+    /// one.
+    execution_timestamp: Option<Timestamp>,
+}
+
+/// The coordinator's statement logging state.
+#[derive(Debug, Default)]
+pub(crate) struct StatementLogging {
+    /// Logged executions that have not yet finished.
+    executions_begun: BTreeMap<StatementLoggingId, BegunStatementExecution>,
+    /// The logged execution that each connection most recently began.
+    current_executions: BTreeMap<ConnectionId, StatementLoggingId>,
+    /// Updates that have not yet been written out.
+    pending_updates: Vec<(Row, Diff)>,
+    /// The entries in `mz_statement_execution_history` and the times at which
+    /// their executions finished, oldest first.
+    retained: VecDeque<(EpochMillis, Row)>,
+    /// The total size in bytes of the rows in `retained`.
+    retained_bytes: usize,
+}
+
+impl StatementLogging {
+    /// Records that `row`, which finished at `finished_at`, was written out.
+    fn retain(&mut self, finished_at: EpochMillis, row: Row) {
+        self.retained_bytes += row.byte_len();
+        self.retained.push_back((finished_at, row));
+    }
+
+    /// Retracts the entries that finished before `cutoff`, and then the
+    /// oldest entries until the remaining ones take up at most `max_bytes`.
+    fn expire(&mut self, cutoff: EpochMillis, max_bytes: usize) {
+        while let Some((finished_at, row)) = self.retained.front() {
+            if *finished_at >= cutoff && self.retained_bytes <= max_bytes {
+                break;
+            }
+            self.retained_bytes -= row.byte_len();
+            let (_, row) = self.retained.pop_front().expect("known to exist");
+            self.pending_updates.push((row, -1));
+        }
+    }
+}
+
+impl Coordinator {
+    /// Decides whether to log the execution of `stmt` in `session`, and if so,
+    /// records that it began.
+    pub(crate) fn begin_statement_execution(
+        &mut self,
+        session: &Session,
+        stmt: &Statement<Raw>,
+    ) -> Option<StatementLoggingId> {
+        // `EXECUTE` runs its prepared statement as a separate execution, which
+        // is logged in its place.
+        if let Statement::Execute(_) = stmt {
+            return None;
+        }
+        let sample_percent = self
+            .catalog
+            .system_config()
+            .statement_logging_sample_percent()
+            .min(100);
+        if sample_percent == 0 || rand::thread_rng().gen_range(0..100) >= sample_percent {
+            return None;
+        }
+
+        let cluster_name = session.vars().cluster().to_string();
+        let cluster_id = self
+            .catalog
+            .resolve_cluster(&cluster_name)
+            .ok()
+            .map(|cluster| cluster.id);
+        let id = Uuid::new_v4();
+        let execution = BegunStatementExecution {
+            conn_id: session.conn_id(),
+            user: session.user().name.clone(),
+            application_name: session.vars().application_name().to_string(),
+            cluster_id,
+            cluster_name: cluster_id.map(|_| cluster_name),
+            sql_hash: Sha256::digest(stmt.to_ast_string_stable().as_bytes()).into(),
+            sample_rate: f64::from(sample_percent) / 100.0,
+            began_at: self.now(),
+            execution_timestamp: None,
+        };
+        self.statement_logging
+            .executions_begun
+            .insert(id, execution);
+        self.statement_logging
+            .current_executions
+            .insert(session.conn_id(), id);
+        Some(id)
+    }
+
+    /// Records the timestamp that timestamp selection chose for the statement
+    /// that `conn_id` is executing, if that execution is being logged.
+    pub(crate) fn set_statement_execution_timestamp(
+        &mut self,
+        conn_id: ConnectionId,
+        timestamp: Timestamp,
+    ) {
+        let logging = &mut self.statement_logging;
+        if let Some(id) = logging.current_executions.get(&conn_id) {
+            if let Some(execution) = logging.executions_begun.get_mut(id) {
+                execution.execution_timestamp = Some(timestamp);
+            }
+        }
+    }
+
+    /// Records that the logged execution `id` finished for `reason`.
+    pub(crate) fn end_statement_execution(
+        &mut self,
+        id: StatementLoggingId,
+        reason: StatementEndedExecutionReason,
+    ) {
+        let finished_at = self.now();
+        let logging = &mut self.statement_logging;
+        let Some(execution) = logging.executions_begun.remove(&id) else {
+            // The session ended before the execution did.
+            return;
+        };
+        if logging.current_executions.get(&execution.conn_id) == Some(&id) {
+            logging.current_executions.remove(&execution.conn_id);
+        }
+        let row = pack_statement_execution_row(id, &execution, finished_at, &reason);
+        logging.pending_updates.push((row.clone(), 1));
+        logging.retain(finished_at, row);
+    }
+
+    /// Records that the logged executions of `conn_id` that have not yet
+    /// finished were aborted, as the session is ending.
+    pub(crate) fn end_session_statement_executions(&mut self, conn_id: ConnectionId) {
+        let ids: Vec<_> = self
+            .statement_logging
+            .executions_begun
+            .iter()
+            .filter(|(_, execution)| execution.conn_id == conn_id)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            self.end_statement_execution(id, StatementEndedExecutionReason::Aborted);
+        }
+        self.statement_logging.current_executions.remove(&conn_id);
+    }
+
+    /// Loads the entries that previous incarnations wrote to
+    /// `mz_statement_execution_history`, so that they are retracted once they
+    /// age out.
+    ///
+    /// Previous incarnations bounded the size of the collection by
+    /// `statement_logging_max_retained_bytes`, so the snapshot is as well,
+    /// unless the limit was lowered since.
+    pub(crate) async fn bootstrap_statement_logging(&mut self) {
+        let id = self
+            .catalog
+            .resolve_builtin_storage_collection(&MZ_STATEMENT_EXECUTION_HISTORY);
+        let as_of = match self.controller.storage.collection(id) {
+            Ok(collection) => match collection.write_frontier.as_option() {
+                Some(upper) if *upper > Timestamp::minimum() => upper.step_back(),
+                _ => None,
+            },
+            Err(_) => None,
+        };
+        let Some(as_of) = as_of else {
+            return;
+        };
+        let snapshot = match self.controller.storage.snapshot(id, as_of).await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("unable to load statement execution history: {e}");
+                return;
+            }
+        };
+        let mut retained = Vec::new();
+        for (row, diff) in snapshot {
+            let finished_at = row.unpack()[FINISHED_AT_COLUMN]
+                .unwrap_timestamptz()
+                .timestamp_millis();
+            let finished_at = u64::try_from(finished_at).unwrap_or(0);
+            for _ in 0..diff {
+                retained.push((finished_at, row.clone()));
+            }
+        }
+        retained.sort_by_key(|(finished_at, _)| *finished_at);
+        for (finished_at, row) in retained {
+            self.statement_logging.retain(finished_at, row);
+        }
+        // Release whatever no longer fits right away; the retractions are
+        // written out by the next flush.
+        let cutoff = self.statement_logging_cutoff();
+        let max_bytes = self.statement_logging_max_retained_bytes();
+        self.statement_logging.expire(cutoff, max_bytes);
+    }
+
+    /// Returns the time before which finished executions age out.
+    fn statement_logging_cutoff(&self) -> EpochMillis {
+        let retention = self.catalog.system_config().statement_logging_retention();
+        let retention = u64::try_from(retention.as_millis()).unwrap_or(u64::MAX);
+        self.now().saturating_sub(retention)
+    }
+
+    /// Returns the maximum total size of the retained entries.
+    fn statement_logging_max_retained_bytes(&self) -> usize {
+        usize::cast_from(
+            self.catalog
+                .system_config()
+                .statement_logging_max_retained_bytes(),
+        )
+    }
+
+    /// Writes out the finished statement executions, and retracts the entries
+    /// that are older than `statement_logging_retention`.
+    pub(crate) async fn flush_statement_log(&mut self) {
+        let cutoff = self.statement_logging_cutoff();
+        let max_bytes = self.statement_logging_max_retained_bytes();
+
+        let logging = &mut self.statement_logging;
+        logging.expire(cutoff, max_bytes);
+        let updates = std::mem::take(&mut logging.pending_updates);
+
+        if !updates.is_empty() {
+            self.controller
+                .storage
+                .append_introspection_updates(IntrospectionType::StatementExecutionHistory, updates)
+                .await;
+        }
+    }
+
+    /// Schedules the next write of finished statement executions.
+    pub(crate) fn schedule_statement_log_flush(&self) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "statement_log_flush", async move {
+            tokio::time::sleep(STATEMENT_LOG_FLUSH_INTERVAL).await;
+            if internal_cmd_tx.send(Message::FlushStatementLog).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+}
+
+fn pack_statement_execution_row(
+    id: StatementLoggingId,
+    execution: &BegunStatementExecution,
+    finished_at: EpochMillis,
+    reason: &StatementEndedExecutionReason,
+) -> Row {
+    let cluster_id = execution.cluster_id.map(|id| id.to_string());
+    let (error, rows_returned) = match reason {
+        StatementEndedExecutionReason::Success { rows_returned } => (
+            None,
+            rows_returned.map(|n| i64::try_from(n).expect("must fit")),
+        ),
+        StatementEndedExecutionReason::Errored { error } => (Some(error.as_str()), None),
+        StatementEndedExecutionReason::Canceled | StatementEndedExecutionReason::Aborted => {
+            (None, None)
+        }
+    };
+    Row::pack_slice(&[
+        Datum::Uuid(id),
+        Datum::UInt32(execution.conn_id),
+        Datum::String(&execution.user),
+        Datum::String(&execution.application_name),
+        Datum::from(cluster_id.as_deref()),
+        Datum::from(execution.cluster_name.as_deref()),
+        Datum::Bytes(&execution.sql_hash),
+        Datum::Float64(execution.sample_rate.into()),
+        Datum::from(execution.execution_timestamp),
+        Datum::TimestampTz(
+            to_datetime(execution.began_at)
+                .try_into()
+                .expect("must fit"),
+        ),
+        Datum::TimestampTz(to_datetime(finished_at).try_into().expect("must fit")),
+        Datum::String(reason.status()),
+        Datum::from(error),
+        Datum::from(rows_returned),
+    ])
+}
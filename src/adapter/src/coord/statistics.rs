@@ -0,0 +1,256 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Statistics about storage collections, for use by the optimizer.
+//!
+//! When `enable_cardinality_estimates` is on, the coordinator periodically
+//! reads the statistics that persist maintains for the data shard of each
+//! storage collection: the number of updates, and the bounds of each column.
+//! The optimizer uses them to estimate the size of join inputs and the number
+//! of matches per key, which informs the order in which inputs are joined.
+//!
+//! The statistics are fetched by a separate task, so that slow reads from
+//! persist do not hold up the coordinator, and are handed back to the
+//! coordinator once they have all arrived.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use futures::future::join_all;
+use timely::progress::Timestamp as TimelyTimestamp;
+use tracing::debug;
+
+use mz_ore::task;
+use mz_persist_client::read::SnapshotStats;
+use mz_persist_types::stats::{ColumnStats, PrimitiveValue};
+use mz_repr::{GlobalId, Timestamp};
+use mz_storage_client::controller::StorageError;
+use mz_transform::StatisticsOracle;
+
+use crate::coord::{Coordinator, Message};
+
+/// How often the statistics of storage collections are refreshed.
+const STATISTICS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a refresh of the statistics may take before it is abandoned.
+const STATISTICS_REFRESH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Statistics about a single storage collection.
+#[derive(Debug, Clone)]
+struct CollectionStatistics {
+    /// An upper bound on the number of records in the collection.
+    num_updates: usize,
+    /// An upper bound on the number of distinct values of each column, if
+    /// known.
+    column_distinct: Vec<Option<usize>>,
+}
+
+/// A [`StatisticsOracle`] backed by the statistics of storage collections.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionStatisticsOracle {
+    collections: BTreeMap<GlobalId, CollectionStatistics>,
+}
+
+impl StatisticsOracle for CollectionStatisticsOracle {
+    fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
+        self.collections.get(&id).map(|stats| stats.num_updates)
+    }
+
+    fn distinct_estimate(&self, id: GlobalId, key: &[usize]) -> Option<usize> {
+        let stats = self.collections.get(&id)?;
+        let mut estimate: Option<usize> = None;
+        for col in key {
+            if let Some(Some(distinct)) = stats.column_distinct.get(*col) {
+                estimate = Some(estimate.unwrap_or(1).saturating_mul(*distinct));
+            }
+        }
+        estimate.map(|estimate| estimate.min(stats.num_updates))
+    }
+}
+
+/// A [`StatisticsOracle`] for one-shot plans, like those of peeks, which may
+/// be tuned to the statistics of the wrapped oracle.
+#[derive(Debug)]
+pub struct OneShotStatisticsOracle<'a>(pub &'a CollectionStatisticsOracle);
+
+impl StatisticsOracle for OneShotStatisticsOracle<'_> {
+    fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
+        self.0.cardinality_estimate(id)
+    }
+
+    fn distinct_estimate(&self, id: GlobalId, key: &[usize]) -> Option<usize> {
+        self.0.distinct_estimate(id, key)
+    }
+
+    fn one_shot(&self) -> bool {
+        true
+    }
+}
+
+/// Returns an upper bound on the number of distinct values in a column
+/// described by `stats`, if one can be derived from its bounds.
+///
+/// This is the number of values between the bounds, which overestimates the
+/// number of distinct values of columns whose values are sparse, and thereby
+/// underestimates the number of matches per key.
+fn column_distinct(stats: &ColumnStats) -> Option<usize> {
+    let values = match (&stats.lower, &stats.upper) {
+        (None, None) => 0,
+        (Some(PrimitiveValue::Bool(lower)), Some(PrimitiveValue::Bool(upper))) => {
+            if lower == upper {
+                1
+            } else {
+                2
+            }
+        }
+        (Some(PrimitiveValue::I64(lower)), Some(PrimitiveValue::I64(upper))) => {
+            usize::try_from(upper.abs_diff(*lower)).unwrap_or(usize::MAX)
+        }
+        (Some(PrimitiveValue::U64(lower)), Some(PrimitiveValue::U64(upper))) => {
+            usize::try_from(upper.abs_diff(*lower)).unwrap_or(usize::MAX)
+        }
+        _ => return None,
+    };
+    let values = if stats.lower.is_some() {
+        values.saturating_add(1)
+    } else {
+        values
+    };
+    Some(values.saturating_add(usize::from(stats.nulls > 0)))
+}
+
+/// Assembles the statistics fetched for each collection, whose columns are
+/// named `names`.
+fn collection_statistics(
+    results: Vec<(GlobalId, Vec<String>, Result<SnapshotStats, StorageError>)>,
+) -> CollectionStatisticsOracle {
+    let mut collections = BTreeMap::new();
+    for (id, names, stats) in results {
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(e) => {
+                debug!("unable to refresh statistics of {id}: {e}");
+                continue;
+            }
+        };
+        let column_distinct = names
+            .iter()
+            .map(|name| {
+                let col = stats.stats.as_ref()?.key.col(name)?;
+                column_distinct(col)
+            })
+            .collect();
+        collections.insert(
+            id,
+            CollectionStatistics {
+                num_updates: stats.num_updates,
+                column_distinct,
+            },
+        );
+    }
+    CollectionStatisticsOracle { collections }
+}
+
+impl Coordinator {
+    /// Starts a refresh of the statistics of storage collections, or discards
+    /// them if `enable_cardinality_estimates` is off.
+    ///
+    /// The refreshed statistics are delivered by
+    /// [`Message::StatisticsRefreshed`], after which the next refresh is
+    /// scheduled.
+    pub(crate) fn refresh_statistics(&mut self) {
+        if !self.catalog.system_config().enable_cardinality_estimates() {
+            self.collection_statistics = CollectionStatisticsOracle::default();
+            self.schedule_statistics_refresh();
+            return;
+        }
+
+        let mut requests = Vec::new();
+        for (id, collection) in self.controller.storage.collections() {
+            // A collection is dropped if its read capability has been advanced
+            // to the empty antichain.
+            if collection.read_capabilities.is_empty() {
+                continue;
+            }
+            let as_of = match collection.write_frontier.as_option() {
+                Some(upper) if *upper > Timestamp::minimum() => upper.step_back(),
+                _ => None,
+            };
+            let Some(as_of) = as_of else {
+                continue;
+            };
+            // Statistics are looked up by column name, which is only
+            // unambiguous if the names are unique.
+            let Some(desc) = collection.collection_metadata.stats_desc() else {
+                continue;
+            };
+            let names: BTreeSet<_> = desc.iter_names().collect();
+            if names.len() != desc.arity() {
+                continue;
+            }
+            let names: Vec<_> = collection
+                .collection_metadata
+                .relation_desc
+                .iter_names()
+                .map(|name| name.as_str().to_string())
+                .collect();
+            match self.controller.storage.snapshot_stats(*id, as_of) {
+                Ok(fetch) => requests.push((*id, names, fetch)),
+                Err(e) => debug!("unable to refresh statistics of {id}: {e}"),
+            }
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "statistics_refresh", async move {
+            let fetches = join_all(
+                requests
+                    .into_iter()
+                    .map(|(id, names, fetch)| async move { (id, names, fetch.await) }),
+            );
+            // Dropping the fetches that have not completed by the timeout
+            // expires their read handles.
+            let statistics = match tokio::time::timeout(STATISTICS_REFRESH_TIMEOUT, fetches).await {
+                Ok(results) => Some(collection_statistics(results)),
+                Err(_) => {
+                    debug!("timed out refreshing collection statistics");
+                    None
+                }
+            };
+            if internal_cmd_tx
+                .send(Message::StatisticsRefreshed(statistics))
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Installs the statistics fetched by [`Coordinator::refresh_statistics`],
+    /// if the refresh did not time out, and schedules the next refresh.
+    pub(crate) fn install_statistics(&mut self, statistics: Option<CollectionStatisticsOracle>) {
+        // The estimates may have been turned off while the refresh ran.
+        if !self.catalog.system_config().enable_cardinality_estimates() {
+            self.collection_statistics = CollectionStatisticsOracle::default();
+        } else if let Some(statistics) = statistics {
+            self.collection_statistics = statistics;
+        }
+        self.schedule_statistics_refresh();
+    }
+
+    /// Schedules the next refresh of the statistics of storage collections.
+    pub(crate) fn schedule_statistics_refresh(&self) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "statistics_refresh", async move {
+            tokio::time::sleep(STATISTICS_REFRESH_INTERVAL).await;
+            if internal_cmd_tx.send(Message::RefreshStatistics).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+}
@@ -109,6 +109,29 @@ pub enum AdapterError {
     },
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// A write violated a table's `CHECK` constraint.
+    CheckViolation {
+        table: String,
+        constraint: String,
+    },
+    /// A write was planned against columns of a table that an `ALTER TABLE`
+    /// has since changed.
+    TableAlteredConcurrently {
+        table: String,
+    },
+    /// A read-write transaction read from a table that another transaction
+    /// wrote to before it could commit.
+    ConcurrentTableWrite {
+        table: String,
+    },
+    /// A write violated a table's `PRIMARY KEY` or `UNIQUE` constraint.
+    UniqueViolation {
+        constraint: String,
+        /// The names of the constrained columns.
+        columns: Vec<String>,
+        /// The duplicated values of the constrained columns.
+        values: Vec<String>,
+    },
     /// Target cluster has no replicas to service query.
     NoClusterReplicasAvailable(String),
     /// The named operation cannot be run in a transaction.
@@ -172,6 +195,8 @@ pub enum AdapterError {
     /// The named parameter is unknown to the system.
     UnknownParameter(String),
     UnknownPreparedStatement(String),
+    /// The named savepoint does not exist.
+    UnknownSavepoint(String),
     /// The named cluster replica does not exist.
     UnknownClusterReplica {
         cluster_name: String,
@@ -196,7 +221,8 @@ pub enum AdapterError {
     },
     /// The transaction is in write-only mode.
     WriteOnlyTransaction,
-    /// The transaction only supports single table writes
+    /// The transaction writes to multiple tables, which the storage layer
+    /// can't commit atomically
     MultiTableWriteTransaction,
     /// An error occurred in the storage layer
     Storage(mz_storage_client::controller::StorageError),
@@ -253,6 +279,13 @@ impl AdapterError {
                 unstable_dependencies.join("\n    "),
             )),
             AdapterError::PlanError(e) => e.detail(),
+            AdapterError::UniqueViolation {
+                columns, values, ..
+            } => Some(format!(
+                "Key ({})=({}) already exists.",
+                columns.join(", "),
+                values.join(", ")
+            )),
             _ => None,
         }
     }
@@ -266,6 +299,9 @@ impl AdapterError {
                 ..
             } => Some(format!("Available values: {}.", valid_values.join(", "))),
             AdapterError::Eval(e) => e.hint(),
+            AdapterError::ConcurrentTableWrite { .. } => {
+                Some("The transaction might succeed if retried.".into())
+            }
             AdapterError::UnknownLoginRole(_) => {
                 // TODO(benesch): this will be a bad hint when people are used
                 // to creating roles in Materialize, since they might drop the
@@ -401,6 +437,27 @@ impl fmt::Display for AdapterError {
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            AdapterError::CheckViolation { table, constraint } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                table.quoted(),
+                constraint.quoted()
+            ),
+            AdapterError::TableAlteredConcurrently { table } => write!(
+                f,
+                "relation {} was altered by a concurrent transaction",
+                table.quoted()
+            ),
+            AdapterError::ConcurrentTableWrite { table } => write!(
+                f,
+                "could not serialize access due to a concurrent write to relation {}",
+                table.quoted()
+            ),
+            AdapterError::UniqueViolation { constraint, .. } => write!(
+                f,
+                "duplicate key value violates unique constraint {}",
+                constraint.quoted()
+            ),
             AdapterError::NoClusterReplicasAvailable(cluster) => {
                 write!(
                     f,
@@ -489,6 +546,9 @@ impl fmt::Display for AdapterError {
             AdapterError::UnknownPreparedStatement(name) => {
                 write!(f, "prepared statement {} does not exist", name.quoted())
             }
+            AdapterError::UnknownSavepoint(name) => {
+                write!(f, "savepoint {} does not exist", name.quoted())
+            }
             AdapterError::UnknownClusterReplica {
                 cluster_name,
                 replica_name,
@@ -103,10 +103,13 @@ where
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
         StatementKind::AlterObjectRename => "alter_object_rename",
+        StatementKind::AlterCluster => "alter_cluster",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSink => "alter_sink",
         StatementKind::AlterSource => "alter_source",
+        StatementKind::AlterTable => "alter_table",
+        StatementKind::AlterMaterializedView => "alter_materialized_view",
         StatementKind::AlterSystemSet => "alter_system_set",
         StatementKind::AlterSystemReset => "alter_system_reset",
         StatementKind::AlterSystemResetAll => "alter_system_reset_all",
@@ -125,8 +128,11 @@ where
         StatementKind::SetTransaction => "set_transaction",
         StatementKind::Commit => "commit",
         StatementKind::Rollback => "rollback",
+        StatementKind::Savepoint => "savepoint",
+        StatementKind::ReleaseSavepoint => "release_savepoint",
         StatementKind::Subscribe => "subscribe",
         StatementKind::Explain => "explain",
+        StatementKind::ExplainAnalyze => "explain_analyze",
         StatementKind::Declare => "declare",
         StatementKind::Fetch => "fetch",
         StatementKind::Close => "close",
@@ -178,10 +178,12 @@ impl<T: TimestampManipulation> Session<T> {
             // - Currently in `READ ONLY`
             // - Already performed a query
             let read_write_prohibited = match txn.ops {
-                TransactionOps::Peeks(_) | TransactionOps::Subscribe => {
+                TransactionOps::Peeks { .. } | TransactionOps::Subscribe => {
                     txn.access == Some(TransactionAccessMode::ReadOnly)
                 }
-                TransactionOps::None | TransactionOps::Writes(_) => false,
+                TransactionOps::None
+                | TransactionOps::Writes(_)
+                | TransactionOps::ReadWrite { .. } => false,
             };
 
             if read_write_prohibited && access == Some(TransactionAccessMode::ReadWrite) {
@@ -199,6 +201,7 @@ impl<T: TimestampManipulation> Session<T> {
                     write_lock_guard: None,
                     access,
                     id,
+                    savepoints: Vec::new(),
                 });
             }
             TransactionStatus::Started(mut txn)
@@ -233,6 +236,7 @@ impl<T: TimestampManipulation> Session<T> {
                 write_lock_guard: None,
                 access: None,
                 id,
+                savepoints: Vec::new(),
             };
             match stmts {
                 1 => self.transaction = TransactionStatus::Started(txn),
@@ -284,24 +288,32 @@ impl<T: TimestampManipulation> Session<T> {
     }
 
     /// Adds operations to the current transaction. An error is produced if
-    /// they cannot be merged (i.e., a timestamp-dependent read cannot be
-    /// merged to an insert).
+    /// they cannot be merged (i.e., a timestamp-dependent read of a source
+    /// cannot be merged to an insert).
+    ///
+    /// Timestamp-dependent reads of user tables can be merged with writes,
+    /// which makes the transaction a read-write transaction.
     pub fn add_transaction_ops(&mut self, add_ops: TransactionOps<T>) -> Result<(), AdapterError> {
         match &mut self.transaction {
             TransactionStatus::Started(Transaction { ops, access, .. })
             | TransactionStatus::InTransaction(Transaction { ops, access, .. })
             | TransactionStatus::InTransactionImplicit(Transaction { ops, access, .. }) => {
+                let read_only = matches!(access, Some(TransactionAccessMode::ReadOnly));
                 match ops {
                     TransactionOps::None => {
-                        if matches!(access, Some(TransactionAccessMode::ReadOnly))
-                            && matches!(add_ops, TransactionOps::Writes(_))
-                        {
+                        if read_only && matches!(add_ops, TransactionOps::Writes(_)) {
                             return Err(AdapterError::ReadOnlyTransaction);
                         }
                         *ops = add_ops;
                     }
-                    TransactionOps::Peeks(txn_timestamp_context) => match add_ops {
-                        TransactionOps::Peeks(add_timestamp_context) => {
+                    TransactionOps::Peeks {
+                        timestamp_context: txn_timestamp_context,
+                        table_reads: txn_table_reads,
+                    } => match add_ops {
+                        TransactionOps::Peeks {
+                            timestamp_context: add_timestamp_context,
+                            table_reads: add_table_reads,
+                        } => {
                             match (&txn_timestamp_context, add_timestamp_context) {
                                 (
                                     TimestampContext::TimelineTimestamp(txn_timeline, txn_ts),
@@ -315,6 +327,12 @@ impl<T: TimestampManipulation> Session<T> {
                                 }
                                 (_, TimestampContext::NoTimestamp) => {}
                             };
+                            match (txn_table_reads.as_mut(), add_table_reads) {
+                                (Some(txn_table_reads), Some(mut add_table_reads)) => {
+                                    txn_table_reads.append(&mut add_table_reads)
+                                }
+                                _ => *txn_table_reads = None,
+                            }
                         }
                         // Iff peeks thus far do not have a timestamp (i.e.
                         // they are constant), we can switch to a write
@@ -324,6 +342,24 @@ impl<T: TimestampManipulation> Session<T> {
                         {
                             *ops = writes;
                         }
+                        // Iff peeks thus far only read from user tables, we
+                        // can switch to a read-write transaction.
+                        TransactionOps::Writes(writes)
+                            if txn_table_reads.is_some() && !read_only =>
+                        {
+                            let TransactionOps::Peeks {
+                                timestamp_context,
+                                table_reads,
+                            } = mem::take(ops)
+                            else {
+                                unreachable!("matched above")
+                            };
+                            *ops = TransactionOps::ReadWrite {
+                                timestamp_context,
+                                table_reads: table_reads.expect("checked above"),
+                                writes,
+                            };
+                        }
                         _ => return Err(AdapterError::ReadOnlyTransaction),
                     },
                     TransactionOps::Subscribe => {
@@ -333,23 +369,55 @@ impl<T: TimestampManipulation> Session<T> {
                         TransactionOps::Writes(mut add_writes) => {
                             // We should have already checked the access above, but make sure we don't miss
                             // it anyway.
-                            assert!(!matches!(access, Some(TransactionAccessMode::ReadOnly)));
+                            assert!(!read_only);
                             txn_writes.append(&mut add_writes);
-
-                            if txn_writes
-                                .iter()
-                                .map(|op| op.id)
-                                .collect::<BTreeSet<_>>()
-                                .len()
-                                > 1
-                            {
-                                return Err(AdapterError::MultiTableWriteTransaction);
-                            }
                         }
                         // Iff peeks do not have a timestamp (i.e. they are
                         // constant), we can permit them.
-                        TransactionOps::Peeks(timestamp_context)
-                            if !timestamp_context.contains_timestamp() => {}
+                        TransactionOps::Peeks {
+                            timestamp_context, ..
+                        } if !timestamp_context.contains_timestamp() => {}
+                        // Iff peeks only read from user tables, we can switch
+                        // to a read-write transaction.
+                        TransactionOps::Peeks {
+                            timestamp_context,
+                            table_reads: Some(table_reads),
+                        } if !read_only => {
+                            *ops = TransactionOps::ReadWrite {
+                                timestamp_context,
+                                table_reads,
+                                writes: mem::take(txn_writes),
+                            };
+                        }
+                        _ => {
+                            return Err(AdapterError::WriteOnlyTransaction);
+                        }
+                    },
+                    TransactionOps::ReadWrite {
+                        timestamp_context: txn_timestamp_context,
+                        table_reads: txn_table_reads,
+                        writes: txn_writes,
+                    } => match add_ops {
+                        TransactionOps::Writes(mut add_writes) => {
+                            txn_writes.append(&mut add_writes);
+                        }
+                        TransactionOps::Peeks {
+                            timestamp_context, ..
+                        } if !timestamp_context.contains_timestamp() => {}
+                        TransactionOps::Peeks {
+                            timestamp_context,
+                            table_reads: Some(mut table_reads),
+                        } => {
+                            // All of a transaction's reads happen at the
+                            // same timestamp, so this indicates a bug.
+                            if *txn_timestamp_context != timestamp_context {
+                                return Err(AdapterError::Internal(format!(
+                                    "read-write transaction read at {:?} after reading at {:?}",
+                                    timestamp_context, txn_timestamp_context
+                                )));
+                            }
+                            txn_table_reads.append(&mut table_reads);
+                        }
                         _ => {
                             return Err(AdapterError::WriteOnlyTransaction);
                         }
@@ -427,7 +495,7 @@ impl<T: TimestampManipulation> Session<T> {
     /// anomalies will occur if cleared.
     pub fn take_transaction_timestamp_context(&mut self) -> Option<TimestampContext<T>> {
         if let Some(Transaction { ops, .. }) = self.transaction.inner_mut() {
-            if let TransactionOps::Peeks(_) = ops {
+            if let TransactionOps::Peeks { .. } = ops {
                 let ops = std::mem::take(ops);
                 Some(ops.timestamp_context().expect("checked above"))
             } else {
@@ -441,15 +509,22 @@ impl<T: TimestampManipulation> Session<T> {
     /// Returns the transaction's read timestamp context, if set.
     ///
     /// Returns `None` if there is no active transaction, or if the active
-    /// transaction is not a read transaction.
+    /// transaction has not read.
     pub fn get_transaction_timestamp_context(&self) -> Option<TimestampContext<T>> {
         match self.transaction.inner() {
             Some(Transaction {
                 pcx: _,
-                ops: TransactionOps::Peeks(timestamp_context),
+                ops:
+                    TransactionOps::Peeks {
+                        timestamp_context, ..
+                    }
+                    | TransactionOps::ReadWrite {
+                        timestamp_context, ..
+                    },
                 write_lock_guard: _,
                 access: _,
                 id: _,
+                savepoints: _,
             }) => Some(timestamp_context.clone()),
             _ => None,
         }
@@ -461,14 +536,79 @@ impl<T: TimestampManipulation> Session<T> {
             self.transaction.inner(),
             Some(Transaction {
                 pcx: _,
-                ops: TransactionOps::Peeks(TimestampContext::TimelineTimestamp(_, _)),
+                ops: TransactionOps::Peeks {
+                    timestamp_context: TimestampContext::TimelineTimestamp(_, _),
+                    ..
+                } | TransactionOps::ReadWrite { .. },
                 write_lock_guard: _,
                 access: _,
                 id: _,
+                savepoints: _,
             })
         )
     }
 
+    /// Returns the writes the current transaction has performed, which have
+    /// not yet been committed.
+    pub fn transaction_writes(&self) -> &[WriteOp] {
+        match self.transaction.inner() {
+            Some(txn) => txn.ops.writes(),
+            None => &[],
+        }
+    }
+
+    /// Establishes a savepoint named `name` in the current transaction.
+    pub fn create_savepoint(&mut self, name: String) -> Result<(), AdapterError> {
+        let TransactionStatus::InTransaction(txn) = &mut self.transaction else {
+            return Err(AdapterError::OperationRequiresTransaction(
+                "SAVEPOINT".into(),
+            ));
+        };
+        let writes = txn.ops.writes().len();
+        txn.savepoints.push(Savepoint { name, writes });
+        Ok(())
+    }
+
+    /// Destroys the most recently established savepoint named `name`, along
+    /// with all savepoints established after it. The writes performed since
+    /// the savepoint was established are kept.
+    pub fn release_savepoint(&mut self, name: &str) -> Result<(), AdapterError> {
+        let TransactionStatus::InTransaction(txn) = &mut self.transaction else {
+            return Err(AdapterError::OperationRequiresTransaction(
+                "RELEASE SAVEPOINT".into(),
+            ));
+        };
+        let idx = txn.savepoint_position(name)?;
+        txn.savepoints.truncate(idx);
+        Ok(())
+    }
+
+    /// Discards the writes performed since the most recently established
+    /// savepoint named `name`, and destroys all savepoints established after
+    /// it. The savepoint itself remains established.
+    ///
+    /// Rolling back to a savepoint of a failed transaction returns the
+    /// transaction to normal operation.
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), AdapterError> {
+        let txn = match &mut self.transaction {
+            TransactionStatus::InTransaction(txn) | TransactionStatus::Failed(txn) => txn,
+            TransactionStatus::Default
+            | TransactionStatus::Started(_)
+            | TransactionStatus::InTransactionImplicit(_) => {
+                return Err(AdapterError::OperationRequiresTransaction(
+                    "ROLLBACK TO SAVEPOINT".into(),
+                ));
+            }
+        };
+        let idx = txn.savepoint_position(name)?;
+        txn.savepoints.truncate(idx + 1);
+        txn.ops.truncate_writes(txn.savepoints[idx].writes);
+        if let TransactionStatus::Failed(txn) = mem::take(&mut self.transaction) {
+            self.transaction = TransactionStatus::InTransaction(txn);
+        }
+        Ok(())
+    }
+
     /// Registers the prepared statement under `name`.
     pub fn set_prepared_statement(&mut self, name: String, statement: PreparedStatement) {
         self.prepared_statements.insert(name, statement);
@@ -871,6 +1011,19 @@ pub struct Transaction<T> {
     write_lock_guard: Option<OwnedMutexGuard<()>>,
     /// Access mode (read only, read write).
     access: Option<TransactionAccessMode>,
+    /// The savepoints established in the transaction, in the order they were
+    /// established.
+    savepoints: Vec<Savepoint>,
+}
+
+/// A savepoint established by `SAVEPOINT`.
+#[derive(Debug)]
+struct Savepoint {
+    /// The name of the savepoint.
+    name: String,
+    /// The number of writes the transaction had performed when the savepoint
+    /// was established.
+    writes: usize,
 }
 
 impl<T> Transaction<T> {
@@ -879,13 +1032,28 @@ impl<T> Transaction<T> {
         self.write_lock_guard = Some(guard);
     }
 
+    /// Returns the index of the most recently established savepoint named
+    /// `name`.
+    fn savepoint_position(&self, name: &str) -> Result<usize, AdapterError> {
+        self.savepoints
+            .iter()
+            .rposition(|savepoint| savepoint.name == name)
+            .ok_or_else(|| AdapterError::UnknownSavepoint(name.into()))
+    }
+
     /// The timeline of the transaction, if one exists.
     fn timeline(&self) -> Option<Timeline> {
         match &self.ops {
-            TransactionOps::Peeks(TimestampContext::TimelineTimestamp(timeline, _)) => {
-                Some(timeline.clone())
+            TransactionOps::Peeks {
+                timestamp_context: TimestampContext::TimelineTimestamp(timeline, _),
+                ..
             }
-            TransactionOps::Peeks(_)
+            | TransactionOps::ReadWrite {
+                timestamp_context: TimestampContext::TimelineTimestamp(timeline, _),
+                ..
+            } => Some(timeline.clone()),
+            TransactionOps::Peeks { .. }
+            | TransactionOps::ReadWrite { .. }
             | TransactionOps::None
             | TransactionOps::Subscribe
             | TransactionOps::Writes(_) => None,
@@ -935,33 +1103,103 @@ impl<T> From<&TransactionStatus<T>> for TransactionCode {
 
 /// The type of operation being performed by the transaction.
 ///
-/// This is needed because we currently do not allow mixing reads and writes in
-/// a transaction. Use this to record what we have done, and what may need to
-/// happen at commit.
+/// This is needed because we only allow mixing reads and writes in a
+/// transaction when the reads are of user tables. Use this to record what we
+/// have done, and what may need to happen at commit.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TransactionOps<T> {
     /// The transaction has been initiated, but no statement has yet been executed
     /// in it.
     None,
-    /// This transaction has had a peek (`SELECT`, `SUBSCRIBE`). If the inner value
-    /// is has a timestamp, it must only do other peeks. However, if it doesn't
-    /// have a timestamp (i.e. the values are constants), the transaction can still
-    /// perform writes.
-    Peeks(TimestampContext<T>),
+    /// This transaction has had a peek (`SELECT`, `SUBSCRIBE`). If the timestamp
+    /// context has a timestamp, it must only do other peeks, unless all peeks
+    /// read from user tables. However, if it doesn't have a timestamp (i.e. the
+    /// values are constants), the transaction can still perform writes.
+    Peeks {
+        /// The timestamp context of the peeks.
+        timestamp_context: TimestampContext<T>,
+        /// The user tables that the peeks read from, or `None` if some peek
+        /// read from an object that is not a user table or a view of user
+        /// tables.
+        table_reads: Option<BTreeSet<GlobalId>>,
+    },
     /// This transaction has done a `SUBSCRIBE` and must do nothing else.
     Subscribe,
     /// This transaction has had a write (`INSERT`, `UPDATE`, `DELETE`) and must
-    /// only do other writes, or reads whose timestamp is None (i.e. constants).
+    /// only do other writes, reads whose timestamp is None (i.e. constants), or
+    /// reads of user tables.
     Writes(Vec<WriteOp>),
+    /// This transaction has read from user tables and written to user tables.
+    /// It must only do other writes, or other reads of user tables at the same
+    /// timestamp. It commits only if none of `table_reads` were written to by
+    /// another transaction after the reads' timestamp.
+    ReadWrite {
+        /// The timestamp context of the reads.
+        timestamp_context: TimestampContext<T>,
+        /// The user tables that the reads read from.
+        table_reads: BTreeSet<GlobalId>,
+        /// The writes to commit.
+        writes: Vec<WriteOp>,
+    },
 }
 
 impl<T> TransactionOps<T> {
     fn timestamp_context(self) -> Option<TimestampContext<T>> {
         match self {
-            TransactionOps::Peeks(timestamp_context) => Some(timestamp_context),
+            TransactionOps::Peeks {
+                timestamp_context, ..
+            }
+            | TransactionOps::ReadWrite {
+                timestamp_context, ..
+            } => Some(timestamp_context),
             TransactionOps::None | TransactionOps::Subscribe | TransactionOps::Writes(_) => None,
         }
     }
+
+    /// The writes that the transaction will commit.
+    pub(crate) fn writes(&self) -> &[WriteOp] {
+        match self {
+            TransactionOps::Writes(writes) | TransactionOps::ReadWrite { writes, .. } => writes,
+            TransactionOps::None | TransactionOps::Peeks { .. } | TransactionOps::Subscribe => &[],
+        }
+    }
+
+    /// Discards all but the first `len` writes. If no writes remain, the ops
+    /// revert to what they were before the transaction first wrote.
+    fn truncate_writes(&mut self, len: usize) {
+        *self = match mem::take(self) {
+            TransactionOps::Writes(mut writes) => {
+                writes.truncate(len);
+                if writes.is_empty() {
+                    TransactionOps::None
+                } else {
+                    TransactionOps::Writes(writes)
+                }
+            }
+            TransactionOps::ReadWrite {
+                timestamp_context,
+                table_reads,
+                mut writes,
+            } => {
+                writes.truncate(len);
+                if writes.is_empty() {
+                    TransactionOps::Peeks {
+                        timestamp_context,
+                        table_reads: Some(table_reads),
+                    }
+                } else {
+                    TransactionOps::ReadWrite {
+                        timestamp_context,
+                        table_reads,
+                        writes,
+                    }
+                }
+            }
+            ops @ (TransactionOps::None
+            | TransactionOps::Peeks { .. }
+            | TransactionOps::Subscribe) => ops,
+        };
+    }
 }
 
 impl<T> Default for TransactionOps<T> {
@@ -336,6 +336,21 @@ pub const MAX_RESULT_SIZE: ServerVar<u32> = ServerVar {
     safe: true,
 };
 
+/// The maximum number of arrangement records a single dataflow may hold on a replica worker.
+///
+/// Dataflows exceeding this limit are dropped with an error. The limit counts records regardless
+/// of their size, so it only approximates the memory a dataflow uses. Zero means that dataflows
+/// are not limited.
+pub const MAX_DATAFLOW_ARRANGEMENT_RECORDS: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("max_dataflow_arrangement_records"),
+    value: &0,
+    description: "The maximum number of arrangement records, regardless of their size, a \
+                  dataflow may hold on each replica worker before it is dropped, or 0 for no \
+                  limit (Materialize).",
+    internal: false,
+    safe: true,
+};
+
 /// The logical compaction window for builtin tables and sources that have the
 /// `retained_metrics_relation` flag set.
 ///
@@ -350,6 +365,63 @@ pub const METRICS_RETENTION: ServerVar<Duration> = ServerVar {
     safe: true,
 };
 
+const TABLE_EXPIRATION_INTERVAL: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("table_expiration_interval"),
+    value: &Duration::from_secs(10),
+    description: "How often to remove the expired rows of tables created with EXPIRE ROWS AFTER \
+                  (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+const STATEMENT_LOGGING_SAMPLE_PERCENT: ServerVar<u32> = ServerVar {
+    name: UncasedStr::new("statement_logging_sample_percent"),
+    value: &0,
+    description: "The percentage of statements to record in mz_statement_execution_history, \
+                  from 0 to 100 (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+const STATEMENT_LOGGING_RETENTION: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("statement_logging_retention"),
+    // 30 days
+    value: &Duration::from_secs(30 * 24 * 60 * 60),
+    description: "The time to retain entries in mz_statement_execution_history (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+const STATEMENT_LOGGING_MAX_RETAINED_BYTES: ServerVar<u32> = ServerVar {
+    name: UncasedStr::new("statement_logging_max_retained_bytes"),
+    // 64 MiB
+    value: &67_108_864,
+    description: "The maximum size in bytes of the entries in mz_statement_execution_history; \
+                  the oldest entries are removed early to stay below it (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+/// Feature flag indicating whether the optimizer uses estimates of the sizes
+/// of collections to order joins.
+const ENABLE_CARDINALITY_ESTIMATES: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_cardinality_estimates"),
+    value: &false,
+    description: "Feature flag indicating whether to use estimates of the sizes of collections when ordering joins (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+/// Feature flag indicating whether identical arrangements built by separate
+/// indexes and materialized views on the same cluster are shared.
+const ENABLE_ARRANGEMENT_SHARING: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_arrangement_sharing"),
+    value: &false,
+    description: "Feature flag indicating whether to share identical arrangements across the dataflows of a cluster (Materialize).",
+    internal: true,
+    safe: true,
+};
+
 static DEFAULT_ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<Vec<String>> = Lazy::new(Vec::new);
 static ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<ServerVar<Vec<String>>> = Lazy::new(|| ServerVar {
     name: UncasedStr::new("allowed_cluster_replica_sizes"),
@@ -378,6 +450,28 @@ const PERSIST_COMPACTION_MINIMUM_TIMEOUT: ServerVar<Duration> = ServerVar {
     safe: true,
 };
 
+/// Controls [`mz_persist_client::cfg::DynamicConfig::stats_collection_enabled`].
+const PERSIST_STATS_COLLECTION_ENABLED: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("persist_stats_collection_enabled"),
+    value: &true,
+    description: "Whether to calculate and record statistics about the data stored in \
+                  persist to be used at read time, see persist_stats_filter_enabled \
+                  (Materialize).",
+    internal: true,
+    safe: true,
+};
+
+/// Controls [`mz_persist_client::cfg::DynamicConfig::stats_filter_enabled`].
+const PERSIST_STATS_FILTER_ENABLED: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("persist_stats_filter_enabled"),
+    value: &true,
+    description: "Whether to use recorded statistics about the data stored in persist \
+                  to filter at read time, see persist_stats_collection_enabled \
+                  (Materialize).",
+    internal: true,
+    safe: true,
+};
+
 /// Boolean flag indicating that the remote configuration was synchronized at
 /// least once with the persistent [SessionVars].
 pub static CONFIG_HAS_SYNCED_ONCE: ServerVar<bool> = ServerVar {
@@ -1033,17 +1127,25 @@ pub struct SystemVars {
     max_secrets: SystemVar<u32>,
     max_roles: SystemVar<u32>,
     max_result_size: SystemVar<u32>,
+    max_dataflow_arrangement_records: SystemVar<usize>,
     allowed_cluster_replica_sizes: SystemVar<Vec<String>>, // TODO: BTreeSet<String> will be better
 
     // features
-    // (empty)
+    enable_cardinality_estimates: SystemVar<bool>,
+    enable_arrangement_sharing: SystemVar<bool>,
 
     // persist configuration
     persist_blob_target_size: SystemVar<usize>,
     persist_compaction_minimum_timeout: SystemVar<Duration>,
+    persist_stats_collection_enabled: SystemVar<bool>,
+    persist_stats_filter_enabled: SystemVar<bool>,
 
     // misc
     metrics_retention: SystemVar<Duration>,
+    table_expiration_interval: SystemVar<Duration>,
+    statement_logging_sample_percent: SystemVar<u32>,
+    statement_logging_retention: SystemVar<Duration>,
+    statement_logging_max_retained_bytes: SystemVar<u32>,
 
     // testing
     mock_audit_event_timestamp: SystemVar<Option<mz_repr::Timestamp>>,
@@ -1066,10 +1168,21 @@ impl Default for SystemVars {
             max_secrets: SystemVar::new(&MAX_SECRETS),
             max_roles: SystemVar::new(&MAX_ROLES),
             max_result_size: SystemVar::new(&MAX_RESULT_SIZE),
+            max_dataflow_arrangement_records: SystemVar::new(&MAX_DATAFLOW_ARRANGEMENT_RECORDS),
             allowed_cluster_replica_sizes: SystemVar::new(&ALLOWED_CLUSTER_REPLICA_SIZES),
             persist_blob_target_size: SystemVar::new(&PERSIST_BLOB_TARGET_SIZE),
             persist_compaction_minimum_timeout: SystemVar::new(&PERSIST_COMPACTION_MINIMUM_TIMEOUT),
+            persist_stats_collection_enabled: SystemVar::new(&PERSIST_STATS_COLLECTION_ENABLED),
+            persist_stats_filter_enabled: SystemVar::new(&PERSIST_STATS_FILTER_ENABLED),
             metrics_retention: SystemVar::new(&METRICS_RETENTION),
+            table_expiration_interval: SystemVar::new(&TABLE_EXPIRATION_INTERVAL),
+            statement_logging_sample_percent: SystemVar::new(&STATEMENT_LOGGING_SAMPLE_PERCENT),
+            statement_logging_retention: SystemVar::new(&STATEMENT_LOGGING_RETENTION),
+            statement_logging_max_retained_bytes: SystemVar::new(
+                &STATEMENT_LOGGING_MAX_RETAINED_BYTES,
+            ),
+            enable_cardinality_estimates: SystemVar::new(&ENABLE_CARDINALITY_ESTIMATES),
+            enable_arrangement_sharing: SystemVar::new(&ENABLE_ARRANGEMENT_SHARING),
             mock_audit_event_timestamp: SystemVar::new(&MOCK_AUDIT_EVENT_TIMESTAMP),
         }
     }
@@ -1079,7 +1192,7 @@ impl SystemVars {
     /// Returns an iterator over the configuration parameters and their current
     /// values on disk.
     pub fn iter(&self) -> impl Iterator<Item = &dyn Var> {
-        let vars: [&dyn Var; 19] = [
+        let vars: [&dyn Var; 27] = [
             &self.config_has_synced_once,
             &self.max_aws_privatelink_connections,
             &self.max_tables,
@@ -1094,10 +1207,19 @@ impl SystemVars {
             &self.max_secrets,
             &self.max_roles,
             &self.max_result_size,
+            &self.max_dataflow_arrangement_records,
             &self.allowed_cluster_replica_sizes,
             &self.persist_blob_target_size,
             &self.persist_compaction_minimum_timeout,
+            &self.persist_stats_collection_enabled,
+            &self.persist_stats_filter_enabled,
             &self.metrics_retention,
+            &self.table_expiration_interval,
+            &self.statement_logging_sample_percent,
+            &self.statement_logging_retention,
+            &self.statement_logging_max_retained_bytes,
+            &self.enable_cardinality_estimates,
+            &self.enable_arrangement_sharing,
             &self.mock_audit_event_timestamp,
         ];
         vars.into_iter()
@@ -1155,14 +1277,32 @@ impl SystemVars {
             Ok(&self.max_roles)
         } else if name == MAX_RESULT_SIZE.name {
             Ok(&self.max_result_size)
+        } else if name == MAX_DATAFLOW_ARRANGEMENT_RECORDS.name {
+            Ok(&self.max_dataflow_arrangement_records)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             Ok(&self.allowed_cluster_replica_sizes)
         } else if name == PERSIST_BLOB_TARGET_SIZE.name {
             Ok(&self.persist_blob_target_size)
         } else if name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name {
             Ok(&self.persist_compaction_minimum_timeout)
+        } else if name == PERSIST_STATS_COLLECTION_ENABLED.name {
+            Ok(&self.persist_stats_collection_enabled)
+        } else if name == PERSIST_STATS_FILTER_ENABLED.name {
+            Ok(&self.persist_stats_filter_enabled)
         } else if name == METRICS_RETENTION.name {
             Ok(&self.metrics_retention)
+        } else if name == TABLE_EXPIRATION_INTERVAL.name {
+            Ok(&self.table_expiration_interval)
+        } else if name == STATEMENT_LOGGING_SAMPLE_PERCENT.name {
+            Ok(&self.statement_logging_sample_percent)
+        } else if name == STATEMENT_LOGGING_RETENTION.name {
+            Ok(&self.statement_logging_retention)
+        } else if name == STATEMENT_LOGGING_MAX_RETAINED_BYTES.name {
+            Ok(&self.statement_logging_max_retained_bytes)
+        } else if name == ENABLE_CARDINALITY_ESTIMATES.name {
+            Ok(&self.enable_cardinality_estimates)
+        } else if name == ENABLE_ARRANGEMENT_SHARING.name {
+            Ok(&self.enable_arrangement_sharing)
         } else if name == MOCK_AUDIT_EVENT_TIMESTAMP.name {
             Ok(&self.mock_audit_event_timestamp)
         } else {
@@ -1208,14 +1348,32 @@ impl SystemVars {
             self.max_roles.is_default(value)
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.is_default(value)
+        } else if name == MAX_DATAFLOW_ARRANGEMENT_RECORDS.name {
+            self.max_dataflow_arrangement_records.is_default(value)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.is_default(value)
         } else if name == PERSIST_BLOB_TARGET_SIZE.name {
             self.persist_blob_target_size.is_default(value)
         } else if name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name {
             self.persist_compaction_minimum_timeout.is_default(value)
+        } else if name == PERSIST_STATS_COLLECTION_ENABLED.name {
+            self.persist_stats_collection_enabled.is_default(value)
+        } else if name == PERSIST_STATS_FILTER_ENABLED.name {
+            self.persist_stats_filter_enabled.is_default(value)
         } else if name == METRICS_RETENTION.name {
             self.metrics_retention.is_default(value)
+        } else if name == TABLE_EXPIRATION_INTERVAL.name {
+            self.table_expiration_interval.is_default(value)
+        } else if name == STATEMENT_LOGGING_SAMPLE_PERCENT.name {
+            self.statement_logging_sample_percent.is_default(value)
+        } else if name == STATEMENT_LOGGING_RETENTION.name {
+            self.statement_logging_retention.is_default(value)
+        } else if name == STATEMENT_LOGGING_MAX_RETAINED_BYTES.name {
+            self.statement_logging_max_retained_bytes.is_default(value)
+        } else if name == ENABLE_CARDINALITY_ESTIMATES.name {
+            self.enable_cardinality_estimates.is_default(value)
+        } else if name == ENABLE_ARRANGEMENT_SHARING.name {
+            self.enable_arrangement_sharing.is_default(value)
         } else if name == MOCK_AUDIT_EVENT_TIMESTAMP.name {
             self.mock_audit_event_timestamp.is_default(value)
         } else {
@@ -1270,14 +1428,32 @@ impl SystemVars {
             self.max_roles.set(value)
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.set(value)
+        } else if name == MAX_DATAFLOW_ARRANGEMENT_RECORDS.name {
+            self.max_dataflow_arrangement_records.set(value)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.set(value)
         } else if name == PERSIST_BLOB_TARGET_SIZE.name {
             self.persist_blob_target_size.set(value)
         } else if name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name {
             self.persist_compaction_minimum_timeout.set(value)
+        } else if name == PERSIST_STATS_COLLECTION_ENABLED.name {
+            self.persist_stats_collection_enabled.set(value)
+        } else if name == PERSIST_STATS_FILTER_ENABLED.name {
+            self.persist_stats_filter_enabled.set(value)
         } else if name == METRICS_RETENTION.name {
             self.metrics_retention.set(value)
+        } else if name == TABLE_EXPIRATION_INTERVAL.name {
+            self.table_expiration_interval.set(value)
+        } else if name == STATEMENT_LOGGING_SAMPLE_PERCENT.name {
+            self.statement_logging_sample_percent.set(value)
+        } else if name == STATEMENT_LOGGING_RETENTION.name {
+            self.statement_logging_retention.set(value)
+        } else if name == STATEMENT_LOGGING_MAX_RETAINED_BYTES.name {
+            self.statement_logging_max_retained_bytes.set(value)
+        } else if name == ENABLE_CARDINALITY_ESTIMATES.name {
+            self.enable_cardinality_estimates.set(value)
+        } else if name == ENABLE_ARRANGEMENT_SHARING.name {
+            self.enable_arrangement_sharing.set(value)
         } else if name == MOCK_AUDIT_EVENT_TIMESTAMP.name {
             self.mock_audit_event_timestamp.set(value)
         } else {
@@ -1327,14 +1503,32 @@ impl SystemVars {
             Ok(self.max_roles.reset())
         } else if name == MAX_RESULT_SIZE.name {
             Ok(self.max_result_size.reset())
+        } else if name == MAX_DATAFLOW_ARRANGEMENT_RECORDS.name {
+            Ok(self.max_dataflow_arrangement_records.reset())
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             Ok(self.allowed_cluster_replica_sizes.reset())
         } else if name == PERSIST_BLOB_TARGET_SIZE.name {
             Ok(self.persist_blob_target_size.reset())
         } else if name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name {
             Ok(self.persist_compaction_minimum_timeout.reset())
+        } else if name == PERSIST_STATS_COLLECTION_ENABLED.name {
+            Ok(self.persist_stats_collection_enabled.reset())
+        } else if name == PERSIST_STATS_FILTER_ENABLED.name {
+            Ok(self.persist_stats_filter_enabled.reset())
         } else if name == METRICS_RETENTION.name {
             Ok(self.metrics_retention.reset())
+        } else if name == TABLE_EXPIRATION_INTERVAL.name {
+            Ok(self.table_expiration_interval.reset())
+        } else if name == STATEMENT_LOGGING_SAMPLE_PERCENT.name {
+            Ok(self.statement_logging_sample_percent.reset())
+        } else if name == STATEMENT_LOGGING_RETENTION.name {
+            Ok(self.statement_logging_retention.reset())
+        } else if name == STATEMENT_LOGGING_MAX_RETAINED_BYTES.name {
+            Ok(self.statement_logging_max_retained_bytes.reset())
+        } else if name == ENABLE_CARDINALITY_ESTIMATES.name {
+            Ok(self.enable_cardinality_estimates.reset())
+        } else if name == ENABLE_ARRANGEMENT_SHARING.name {
+            Ok(self.enable_arrangement_sharing.reset())
         } else if name == MOCK_AUDIT_EVENT_TIMESTAMP.name {
             Ok(self.mock_audit_event_timestamp.reset())
         } else {
@@ -1412,6 +1606,11 @@ impl SystemVars {
         *self.max_result_size.value()
     }
 
+    /// Returns the value of the `max_dataflow_arrangement_records` configuration parameter.
+    pub fn max_dataflow_arrangement_records(&self) -> usize {
+        *self.max_dataflow_arrangement_records.value()
+    }
+
     /// Returns the value of the `allowed_cluster_replica_sizes` configuration parameter.
     pub fn allowed_cluster_replica_sizes(&self) -> &Vec<String> {
         self.allowed_cluster_replica_sizes.value()
@@ -1427,11 +1626,52 @@ impl SystemVars {
         *self.persist_compaction_minimum_timeout.value()
     }
 
+    /// Returns the `persist_stats_collection_enabled` configuration parameter.
+    pub fn persist_stats_collection_enabled(&self) -> bool {
+        *self.persist_stats_collection_enabled.value()
+    }
+
+    /// Returns the `persist_stats_filter_enabled` configuration parameter.
+    pub fn persist_stats_filter_enabled(&self) -> bool {
+        *self.persist_stats_filter_enabled.value()
+    }
+
     /// Returns the `metrics_retention` configuration parameter.
     pub fn metrics_retention(&self) -> Duration {
         *self.metrics_retention.value()
     }
 
+    /// Returns the `table_expiration_interval` configuration parameter.
+    pub fn table_expiration_interval(&self) -> Duration {
+        *self.table_expiration_interval.value()
+    }
+
+    /// Returns the `statement_logging_sample_percent` configuration parameter.
+    pub fn statement_logging_sample_percent(&self) -> u32 {
+        *self.statement_logging_sample_percent.value()
+    }
+
+    /// Returns the `statement_logging_retention` configuration parameter.
+    pub fn statement_logging_retention(&self) -> Duration {
+        *self.statement_logging_retention.value()
+    }
+
+    /// Returns the `statement_logging_max_retained_bytes` configuration
+    /// parameter.
+    pub fn statement_logging_max_retained_bytes(&self) -> u32 {
+        *self.statement_logging_max_retained_bytes.value()
+    }
+
+    /// Returns the `enable_cardinality_estimates` configuration parameter.
+    pub fn enable_cardinality_estimates(&self) -> bool {
+        *self.enable_cardinality_estimates.value()
+    }
+
+    /// Returns the `enable_arrangement_sharing` configuration parameter.
+    pub fn enable_arrangement_sharing(&self) -> bool {
+        *self.enable_arrangement_sharing.value()
+    }
+
     /// Returns the `mock_audit_event_timestamp` configuration parameter.
     pub fn mock_audit_event_timestamp(&self) -> Option<mz_repr::Timestamp> {
         *self.mock_audit_event_timestamp.value()
@@ -2272,7 +2512,9 @@ impl From<TransactionIsolationLevel> for IsolationLevel {
 
 /// Returns whether the named variable is a compute configuration parameter.
 pub(crate) fn is_compute_config_var(name: &str) -> bool {
-    name == MAX_RESULT_SIZE.name() || is_persist_config_var(name)
+    name == MAX_RESULT_SIZE.name()
+        || name == MAX_DATAFLOW_ARRANGEMENT_RECORDS.name()
+        || is_persist_config_var(name)
 }
 
 /// Returns whether the named variable is a storage configuration parameter.
@@ -2282,5 +2524,8 @@ pub(crate) fn is_storage_config_var(name: &str) -> bool {
 
 /// Returns whether the named variable is a persist configuration parameter.
 fn is_persist_config_var(name: &str) -> bool {
-    name == PERSIST_BLOB_TARGET_SIZE.name() || name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name()
+    name == PERSIST_BLOB_TARGET_SIZE.name()
+        || name == PERSIST_COMPACTION_MINIMUM_TIMEOUT.name()
+        || name == PERSIST_STATS_COLLECTION_ENABLED.name()
+        || name == PERSIST_STATS_FILTER_ENABLED.name()
 }
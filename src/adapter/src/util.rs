@@ -31,7 +31,7 @@ use mz_transform::TransformError;
 
 use crate::catalog::Catalog;
 use crate::command::{Command, Response};
-use crate::coord::Message;
+use crate::coord::{Message, StatementEndedExecutionReason, StatementLoggingId};
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, Session};
 use crate::{ExecuteResponse, PeekResponseUnary};
@@ -44,6 +44,9 @@ pub struct ClientTransmitter<T: Transmittable> {
     /// Expresses an optional [`soft_assert`] on the set of values allowed to be
     /// sent from `self`.
     allowed: Option<Vec<T::Allowed>>,
+    /// The logged statement execution that finishes with the response sent
+    /// from `self`, if any.
+    logged_statement: Option<StatementLoggingId>,
 }
 
 impl<T: Transmittable> ClientTransmitter<T> {
@@ -56,6 +59,7 @@ impl<T: Transmittable> ClientTransmitter<T> {
             tx: Some(tx),
             internal_cmd_tx,
             allowed: None,
+            logged_statement: None,
         }
     }
 
@@ -76,6 +80,11 @@ impl<T: Transmittable> ClientTransmitter<T> {
             see ClientTransmitter::set_allowed"
         );
 
+        let result = match self.logged_statement.take() {
+            Some(id) => T::end_logged_statement(result, id, &self.internal_cmd_tx),
+            None => result,
+        };
+
         // If we were not able to send a message, we must clean up the session
         // ourselves. Return it to the caller for disposal.
         if let Err(res) = self
@@ -105,6 +114,12 @@ impl<T: Transmittable> ClientTransmitter<T> {
     pub fn set_allowed(&mut self, allowed: Vec<T::Allowed>) {
         self.allowed = Some(allowed);
     }
+
+    /// Sets `self` so that the logged statement execution `id` finishes with
+    /// the response sent from `self`.
+    pub fn set_logged_statement(&mut self, id: StatementLoggingId) {
+        self.logged_statement = Some(id);
+    }
 }
 
 /// A helper trait for [`ClientTransmitter`].
@@ -121,6 +136,33 @@ pub trait Transmittable {
     ///   trait for `bool`, and return `true`. However, it might not be
     ///   semantically appropriate to expose `From<&Self> for bool`.
     fn to_allowed(&self) -> Self::Allowed;
+
+    /// Reports to the coordinator, via `internal_cmd_tx`, how the logged
+    /// statement execution `id` finished, given its response `result`.
+    ///
+    /// Implementations whose responses finish later than they are sent, e.g.
+    /// because they stream rows, may instead arrange for the report to be
+    /// made once they do.
+    fn end_logged_statement(
+        result: Result<Self, AdapterError>,
+        id: StatementLoggingId,
+        internal_cmd_tx: &UnboundedSender<Message>,
+    ) -> Result<Self, AdapterError>
+    where
+        Self: Sized,
+    {
+        let reason = match &result {
+            Ok(_) => StatementEndedExecutionReason::Success {
+                rows_returned: None,
+            },
+            Err(e) => StatementEndedExecutionReason::Errored {
+                error: e.to_string(),
+            },
+        };
+        // If sending fails, the coordinator has shut down.
+        let _ = internal_cmd_tx.send(Message::StatementExecutionEnded { id, reason });
+        result
+    }
 }
 
 /// `ClientTransmitter` with a response to send.
@@ -306,6 +348,7 @@ impl ShouldHalt for StorageError {
             | StorageError::InvalidUppers(_) => true,
             StorageError::SourceIdReused(_)
             | StorageError::IdentifierMissing(_)
+            | StorageError::AtomicAppendsUnsupported(_)
             | StorageError::ClientError(_)
             | StorageError::DataflowError(_) => false,
             StorageError::IOError(e) => e.should_halt(),
@@ -360,7 +403,8 @@ impl ShouldHalt for TransformError {
         match self {
             TransformError::Internal(_)
             | TransformError::LetRecUnsupported
-            | TransformError::IdentifierMissing(_) => false,
+            | TransformError::IdentifierMissing(_)
+            | TransformError::InvalidHint(_) => false,
         }
     }
 }
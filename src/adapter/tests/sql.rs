@@ -155,7 +155,10 @@ async fn datadriven() {
                                         item: CatalogItem::Table(Table {
                                             create_sql: "TODO".to_string(),
                                             desc: RelationDesc::empty(),
+                                            storage_desc: RelationDesc::empty(),
+                                            layout: None,
                                             defaults: vec![Expr::null(); 0],
+                                            constraints: vec![],
                                             conn_id: None,
                                             depends_on: vec![],
                                             custom_logical_compaction_window: None,
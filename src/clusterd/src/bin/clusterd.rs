@@ -98,6 +98,7 @@ use mz_ore::tracing::TracingHandle;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::cfg::PersistConfig;
 use mz_pid_file::PidFile;
+use mz_repr::spill::SpillConfig;
 use mz_service::emit_boot_diagnostics;
 use mz_service::grpc::GrpcServer;
 use mz_service::secrets::SecretsReaderCliArgs;
@@ -145,6 +146,40 @@ struct Args {
     #[clap(long, env = "STORAGE_WORKERS", value_name = "N", default_value = "1")]
     storage_workers: usize,
 
+    // === Compute options. ===
+    /// A local directory in which to spill arrangement data once the replica
+    /// holds more than `--arrangement-memory-limit-bytes` of it in memory.
+    #[clap(long, env = "ARRANGEMENT_SPILL_DIRECTORY", value_name = "PATH")]
+    arrangement_spill_directory: Option<PathBuf>,
+    /// The maximum total size of arrangement data to keep in memory before
+    /// spilling to `--arrangement-spill-directory`.
+    #[clap(long, env = "ARRANGEMENT_MEMORY_LIMIT_BYTES", value_name = "N")]
+    arrangement_memory_limit_bytes: Option<usize>,
+
+    // === Persist options. ===
+    /// A local directory in which to cache batch parts fetched from persist's
+    /// blob storage. The cache lives in a `persist-blob-cache` subdirectory of
+    /// it, which is cleared on startup.
+    #[clap(long, env = "PERSIST_BLOB_CACHE_DIRECTORY", value_name = "PATH")]
+    persist_blob_cache_directory: Option<PathBuf>,
+    /// The maximum total size of batch parts to keep in
+    /// `--persist-blob-cache-directory`.
+    #[clap(
+        long,
+        env = "PERSIST_BLOB_CACHE_DISK_LIMIT_BYTES",
+        value_name = "N",
+        default_value_t = PersistConfig::DEFAULT_BLOB_CACHE_DISK_LIMIT_BYTES
+    )]
+    persist_blob_cache_disk_limit_bytes: usize,
+    /// The maximum total size of batch parts to keep cached in memory.
+    #[clap(
+        long,
+        env = "PERSIST_BLOB_CACHE_MEM_LIMIT_BYTES",
+        value_name = "N",
+        default_value = "0"
+    )]
+    persist_blob_cache_mem_limit_bytes: usize,
+
     // === Cloud options. ===
     /// An external ID to be supplied to all AWS AssumeRole operations.
     ///
@@ -263,10 +298,11 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         )
     });
 
-    let persist_clients = Arc::new(PersistClientCache::new(
-        PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone()),
-        &metrics_registry,
-    ));
+    let mut persist_cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+    persist_cfg.blob_cache_disk_path = args.persist_blob_cache_directory;
+    persist_cfg.blob_cache_disk_limit_bytes = args.persist_blob_cache_disk_limit_bytes;
+    persist_cfg.blob_cache_mem_limit_bytes = args.persist_blob_cache_mem_limit_bytes;
+    let persist_clients = Arc::new(PersistClientCache::new(persist_cfg, &metrics_registry));
 
     // Start storage server.
     let (_storage_server, storage_client) = mz_storage::serve(mz_storage::Config {
@@ -303,10 +339,25 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
     );
 
     // Start compute server.
+    let arrangement_spill = match (
+        args.arrangement_spill_directory,
+        args.arrangement_memory_limit_bytes,
+    ) {
+        (Some(directory), Some(memory_limit)) => Some(SpillConfig {
+            directory,
+            memory_limit,
+        }),
+        (None, None) => None,
+        _ => bail!(
+            "--arrangement-spill-directory and --arrangement-memory-limit-bytes \
+             must be specified together"
+        ),
+    };
     let (_compute_server, compute_client) =
         mz_compute::server::serve(mz_compute::server::Config {
             metrics_registry,
             persist_clients,
+            arrangement_spill,
         })?;
     info!(
         "listening for compute controller connections on {}",
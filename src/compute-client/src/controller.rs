@@ -508,6 +508,16 @@ where
         Ok(())
     }
 
+    /// Allow the given read-only persist sink to write to its storage collection.
+    pub fn allow_writes(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        sink_id: GlobalId,
+    ) -> Result<(), CollectionUpdateError> {
+        self.instance(instance_id)?.allow_writes(sink_id)?;
+        Ok(())
+    }
+
     /// Initiate a peek request for the contents of the given collection at `timestamp`.
     pub fn peek(
         &mut self,
@@ -738,4 +748,12 @@ impl<T: Timestamp> CollectionState<T> {
     pub fn write_frontier(&self) -> AntichainRef<T> {
         self.write_frontier.borrow()
     }
+
+    /// Reports the write frontier of the given replica, if the replica has
+    /// reported one.
+    pub fn replica_write_frontier(&self, replica_id: ReplicaId) -> Option<AntichainRef<T>> {
+        self.replica_write_frontiers
+            .get(&replica_id)
+            .map(|frontier| frontier.borrow())
+    }
 }
@@ -139,6 +139,12 @@ pub(super) struct Instance<T> {
     /// on the subscribe's input. `subscribes` is only used to track which updates have been
     /// emitted, to decide if new ones should be emitted or suppressed.
     subscribes: BTreeMap<GlobalId, ActiveSubscribe<T>>,
+    /// The storage collections written by the persist sinks of this instance.
+    ///
+    /// Write frontiers of persist sinks are forwarded to the storage controller, as the write
+    /// frontiers of their storage collections. Frontiers of read-only sinks are only forwarded
+    /// once the sinks have been allowed to write, through [`ActiveInstance::allow_writes`].
+    persist_sinks: BTreeMap<GlobalId, PersistSinkTarget>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<T>,
     /// IDs of replicas that have failed and require rehydration.
@@ -236,6 +242,7 @@ where
             arranged_logs,
             peeks: Default::default(),
             subscribes: Default::default(),
+            persist_sinks: Default::default(),
             history: Default::default(),
             failed_replicas: Default::default(),
             ready_responses: Default::default(),
@@ -389,6 +396,13 @@ where
             self.compute
                 .collections
                 .insert(*log_id, CollectionState::new_log_collection());
+            self.compute.persist_sinks.insert(
+                *log_id,
+                PersistSinkTarget {
+                    storage_id: *log_id,
+                    writable: true,
+                },
+            );
         }
 
         config.logging.index_logs = self.compute.arranged_logs.clone();
@@ -636,15 +650,25 @@ where
             for (id, se) in d.sink_exports {
                 let connection = match se.connection {
                     ComputeSinkConnection::Persist(conn) => {
+                        let storage_id = conn.storage_id;
                         let metadata = self
                             .storage_controller
-                            .collection(id)
-                            .map_err(|_| DataflowCreationError::CollectionMissing(id))?
+                            .collection(storage_id)
+                            .map_err(|_| DataflowCreationError::CollectionMissing(storage_id))?
                             .collection_metadata
                             .clone();
+                        self.compute.persist_sinks.insert(
+                            id,
+                            PersistSinkTarget {
+                                storage_id,
+                                writable: !conn.read_only,
+                            },
+                        );
                         let conn = PersistSinkConnection {
                             value_desc: conn.value_desc,
+                            storage_id,
                             storage_metadata: metadata,
+                            read_only: conn.read_only,
                         };
                         ComputeSinkConnection::Persist(conn)
                     }
@@ -687,12 +711,35 @@ where
         // Validate that the ids exist.
         self.validate_ids(ids.iter().cloned())?;
 
+        // Dropped sinks must not advance the frontiers of their storage collections anymore,
+        // which might have been taken over by another sink.
+        for id in &ids {
+            self.compute.persist_sinks.remove(id);
+        }
+
         let policies = ids
             .into_iter()
             .map(|id| (id, ReadPolicy::ValidFrom(Antichain::new())));
         self.set_read_policy(policies.collect())
     }
 
+    /// Allows the given read-only persist sink to write to its storage collection.
+    ///
+    /// From now on, the sink's write frontier is forwarded to the storage controller. The caller
+    /// is responsible for ensuring that no other sink writes to the same storage collection.
+    pub fn allow_writes(&mut self, id: GlobalId) -> Result<(), CollectionMissing> {
+        let target = self
+            .compute
+            .persist_sinks
+            .get_mut(&id)
+            .ok_or(CollectionMissing(id))?;
+        if !target.writable {
+            target.writable = true;
+            self.compute.send(ComputeCommand::AllowWrites(id));
+        }
+        Ok(())
+    }
+
     /// Initiate a peek request for the contents of `id` at `timestamp`.
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn peek(
@@ -901,10 +948,14 @@ where
         // relying on others for that information.
         let storage_updates: Vec<_> = advanced_collections
             .into_iter()
-            .filter(|id| self.storage_controller.collection(*id).is_ok())
-            .map(|id| {
+            .filter_map(|id| {
+                let target = self.compute.persist_sinks.get(&id)?;
+                target.writable.then_some((id, target.storage_id))
+            })
+            .filter(|(_, storage_id)| self.storage_controller.collection(*storage_id).is_ok())
+            .map(|(id, storage_id)| {
                 let collection = self.compute.collection(id).unwrap();
-                (id, collection.write_frontier.clone())
+                (storage_id, collection.write_frontier.clone())
             })
             .collect();
         self.storage_controller
@@ -1048,6 +1099,13 @@ where
             ComputeResponse::SubscribeResponse(id, response) => {
                 self.handle_subscribe_response(id, response, replica_id)
             }
+            ComputeResponse::CollectionFailed(id, error) => {
+                // The replica has stopped maintaining the collection, so its frontier will not
+                // advance further. The failure is surfaced to users through the replica's
+                // introspection sources and peeks, so we only need to record it here.
+                tracing::warn!(%replica_id, %id, %error, "replica reported failed collection");
+                None
+            }
         }
     }
 
@@ -1250,3 +1308,11 @@ impl<T: Timestamp> ActiveSubscribe<T> {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+struct PersistSinkTarget {
+    /// ID of the storage collection the sink writes to.
+    storage_id: GlobalId,
+    /// Whether the sink is allowed to write to its storage collection.
+    writable: bool,
+}
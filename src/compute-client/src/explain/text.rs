@@ -277,6 +277,21 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                             write!(f, " limit={}", limit)?;
                         }
                     }
+                    TopKPlan::Bounded(plan) => {
+                        write!(f, "{}TopK::Bounded", ctx.indent)?;
+                        if plan.group_key.len() > 0 {
+                            let group_by = Indices(&plan.group_key);
+                            write!(f, " group_by=[{}]", group_by)?;
+                        }
+                        if plan.order_key.len() > 0 {
+                            let order_by = separated(", ", &plan.order_key);
+                            write!(f, " order_by=[{}]", order_by)?;
+                        }
+                        write!(f, " limit={}", plan.limit)?;
+                        if &plan.offset > &0 {
+                            write!(f, " offset={}", plan.offset)?;
+                        }
+                    }
                     TopKPlan::Basic(plan) => {
                         write!(f, "{}TopK::Basic", ctx.indent)?;
                         if plan.group_key.len() > 0 {
@@ -600,7 +615,7 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for HierarchicalPlan {
                 writeln!(f, "{}aggr_funcs=[{}]", ctx.indent, aggr_funcs)?;
                 let skips = separated(", ", &plan.skips);
                 writeln!(f, "{}skips=[{}]", ctx.indent, skips)?;
-                let buckets = separated(", ", &plan.skips);
+                let buckets = separated(", ", &plan.buckets);
                 writeln!(f, "{}buckets=[{}]", ctx.indent, buckets)?;
             }
         }
@@ -267,6 +267,9 @@ pub enum ComputeLog {
     PeekDuration,
     FrontierDelay,
     SourceFrontierCurrent,
+    LirMapping,
+    ArrangementSpill,
+    ExportErrors,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -281,6 +284,9 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::PeekDuration => PeekDuration(()),
                 ComputeLog::FrontierDelay => FrontierDelay(()),
                 ComputeLog::SourceFrontierCurrent => SourceFrontierCurrent(()),
+                ComputeLog::LirMapping => LirMapping(()),
+                ComputeLog::ArrangementSpill => ArrangementSpill(()),
+                ComputeLog::ExportErrors => ExportErrors(()),
             }),
         }
     }
@@ -295,6 +301,9 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(PeekDuration(())) => Ok(ComputeLog::PeekDuration),
             Some(FrontierDelay(())) => Ok(ComputeLog::FrontierDelay),
             Some(SourceFrontierCurrent(())) => Ok(ComputeLog::SourceFrontierCurrent),
+            Some(LirMapping(())) => Ok(ComputeLog::LirMapping),
+            Some(ArrangementSpill(())) => Ok(ComputeLog::ArrangementSpill),
+            Some(ExportErrors(())) => Ok(ComputeLog::ExportErrors),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -321,6 +330,9 @@ pub static DEFAULT_LOG_VARIANTS: Lazy<Vec<LogVariant>> = Lazy::new(|| {
         LogVariant::Compute(ComputeLog::FrontierDelay),
         LogVariant::Compute(ComputeLog::PeekCurrent),
         LogVariant::Compute(ComputeLog::PeekDuration),
+        LogVariant::Compute(ComputeLog::LirMapping),
+        LogVariant::Compute(ComputeLog::ArrangementSpill),
+        LogVariant::Compute(ComputeLog::ExportErrors),
     ];
 
     default_logs
@@ -769,6 +781,28 @@ impl LogVariant {
                 .with_column("count", ScalarType::UInt64.nullable(false))
                 .with_column("sum", ScalarType::UInt64.nullable(true))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Compute(ComputeLog::LirMapping) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("lir_id", ScalarType::UInt64.nullable(false))
+                .with_column("parent_lir_id", ScalarType::UInt64.nullable(true))
+                .with_column("nesting", ScalarType::UInt16.nullable(false))
+                .with_column("operator", ScalarType::String.nullable(false))
+                .with_column("operator_id_start", ScalarType::UInt64.nullable(false))
+                .with_column("operator_id_end", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1, 2]),
+
+            LogVariant::Compute(ComputeLog::ArrangementSpill) => RelationDesc::empty()
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("spilled_bytes", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0]),
+
+            LogVariant::Compute(ComputeLog::ExportErrors) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("error", ScalarType::String.nullable(false))
+                .with_key(vec![0, 1]),
         }
     }
 
@@ -818,6 +852,9 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::FrontierDelay) => vec![],
             LogVariant::Compute(ComputeLog::PeekCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekDuration) => vec![],
+            LogVariant::Compute(ComputeLog::LirMapping) => vec![],
+            LogVariant::Compute(ComputeLog::ArrangementSpill) => vec![],
+            LogVariant::Compute(ComputeLog::ExportErrors) => vec![],
         }
     }
 }
@@ -382,6 +382,52 @@ impl<T> Plan<T> {
             .chain(rest.into_iter().flatten())
             .chain(last)
     }
+
+    /// Returns a one-line description of this node, without its inputs, in
+    /// the terms used by `EXPLAIN PHYSICAL PLAN`.
+    ///
+    /// Used to describe the node in the `mz_lir_mapping` introspection source.
+    pub fn node_name(&self) -> String {
+        use Plan::*;
+        match self {
+            Constant { rows: Ok(_) } => "Constant".into(),
+            Constant { rows: Err(_) } => "Error".into(),
+            Get { id, plan, .. } => match plan {
+                GetPlan::PassArrangements => format!("Get::PassArrangements {id}"),
+                GetPlan::Arrangement(..) => format!("Get::Arrangement {id}"),
+                GetPlan::Collection(_) => format!("Get::Collection {id}"),
+            },
+            Let { id, .. } => format!("Let {id}"),
+            LetRec { .. } => "LetRec".into(),
+            Mfp { .. } => "Mfp".into(),
+            FlatMap { func, .. } => format!("FlatMap {func}"),
+            Join { plan, .. } => match plan {
+                JoinPlan::Linear(_) => "Join::Linear".into(),
+                JoinPlan::Delta(_) => "Join::Delta".into(),
+            },
+            Reduce { plan, .. } => match plan {
+                ReducePlan::Distinct => "Reduce::Distinct".into(),
+                ReducePlan::DistinctNegated => "Reduce::DistinctNegated".into(),
+                ReducePlan::Accumulable(_) => "Reduce::Accumulable".into(),
+                ReducePlan::Hierarchical(_) => "Reduce::Hierarchical".into(),
+                ReducePlan::Basic(_) => "Reduce::Basic".into(),
+                ReducePlan::Collation(_) => "Reduce::Collation".into(),
+            },
+            TopK { top_k_plan, .. } => match top_k_plan {
+                TopKPlan::MonotonicTop1(_) => "TopK::MonotonicTop1".into(),
+                TopKPlan::MonotonicTopK(_) => "TopK::MonotonicTopK".into(),
+                TopKPlan::Bounded(_) => "TopK::Bounded".into(),
+                TopKPlan::Basic(_) => "TopK::Basic".into(),
+            },
+            Negate { .. } => "Negate".into(),
+            Threshold { threshold_plan, .. } => match threshold_plan {
+                ThresholdPlan::Basic(_) => "Threshold::Basic".into(),
+                ThresholdPlan::Retractions(_) => "Threshold::Retractions".into(),
+            },
+            Union { .. } => "Union".into(),
+            ArrangeBy { .. } => "ArrangeBy".into(),
+        }
+    }
 }
 
 impl Arbitrary for Plan {
@@ -1156,6 +1202,7 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 inputs,
                 equivalences,
                 implementation,
+                hints: _,
             } => {
                 let input_mapper = JoinInputMapper::new(inputs);
 
@@ -1328,6 +1375,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                 limit,
                 offset,
                 monotonic,
+                expected_group_size,
             } => {
                 let arity = input.arity();
                 let (input, keys) = Self::from_mir_inner(input, arrangements, debug_info)?;
@@ -1339,6 +1387,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     *limit,
                     arity,
                     *monotonic,
+                    *expected_group_size,
                 );
 
                 // We don't have an MFP here -- install an operator to permute the
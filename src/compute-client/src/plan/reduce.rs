@@ -71,6 +71,7 @@ use mz_expr::permutation_for_arrangement;
 use mz_expr::AggregateExpr;
 use mz_expr::AggregateFunc;
 use mz_expr::MirScalarExpr;
+use mz_ore::cast::CastFrom;
 use mz_ore::soft_assert_or_log;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 
@@ -667,26 +668,7 @@ impl ReducePlan {
                     let monotonic = MonotonicPlan { aggr_funcs, skips };
                     ReducePlan::Hierarchical(HierarchicalPlan::Monotonic(monotonic))
                 } else {
-                    let mut buckets = vec![];
-                    let mut current = 16;
-
-                    // Plan for 4B records in the expected case if the user
-                    // didn't specify a group size.
-                    let limit = expected_group_size.unwrap_or(4_000_000_000);
-
-                    // Distribute buckets in powers of 16, so that we can strike
-                    // a balance between how many inputs each layer gets from
-                    // the preceding layer, while also limiting the number of
-                    // layers.
-                    while current < limit {
-                        // TODO(benesch): fix this dangerous use of `as`.
-                        #[allow(clippy::as_conversions)]
-                        buckets.push(current as u64);
-                        current = current.saturating_mul(16);
-                    }
-                    // We need to store the bucket numbers in decreasing order.
-                    buckets.reverse();
-
+                    let buckets = bucketed_plan_buckets(expected_group_size);
                     let bucketed = BucketedPlan {
                         aggr_funcs,
                         skips,
@@ -818,6 +800,103 @@ pub fn convert_indexes_to_skips(mut indexes: Vec<usize>) -> Vec<usize> {
     indexes
 }
 
+/// The group size that bucketed hierarchical reductions plan for if the user
+/// didn't specify a group size and none could be estimated.
+const DEFAULT_EXPECTED_GROUP_SIZE: u64 = 4_000_000_000;
+
+/// Bounds the memory that the intermediate layers of a bucketed hierarchical
+/// reduction may use: they may hold at most one record for every
+/// `BUCKETED_MEMORY_BUDGET_DIVISOR` records of the group they reduce.
+const BUCKETED_MEMORY_BUDGET_DIVISOR: u64 = 8;
+
+/// Chooses the number of buckets in each layer of a bucketed hierarchical
+/// reduction, in decreasing order.
+///
+/// Every layer reduces the values of each of its buckets, so an update to the
+/// input of a group re-reduces one bucket per layer, and each layer holds as
+/// many records per group as it has (non-empty) buckets. For a group of `n`
+/// records, more layers make updates cheaper but cost memory, which would be
+/// wasted entirely on groups that are small enough to be reduced directly.
+///
+/// If the expected group size is known, we pick the layers that minimize the
+/// work of an update, out of those whose records fit into the memory budget
+/// set by [`BUCKETED_MEMORY_BUDGET_DIVISOR`], spacing the buckets of each
+/// candidate evenly on a logarithmic scale. Otherwise, we plan for
+/// [`DEFAULT_EXPECTED_GROUP_SIZE`] records with buckets in powers of 16, which
+/// respects the same budget.
+fn bucketed_plan_buckets(expected_group_size: Option<usize>) -> Vec<u64> {
+    let Some(group_size) = expected_group_size else {
+        let mut buckets = vec![];
+        let mut current = 16;
+        // Distribute buckets in powers of 16, so that we can strike
+        // a balance between how many inputs each layer gets from
+        // the preceding layer, while also limiting the number of
+        // layers.
+        while current < DEFAULT_EXPECTED_GROUP_SIZE {
+            buckets.push(current);
+            current = current.saturating_mul(16);
+        }
+        // We need to store the bucket numbers in decreasing order.
+        buckets.reverse();
+        return buckets;
+    };
+
+    let group_size = std::cmp::max(u64::cast_from(group_size), 1);
+    // Reducing a group directly uses no extra memory, and re-reduces the
+    // whole group on every update.
+    let mut best_work = group_size;
+    let mut best_buckets = vec![];
+    for layers in 1..u64::BITS {
+        // The smallest fanout between layers that reduces the group in
+        // `layers + 1` steps.
+        let fanout = ceil_root(group_size, layers + 1);
+        if fanout < 2 {
+            break;
+        }
+        let buckets: Vec<u64> = (1..=layers)
+            .rev()
+            .map(|layer| fanout.saturating_pow(layer))
+            .filter(|buckets| *buckets < group_size)
+            .collect();
+        let memory = buckets.iter().fold(0, |sum: u64, b| sum.saturating_add(*b));
+        if memory.saturating_mul(BUCKETED_MEMORY_BUDGET_DIVISOR) <= group_size {
+            // Each layer reduces the buckets of the preceding layer that map to
+            // the same one of its buckets, and the final reduction combines all
+            // buckets of the last layer.
+            let mut work = 0;
+            let mut inputs = group_size;
+            for buckets in buckets.iter() {
+                work = work.saturating_add((inputs - 1) / buckets + 1);
+                inputs = *buckets;
+            }
+            work = work.saturating_add(inputs);
+            if work < best_work {
+                best_work = work;
+                best_buckets = buckets;
+            }
+        }
+        // Adding layers beyond a fanout of two only adds work.
+        if fanout == 2 {
+            break;
+        }
+    }
+    best_buckets
+}
+
+/// Returns the smallest `r` such that `r.pow(k) >= n`.
+fn ceil_root(n: u64, k: u32) -> u64 {
+    let (mut lo, mut hi) = (1, std::cmp::max(n, 1));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if mid.checked_pow(k).map_or(true, |pow| pow >= n) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
 /// Determines whether a function can be accumulated in an update's "difference" field,
 /// and whether it can be subjected to recursive (hierarchical) aggregation.
 ///
@@ -907,4 +986,37 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[test]
+    fn bucketed_plan_buckets_default() {
+        assert_eq!(
+            bucketed_plan_buckets(None),
+            vec![268435456, 16777216, 1048576, 65536, 4096, 256, 16]
+        );
+    }
+
+    #[test]
+    fn bucketed_plan_buckets_expected_group_size() {
+        // Small groups are reduced directly.
+        for group_size in [0, 1, 5, 17] {
+            assert_eq!(bucketed_plan_buckets(Some(group_size)), Vec::<u64>::new());
+        }
+        assert_eq!(bucketed_plan_buckets(Some(100)), vec![10]);
+        assert_eq!(bucketed_plan_buckets(Some(1000)), vec![100, 10]);
+        assert_eq!(
+            bucketed_plan_buckets(Some(1_000_000)),
+            vec![100000, 10000, 1000, 100, 10]
+        );
+
+        for group_size in [64, 1000, 123_456, 4_000_000_000, usize::MAX] {
+            let buckets = bucketed_plan_buckets(Some(group_size));
+            let group_size = u64::cast_from(group_size);
+            // Buckets are decreasing and smaller than the group.
+            assert!(buckets.windows(2).all(|w| w[0] > w[1]));
+            assert!(buckets.iter().all(|b| *b < group_size));
+            // The layers respect the memory budget.
+            let memory: u64 = buckets.iter().sum();
+            assert!(memory <= group_size / BUCKETED_MEMORY_BUDGET_DIVISOR);
+        }
+    }
 }
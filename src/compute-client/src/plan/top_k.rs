@@ -15,6 +15,8 @@
 //! The TopK variants can be distinguished as follows:
 //! * A [MonotonicTop1Plan] maintains a single row per key and is suitable for monotonic inputs.
 //! * A [MonotonicTopKPlan] maintains up to K rows per key and is suitable for monotonic inputs.
+//! * A [BoundedTopKPlan] maintains up to K rows per key in a single stage, can handle
+//!   retractions, and is suitable for groups that are known to be small.
 //! * A [BasicTopKPlan] maintains up to K rows per key and can handle retractions.
 
 use proptest_derive::Arbitrary;
@@ -32,10 +34,21 @@ pub enum TopKPlan {
     MonotonicTop1(MonotonicTop1Plan),
     /// A plan for TopK for monotonic inputs.
     MonotonicTopK(MonotonicTopKPlan),
+    /// A plan for TopK over small groups.
+    Bounded(BoundedTopKPlan),
     /// A plan for generic TopK operations.
     Basic(BasicTopKPlan),
 }
 
+/// The factor by which the expected group size may exceed `offset + limit` for us to plan
+/// a [BoundedTopKPlan].
+///
+/// A bounded TopK considers every record of a group whenever the group changes. Each stage
+/// of a [BasicTopKPlan] considers up to this many times `offset + limit` records of a group
+/// whenever the group changes, so a bounded TopK over groups of at most this size does no
+/// more work per update than a single one of those stages.
+const BOUNDED_TOP_K_MAX_GROUP_FACTOR: usize = 16;
+
 impl TopKPlan {
     /// Create a plan from the information provided. Here we decide on which of the TopK plan
     /// variants to select.
@@ -46,6 +59,7 @@ impl TopKPlan {
     /// * `limit` - An optional limit of how many rows should be revealed.
     /// * `arity` - The number of columns in the input and output.
     /// * `monotonic` - `true` if the input is monotonic.
+    /// * `expected_group_size` - A user hint on the number of rows per group, if any.
     pub(crate) fn create_from(
         group_key: Vec<usize>,
         order_key: Vec<ColumnOrder>,
@@ -53,6 +67,7 @@ impl TopKPlan {
         limit: Option<usize>,
         arity: usize,
         monotonic: bool,
+        expected_group_size: Option<usize>,
     ) -> Self {
         if monotonic && offset == 0 && limit == Some(1) {
             TopKPlan::MonotonicTop1(MonotonicTop1Plan {
@@ -74,6 +89,23 @@ impl TopKPlan {
                 limit,
                 arity,
             })
+        } else if let Some(limit) = limit.filter(|limit| {
+            !group_key.is_empty()
+                && expected_group_size.map_or(false, |size| {
+                    size <= (offset + limit).saturating_mul(BOUNDED_TOP_K_MAX_GROUP_FACTOR)
+                })
+        }) {
+            // For non-monotonic inputs, the hierarchical stages of a basic TopK protect against
+            // large groups, at the cost of arranging most of the input once per stage. When the
+            // user tells us that the groups are small, we instead compute the result of each
+            // group in a single pass over its records.
+            TopKPlan::Bounded(BoundedTopKPlan {
+                group_key,
+                order_key,
+                limit,
+                offset,
+                arity,
+            })
         } else {
             // A plan for all other inputs
             TopKPlan::Basic(BasicTopKPlan {
@@ -96,6 +128,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
                 TopKPlan::Basic(plan) => Some(Basic(plan.into_proto())),
                 TopKPlan::MonotonicTop1(plan) => Some(MonotonicTop1(plan.into_proto())),
                 TopKPlan::MonotonicTopK(plan) => Some(MonotonicTopK(plan.into_proto())),
+                TopKPlan::Bounded(plan) => Some(Bounded(plan.into_proto())),
             },
         }
     }
@@ -107,6 +140,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
             Some(Basic(plan)) => Ok(TopKPlan::Basic(plan.into_rust()?)),
             Some(MonotonicTop1(plan)) => Ok(TopKPlan::MonotonicTop1(plan.into_rust()?)),
             Some(MonotonicTopK(plan)) => Ok(TopKPlan::MonotonicTopK(plan.into_rust()?)),
+            Some(Bounded(plan)) => Ok(TopKPlan::Bounded(plan.into_rust()?)),
             None => Err(TryFromProtoError::missing_field("ProtoTopKPlan::kind")),
         }
     }
@@ -185,6 +219,53 @@ impl RustType<ProtoMonotonicTopKPlan> for MonotonicTopKPlan {
     }
 }
 
+/// A plan for TopKs over grouped, possibly non-monotonic inputs with groups that are known
+/// to be small.
+///
+/// Rather than the hierarchy of reductions of a [BasicTopKPlan], this plan uses a single
+/// reduction per group. Whenever a group changes, it selects the first `offset + limit`
+/// records of the group in one pass, keeping at most that many records in its working set,
+/// and its output contains at most `limit` records per group.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BoundedTopKPlan {
+    /// The columns that form the key for each group.
+    pub group_key: Vec<usize>,
+    /// Ordering that is used within each group.
+    pub order_key: Vec<mz_expr::ColumnOrder>,
+    /// An upper bound on the per-group ordinal position of the records to
+    /// produce from each group.
+    pub limit: usize,
+    /// A lower bound on the per-group ordinal position of the records to
+    /// produce from each group.
+    ///
+    /// This can be set to zero to have no effect.
+    pub offset: usize,
+    /// The number of columns in the input and output.
+    pub arity: usize,
+}
+
+impl RustType<ProtoBoundedTopKPlan> for BoundedTopKPlan {
+    fn into_proto(&self) -> ProtoBoundedTopKPlan {
+        ProtoBoundedTopKPlan {
+            group_key: self.group_key.into_proto(),
+            order_key: self.order_key.into_proto(),
+            limit: self.limit.into_proto(),
+            offset: self.offset.into_proto(),
+            arity: self.arity.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoBoundedTopKPlan) -> Result<Self, TryFromProtoError> {
+        Ok(BoundedTopKPlan {
+            group_key: proto.group_key.into_rust()?,
+            order_key: proto.order_key.into_rust()?,
+            limit: proto.limit.into_rust()?,
+            offset: proto.offset.into_rust()?,
+            arity: proto.arity.into_rust()?,
+        })
+    }
+}
+
 /// A plan for generic TopKs that don't fit any more specific category.
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct BasicTopKPlan {
@@ -240,4 +321,47 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[test]
+    fn bounded_plan_for_small_groups() {
+        let plan = |group_key: Vec<usize>, offset, limit, monotonic, expected_group_size| {
+            TopKPlan::create_from(
+                group_key,
+                vec![],
+                offset,
+                limit,
+                2,
+                monotonic,
+                expected_group_size,
+            )
+        };
+        assert!(matches!(
+            plan(vec![0], 0, Some(1), false, Some(16)),
+            TopKPlan::Bounded(_)
+        ));
+        assert!(matches!(
+            plan(vec![0], 4, Some(12), false, Some(256)),
+            TopKPlan::Bounded(_)
+        ));
+        assert!(matches!(
+            plan(vec![0], 0, Some(1), true, Some(16)),
+            TopKPlan::MonotonicTop1(_)
+        ));
+        assert!(matches!(
+            plan(vec![0], 0, Some(1), false, None),
+            TopKPlan::Basic(_)
+        ));
+        assert!(matches!(
+            plan(vec![0], 0, Some(1), false, Some(17)),
+            TopKPlan::Basic(_)
+        ));
+        assert!(matches!(
+            plan(vec![0], 0, None, false, Some(1)),
+            TopKPlan::Basic(_)
+        ));
+        assert!(matches!(
+            plan(vec![], 0, Some(1), false, Some(1)),
+            TopKPlan::Basic(_)
+        ));
+    }
 }
@@ -87,6 +87,7 @@
 //!
 //!   - [`CreateDataflows`]
 //!   - [`AllowCompaction`]
+//!   - [`AllowWrites`]
 //!   - [`Peek`]
 //!   - [`CancelPeeks`]
 //!   - [`UpdateConfiguration`]
@@ -109,6 +110,7 @@
 //! [`InitializationComplete`]: self::command::ComputeCommand::InitializationComplete
 //! [`CreateDataflows`]: self::command::ComputeCommand::CreateDataflows
 //! [`AllowCompaction`]: self::command::ComputeCommand::AllowCompaction
+//! [`AllowWrites`]: self::command::ComputeCommand::AllowWrites
 //! [`Peek`]: self::command::ComputeCommand::Peek
 //! [`CancelPeeks`]: self::command::ComputeCommand::CancelPeeks
 //! [`UpdateConfiguration`]: self::command::ComputeCommand::UpdateConfiguration
@@ -178,6 +178,24 @@ pub enum ComputeCommand<T = mz_repr::Timestamp> {
     /// [#16275]: https://github.com/MaterializeInc/materialize/issues/16275
     AllowCompaction(Vec<(GlobalId, Antichain<T>)>),
 
+    /// `AllowWrites` instructs the replica to let the identified persist sink write to its
+    /// storage collection.
+    ///
+    /// Persist sinks that are created with [`PersistSinkConnection::read_only`] set compute their
+    /// output, and report the progress of that computation through [`FrontierUppers`] responses,
+    /// but do not write it to their storage collection until the replica receives an
+    /// `AllowWrites` command for them. This allows the controller to hydrate a new sink for a
+    /// storage collection while another sink is still writing to it, and to switch over once the
+    /// new sink has caught up.
+    ///
+    /// It is invalid to send an `AllowWrites` command that references a collection that is not a
+    /// persist sink created by a corresponding `CreateDataflows` command before. Doing so may
+    /// cause the replica to exhibit undefined behavior.
+    ///
+    /// [`PersistSinkConnection::read_only`]: crate::types::sinks::PersistSinkConnection::read_only
+    /// [`FrontierUppers`]: super::response::ComputeResponse::FrontierUppers
+    AllowWrites(GlobalId),
+
     /// `Peek` instructs the replica to perform a peek at an index.
     ///
     /// The [`Peek`] description must have the following properties:
@@ -254,6 +272,7 @@ impl RustType<ProtoComputeCommand> for ComputeCommand<mz_repr::Timestamp> {
                         collections: collections.into_proto(),
                     })
                 }
+                ComputeCommand::AllowWrites(id) => AllowWrites(id.into_proto()),
                 ComputeCommand::Peek(peek) => Peek(peek.into_proto()),
                 ComputeCommand::CancelPeeks { uuids } => CancelPeeks(ProtoCancelPeeks {
                     uuids: uuids.into_proto(),
@@ -285,6 +304,7 @@ impl RustType<ProtoComputeCommand> for ComputeCommand<mz_repr::Timestamp> {
             Some(AllowCompaction(ProtoAllowCompaction { collections })) => {
                 Ok(ComputeCommand::AllowCompaction(collections.into_rust()?))
             }
+            Some(AllowWrites(id)) => Ok(ComputeCommand::AllowWrites(id.into_rust()?)),
             Some(Peek(peek)) => Ok(ComputeCommand::Peek(peek.into_rust()?)),
             Some(CancelPeeks(ProtoCancelPeeks { uuids })) => Ok(ComputeCommand::CancelPeeks {
                 uuids: uuids.into_rust()?,
@@ -336,6 +356,9 @@ impl Arbitrary for ComputeCommand<mz_repr::Timestamp> {
                     )
                 })
                 .boxed(),
+                any::<GlobalId>()
+                    .prop_map(ComputeCommand::AllowWrites)
+                    .boxed(),
                 any::<Peek>().prop_map(ComputeCommand::Peek).boxed(),
                 proptest::collection::vec(any_uuid(), 1..6)
                     .prop_map(|uuids| ComputeCommand::CancelPeeks {
@@ -481,6 +504,11 @@ pub struct ComputeParameters {
     /// [`PeekResponse::Error`]: super::response::PeekResponse::Error
     /// [`SubscribeBatch::updates`]: super::response::SubscribeBatch::updates
     pub max_result_size: Option<u32>,
+    /// The maximum number of arrangement records a dataflow may hold on a worker.
+    ///
+    /// Dataflows exceeding this maximum are dropped, and their exports report the error instead
+    /// of data. A value of zero means that dataflows are not limited.
+    pub max_dataflow_arrangement_records: Option<u64>,
     /// Persist client configuration.
     pub persist: PersistParameters,
 }
@@ -491,12 +519,17 @@ impl ComputeParameters {
         if let Some(v) = other.max_result_size {
             self.max_result_size = Some(v);
         }
+        if let Some(v) = other.max_dataflow_arrangement_records {
+            self.max_dataflow_arrangement_records = Some(v);
+        }
         self.persist.update(other.persist);
     }
 
     /// Return whether all parameters are unset.
     pub fn all_unset(&self) -> bool {
-        self.max_result_size.is_none() && self.persist.all_unset()
+        self.max_result_size.is_none()
+            && self.max_dataflow_arrangement_records.is_none()
+            && self.persist.all_unset()
     }
 }
 
@@ -504,6 +537,7 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
     fn into_proto(&self) -> ProtoComputeParameters {
         ProtoComputeParameters {
             max_result_size: self.max_result_size.into_proto(),
+            max_dataflow_arrangement_records: self.max_dataflow_arrangement_records.into_proto(),
             persist: Some(self.persist.into_proto()),
         }
     }
@@ -511,6 +545,7 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
     fn from_proto(proto: ProtoComputeParameters) -> Result<Self, TryFromProtoError> {
         Ok(Self {
             max_result_size: proto.max_result_size.into_rust()?,
+            max_dataflow_arrangement_records: proto.max_dataflow_arrangement_records.into_rust()?,
             persist: proto
                 .persist
                 .into_rust_if_some("ProtoComputeParameters::persist")?,
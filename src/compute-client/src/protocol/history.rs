@@ -14,6 +14,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use timely::progress::Antichain;
 
 use super::command::{ComputeCommand, ComputeParameters, Peek};
+use crate::types::sinks::ComputeSinkConnection;
 
 #[derive(Debug)]
 pub struct ComputeCommandHistory<T = mz_repr::Timestamp> {
@@ -69,6 +70,7 @@ impl<T: timely::progress::Timestamp> ComputeCommandHistory<T> {
         let mut live_dataflows = Vec::new();
         let mut live_peeks = Vec::new();
         let mut live_cancels = BTreeSet::new();
+        let mut allowed_writes = BTreeSet::new();
 
         let mut create_inst_command = None;
         let mut create_timely_command = None;
@@ -106,6 +108,9 @@ impl<T: timely::progress::Timestamp> ComputeCommandHistory<T> {
                         final_frontiers.insert(id, frontier.clone());
                     }
                 }
+                ComputeCommand::AllowWrites(id) => {
+                    allowed_writes.insert(id);
+                }
                 ComputeCommand::Peek(peek) => {
                     live_peeks.push(peek);
                 }
@@ -152,6 +157,15 @@ impl<T: timely::progress::Timestamp> ComputeCommandHistory<T> {
             }
 
             dataflow.as_of = Some(as_of);
+
+            // Fold allowed writes into the sinks, so they are created writable.
+            for (id, sink) in dataflow.sink_exports.iter_mut() {
+                if let ComputeSinkConnection::Persist(conn) = &mut sink.connection {
+                    if allowed_writes.contains(id) {
+                        conn.read_only = false;
+                    }
+                }
+            }
         }
 
         // Discard dataflows whose outputs have all been allowed to compact away.
@@ -122,6 +122,23 @@ pub enum ComputeResponse<T = mz_repr::Timestamp> {
     /// [`AllowCompaction` command]: super::command::ComputeCommand::AllowCompaction
     /// [#16247]: https://github.com/MaterializeInc/materialize/issues/16247
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+
+    /// `CollectionFailed` reports that the replica stopped maintaining the specified compute
+    /// collection because its dataflow failed with the given error.
+    ///
+    /// A failed collection does not advance further: the replica must not send further
+    /// `FrontierUppers` responses for it until it is dropped in response to an [`AllowCompaction`
+    /// command] that advanced its read frontier to the empty frontier, at which point the replica
+    /// reports advancement to the empty frontier as for any other dropped collection. The replica
+    /// must answer peeks targeting a failed collection with the error.
+    ///
+    /// The replica must send at most one `CollectionFailed` response for each collection. It must
+    /// not send `CollectionFailed` responses for subscribes, whose failures are reported through
+    /// [`SubscribeResponse`]s.
+    ///
+    /// [`AllowCompaction` command]: super::command::ComputeCommand::AllowCompaction
+    /// [`SubscribeResponse`]: ComputeResponse::SubscribeResponse
+    CollectionFailed(GlobalId, String),
 }
 
 impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
@@ -144,6 +161,12 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                         resp: Some(resp.into_proto()),
                     })
                 }
+                ComputeResponse::CollectionFailed(id, error) => {
+                    CollectionFailed(ProtoCollectionFailedKind {
+                        id: Some(id.into_proto()),
+                        error: error.clone(),
+                    })
+                }
             }),
         }
     }
@@ -165,6 +188,10 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                 resp.resp
                     .into_rust_if_some("ProtoSubscribeResponseKind::resp")?,
             )),
+            Some(CollectionFailed(resp)) => Ok(ComputeResponse::CollectionFailed(
+                resp.id.into_rust_if_some("ProtoCollectionFailedKind::id")?,
+                resp.error,
+            )),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeResponse::kind",
             )),
@@ -189,6 +216,9 @@ impl Arbitrary for ComputeResponse<mz_repr::Timestamp> {
             (any::<GlobalId>(), any::<SubscribeResponse>())
                 .prop_map(|(id, resp)| ComputeResponse::SubscribeResponse(id, resp))
                 .boxed(),
+            (any::<GlobalId>(), any::<String>())
+                .prop_map(|(id, error)| ComputeResponse::CollectionFailed(id, error))
+                .boxed(),
         ])
     }
 }
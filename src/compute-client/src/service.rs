@@ -13,7 +13,7 @@
 
 //! Compute layer client and server.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
 
 use async_trait::async_trait;
@@ -157,6 +157,12 @@ pub struct PartitionedComputeState<T> {
     /// the tracking state maintained for it and b) we won't re-initialize tracking for a subscribe
     /// we have already dropped.
     pending_subscribes: BTreeMap<GlobalId, PendingSubscribe<T>>,
+    /// Collections for which a `CollectionFailed` response has already been emitted.
+    ///
+    /// Each shard may report the failure of a collection independently, but we only forward the
+    /// first report. A failed collection remains in this set until all shards have reported its
+    /// advancement to the empty frontier, which they do once the collection is dropped.
+    failed_collections: BTreeSet<GlobalId>,
 }
 
 impl<T> Partitionable<ComputeCommand<T>, ComputeResponse<T>>
@@ -172,6 +178,7 @@ where
             uppers: BTreeMap::new(),
             peek_responses: BTreeMap::new(),
             pending_subscribes: BTreeMap::new(),
+            failed_collections: BTreeSet::new(),
         }
     }
 }
@@ -186,10 +193,12 @@ where
             uppers,
             peek_responses,
             pending_subscribes,
+            failed_collections,
         } = self;
         uppers.clear();
         peek_responses.clear();
         pending_subscribes.clear();
+        failed_collections.clear();
     }
 
     /// Observes commands that move past, and prepares state for responses.
@@ -221,6 +230,7 @@ where
             previous.is_some(),
             "ceasing frontier tracking for absent identifier {id}",
         );
+        self.failed_collections.remove(&id);
     }
 }
 
@@ -410,6 +420,14 @@ where
 
                 emit_response
             }
+            ComputeResponse::CollectionFailed(id, error) => {
+                // Forward only the first failure report for each collection.
+                if self.failed_collections.insert(id) {
+                    Some(Ok(ComputeResponse::CollectionFailed(id, error)))
+                } else {
+                    None
+                }
+            }
         }
     }
 }
@@ -142,14 +142,26 @@ pub struct SubscribeSinkConnection {}
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PersistSinkConnection<S> {
     pub value_desc: RelationDesc,
+    /// The ID of the storage collection the sink writes to.
+    ///
+    /// This is usually the ID of the sink itself, but differs for sinks that are built to replace
+    /// the sink currently writing to a storage collection.
+    pub storage_id: GlobalId,
     pub storage_metadata: S,
+    /// Whether the sink must not write to its storage collection until it is allowed to, through
+    /// a [`ComputeCommand::AllowWrites`] command.
+    ///
+    /// [`ComputeCommand::AllowWrites`]: crate::protocol::command::ComputeCommand::AllowWrites
+    pub read_only: bool,
 }
 
 impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMetadata> {
     fn into_proto(&self) -> ProtoPersistSinkConnection {
         ProtoPersistSinkConnection {
             value_desc: Some(self.value_desc.into_proto()),
+            storage_id: Some(self.storage_id.into_proto()),
             storage_metadata: Some(self.storage_metadata.into_proto()),
+            read_only: self.read_only,
         }
     }
 
@@ -158,9 +170,13 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
             value_desc: proto
                 .value_desc
                 .into_rust_if_some("ProtoPersistSinkConnection::value_desc")?,
+            storage_id: proto
+                .storage_id
+                .into_rust_if_some("ProtoPersistSinkConnection::storage_id")?,
             storage_metadata: proto
                 .storage_metadata
                 .into_rust_if_some("ProtoPersistSinkConnection::storage_metadata")?,
+            read_only: proto.read_only,
         })
     }
 }
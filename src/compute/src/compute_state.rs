@@ -6,7 +6,7 @@
 //! Worker-local state for compute timely instances.
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroUsize;
 use std::ops::DerefMut;
@@ -15,11 +15,14 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use bytesize::ByteSize;
+use differential_dataflow::logging::DifferentialEvent;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use differential_dataflow::trace::TraceReader;
 use differential_dataflow::Collection;
 use timely::communication::Allocate;
-use timely::logging::Logger;
+use timely::dataflow::operators::{Broadcast, Input, Inspect};
+use timely::dataflow::InputHandle;
+use timely::logging::{Logger, TimelyEvent, WorkerIdentifier};
 use timely::order::PartialOrder;
 use timely::progress::frontier::Antichain;
 use timely::progress::reachability::logging::TrackerEvent;
@@ -32,11 +35,15 @@ use mz_compute_client::logging::LoggingConfig;
 use mz_compute_client::plan::Plan;
 use mz_compute_client::protocol::command::{ComputeCommand, ComputeParameters, Peek};
 use mz_compute_client::protocol::history::ComputeCommandHistory;
-use mz_compute_client::protocol::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use mz_compute_client::protocol::response::{
+    ComputeResponse, PeekResponse, SubscribeBatch, SubscribeResponse,
+};
 use mz_compute_client::types::dataflows::DataflowDescription;
+use mz_compute_client::types::sinks::ComputeSinkConnection;
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::cache::PersistClientCache;
+use mz_repr::spill::SpillStats;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::types::errors::DataflowError;
@@ -67,6 +74,17 @@ pub struct ComputeState {
     /// Frontier of sink writes (all subsequent writes will be at times at or
     /// equal to this frontier)
     pub sink_write_frontiers: BTreeMap<GlobalId, Rc<RefCell<Antichain<Timestamp>>>>,
+    /// Flags by which read-only persist sinks learn that they are allowed to write to their
+    /// storage collection.
+    ///
+    /// Entries are removed once writes have been allowed.
+    pub sink_writes_allowed: BTreeMap<GlobalId, Rc<Cell<bool>>>,
+    /// The storage collections of read-only persist sinks that replace the sink currently
+    /// writing to the collection.
+    ///
+    /// Once allowed to write, such a sink is logged under the ID of its storage collection, like
+    /// the sink it replaces. Entries are removed once writes have been allowed.
+    pub replacing_sinks: BTreeMap<GlobalId, GlobalId>,
     /// Probe handles for regulating the output of dataflow sources.
     ///
     /// Keys are IDs of indexes that are (transitively) fed by a flow-controlled source. New
@@ -90,6 +108,22 @@ pub struct ComputeState {
     pub max_result_size: u32,
     /// Metrics for this replica.
     pub metrics: ComputeMetrics,
+    /// Sizes of the arrangement data allocated by this worker.
+    pub spill_stats: Arc<SpillStats>,
+    /// The number of spilled bytes last reported to the compute logger.
+    pub reported_spilled_bytes: Option<usize>,
+    /// The maximum number of arrangement records a dataflow may hold on this worker, if limited.
+    pub max_dataflow_arrangement_records: Option<usize>,
+    /// The number of arrangement records held by the dataflows of this worker.
+    pub arrangement_accounting: Rc<RefCell<ArrangementAccounting>>,
+    /// The exports of installed compute dataflows by timely dataflow index, each with whether it
+    /// is a subscribe.
+    pub dataflow_exports: BTreeMap<usize, BTreeMap<GlobalId, bool>>,
+    /// Means by which workers agree on dataflows to drop for exceeding their record limits.
+    pub dataflow_record_limits: Option<DataflowRecordLimits>,
+    /// Exports whose dataflows failed, and the error to report for them until they are dropped
+    /// by the controller.
+    pub failed_exports: BTreeMap<GlobalId, String>,
 }
 
 impl ComputeState {
@@ -142,6 +176,7 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
             UpdateConfiguration(params) => self.handle_update_configuration(params),
             CreateDataflows(dataflows) => self.handle_create_dataflows(dataflows),
             AllowCompaction(list) => self.handle_allow_compaction(list),
+            AllowWrites(id) => self.handle_allow_writes(id),
             Peek(peek) => {
                 peek.otel_ctx.attach_as_parent();
                 self.handle_peek(peek)
@@ -152,6 +187,7 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
 
     fn handle_create_instance(&mut self, logging: LoggingConfig) {
         self.initialize_logging(&logging);
+        self.initialize_dataflow_record_limits();
     }
 
     fn handle_update_configuration(&mut self, params: ComputeParameters) {
@@ -160,9 +196,19 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         if let Some(v) = params.max_result_size {
             self.compute_state.max_result_size = v;
         }
+        if let Some(v) = params.max_dataflow_arrangement_records {
+            let v = usize::cast_from(v);
+            self.compute_state.max_dataflow_arrangement_records = (v > 0).then_some(v);
+            // Dataflows must be checked against the new limit, even if their sizes are unchanged.
+            self.compute_state
+                .arrangement_accounting
+                .borrow_mut()
+                .updated = true;
+        }
 
-        // TODO(#16753): apply config to `self.compute_state.persist_clients`
-        let _ = params.persist;
+        params
+            .persist
+            .apply(self.compute_state.persist_clients.cfg());
     }
 
     fn handle_create_dataflows(
@@ -182,6 +228,27 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 .map(|(idx_id, (idx, _))| (*idx_id, idx.on_id));
             let exported_ids = index_ids.chain(sink_ids);
 
+            // Persist sinks that replace another sink assume its identity in the logs once they
+            // are allowed to write.
+            for (sink_id, sink) in &dataflow.sink_exports {
+                let ComputeSinkConnection::Persist(connection) = &sink.connection else {
+                    continue;
+                };
+                if connection.storage_id == *sink_id {
+                    continue;
+                }
+                if connection.read_only {
+                    self.compute_state
+                        .replacing_sinks
+                        .insert(*sink_id, connection.storage_id);
+                } else if let Some(logger) = self.compute_state.compute_logger.as_mut() {
+                    logger.log(ComputeEvent::ExportRenamed {
+                        export_id: *sink_id,
+                        log_id: connection.storage_id,
+                    });
+                }
+            }
+
             // Initialize frontiers for each object, and optionally log their construction.
             for (object_id, collection_id) in exported_ids {
                 if let Some(frontier) = self.compute_state.reported_frontiers.insert(
@@ -210,10 +277,73 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 }
             }
 
+            // Remember the timely dataflow that maintains the exports, so we can drop it should it
+            // exceed its limits.
+            let dataflow_index = self.timely_worker.next_dataflow_index();
+            let exports: BTreeMap<_, _> = dataflow
+                .export_ids()
+                .map(|id| {
+                    let is_subscribe = dataflow
+                        .sink_exports
+                        .get(&id)
+                        .map_or(false, |sink| sink.connection.is_subscribe());
+                    (id, is_subscribe)
+                })
+                .collect();
+            self.compute_state
+                .dataflow_exports
+                .insert(dataflow_index, exports.clone());
+
+            // Dataflows importing failed indexes cannot be built, and fail as well. Other workers
+            // might not know about the failure yet and build the dataflow, so we install an empty
+            // dataflow in its place to keep dataflow indexes aligned across workers.
+            let failed_import = dataflow
+                .index_imports
+                .keys()
+                .find_map(|id| self.compute_state.failed_exports.get(id))
+                .cloned();
+            if let Some(error) = failed_import {
+                self.timely_worker
+                    .dataflow_named::<Timestamp, _, _>(&dataflow.debug_name, |_scope| ());
+                self.fail_exports(exports, &error);
+                self.announce_failed_dataflow(dataflow_index, error);
+                continue;
+            }
+
             crate::render::build_compute_dataflow(self.timely_worker, self.compute_state, dataflow);
         }
     }
 
+    fn handle_allow_writes(&mut self, id: GlobalId) {
+        // Sinks that were created writable, or that have been allowed to write already, have no
+        // entry. This happens when the command is replayed during reconciliation.
+        if let Some(allowed) = self.compute_state.sink_writes_allowed.remove(&id) {
+            allowed.set(true);
+        }
+
+        if let Some(storage_id) = self.compute_state.replacing_sinks.remove(&id) {
+            // The logging dataflow does not know the current frontier of the sink, so we rename
+            // it ourselves.
+            let time = self
+                .compute_state
+                .reported_frontiers
+                .get(&id)
+                .and_then(|frontier| frontier.get(0).copied());
+            if let Some(logger) = self.compute_state.compute_logger.as_mut() {
+                if let Some(time) = time {
+                    logger.log(ComputeEvent::Frontier(id, time, -1));
+                }
+                logger.log(ComputeEvent::ExportRenamed {
+                    export_id: id,
+                    log_id: storage_id,
+                });
+                if let Some(time) = time {
+                    logger.log(ComputeEvent::Frontier(id, time, 1));
+                }
+            }
+        }
+    }
+
     fn handle_allow_compaction(&mut self, list: Vec<(GlobalId, Antichain<Timestamp>)>) {
         for (id, frontier) in list {
             if frontier.is_empty() {
@@ -224,22 +354,36 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
 
                 // Sink-specific work:
                 self.compute_state.sink_write_frontiers.remove(&id);
+                self.compute_state.sink_writes_allowed.remove(&id);
+                self.compute_state.replacing_sinks.remove(&id);
                 self.compute_state.sink_tokens.remove(&id);
                 // Index-specific work:
                 self.compute_state.traces.del_trace(&id);
                 self.compute_state.flow_control_probes.remove(&id);
 
+                self.compute_state.dataflow_exports.retain(|_, exports| {
+                    exports.remove(&id);
+                    !exports.is_empty()
+                });
+
                 // Work common to sinks and indexes (removing frontier tracking and cleaning up logging).
                 let prev_frontier = self
                     .compute_state
                     .reported_frontiers
                     .remove(&id)
                     .expect("Dropped compute collection with no frontier");
+                let failed = self.compute_state.failed_exports.remove(&id).is_some();
                 if let Some(logger) = self.compute_state.compute_logger.as_mut() {
                     logger.log(ComputeEvent::Dataflow(id, false));
                     if let Some(time) = prev_frontier.get(0) {
                         logger.log(ComputeEvent::Frontier(id, *time, -1));
                     }
+                    if failed {
+                        logger.log(ComputeEvent::ExportError {
+                            export_id: id,
+                            error: None,
+                        });
+                    }
                 }
 
                 // We need to emit a final response reporting the dropping of this collection,
@@ -262,6 +406,16 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
 
     #[tracing::instrument(level = "debug", skip(self))]
     fn handle_peek(&mut self, peek: Peek) {
+        // The dataflow maintaining the index might have failed for exceeding its limits.
+        if let Some(error) = self.compute_state.failed_exports.get(&peek.id) {
+            self.send_compute_response(ComputeResponse::PeekResponse(
+                peek.uuid,
+                PeekResponse::Error(error.clone()),
+                OpenTelemetryContext::obtain(),
+            ));
+            return;
+        }
+
         // Acquire a copy of the trace suitable for fulfilling the peek.
         let mut trace_bundle = self.compute_state.traces.get(&peek.id).unwrap().clone();
         let timestamp_frontier = Antichain::from_elem(peek.timestamp);
@@ -389,13 +543,15 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
 
         // Register each logger endpoint.
         let activator = t_activator.clone();
+        let accounting = Rc::clone(&self.compute_state.arrangement_accounting);
         self.timely_worker.log_register().insert_logger(
             "timely",
             Logger::new(
                 now,
                 start_offset,
                 self.timely_worker.index(),
-                move |time, data| {
+                move |time, data: &mut Vec<(Duration, WorkerIdentifier, TimelyEvent)>| {
+                    accounting.borrow_mut().absorb_timely(data);
                     t_logger.publish_batch(time, data);
                     activator.activate();
                 },
@@ -454,13 +610,15 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         );
 
         let activator = d_activator.clone();
+        let accounting = Rc::clone(&self.compute_state.arrangement_accounting);
         self.timely_worker.log_register().insert_logger(
             "differential/arrange",
             Logger::new(
                 now,
                 start_offset,
                 self.timely_worker.index(),
-                move |time, data| {
+                move |time, data: &mut Vec<(Duration, WorkerIdentifier, DifferentialEvent)>| {
+                    accounting.borrow_mut().absorb_differential(data);
                     d_logger.publish_batch(time, data);
                     activator.activate();
                 },
@@ -576,6 +734,28 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         self.compute_state.compute_logger = Some(logger);
     }
 
+    /// Installs the dataflow by which workers agree on dataflows that exceed their record limits.
+    fn initialize_dataflow_record_limits(&mut self) {
+        let failed = Rc::new(RefCell::new(Vec::new()));
+        let input = self
+            .timely_worker
+            .dataflow_named("Dataflow: record limits", |scope| {
+                let mut input = InputHandle::new();
+                let failed = Rc::clone(&failed);
+                scope
+                    .input_from(&mut input)
+                    .broadcast()
+                    .inspect(move |announcement| failed.borrow_mut().push(announcement.clone()));
+                input
+            });
+
+        self.compute_state.dataflow_record_limits = Some(DataflowRecordLimits {
+            input,
+            failed,
+            announced: BTreeSet::new(),
+        });
+    }
+
     /// Disables timely dataflow logging.
     ///
     /// This does not unpublish views and is only useful to terminate logging streams to ensure that
@@ -658,6 +838,161 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Log changes to the amount of arrangement data this worker has spilled to disk.
+    pub fn report_arrangement_spill(&mut self) {
+        let spilled_bytes = self.compute_state.spill_stats.spilled_bytes();
+        if self.compute_state.reported_spilled_bytes == Some(spilled_bytes) {
+            return;
+        }
+        if let Some(logger) = self.compute_state.compute_logger.as_mut() {
+            logger.log(ComputeEvent::ArrangementSpill { spilled_bytes });
+            self.compute_state.reported_spilled_bytes = Some(spilled_bytes);
+        }
+    }
+
+    /// Drop dataflows whose arrangements hold more records than allowed.
+    ///
+    /// Workers only know the sizes of their own arrangements, but must all drop the same
+    /// dataflows. Each worker thus announces the dataflows that exceed the limit locally to all
+    /// workers, and drops the dataflows that any worker has announced.
+    pub fn enforce_dataflow_record_limits(&mut self) {
+        if let Some(max_records) = self.compute_state.max_dataflow_arrangement_records {
+            // Loggers buffer events until their buffers fill up or are flushed, so without
+            // flushing the accounting would only learn about arrangement growth with a delay.
+            self.timely_worker.log_register().flush();
+            let dataflow_records = {
+                let mut accounting = self.compute_state.arrangement_accounting.borrow_mut();
+                std::mem::take(&mut accounting.updated).then(|| accounting.dataflow_records())
+            };
+            for (index, records) in dataflow_records.into_iter().flatten() {
+                let exceeded = usize::try_from(records).map_or(false, |r| r > max_records);
+                if exceeded && self.compute_state.dataflow_exports.contains_key(&index) {
+                    let error = format!(
+                        "dataflow exceeded max_dataflow_arrangement_records ({max_records} \
+                         records per worker) and was dropped"
+                    );
+                    self.announce_failed_dataflow(index, error);
+                }
+            }
+        }
+
+        let Some(limits) = self.compute_state.dataflow_record_limits.as_mut() else {
+            return;
+        };
+        // Other workers might announce dataflows this worker has not yet built. Those are dropped
+        // once this worker has caught up.
+        let next_index = self.timely_worker.next_dataflow_index();
+        let (failed, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut *limits.failed.borrow_mut())
+            .into_iter()
+            .partition(|(index, _)| *index < next_index);
+        *limits.failed.borrow_mut() = pending;
+
+        for (index, error) in failed {
+            self.drop_failed_dataflow(index, error);
+        }
+    }
+
+    /// Announces to all workers that the dataflow with the given index failed with `error`.
+    fn announce_failed_dataflow(&mut self, index: usize, error: String) {
+        if let Some(limits) = self.compute_state.dataflow_record_limits.as_mut() {
+            if limits.announced.insert(index) {
+                limits.input.send((index, error));
+                let next = *limits.input.time() + 1;
+                limits.input.advance_to(next);
+            }
+        }
+    }
+
+    /// Drops the dataflow with the given index, failing all of its exports with `error`.
+    fn drop_failed_dataflow(&mut self, index: usize, error: String) {
+        // The controller might have dropped the dataflow's exports already, but the dataflow
+        // might still be installed and must be dropped on all workers alike.
+        let exports = self
+            .compute_state
+            .dataflow_exports
+            .remove(&index)
+            .unwrap_or_default();
+        self.fail_exports(exports, &error);
+
+        if self.timely_worker.installed_dataflows().contains(&index) {
+            info!("dropping dataflow {index}: {error}");
+            self.timely_worker.drop_dataflow(index);
+        }
+        if let Some(limits) = self.compute_state.dataflow_record_limits.as_mut() {
+            limits.announced.remove(&index);
+        }
+    }
+
+    /// Fails the given exports, each paired with whether it is a subscribe, with `error`.
+    ///
+    /// Failed subscribes are completed with `error`. Other failed exports are reported to the
+    /// controller through `CollectionFailed` responses, stop advancing their frontiers, and answer
+    /// peeks with `error`, until the controller drops them.
+    fn fail_exports(&mut self, exports: BTreeMap<GlobalId, bool>, error: &str) {
+        for (id, is_subscribe) in exports {
+            if is_subscribe {
+                // An empty upper makes the controller forward the error and retire the subscribe.
+                let response = SubscribeResponse::Batch(SubscribeBatch {
+                    lower: Antichain::from_elem(timely::progress::Timestamp::minimum()),
+                    upper: Antichain::new(),
+                    updates: Err(error.to_string()),
+                });
+                self.compute_state
+                    .subscribe_response_buffer
+                    .borrow_mut()
+                    .push((id, response));
+            }
+
+            self.compute_state.sink_write_frontiers.remove(&id);
+            self.compute_state.sink_writes_allowed.remove(&id);
+            self.compute_state.replacing_sinks.remove(&id);
+            self.compute_state.sink_tokens.remove(&id);
+            self.compute_state.traces.del_trace(&id);
+            self.compute_state.flow_control_probes.remove(&id);
+
+            if is_subscribe {
+                if let Some(frontier) = self.compute_state.reported_frontiers.get_mut(&id) {
+                    if let Some(logger) = self.compute_state.compute_logger.as_mut() {
+                        if let Some(time) = frontier.get(0) {
+                            logger.log(ComputeEvent::Frontier(id, *time, -1));
+                        }
+                    }
+                    *frontier = Antichain::new();
+                }
+            } else if self.compute_state.reported_frontiers.contains_key(&id) {
+                // The reported frontier stays where it is, so that the collection is reported as
+                // dropped once the controller allows its compaction to the empty frontier.
+                self.send_compute_response(ComputeResponse::CollectionFailed(
+                    id,
+                    error.to_string(),
+                ));
+            }
+            if let Some(logger) = self.compute_state.compute_logger.as_mut() {
+                logger.log(ComputeEvent::ExportError {
+                    export_id: id,
+                    error: Some(error.to_string()),
+                });
+            }
+            self.compute_state
+                .failed_exports
+                .insert(id, error.to_string());
+        }
+
+        // Peeks waiting for the failed exports would otherwise never be answered.
+        let pending_peeks = std::mem::take(&mut self.compute_state.pending_peeks);
+        for (uuid, peek) in pending_peeks {
+            match self.compute_state.failed_exports.get(&peek.peek.id) {
+                Some(error) => {
+                    let response = PeekResponse::Error(error.clone());
+                    self.send_peek_response(peek, response);
+                }
+                None => {
+                    self.compute_state.pending_peeks.insert(uuid, peek);
+                }
+            }
+        }
+    }
+
     /// Scan pending peeks and attempt to retire each.
     pub fn process_peeks(&mut self) {
         let mut upper = Antichain::new();
@@ -712,6 +1047,89 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
     }
 }
 
+/// The number of records held by the arrangements of each dataflow on a worker.
+///
+/// Maintained from timely and differential logging events, which describe the dataflows that
+/// operators belong to and the sizes of arrangements, respectively.
+#[derive(Debug, Default)]
+pub struct ArrangementAccounting {
+    /// The index of the dataflow containing each operator, by operator ID.
+    operator_dataflows: BTreeMap<usize, usize>,
+    /// The number of records held by each arrangement operator, by operator ID.
+    operator_records: BTreeMap<usize, Diff>,
+    /// Whether the accounting changed since dataflows were last checked against their limits.
+    updated: bool,
+}
+
+impl ArrangementAccounting {
+    /// Absorbs timely logging events, which describe the creation and removal of operators.
+    fn absorb_timely(&mut self, events: &[(Duration, WorkerIdentifier, TimelyEvent)]) {
+        for (_, _, event) in events {
+            match event {
+                TimelyEvent::Operates(event) => {
+                    if let Some(dataflow) = event.addr.first() {
+                        self.operator_dataflows.insert(event.id, *dataflow);
+                    }
+                }
+                TimelyEvent::Shutdown(event) => {
+                    // Arrangements might outlive their operators, so their records are removed
+                    // only once they reach zero.
+                    self.operator_dataflows.remove(&event.id);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Absorbs differential logging events, which describe changes to arrangement sizes.
+    fn absorb_differential(&mut self, events: &[(Duration, WorkerIdentifier, DifferentialEvent)]) {
+        let as_diff = |length: usize| Diff::cast_from(length);
+        for (_, _, event) in events {
+            let (operator, diff) = match event {
+                DifferentialEvent::Batch(event) => (event.operator, as_diff(event.length)),
+                DifferentialEvent::Merge(event) => match event.complete {
+                    Some(done) => (
+                        event.operator,
+                        as_diff(done) - as_diff(event.length1 + event.length2),
+                    ),
+                    None => continue,
+                },
+                DifferentialEvent::Drop(event) => (event.operator, -as_diff(event.length)),
+                _ => continue,
+            };
+
+            let records = self.operator_records.entry(operator).or_default();
+            *records += diff;
+            if *records == 0 {
+                self.operator_records.remove(&operator);
+            }
+            self.updated = true;
+        }
+    }
+
+    /// Returns the number of records held by each dataflow, by dataflow index.
+    fn dataflow_records(&self) -> BTreeMap<usize, Diff> {
+        let mut dataflow_records = BTreeMap::new();
+        for (operator, records) in self.operator_records.iter() {
+            if let Some(dataflow) = self.operator_dataflows.get(operator) {
+                *dataflow_records.entry(*dataflow).or_default() += records;
+            }
+        }
+        dataflow_records
+    }
+}
+
+/// A dataflow by which workers announce to each other the dataflows that failed, for exceeding
+/// their limits or importing indexes that did.
+pub struct DataflowRecordLimits {
+    /// Input for announcing failed dataflows, as pairs of dataflow index and error.
+    input: InputHandle<u64, (usize, String)>,
+    /// Announcements received from any worker, not yet acted upon.
+    failed: Rc<RefCell<Vec<(usize, String)>>>,
+    /// Dataflows this worker has announced, but not yet dropped.
+    announced: BTreeSet<usize>,
+}
+
 /// An in-progress peek, and data to eventually fulfill it.
 ///
 /// Note that `PendingPeek` intentionally does not implement or derive `Clone`,
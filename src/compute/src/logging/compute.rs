@@ -57,6 +57,63 @@ pub enum ComputeEvent {
     Frontier(GlobalId, Timestamp, i64),
     // Available frontier information for source instantiations.
     SourceFrontier(GlobalId, GlobalId, Timestamp, i8),
+    /// The operators that render a node of a dataflow's LIR plan.
+    LirMapping {
+        /// Globally unique identifier for the dataflow export.
+        export_id: GlobalId,
+        /// The node's identifier, unique within the dataflow.
+        lir_id: u64,
+        /// The identifier of the node that consumes this node's output, if any.
+        parent_lir_id: Option<u64>,
+        /// The depth of the node in the plan it is part of.
+        nesting: u16,
+        /// A description of the node.
+        operator: String,
+        /// The timely operator identifiers allocated while rendering the node,
+        /// and its inputs, lie strictly between these bounds.
+        operator_id_start: usize,
+        operator_id_end: usize,
+    },
+    /// The number of bytes of arrangement data the worker has spilled to disk.
+    ArrangementSpill {
+        /// The total number of spilled bytes, replacing any previously logged total.
+        spilled_bytes: usize,
+    },
+    /// A dataflow export failed, or stopped failing if `error` is `None`.
+    ExportError {
+        /// Globally unique identifier for the dataflow export.
+        export_id: GlobalId,
+        /// The error the export failed with, replacing any previously logged error.
+        error: Option<String>,
+    },
+    /// A dataflow export is logged under another identifier from now on, including the
+    /// events that were logged for it before.
+    ///
+    /// A persist sink that takes over the storage collection of another sink assumes the
+    /// identity of the sink it replaces, so its logs can be found by the collection's ID.
+    ExportRenamed {
+        /// Globally unique identifier for the dataflow export.
+        export_id: GlobalId,
+        /// The identifier under which the export is logged from now on.
+        log_id: GlobalId,
+    },
+}
+
+impl ComputeEvent {
+    /// Returns the identifier of the dataflow export the event pertains to, if any.
+    fn export_id_mut(&mut self) -> Option<&mut GlobalId> {
+        match self {
+            ComputeEvent::Dataflow(id, _)
+            | ComputeEvent::Frontier(id, _, _)
+            | ComputeEvent::SourceFrontier(id, _, _, _)
+            | ComputeEvent::DataflowDependency { dataflow: id, .. }
+            | ComputeEvent::LirMapping { export_id: id, .. }
+            | ComputeEvent::ExportError { export_id: id, .. } => Some(id),
+            ComputeEvent::Peek(..)
+            | ComputeEvent::ArrangementSpill { .. }
+            | ComputeEvent::ExportRenamed { .. } => None,
+        }
+    }
 }
 
 /// A logged peek event.
@@ -119,11 +176,19 @@ pub fn construct<A: Allocate>(
         let (mut frontier_delay_out, frontier_delay) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut lir_mapping_out, lir_mapping) = demux.new_output();
+        let (mut arrangement_spill_out, arrangement_spill) = demux.new_output();
+        let (mut export_error_out, export_error) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
         demux.build(move |_capability| {
             let mut active_dataflows = BTreeMap::new();
             let mut peek_stash = BTreeMap::new();
+            let mut lir_mappings = BTreeMap::<(GlobalId, usize), Vec<Row>>::new();
+            let mut spilled_bytes_by_worker = BTreeMap::new();
+            let mut export_errors = BTreeMap::<(GlobalId, usize), String>::new();
+            let mut renamed_exports = BTreeMap::<(GlobalId, usize), GlobalId>::new();
+            let mut source_frontiers = BTreeMap::<(GlobalId, GlobalId, usize), Timestamp>::new();
             let mut storage_sources = BTreeMap::<
                 (GlobalId, usize),
                 BTreeMap<GlobalId, (VecDeque<(mz_repr::Timestamp, u128)>, BTreeMap<u128, i32>)>,
@@ -136,6 +201,9 @@ pub fn construct<A: Allocate>(
                 let mut frontier_delay = frontier_delay_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut lir_mapping = lir_mapping_out.activate();
+                let mut arrangement_spill = arrangement_spill_out.activate();
+                let mut export_error = export_error_out.activate();
 
                 input.for_each(|time, data| {
                     data.swap(&mut demux_buffer);
@@ -147,12 +215,22 @@ pub fn construct<A: Allocate>(
                     let mut frontier_delay_session = frontier_delay.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
+                    let mut lir_mapping_session = lir_mapping.session(&time);
+                    let mut arrangement_spill_session = arrangement_spill.session(&time);
+                    let mut export_error_session = export_error.session(&time);
 
-                    for (time, worker, datum) in demux_buffer.drain(..) {
+                    for (time, worker, mut datum) in demux_buffer.drain(..) {
                         let time_ms = (((time.as_millis() / interval_ms) + 1) * interval_ms)
                             .try_into()
                             .expect("must fit");
 
+                        // Events of renamed exports are logged under their new identifier.
+                        if let Some(id) = datum.export_id_mut() {
+                            if let Some(log_id) = renamed_exports.get(&(*id, worker)) {
+                                *id = *log_id;
+                            }
+                        }
+
                         match datum {
                             ComputeEvent::Dataflow(id, is_create) => {
                                 let diff = if is_create { 1 } else { -1 };
@@ -184,6 +262,41 @@ pub fn construct<A: Allocate>(
                                             key.0, worker
                                         ),
                                     }
+                                    // Events of a renamed export that arrive after its drop
+                                    // cannot be renamed anymore, so retract its source frontiers
+                                    // now rather than when the dataflow shuts down.
+                                    let len = renamed_exports.len();
+                                    renamed_exports
+                                        .retain(|(_, w), log_id| (*log_id, *w) != (id, worker));
+                                    if renamed_exports.len() < len {
+                                        let dropped_frontiers: Vec<_> = source_frontiers
+                                            .iter()
+                                            .filter(|((dataflow, _, w), _)| {
+                                                (*dataflow, *w) == (id, worker)
+                                            })
+                                            .map(|(key, logical)| (*key, *logical))
+                                            .collect();
+                                        for (key, logical) in dropped_frontiers {
+                                            source_frontiers.remove(&key);
+                                            source_frontier_session.give((
+                                                Row::pack_slice(&[
+                                                    Datum::String(&id.to_string()),
+                                                    Datum::String(&key.1.to_string()),
+                                                    Datum::UInt64(u64::cast_from(worker)),
+                                                    Datum::MzTimestamp(logical),
+                                                ]),
+                                                time_ms,
+                                                -1,
+                                            ));
+                                        }
+                                    }
+                                    // Retract the mapping of the dataflow's plan to its
+                                    // operators.
+                                    if let Some(rows) = lir_mappings.remove(key) {
+                                        for row in rows {
+                                            lir_mapping_session.give((row, time_ms, -1));
+                                        }
+                                    }
                                     // dataflow may or may not be associated to a storage
                                     // source instantiation. Report removal if so.
                                     if let Some(source_map) = storage_sources.remove(key) {
@@ -265,6 +378,17 @@ pub fn construct<A: Allocate>(
                                 }
                             }
                             ComputeEvent::SourceFrontier(dataflow, source_id, logical, delta) => {
+                                // Remember the current frontier, so it can be renamed.
+                                let frontier_key = (dataflow, source_id, worker);
+                                if delta > 0 {
+                                    source_frontiers.insert(frontier_key, logical);
+                                } else if source_frontiers.get(&frontier_key) == Some(&logical) {
+                                    source_frontiers.remove(&frontier_key);
+                                } else {
+                                    // The frontier has already been retracted, when its dataflow
+                                    // was dropped.
+                                    continue;
+                                }
                                 // report source instantiation frontier advancement
                                 source_frontier_session.give((
                                     Row::pack_slice(&[
@@ -296,6 +420,157 @@ pub fn construct<A: Allocate>(
                                     }
                                 }
                             }
+                            ComputeEvent::LirMapping {
+                                export_id,
+                                lir_id,
+                                parent_lir_id,
+                                nesting,
+                                operator,
+                                operator_id_start,
+                                operator_id_end,
+                            } => {
+                                let row = Row::pack_slice(&[
+                                    Datum::String(&export_id.to_string()),
+                                    Datum::UInt64(u64::cast_from(worker)),
+                                    Datum::UInt64(lir_id),
+                                    Datum::from(parent_lir_id),
+                                    Datum::UInt16(nesting),
+                                    Datum::String(&operator),
+                                    Datum::UInt64(u64::cast_from(operator_id_start)),
+                                    Datum::UInt64(u64::cast_from(operator_id_end)),
+                                ]);
+                                lir_mapping_session.give((row.clone(), time_ms, 1));
+                                lir_mappings
+                                    .entry((export_id, worker))
+                                    .or_default()
+                                    .push(row);
+                            }
+                            ComputeEvent::ArrangementSpill { spilled_bytes } => {
+                                if let Some(prev) =
+                                    spilled_bytes_by_worker.insert(worker, spilled_bytes)
+                                {
+                                    arrangement_spill_session.give(((worker, prev), time_ms, -1));
+                                }
+                                arrangement_spill_session.give((
+                                    (worker, spilled_bytes),
+                                    time_ms,
+                                    1,
+                                ));
+                            }
+                            ComputeEvent::ExportRenamed { export_id, log_id } => {
+                                let from = (export_id, worker);
+                                let to = (log_id, worker);
+                                renamed_exports.insert(from, log_id);
+                                // Replace everything logged under the previous identifier.
+                                if let Some(sources) = active_dataflows.remove(&from) {
+                                    dataflow_session.give((from, time_ms, -1));
+                                    dataflow_session.give((to, time_ms, 1));
+                                    for (source, worker) in &sources {
+                                        dependency_session.give((
+                                            (export_id, *source, *worker),
+                                            time_ms,
+                                            -1,
+                                        ));
+                                        dependency_session.give((
+                                            (log_id, *source, *worker),
+                                            time_ms,
+                                            1,
+                                        ));
+                                    }
+                                    active_dataflows.insert(to, sources);
+                                }
+                                if let Some(rows) = lir_mappings.remove(&from) {
+                                    let log_id_str = log_id.to_string();
+                                    let renamed_rows: Vec<_> = rows
+                                        .into_iter()
+                                        .map(|row| {
+                                            let mut datums = row.unpack();
+                                            lir_mapping_session.give((row.clone(), time_ms, -1));
+                                            datums[0] = Datum::String(&log_id_str);
+                                            let renamed = Row::pack_slice(&datums);
+                                            lir_mapping_session.give((renamed.clone(), time_ms, 1));
+                                            renamed
+                                        })
+                                        .collect();
+                                    lir_mappings.insert(to, renamed_rows);
+                                }
+                                if let Some(error) = export_errors.remove(&from) {
+                                    export_error_session.give((
+                                        (export_id, worker, error.clone()),
+                                        time_ms,
+                                        -1,
+                                    ));
+                                    export_error_session.give((
+                                        (log_id, worker, error.clone()),
+                                        time_ms,
+                                        1,
+                                    ));
+                                    export_errors.insert(to, error);
+                                }
+                                if let Some(source_map) = storage_sources.remove(&from) {
+                                    for (source_id, (_, delay_map)) in &source_map {
+                                        for (delay_ns, delay_count) in delay_map {
+                                            let delay_pow = delay_ns.next_power_of_two();
+                                            let delay_ns: i128 =
+                                                (*delay_ns).try_into().expect("delay too big");
+                                            let delay_count = *delay_count;
+                                            let delay = delay_ns * i128::from(delay_count);
+                                            frontier_delay_session.give((
+                                                (export_id, *source_id, worker, delay_pow),
+                                                time_ms,
+                                                (-delay, -delay_count),
+                                            ));
+                                            frontier_delay_session.give((
+                                                (log_id, *source_id, worker, delay_pow),
+                                                time_ms,
+                                                (delay, delay_count),
+                                            ));
+                                        }
+                                    }
+                                    storage_sources.insert(to, source_map);
+                                }
+                                let renamed_frontiers: Vec<_> = source_frontiers
+                                    .iter()
+                                    .filter(|((dataflow, _, w), _)| {
+                                        *dataflow == export_id && *w == worker
+                                    })
+                                    .map(|(key, logical)| (*key, *logical))
+                                    .collect();
+                                for ((_, source_id, _), logical) in renamed_frontiers {
+                                    source_frontiers.remove(&(export_id, source_id, worker));
+                                    source_frontiers.insert((log_id, source_id, worker), logical);
+                                    for (id, delta) in [(export_id, -1), (log_id, 1)] {
+                                        source_frontier_session.give((
+                                            Row::pack_slice(&[
+                                                Datum::String(&id.to_string()),
+                                                Datum::String(&source_id.to_string()),
+                                                Datum::UInt64(u64::cast_from(worker)),
+                                                Datum::MzTimestamp(logical),
+                                            ]),
+                                            time_ms,
+                                            delta,
+                                        ));
+                                    }
+                                }
+                            }
+                            ComputeEvent::ExportError { export_id, error } => {
+                                let key = (export_id, worker);
+                                if let Some(prev) = export_errors.remove(&key) {
+                                    export_error_session.give((
+                                        (export_id, worker, prev),
+                                        time_ms,
+                                        -1,
+                                    ));
+                                }
+                                if let Some(error) = error {
+                                    export_error_session.give((
+                                        (export_id, worker, error.clone()),
+                                        time_ms,
+                                        1,
+                                    ));
+                                    export_errors.insert(key, error);
+                                }
+                            }
                             ComputeEvent::Peek(peek, is_install) => {
                                 let key = (worker, peek.uuid);
                                 if is_install {
@@ -414,6 +689,27 @@ pub fn construct<A: Allocate>(
                 ])
             });
 
+        let lir_mapping = lir_mapping.as_collection();
+
+        let arrangement_spill = arrangement_spill.as_collection().map({
+            move |(worker, spilled_bytes)| {
+                Row::pack_slice(&[
+                    Datum::UInt64(u64::cast_from(worker)),
+                    Datum::UInt64(u64::cast_from(spilled_bytes)),
+                ])
+            }
+        });
+
+        let export_error = export_error.as_collection().map({
+            move |(export_id, worker, error)| {
+                Row::pack_slice(&[
+                    Datum::String(&export_id.to_string()),
+                    Datum::UInt64(u64::cast_from(worker)),
+                    Datum::String(&error),
+                ])
+            }
+        });
+
         let logs = vec![
             (
                 LogVariant::Compute(ComputeLog::DataflowCurrent),
@@ -437,6 +733,12 @@ pub fn construct<A: Allocate>(
             ),
             (LogVariant::Compute(ComputeLog::PeekCurrent), peek_current),
             (LogVariant::Compute(ComputeLog::PeekDuration), peek_duration),
+            (LogVariant::Compute(ComputeLog::LirMapping), lir_mapping),
+            (
+                LogVariant::Compute(ComputeLog::ArrangementSpill),
+                arrangement_spill,
+            ),
+            (LogVariant::Compute(ComputeLog::ExportErrors), export_error),
         ];
 
         let mut result = BTreeMap::new();
@@ -33,6 +33,7 @@ pub(crate) fn persist_sink<G>(
         target,
         desired_collection,
         as_of,
+        false,
         compute_state,
         Vec::new(),
     );
@@ -36,6 +36,7 @@ use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 
+use crate::logging::compute::Logger;
 use crate::typedefs::{ErrSpine, RowSpine, TraceErrHandle, TraceRowHandle};
 
 // Local type definition to avoid the horror in signatures.
@@ -88,6 +89,15 @@ where
     pub until: Antichain<T>,
     /// Bindings of identifiers to collections.
     pub bindings: BTreeMap<Id, CollectionBundle<S, V, T>>,
+    /// A logger for compute events, used to record which operators render
+    /// each node of the plan.
+    pub(crate) compute_logger: Option<Logger>,
+    /// The identifiers of the dataflow's exports.
+    pub(crate) export_ids: Vec<GlobalId>,
+    /// The identifier to assign to the next plan node that is rendered.
+    pub(crate) next_lir_id: u64,
+    /// The identifiers of the plan nodes being rendered, innermost last.
+    pub(crate) lir_stack: Vec<u64>,
 }
 
 impl<S: Scope, V: Data + columnation::Columnation> Context<S, V>
@@ -113,6 +123,10 @@ where
             as_of_frontier,
             until: dataflow.until.clone(),
             bindings: BTreeMap::new(),
+            compute_logger: None,
+            export_ids: dataflow.export_ids().collect(),
+            next_lir_id: 0,
+            lir_stack: Vec::new(),
         }
     }
 }
@@ -114,7 +114,7 @@ use timely::dataflow::{Scope, Stream};
 use timely::order::Product;
 use timely::progress::timestamp::Refines;
 use timely::progress::Timestamp;
-use timely::worker::Worker as TimelyWorker;
+use timely::worker::{AsWorker, Worker as TimelyWorker};
 use timely::PartialOrder;
 
 use mz_compute_client::plan::Plan;
@@ -269,6 +269,7 @@ pub fn build_compute_dataflow<A: Allocate>(
             scope.clone().iterative::<usize, _, _>(|region| {
                 let mut context =
                     crate::render::context::Context::for_dataflow_in(&dataflow, region.clone());
+                context.compute_logger = compute_state.compute_logger.clone();
 
                 for (id, (oks, errs)) in imported_sources.into_iter() {
                     let bundle = crate::render::CollectionBundle::from_collections(
@@ -356,6 +357,7 @@ pub fn build_compute_dataflow<A: Allocate>(
             scope.clone().region_named(&build_name, |region| {
                 let mut context =
                     crate::render::context::Context::for_dataflow_in(&dataflow, region.clone());
+                context.compute_logger = compute_state.compute_logger.clone();
 
                 for (id, (oks, errs)) in imported_sources.into_iter() {
                     let bundle = crate::render::CollectionBundle::from_collections(
@@ -659,7 +661,46 @@ where
     ///
     /// The return type reflects the uncertainty about the data representation, perhaps
     /// as a stream of data, perhaps as an arrangement, perhaps as a stream of batches.
+    ///
+    /// If compute logging is enabled, this also records the range of timely operators
+    /// that render each node of the plan, so that their introspection data can be
+    /// attributed to the node (e.g., by `EXPLAIN ANALYZE`).
     pub fn render_plan(&mut self, plan: Plan) -> CollectionBundle<G, Row> {
+        let Some(logger) = self.compute_logger.clone() else {
+            return self.render_plan_node(plan);
+        };
+
+        let lir_id = self.next_lir_id;
+        self.next_lir_id += 1;
+        let parent_lir_id = self.lir_stack.last().copied();
+        let nesting = u16::try_from(self.lir_stack.len()).unwrap_or(u16::MAX);
+        let operator = plan.node_name();
+
+        // Operators are assigned increasing identifiers as they are created, so
+        // the operators that render this node and its inputs are exactly those
+        // whose identifiers lie between these two.
+        let operator_id_start = self.scope.new_identifier();
+        self.lir_stack.push(lir_id);
+        let bundle = self.render_plan_node(plan);
+        self.lir_stack.pop();
+        let operator_id_end = self.scope.new_identifier();
+
+        for export_id in &self.export_ids {
+            logger.log(ComputeEvent::LirMapping {
+                export_id: *export_id,
+                lir_id,
+                parent_lir_id,
+                nesting,
+                operator: operator.clone(),
+                operator_id_start,
+                operator_id_end,
+            });
+        }
+        bundle
+    }
+
+    /// Renders a single node of a plan, and its inputs.
+    fn render_plan_node(&mut self, plan: Plan) -> CollectionBundle<G, Row> {
         match plan {
             Plan::Constant { rows } => {
                 // Produce both rows and errs to avoid conditional dataflow construction.
@@ -12,6 +12,7 @@
 //! Consult [TopKPlan] documentation for details.
 
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
@@ -28,7 +29,7 @@ use timely::dataflow::operators::Operator;
 use timely::dataflow::Scope;
 
 use mz_compute_client::plan::top_k::{
-    BasicTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, TopKPlan,
+    BasicTopKPlan, BoundedTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, TopKPlan,
 };
 use mz_repr::{DatumVec, Diff, Row};
 
@@ -117,6 +118,13 @@ where
 
                     result.map(|((_key, _hash), row)| row)
                 }
+                TopKPlan::Bounded(BoundedTopKPlan {
+                    group_key,
+                    order_key,
+                    limit,
+                    offset,
+                    arity,
+                }) => build_bounded_topk(ok_input, group_key, order_key, offset, limit, arity),
                 TopKPlan::Basic(BasicTopKPlan {
                     group_key,
                     order_key,
@@ -293,6 +301,120 @@ where
                 .consolidate()
         }
 
+        /// Constructs a single-stage TopK dataflow subgraph for groups that are known to be
+        /// small.
+        ///
+        /// Each time a group changes, we select its first `offset + limit` records in a single
+        /// pass, and produce the last `limit` of those. The pass visits the entire group, which
+        /// is why we only plan this for small groups. Unlike `build_topk_stage`, we never sort
+        /// the entire group, and we produce the output records rather than retractions of the
+        /// records that are not output, which keeps the output to `limit` records per group.
+        fn build_bounded_topk<G>(
+            collection: Collection<G, Row, Diff>,
+            group_key: Vec<usize>,
+            order_key: Vec<mz_expr::ColumnOrder>,
+            offset: usize,
+            limit: usize,
+            arity: usize,
+        ) -> Collection<G, Row, Diff>
+        where
+            G: Scope,
+            G::Timestamp: Lattice,
+        {
+            use differential_dataflow::operators::Reduce;
+
+            let mut datum_vec = mz_repr::DatumVec::new();
+            let collection = collection.map(move |row| {
+                let group_row = {
+                    let datums = datum_vec.borrow_with(&row);
+                    let iterator = group_key.iter().map(|i| datums[*i]);
+                    let total_size = mz_repr::datums_size(iterator.clone());
+                    let mut group_row = Row::with_capacity(total_size);
+                    group_row.packer().extend(iterator);
+                    group_row
+                };
+                (group_row, row)
+            });
+
+            // TODO(#16549): Use explicit arrangement
+            collection
+                .reduce_named(
+                    "TopKBounded",
+                    move |_key, source, target: &mut Vec<(Row, Diff)>| {
+                        select_bounded_topk(source, target, &order_key, offset, limit, arity)
+                    },
+                )
+                .map(|(_group_row, row)| row)
+        }
+
+        /// Produces into `target` the records of the group `source` at the ordinal positions
+        /// from `offset` up to `offset + limit`, according to `order_key`.
+        ///
+        /// We consider the records one at a time, and only keep those that may still be among
+        /// the first `offset + limit` records of the group.
+        fn select_bounded_topk(
+            source: &[(&Row, Diff)],
+            target: &mut Vec<(Row, Diff)>,
+            order_key: &[mz_expr::ColumnOrder],
+            offset: usize,
+            limit: usize,
+            arity: usize,
+        ) {
+            // We decode the datums once, into a common buffer for efficiency.
+            // Each row should contain `arity` columns; we should check that.
+            let mut buffer = Vec::with_capacity(arity * source.len());
+            for (index, row) in source.iter().enumerate() {
+                buffer.extend(row.0.iter());
+                assert_eq!(buffer.len(), arity * (index + 1));
+            }
+            let datums = |index: usize| &buffer[index * arity..][..arity];
+
+            // The indexes of the first records of the group in order, and the sum of their counts.
+            // We keep only as many records as needed for the sum to reach `bound`.
+            let bound = Diff::try_from(offset + limit).expect("must fit");
+            let mut selected: Vec<usize> = Vec::with_capacity(offset + limit);
+            let mut count: Diff = 0;
+            for (index, (_row, diff)) in source.iter().enumerate() {
+                if *diff <= 0 {
+                    continue;
+                }
+                let compare = |other: &usize| {
+                    let (left, right) = (datums(*other), datums(index));
+                    mz_expr::compare_columns(order_key, left, right, || left.cmp(right))
+                };
+                // A record that is not before all of a full selection cannot be produced.
+                if count >= bound && selected.last().map(compare) != Some(Ordering::Greater) {
+                    continue;
+                }
+                let position = selected.partition_point(|other| compare(other).is_le());
+                selected.insert(position, index);
+                count += diff;
+                // Drop the last selected records, as long as the others still suffice.
+                while let Some(last) = selected.last() {
+                    let last_diff = source[*last].1;
+                    if count - last_diff < bound {
+                        break;
+                    }
+                    count -= last_diff;
+                    selected.pop();
+                }
+            }
+
+            // Skip the first `offset` records, and produce at most `limit` records.
+            let mut offset = Diff::try_from(offset).expect("must fit");
+            let mut limit = Diff::try_from(limit).expect("must fit");
+            for index in selected {
+                let (row, mut diff) = source[index];
+                let to_skip = std::cmp::min(offset, diff);
+                offset -= to_skip;
+                diff = std::cmp::min(diff - to_skip, limit);
+                limit -= diff;
+                if diff > 0 {
+                    target.push((row.clone(), diff));
+                }
+            }
+        }
+
         fn render_top1_monotonic<G>(
             collection: Collection<G, Row, Diff>,
             group_key: Vec<usize>,
@@ -41,6 +41,7 @@ use mz_ore::cast::CastFrom;
 use mz_ore::halt;
 use mz_ore::metrics::MetricsRegistry;
 use mz_persist_client::cache::PersistClientCache;
+use mz_repr::spill::SpillConfig;
 use mz_service::client::{GenericClient, Partitioned};
 use mz_service::local::LocalClient;
 use tracing::{info, warn};
@@ -58,6 +59,8 @@ pub struct Config {
     pub metrics_registry: MetricsRegistry,
     /// `persist` client cache.
     pub persist_clients: Arc<PersistClientCache>,
+    /// Where and when to spill arrangement data to disk, if at all.
+    pub arrangement_spill: Option<SpillConfig>,
 }
 
 /// A client managing access to the local portion of a Timely cluster
@@ -103,6 +106,15 @@ pub fn serve(
     let trace_metrics = TraceMetrics::register_with(&config.metrics_registry);
     let compute_metrics = ComputeMetrics::register_with(&config.metrics_registry);
 
+    if let Some(spill_config) = config.arrangement_spill {
+        info!(
+            "spilling arrangement data to {} beyond {} bytes",
+            spill_config.directory.display(),
+            spill_config.memory_limit
+        );
+        mz_repr::spill::configure(spill_config);
+    }
+
     let tokio_executor = tokio::runtime::Handle::current();
     let timely_container = Arc::new(tokio::sync::Mutex::new(None));
 
@@ -554,8 +566,10 @@ impl<'w, A: Allocate> Worker<'w, A> {
 
             // Report frontier information back the coordinator.
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
+                compute_state.enforce_dataflow_record_limits();
                 compute_state.report_compute_frontiers();
                 compute_state.report_dropped_collections();
+                compute_state.report_arrangement_spill();
             }
 
             // Handle any received commands.
@@ -603,6 +617,8 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         std::cell::RefCell::new(Vec::new()),
                     ),
                     sink_write_frontiers: BTreeMap::new(),
+                    sink_writes_allowed: BTreeMap::new(),
+                    replacing_sinks: BTreeMap::new(),
                     flow_control_probes: BTreeMap::new(),
                     pending_peeks: BTreeMap::new(),
                     reported_frontiers: BTreeMap::new(),
@@ -612,6 +628,13 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     command_history: ComputeCommandHistory::default(),
                     max_result_size: u32::MAX,
                     metrics: self.compute_metrics.clone(),
+                    spill_stats: mz_repr::spill::thread_stats(),
+                    reported_spilled_bytes: None,
+                    max_dataflow_arrangement_records: None,
+                    arrangement_accounting: Default::default(),
+                    dataflow_exports: BTreeMap::new(),
+                    dataflow_record_limits: None,
+                    failed_exports: BTreeMap::new(),
                 });
             }
             _ => (),
@@ -759,7 +782,11 @@ impl<'w, A: Allocate> Worker<'w, A> {
                                     .sink_exports
                                     .iter()
                                     .all(|(_id, sink)| !sink.connection.is_subscribe());
-                                if compatible && uncompacted && subscribe_free {
+                                // Dataflows dropped for exceeding their limits must be rebuilt.
+                                let installed = !export_ids
+                                    .iter()
+                                    .any(|id| compute_state.failed_exports.contains_key(id));
+                                if compatible && uncompacted && subscribe_free && installed {
                                     // Match found; remove the match from the deletion queue,
                                     // and compact its outputs to the dataflow's `as_of`.
                                     old_dataflows.remove(&export_ids);
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -31,7 +31,8 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::HashMap;
 use mz_persist_client::batch::Batch;
 use mz_persist_client::cache::PersistClientCache;
-use mz_persist_client::write::WriterEnrichedHollowBatch;
+use mz_persist_client::write::{WriteHandle, WriterEnrichedHollowBatch};
+use mz_persist_client::ShardId;
 use mz_persist_types::codec_impls::UnitSchema;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
@@ -71,17 +72,24 @@ where
             &self.storage_metadata,
             desired_collection,
             sink.as_of.frontier.clone(),
+            self.read_only,
             compute_state,
             probes,
         )
     }
 }
 
+/// Renders a sink that writes `desired_collection` into the persist shard described by `target`.
+///
+/// A `read_only` sink does not write to the shard until it is allowed to, through an
+/// `AllowWrites` command. Until then, it reports the frontier of `desired_collection` as its
+/// write frontier, so observers can tell when the sink has caught up.
 pub(crate) fn persist_sink<G>(
     sink_id: GlobalId,
     target: &CollectionMetadata,
     desired_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     as_of: Antichain<Timestamp>,
+    read_only: bool,
     compute_state: &mut ComputeState,
     probes: Vec<probe::Handle<Timestamp>>,
 ) -> Option<Rc<dyn Any>>
@@ -118,6 +126,7 @@ where
             desired_collection,
             persist_collection,
             as_of,
+            read_only,
             compute_state,
             probes,
         ),
@@ -152,6 +161,7 @@ fn install_desired_into_persist<G>(
     desired_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     persist_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     as_of: Antichain<Timestamp>,
+    read_only: bool,
     compute_state: &mut crate::compute_state::ComputeState,
     probes: Vec<probe::Handle<Timestamp>>,
 ) -> Option<Rc<dyn Any>>
@@ -190,6 +200,7 @@ where
         &desired_collection.inner,
         &persist_feedback_stream,
         as_of,
+        read_only,
         Arc::clone(&persist_clients),
         compute_state,
     );
@@ -233,6 +244,10 @@ where
 ///
 /// This also keeps the shared frontier that is stored in `compute_state` in
 /// sync with the upper of the persist shard.
+///
+/// If the sink is `read_only`, no batch descriptions are minted until the sink
+/// is allowed to write, and the shared frontier tracks the frontier of
+/// `desired_stream` instead.
 fn mint_batch_descriptions<G>(
     sink_id: GlobalId,
     operator_name: String,
@@ -240,6 +255,7 @@ fn mint_batch_descriptions<G>(
     desired_stream: &Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>,
     persist_feedback_stream: &Stream<G, ()>,
     as_of: Antichain<Timestamp>,
+    read_only: bool,
     persist_clients: Arc<PersistClientCache>,
     compute_state: &mut crate::compute_state::ComputeState,
 ) -> (
@@ -279,6 +295,13 @@ where
         .sink_write_frontiers
         .insert(sink_id, Rc::clone(&shared_frontier));
 
+    let writes_allowed = Rc::new(Cell::new(!read_only));
+    if read_only {
+        compute_state
+            .sink_writes_allowed
+            .insert(sink_id, Rc::clone(&writes_allowed));
+    }
+
     let mut mint_op =
         AsyncOperatorBuilder::new(format!("{} mint_batch_descriptions", operator_name), scope);
 
@@ -312,41 +335,20 @@ where
             .await
             .expect("could not open persist shard");
 
-        let mut current_persist_frontier = write.upper().clone();
-
-        // Advance the persist shard's upper to at least our write lower
-        // bound.
-        if PartialOrder::less_than(&current_persist_frontier, &write_lower_bound) {
-            if sink_id.is_user() {
-                trace!(
-                    "persist_sink {sink_id}/{shard_id}: \
-                        advancing to write_lower_bound: {:?}",
-                    write_lower_bound
-                );
-            }
-
-            let empty_updates: &[((SourceData, ()), Timestamp, Diff)] = &[];
-            // It's fine if we don't succeed here. This just means that
-            // someone else already advanced the persist frontier further,
-            // which is great!
-            let res = write
-                .append(
-                    empty_updates,
-                    current_persist_frontier.clone(),
-                    write_lower_bound.clone(),
-                )
-                .await
-                .expect("invalid usage");
-
-            if sink_id.is_user() {
-                trace!(
-                    "persist_sink {sink_id}/{shard_id}: \
-                        advancing to write_lower_bound result: {:?}",
-                    res
-                );
-            }
-
-            current_persist_frontier.clone_from(&write_lower_bound);
+        // A read-only sink must not touch the persist shard, so it only
+        // advances the upper once it is allowed to write.
+        let mut advanced_to_lower_bound = false;
+        if writes_allowed.get() {
+            let current_persist_frontier = write.upper().clone();
+            advance_to_lower_bound(
+                &mut write,
+                current_persist_frontier,
+                &write_lower_bound,
+                sink_id,
+                shard_id,
+            )
+            .await;
+            advanced_to_lower_bound = true;
         }
 
         // The current input frontiers.
@@ -391,6 +393,29 @@ where
                 }
             };
 
+            if !writes_allowed.get() {
+                // Report how far the sink has computed its output, so the
+                // controller can tell when it has caught up with the sink
+                // that is currently writing to the shard.
+                if PartialOrder::less_than(&*shared_frontier.borrow(), &desired_frontier) {
+                    shared_frontier.borrow_mut().clone_from(&desired_frontier);
+                }
+                continue;
+            }
+
+            if !advanced_to_lower_bound {
+                let current_persist_frontier = write.fetch_recent_upper().await.clone();
+                advance_to_lower_bound(
+                    &mut write,
+                    current_persist_frontier,
+                    &write_lower_bound,
+                    sink_id,
+                    shard_id,
+                )
+                .await;
+                advanced_to_lower_bound = true;
+            }
+
             if PartialOrder::less_than(&*shared_frontier.borrow(), &persist_frontier) {
                 if sink_id.is_user() {
                     trace!(
@@ -493,6 +518,49 @@ where
     (output_stream, token)
 }
 
+/// Advances the upper of the persist shard written by `write` from
+/// `current_persist_frontier` to at least `write_lower_bound`.
+async fn advance_to_lower_bound(
+    write: &mut WriteHandle<SourceData, (), Timestamp, Diff>,
+    current_persist_frontier: Antichain<Timestamp>,
+    write_lower_bound: &Antichain<Timestamp>,
+    sink_id: GlobalId,
+    shard_id: ShardId,
+) {
+    if !PartialOrder::less_than(&current_persist_frontier, write_lower_bound) {
+        return;
+    }
+
+    if sink_id.is_user() {
+        trace!(
+            "persist_sink {sink_id}/{shard_id}: \
+                advancing to write_lower_bound: {:?}",
+            write_lower_bound
+        );
+    }
+
+    let empty_updates: &[((SourceData, ()), Timestamp, Diff)] = &[];
+    // It's fine if we don't succeed here. This just means that
+    // someone else already advanced the persist frontier further,
+    // which is great!
+    let res = write
+        .append(
+            empty_updates,
+            current_persist_frontier,
+            write_lower_bound.clone(),
+        )
+        .await
+        .expect("invalid usage");
+
+    if sink_id.is_user() {
+        trace!(
+            "persist_sink {sink_id}/{shard_id}: \
+                advancing to write_lower_bound result: {:?}",
+            res
+        );
+    }
+}
+
 /// Writes `desired_stream - persist_stream` to persist, but only for updates
 /// that fall into batch a description that we get via `batch_descriptions`.
 /// This forwards a `HollowBatch` for any batch of updates that was written.
@@ -78,6 +78,11 @@ pub struct ReplicaAllocation {
     pub scale: u16,
     /// The number of worker threads in the replica.
     pub workers: usize,
+    /// The number of bytes of arrangement data each process in the replica
+    /// keeps in memory before spilling it to disk, if the controller is
+    /// configured with a spill directory. If `None`, the replica never spills.
+    #[serde(default)]
+    pub arrangement_memory_limit: Option<MemoryLimit>,
 }
 
 /// Configures the location of a cluster replica.
@@ -99,6 +104,14 @@ impl ReplicaLocation {
         }
     }
 
+    /// Returns the size specified by this replica location, if any.
+    pub fn size(&self) -> Option<&str> {
+        match self {
+            ReplicaLocation::Unmanaged(_) => None,
+            ReplicaLocation::Managed(m) => Some(&m.size),
+        }
+    }
+
     /// Returns the number of processes specified by this replica location.
     pub fn num_processes(&self) -> usize {
         match self {
@@ -283,7 +296,8 @@ where
             if let Some(jh) = metrics_task_join_handle {
                 self.metrics_tasks.insert(replica_id, jh);
             }
-            self.storage.connect_replica(cluster_id, storage_addr);
+            self.storage
+                .connect_replica(cluster_id, replica_id, storage_addr);
             self.active_compute().add_replica_to_instance(
                 cluster_id,
                 replica_id,
@@ -308,8 +322,7 @@ where
         self.deprovision_replica(cluster_id, replica_id).await?;
         self.metrics_tasks.remove(&replica_id);
 
-        // Storage does not support active-active replication and so does not
-        // have an API for dropping replicas.
+        self.storage.drop_replica(cluster_id, replica_id);
         self.active_compute().drop_replica(cluster_id, replica_id)?;
         Ok(())
     }
@@ -400,7 +413,7 @@ where
                     image: self.clusterd_image.clone(),
                     init_container_image: self.init_container_image.clone(),
                     args: &|assigned| {
-                        vec![
+                        let mut args = vec![
                             format!("--storage-workers={}", location.allocation.workers),
                             format!(
                                 "--storage-controller-listen-addr={}",
@@ -413,7 +426,18 @@ where
                             format!("--internal-http-listen-addr={}", assigned["internal-http"]),
                             format!("--opentelemetry-resource=cluster_id={}", cluster_id),
                             format!("--opentelemetry-resource=replica_id={}", replica_id),
-                        ]
+                        ];
+                        if let (Some(directory), Some(MemoryLimit(limit))) = (
+                            &self.arrangement_spill_directory,
+                            location.allocation.arrangement_memory_limit,
+                        ) {
+                            args.push(format!(
+                                "--arrangement-spill-directory={}",
+                                directory.display()
+                            ));
+                            args.push(format!("--arrangement-memory-limit-bytes={}", limit.0));
+                        }
+                        args
                     },
                     ports: vec![
                         ServicePort {
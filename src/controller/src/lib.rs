@@ -92,6 +92,7 @@
 use std::collections::BTreeMap;
 use std::mem;
 use std::num::NonZeroI64;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -148,6 +149,9 @@ pub struct ControllerConfig {
     pub clusterd_image: String,
     /// The init container image to use for clusterd.
     pub init_container_image: Option<String>,
+    /// The directory in which cluster replicas spill arrangement data, if
+    /// their size has an arrangement memory limit.
+    pub arrangement_spill_directory: Option<PathBuf>,
     /// The now function to advance the controller's introspection collections.
     pub now: NowFn,
     /// The postgres stash factory.
@@ -221,6 +225,8 @@ pub struct Controller<T = mz_repr::Timestamp> {
     clusterd_image: String,
     /// The init container image to use for clusterd.
     init_container_image: Option<String>,
+    /// The directory in which cluster replicas spill arrangement data.
+    arrangement_spill_directory: Option<PathBuf>,
     /// The cluster orchestrator.
     orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// Tracks the readiness of the underlying controllers.
@@ -362,6 +368,7 @@ where
             compute: compute_controller,
             clusterd_image: config.clusterd_image,
             init_container_image: config.init_container_image,
+            arrangement_spill_directory: config.arrangement_spill_directory,
             orchestrator: config.orchestrator.namespace("cluster"),
             readiness: Readiness::NotReady,
             metrics_tasks: BTreeMap::new(),
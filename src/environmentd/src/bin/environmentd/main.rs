@@ -416,6 +416,15 @@ pub struct Args {
         requires = "bootstrap-default-cluster-replica-size"
     )]
     cluster_replica_sizes: Option<String>,
+    /// A directory, local to each cluster replica process, in which replicas
+    /// whose size specifies an `arrangement_memory_limit` spill arrangement
+    /// data that exceeds the limit.
+    #[clap(
+        long,
+        env = "CLUSTER_REPLICA_ARRANGEMENT_SPILL_DIRECTORY",
+        value_name = "PATH"
+    )]
+    cluster_replica_arrangement_spill_directory: Option<PathBuf>,
     /// The size of the default cluster replica if bootstrapping.
     #[clap(
         long,
@@ -725,6 +734,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         storage_stash_url: args.storage_stash_url,
         clusterd_image: args.clusterd_image.expect("clap enforced"),
         init_container_image: args.orchestrator_kubernetes_init_container_image,
+        arrangement_spill_directory: args.cluster_replica_arrangement_spill_directory,
         now: SYSTEM_TIME.clone(),
         postgres_factory: StashFactory::new(&metrics_registry),
         metrics_registry: metrics_registry.clone(),
@@ -726,6 +726,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedViews { .. }
         | ExecuteResponse::CreatedMaterializedView { .. }
         | ExecuteResponse::CreatedType
+        | ExecuteResponse::CreatedSavepoint
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
         | ExecuteResponse::DiscardedAll
@@ -746,6 +747,8 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::EmptyQuery
         | ExecuteResponse::Inserted(_)
         | ExecuteResponse::Raised
+        | ExecuteResponse::ReleasedSavepoint
+        | ExecuteResponse::RolledBackToSavepoint
         | ExecuteResponse::SetVariable { .. }
         | ExecuteResponse::StartedTransaction { .. }
         | ExecuteResponse::TransactionCommitted
@@ -323,6 +323,7 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
             orchestrator,
             clusterd_image: "clusterd".into(),
             init_container_image: None,
+            arrangement_spill_directory: None,
             persist_location: PersistLocation {
                 blob_uri: format!("file://{}/persist/blob", data_directory.display()),
                 consensus_uri,
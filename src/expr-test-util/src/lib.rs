@@ -194,6 +194,11 @@ impl<'a> TestCatalog {
         self.objects.get(name)
     }
 
+    /// Looks up the id of the object named `name`.
+    pub fn get_source_id(&'a self, name: &str) -> Option<GlobalId> {
+        self.get(name).map(|(id, _)| *id)
+    }
+
     /// Looks up the name of the object referred to as `id`.
     pub fn get_source_name(&'a self, id: &GlobalId) -> Option<&'a String> {
         self.names.get(id)
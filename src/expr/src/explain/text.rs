@@ -21,7 +21,7 @@ use mz_repr::{GlobalId, Row};
 
 use super::{ExplainMultiPlan, ExplainSinglePlan};
 use crate::{
-    AggregateExpr, Id, JoinImplementation, JoinInputCharacteristics, MapFilterProject,
+    AggregateExpr, Id, JoinHints, JoinImplementation, JoinInputCharacteristics, MapFilterProject,
     MirRelationExpr, MirScalarExpr, RowSetFinishing,
 };
 
@@ -372,6 +372,7 @@ impl MirRelationExpr {
                     implementation @ (JoinImplementation::Differential(..)
                     | JoinImplementation::DeltaQuery(..)
                     | JoinImplementation::Unimplemented),
+                hints,
             } => {
                 let has_equivalences = !equivalences.is_empty();
                 let equivalences = separated(
@@ -393,6 +394,9 @@ impl MirRelationExpr {
                 if let Some(name) = implementation.name() {
                     write!(f, " type={}", name)?;
                 }
+                if !hints.is_empty() {
+                    Self::fmt_join_hints(f, ctx, hints)?;
+                }
                 self.fmt_attributes(f, ctx)?;
 
                 if ctx.config.join_impls {
@@ -557,6 +561,7 @@ impl MirRelationExpr {
                 offset,
                 monotonic,
                 input,
+                expected_group_size,
             } => {
                 FmtNode {
                     fmt_root: |f, ctx| {
@@ -576,6 +581,9 @@ impl MirRelationExpr {
                             write!(f, " offset={}", offset)?
                         }
                         write!(f, " monotonic={}", monotonic)?;
+                        if let Some(expected_group_size) = expected_group_size {
+                            write!(f, " exp_group_size={}", expected_group_size)?;
+                        }
                         self.fmt_attributes(f, ctx)
                     },
                     fmt_children: |f, ctx| {
@@ -638,6 +646,31 @@ impl MirRelationExpr {
         Ok(())
     }
 
+    fn fmt_join_hints(
+        f: &mut fmt::Formatter<'_>,
+        ctx: &PlanRenderingContext<'_, MirRelationExpr>,
+        hints: &JoinHints,
+    ) -> fmt::Result {
+        let name = |id: &GlobalId| -> String {
+            ctx.humanizer
+                .humanize_id_unqualified(*id)
+                .unwrap_or_else(|| id.to_string())
+        };
+        let mut parts = vec![];
+        if let Some(algorithm) = &hints.algorithm {
+            parts.push(format!("algorithm={}", algorithm));
+        }
+        if !hints.order.is_empty() {
+            let order = separated(", ", hints.order.iter().map(name));
+            parts.push(format!("order=[{}]", order));
+        }
+        if !hints.indexes.is_empty() {
+            let indexes = separated(", ", hints.indexes.iter().map(|index| name(&index.id)));
+            parts.push(format!("indexes=[{}]", indexes));
+        }
+        write!(f, " hints=({})", separated(", ", parts))
+    }
+
     fn fmt_attributes(
         &self,
         f: &mut fmt::Formatter<'_>,
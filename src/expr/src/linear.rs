@@ -1742,6 +1742,14 @@ pub mod plan {
             }
         }
 
+        /// Returns the non-temporal portion of the plan.
+        ///
+        /// Every predicate of the returned plan must evaluate to true for a
+        /// row to be emitted, regardless of the temporal bounds.
+        pub fn nontemporal(&self) -> &SafeMfpPlan {
+            &self.mfp
+        }
+
         /// Returns an iterator over mutable references to all non-temporal
         /// scalar expressions in the plan.
         ///
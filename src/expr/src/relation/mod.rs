@@ -200,6 +200,9 @@ pub enum MirRelationExpr {
         /// Join implementation information.
         #[serde(default)]
         implementation: JoinImplementation,
+        /// User hints that constrain the join implementation.
+        #[serde(default)]
+        hints: JoinHints,
     },
     /// Group a dataflow by some columns and aggregate over each group
     ///
@@ -241,6 +244,9 @@ pub enum MirRelationExpr {
         /// True iff the input is known to monotonically increase (only addition of records).
         #[serde(default)]
         monotonic: bool,
+        /// User hint: expected number of values per group key. Used to optimize physical rendering.
+        #[serde(default)]
+        expected_group_size: Option<usize>,
     },
     /// Return a dataflow where the row counts are negated
     ///
@@ -1180,7 +1186,21 @@ impl MirRelationExpr {
             inputs,
             equivalences,
             implementation: JoinImplementation::Unimplemented,
+            hints: JoinHints::default(),
+        }
+    }
+
+    /// Attaches `hints` to the join operator `self`.
+    ///
+    /// Does nothing if `self` is not a join.
+    pub fn with_join_hints(mut self, hints: JoinHints) -> Self {
+        if let MirRelationExpr::Join {
+            hints: join_hints, ..
+        } = &mut self
+        {
+            *join_hints = hints;
         }
+        self
     }
 
     /// Perform a key-wise reduction / aggregation.
@@ -1215,6 +1235,7 @@ impl MirRelationExpr {
         order_key: Vec<ColumnOrder>,
         limit: Option<usize>,
         offset: usize,
+        expected_group_size: Option<usize>,
     ) -> Self {
         MirRelationExpr::TopK {
             input: Box::new(self),
@@ -1223,6 +1244,7 @@ impl MirRelationExpr {
             limit,
             offset,
             monotonic: false,
+            expected_group_size,
         }
     }
 
@@ -2508,6 +2530,97 @@ impl Default for JoinImplementation {
     }
 }
 
+/// User hints that constrain how a join is implemented.
+///
+/// Hints refer to the collections that the inputs of the join read, rather
+/// than to the positions of the inputs, so that they survive the fusion and
+/// reordering of join inputs during optimization.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct JoinHints {
+    /// The join algorithm to use, if the user requested one.
+    pub algorithm: Option<JoinAlgorithm>,
+    /// Collections in the order in which the join should visit the inputs that
+    /// read them. Inputs that read none of them are ordered after these. Each
+    /// collection must be read by at most one input.
+    pub order: Vec<GlobalId>,
+    /// Indexes that the join should use. A join uses no other indexes on the
+    /// collections that these indexes are on.
+    pub indexes: Vec<JoinIndexHint>,
+}
+
+impl JoinHints {
+    /// Reports whether no hints are present.
+    pub fn is_empty(&self) -> bool {
+        self.algorithm.is_none() && self.order.is_empty() && self.indexes.is_empty()
+    }
+
+    /// Adds the hints in `other` about which `self` says nothing.
+    pub fn merge(&mut self, other: JoinHints) {
+        if self.algorithm.is_none() {
+            self.algorithm = other.algorithm;
+        }
+        if self.order.is_empty() {
+            self.order = other.order;
+        }
+        for index in other.indexes {
+            if !self.indexes.contains(&index) {
+                self.indexes.push(index);
+            }
+        }
+    }
+
+    /// Returns the keys of the hinted indexes on the collection `id`, if any
+    /// index on it is hinted.
+    pub fn index_keys(&self, id: GlobalId) -> Option<Vec<&[MirScalarExpr]>> {
+        let keys = self
+            .indexes
+            .iter()
+            .filter(|index| index.on_id == id)
+            .map(|index| index.key.as_slice())
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            None
+        } else {
+            Some(keys)
+        }
+    }
+}
+
+/// A join algorithm that the user requested through a hint.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub enum JoinAlgorithm {
+    /// A delta join, see [`JoinImplementation::DeltaQuery`].
+    Delta,
+    /// A differential join, see [`JoinImplementation::Differential`].
+    Differential,
+}
+
+impl fmt::Display for JoinAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinAlgorithm::Delta => f.write_str("delta"),
+            JoinAlgorithm::Differential => f.write_str("differential"),
+        }
+    }
+}
+
+/// An index that a join should use, according to a hint.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect)]
+pub struct JoinIndexHint {
+    /// The ID of the index.
+    pub id: GlobalId,
+    /// The name of the index, as the user wrote it.
+    pub name: String,
+    /// The ID of the collection that the index is on.
+    pub on_id: GlobalId,
+    /// The key of the index.
+    pub key: Vec<MirScalarExpr>,
+}
+
 impl JoinImplementation {
     /// Returns `true` iff the value is not [`JoinImplementation::Unimplemented`].
     pub fn is_implemented(&self) -> bool {
@@ -2532,13 +2645,17 @@ impl JoinImplementation {
 ///
 /// A candidate is described by a collection and a key, and may have various liabilities.
 /// Primarily, the candidate may risk substantial inflation of records, which is something
-/// that concerns us greatly. Additionally the candidate may be unarranged, and we would
-/// prefer candidates that do not require additional memory. Finally, we prefer lower id
-/// collections in the interest of consistent tie-breaking.
+/// that concerns us greatly. When statistics about the collections are available, we
+/// estimate this inflation directly. Additionally the candidate may be unarranged, and we
+/// would prefer candidates that do not require additional memory. Finally, we prefer lower
+/// id collections in the interest of consistent tie-breaking.
 #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Serialize, Deserialize, Hash, MzReflect)]
 pub struct JoinInputCharacteristics {
     /// An excellent indication that record count will not increase.
     pub unique_key: bool,
+    /// An estimate of the number of records of the candidate that each record joined
+    /// with it matches, present only if there are statistics about all inputs of the join.
+    pub fanout: Option<std::cmp::Reverse<usize>>,
     /// A weaker signal that record count will not increase.
     pub key_length: usize,
     /// Indicates that there will be no additional in-memory footprint.
@@ -2553,6 +2670,7 @@ impl JoinInputCharacteristics {
     /// Creates a new instance with the given characteristics.
     pub fn new(
         unique_key: bool,
+        fanout: Option<usize>,
         key_length: usize,
         arranged: bool,
         filters: FilterCharacteristics,
@@ -2560,6 +2678,7 @@ impl JoinInputCharacteristics {
     ) -> Self {
         Self {
             unique_key,
+            fanout: fanout.map(std::cmp::Reverse),
             key_length,
             arranged,
             filters,
@@ -2580,6 +2699,9 @@ impl JoinInputCharacteristics {
             e.push_str("A");
         }
         e.push_str(&self.filters.explain());
+        if let Some(std::cmp::Reverse(fanout)) = self.fanout {
+            e.push_str(&format!("~{}", fanout));
+        }
         e
     }
 }
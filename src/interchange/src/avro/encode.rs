@@ -82,7 +82,7 @@ static DEBEZIUM_TRANSACTION_SCHEMA: Lazy<Schema> = Lazy::new(|| {
     .expect("valid schema constructed")
 });
 
-fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
+pub(crate) fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
     // The first byte is a magic byte (0) that indicates the Confluent
     // serialization format version, and the next four bytes are a
     // 32-bit schema ID.
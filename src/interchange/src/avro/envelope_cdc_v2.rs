@@ -10,6 +10,8 @@
 //! Logic for the Avro representation of the CDCv2 protocol.
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::rc::Rc;
 
 use anyhow::anyhow;
@@ -18,15 +20,18 @@ use serde_json::json;
 
 use mz_avro::error::{DecodeError, Error as AvroError};
 use mz_avro::schema::{FullName, Schema, SchemaNode};
-use mz_avro::types::Scalar;
+use mz_avro::types::{Scalar, Value};
 use mz_avro::{
     define_unexpected, ArrayAsVecDecoder, AvroDecodable, AvroDecode, AvroDeserializer, AvroRead,
     StatefulAvroDecodable,
 };
 use mz_avro_derive::AvroDecodable;
-use mz_repr::{Diff, Row, Timestamp};
+use mz_repr::{ColumnName, ColumnType, Datum, Diff, RelationDesc, Row, Timestamp};
 
 use super::decode::RowWrapper;
+use super::encode::{encode_avro_header, encode_datums_as_avro};
+use crate::encode::{column_names_and_types, Encode};
+use crate::json::build_row_schema_json;
 
 pub fn extract_data_columns<'a>(schema: &'a Schema) -> anyhow::Result<SchemaNode<'a>> {
     let data_name = FullName::from_parts("data", Some("com.materialize.cdc"), "");
@@ -226,88 +231,149 @@ impl mz_avro::AvroDecode for TimestampDecoder {
     }
 }
 
+/// Returns the writer schema of CDCv2 messages whose updates are to rows with
+/// the given columns.
+pub fn writer_schema(columns: &[(ColumnName, ColumnType)]) -> Result<Schema, anyhow::Error> {
+    let row_schema = build_row_schema_json(columns, "data", &BTreeMap::new())?;
+    Ok(build_schema(row_schema))
+}
+
+/// Encodes update batches and progress statements as Avro, in the Confluent
+/// wire format.
+///
+/// As an [`Encode`] implementation, it encodes each value [`Row`] as a batch
+/// containing a single update. The row must contain the columns of the
+/// updated row, followed by the update's time (an `mz_timestamp`) and diff
+/// (an `int8`).
+pub struct Encoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    schema: Schema,
+    schema_id: i32,
+}
+
+impl fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Encoder")
+            .field("writer_schema", &self.schema)
+            .finish()
+    }
+}
+
+impl Encoder {
+    /// Creates a new CDCv2 encoder for updates to the relation described by
+    /// `desc`, whose writer schema is registered under `schema_id`.
+    pub fn new(desc: RelationDesc, schema_id: i32) -> Result<Self, anyhow::Error> {
+        let columns = column_names_and_types(desc);
+        let schema = writer_schema(&columns)?;
+        Ok(Encoder {
+            columns,
+            schema,
+            schema_id,
+        })
+    }
+
+    /// Encodes a batch of updates.
+    pub fn encode_updates(&self, updates: &[(Row, Timestamp, Diff)]) -> Vec<u8> {
+        let updates = updates
+            .iter()
+            .map(|(data, time, diff)| self.update_value(data.iter(), *time, *diff))
+            .collect();
+        self.encode_message(0, Value::Array(updates))
+    }
+
+    /// Encodes a progress statement, which attests that all updates at times
+    /// in `[lower, upper)` have been sent, and that `counts` records how many
+    /// updates there were at each of those times.
+    pub fn encode_progress(
+        &self,
+        lower: &[Timestamp],
+        upper: &[Timestamp],
+        counts: &[(Timestamp, usize)],
+    ) -> Vec<u8> {
+        let counts = counts
+            .iter()
+            .map(|(time, count)| {
+                Value::Record(vec![
+                    ("time".to_string(), timestamp_value(*time)),
+                    (
+                        "count".to_string(),
+                        Value::Long(i64::try_from(*count).expect("count fits in i64")),
+                    ),
+                ])
+            })
+            .collect();
+        let progress = Value::Record(vec![
+            ("lower".to_string(), timestamp_array_value(lower)),
+            ("upper".to_string(), timestamp_array_value(upper)),
+            ("counts".to_string(), Value::Array(counts)),
+        ]);
+        self.encode_message(1, progress)
+    }
+
+    fn update_value<'a, I>(&self, data: I, time: Timestamp, diff: Diff) -> Value
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        Value::Record(vec![
+            (
+                "data".to_string(),
+                encode_datums_as_avro(data, &self.columns),
+            ),
+            ("time".to_string(), timestamp_value(time)),
+            ("diff".to_string(), Value::Long(diff)),
+        ])
+    }
+
+    fn encode_message(&self, index: usize, inner: Value) -> Vec<u8> {
+        let value = Value::Union {
+            index,
+            inner: Box::new(inner),
+            n_variants: 2,
+            null_variant: None,
+        };
+        let mut buf = vec![];
+        encode_avro_header(&mut buf, self.schema_id);
+        mz_avro::encode_unchecked(&value, &self.schema, &mut buf);
+        buf
+    }
+}
+
+impl Encode for Encoder {
+    fn get_format_name(&self) -> &str {
+        "avro-cdcv2"
+    }
+
+    fn encode_key_unchecked(&self, _: Row) -> Vec<u8> {
+        unreachable!("CDCv2 messages do not have keys")
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        let datums: Vec<_> = row.iter().collect();
+        let (data, update) = datums.split_at(datums.len() - 2);
+        let time = update[0].unwrap_mz_timestamp();
+        let diff = update[1].unwrap_int64();
+        let update = self.update_value(data.iter().cloned(), time, diff);
+        self.encode_message(0, Value::Array(vec![update]))
+    }
+}
+
+fn timestamp_value(time: Timestamp) -> Value {
+    Value::Long(i64::try_from(time).expect("timestamp fits in i64"))
+}
+
+fn timestamp_array_value(times: &[Timestamp]) -> Value {
+    Value::Array(times.iter().map(|time| timestamp_value(*time)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
-    use mz_avro::types::Value;
     use mz_avro::AvroDeserializer;
     use mz_avro::GeneralDeserializer;
-    use mz_repr::{ColumnName, ColumnType, RelationDesc, Row, ScalarType};
+    use mz_repr::ScalarType;
 
     use super::*;
-    use crate::avro::encode_datums_as_avro;
-    use crate::encode::column_names_and_types;
-    use crate::json::build_row_schema_json;
-
-    /// Collected state to encode update batches and progress statements.
-    #[derive(Debug)]
-    struct Encoder {
-        columns: Vec<(ColumnName, ColumnType)>,
-    }
-
-    impl Encoder {
-        /// Creates a new CDCv2 encoder from a relation description.
-        pub fn new(desc: RelationDesc) -> Self {
-            let columns = column_names_and_types(desc);
-            Self { columns }
-        }
-
-        /// Encodes a batch of updates as an Avro value.
-        pub fn encode_updates(&self, updates: &[(Row, i64, i64)]) -> Value {
-            let mut enc_updates = Vec::new();
-            for (data, time, diff) in updates {
-                let enc_data = encode_datums_as_avro(&**data, &self.columns);
-                let enc_time = Value::Long(time.clone());
-                let enc_diff = Value::Long(diff.clone());
-                enc_updates.push(Value::Record(vec![
-                    ("data".to_string(), enc_data),
-                    ("time".to_string(), enc_time),
-                    ("diff".to_string(), enc_diff),
-                ]));
-            }
-            Value::Union {
-                index: 0,
-                inner: Box::new(Value::Array(enc_updates)),
-                n_variants: 2,
-                null_variant: None,
-            }
-        }
-
-        /// Encodes the contents of a progress statement as an Avro value.
-        pub fn encode_progress(
-            &self,
-            lower: &[i64],
-            upper: &[i64],
-            counts: &[(i64, i64)],
-        ) -> Value {
-            let enc_lower = Value::Array(lower.iter().cloned().map(Value::Long).collect());
-            let enc_upper = Value::Array(upper.iter().cloned().map(Value::Long).collect());
-            let enc_counts = Value::Array(
-                counts
-                    .iter()
-                    .map(|(time, count)| {
-                        Value::Record(vec![
-                            ("time".to_string(), Value::Long(time.clone())),
-                            ("count".to_string(), Value::Long(count.clone())),
-                        ])
-                    })
-                    .collect(),
-            );
-            let enc_progress = Value::Record(vec![
-                ("lower".to_string(), enc_lower),
-                ("upper".to_string(), enc_upper),
-                ("counts".to_string(), enc_counts),
-            ]);
-
-            Value::Union {
-                index: 1,
-                inner: Box::new(enc_progress),
-                n_variants: 2,
-                null_variant: None,
-            }
-        }
-    }
 
     #[test]
     fn test_roundtrip() {
@@ -315,25 +381,18 @@ mod tests {
             .with_column("id", ScalarType::Int64.nullable(false))
             .with_column("price", ScalarType::Float64.nullable(true));
 
-        let encoder = Encoder::new(desc.clone());
-        let row_schema = build_row_schema_json(
-            &crate::encode::column_names_and_types(desc),
-            "data",
-            &BTreeMap::new(),
-        )
-        .unwrap();
-        let schema = build_schema(row_schema);
+        let encoder = Encoder::new(desc.clone(), 1).unwrap();
+        let schema = writer_schema(&column_names_and_types(desc)).unwrap();
 
-        let values = vec![
+        // Strip the Confluent wire format header.
+        let mut values: Vec<_> = vec![
             encoder.encode_updates(&[]),
-            encoder.encode_progress(&[0], &[3], &[]),
-            encoder.encode_progress(&[3], &[], &[]),
-        ];
-        use mz_avro::encode::encode_to_vec;
-        let mut values: Vec<_> = values
-            .into_iter()
-            .map(|v| encode_to_vec(&v, &schema))
-            .collect();
+            encoder.encode_progress(&[Timestamp::new(0)], &[Timestamp::new(3)], &[]),
+            encoder.encode_progress(&[Timestamp::new(3)], &[], &[]),
+        ]
+        .into_iter()
+        .map(|v| v[5..].to_vec())
+        .collect();
 
         let g = GeneralDeserializer {
             schema: schema.top_node(),
@@ -351,4 +410,33 @@ mod tests {
             Message::Progress(_)
         ),);
     }
+
+    #[test]
+    fn test_roundtrip_update() {
+        let desc = RelationDesc::empty()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true));
+
+        let encoder = Encoder::new(desc.clone(), 1).unwrap();
+        let schema = writer_schema(&column_names_and_types(desc)).unwrap();
+
+        let data = Row::pack_slice(&[Datum::Int64(1), Datum::String("a")]);
+        let row = Row::pack_slice(&[
+            Datum::Int64(1),
+            Datum::String("a"),
+            Datum::MzTimestamp(Timestamp::new(7)),
+            Datum::Int64(-1),
+        ]);
+        let value = encoder.encode_value_unchecked(row);
+
+        let g = GeneralDeserializer {
+            schema: schema.top_node(),
+        };
+        match g.deserialize(&mut &value[5..], Decoder).unwrap() {
+            Message::Updates(updates) => {
+                assert_eq!(updates, vec![(data, Timestamp::new(7), -1)]);
+            }
+            Message::Progress(_) => panic!("expected updates"),
+        }
+    }
 }
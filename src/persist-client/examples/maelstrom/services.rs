@@ -182,7 +182,7 @@ impl MaelstromBlob {
 
 #[async_trait]
 impl Blob for MaelstromBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         let value = match self
             .handle
             .lin_kv_read(Value::from(format!("blob/{}", key)))
@@ -195,9 +195,9 @@ impl Blob for MaelstromBlob {
         let value = value
             .as_str()
             .ok_or_else(|| anyhow!("invalid blob at {}: {:?}", key, value))?;
-        let value = serde_json::from_str(value)
+        let value: Vec<u8> = serde_json::from_str(value)
             .map_err(|err| anyhow!("invalid blob at {}: {}", key, err))?;
-        Ok(Some(value))
+        Ok(Some(Bytes::from(value)))
     }
 
     async fn list_keys_and_metadata(
@@ -238,7 +238,7 @@ impl Blob for MaelstromBlob {
 #[derive(Debug)]
 pub struct CachingBlob {
     blob: Arc<dyn Blob + Send + Sync>,
-    cache: Mutex<BTreeMap<String, Vec<u8>>>,
+    cache: Mutex<BTreeMap<String, Bytes>>,
 }
 
 impl CachingBlob {
@@ -252,7 +252,7 @@ impl CachingBlob {
 
 #[async_trait]
 impl Blob for CachingBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         // Fetch the cached value if there is one.
         let cache = self.cache.lock().await;
         if let Some(value) = cache.get(key) {
@@ -30,6 +30,8 @@ use mz_ore::cast::CastFrom;
 use mz_persist::indexed::columnar::{ColumnarRecords, ColumnarRecordsBuilder};
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::{Atomicity, Blob};
+use mz_persist_types::columnar::Schema;
+use mz_persist_types::stats::{PartStats, StatsBuilder};
 use mz_persist_types::{Codec, Codec64};
 
 use crate::async_runtime::CpuHeavyRuntime;
@@ -198,7 +200,7 @@ where
     metrics: Arc<Metrics>,
     consolidate: bool,
 
-    buffer: BatchBuffer<D>,
+    buffer: BatchBuffer<K, D>,
 
     max_kvt_in_run: Option<(Vec<u8>, Vec<u8>, Vec<u8>)>,
     runs: Vec<usize>,
@@ -231,6 +233,7 @@ where
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         shard_id: ShardId,
         writer_id: WriterId,
+        key_schema: Arc<K::Schema>,
         since: Antichain<T>,
         inline_upper: Option<Antichain<T>>,
         consolidate: bool,
@@ -254,6 +257,7 @@ where
                 batch_write_metrics,
                 cfg.blob_target_size,
                 consolidate,
+                cfg.dynamic.stats_collection_enabled().then_some(key_schema),
             ),
             metrics,
             consolidate,
@@ -310,8 +314,8 @@ where
             });
         }
 
-        let remainder = self.buffer.drain();
-        self.flush_part(remainder).await;
+        let (remainder, stats) = self.buffer.drain();
+        self.flush_part(remainder, stats).await;
 
         let parts = self.parts.finish().await;
 
@@ -351,8 +355,8 @@ where
         self.max_ts.join_assign(ts);
 
         match self.buffer.push(key, val, ts, diff.clone()) {
-            Some(part_to_flush) => {
-                self.flush_part(part_to_flush).await;
+            Some((part_to_flush, stats)) => {
+                self.flush_part(part_to_flush, stats).await;
                 Ok(Added::RecordAndParts)
             }
             None => Ok(Added::Record),
@@ -363,7 +367,7 @@ where
     /// the updates. It is the caller's responsibility to chunk `current_part` to be no greater
     /// than [crate::PersistConfig::blob_target_size], and must absolutely be less than
     /// [mz_persist::indexed::columnar::KEY_VAL_DATA_MAX_LEN]
-    async fn flush_part(&mut self, columnar: ColumnarRecords) {
+    async fn flush_part(&mut self, columnar: ColumnarRecords, stats: Option<PartStats>) {
         let num_updates = columnar.len();
         if num_updates == 0 {
             return;
@@ -412,7 +416,12 @@ where
 
         let start = Instant::now();
         self.parts
-            .write(columnar, self.inline_upper.clone(), self.since.clone())
+            .write(
+                columnar,
+                stats,
+                self.inline_upper.clone(),
+                self.since.clone(),
+            )
             .await;
         self.metrics
             .compaction
@@ -426,12 +435,19 @@ where
 }
 
 #[derive(Debug)]
-struct BatchBuffer<D> {
+struct BatchBuffer<K: Codec, D> {
     metrics: Arc<Metrics>,
     batch_write_metrics: BatchWriteMetrics,
     blob_target_size: usize,
     consolidate: bool,
 
+    // The schema used to compute key stats for each part, or None if stats
+    // collection is disabled. The stats are computed over the keys as they are
+    // pushed, before consolidation, so they're a (correct but possibly loose)
+    // bound on the contents of the part.
+    key_schema: Option<Arc<K::Schema>>,
+    key_stats: Option<Box<dyn StatsBuilder<K>>>,
+
     key_buf: Vec<u8>,
     val_buf: Vec<u8>,
 
@@ -441,8 +457,9 @@ struct BatchBuffer<D> {
     current_part_value_bytes: usize,
 }
 
-impl<D> BatchBuffer<D>
+impl<K, D> BatchBuffer<K, D>
 where
+    K: Codec,
     D: Semigroup + Codec64,
 {
     fn new(
@@ -450,12 +467,16 @@ where
         batch_write_metrics: BatchWriteMetrics,
         blob_target_size: usize,
         should_consolidate: bool,
+        key_schema: Option<Arc<K::Schema>>,
     ) -> Self {
+        let key_stats = key_schema.as_ref().and_then(|x| x.stats_builder());
         BatchBuffer {
             metrics,
             batch_write_metrics,
             blob_target_size,
             consolidate: should_consolidate,
+            key_schema,
+            key_stats,
             key_buf: Default::default(),
             val_buf: Default::default(),
             current_part: Default::default(),
@@ -465,13 +486,13 @@ where
         }
     }
 
-    fn push<K: Codec, V: Codec, T: Codec64>(
+    fn push<V: Codec, T: Codec64>(
         &mut self,
         key: &K,
         val: &V,
         ts: &T,
         diff: D,
-    ) -> Option<ColumnarRecords> {
+    ) -> Option<(ColumnarRecords, Option<PartStats>)> {
         let initial_key_buf_len = self.key_buf.len();
         let initial_val_buf_len = self.val_buf.len();
         self.metrics
@@ -486,6 +507,9 @@ where
         let v_range = initial_val_buf_len..self.val_buf.len();
         let size = ColumnarRecordsBuilder::columnar_record_size(k_range.len(), v_range.len());
         let ts = T::encode(ts);
+        if let Some(key_stats) = self.key_stats.as_mut() {
+            key_stats.push(key);
+        }
 
         self.current_part_total_bytes += size;
         self.current_part_key_bytes += k_range.len();
@@ -500,7 +524,8 @@ where
         }
     }
 
-    fn drain(&mut self) -> ColumnarRecords {
+    fn drain(&mut self) -> (ColumnarRecords, Option<PartStats>) {
+        let stats = self.drain_stats();
         let mut updates = Vec::with_capacity(self.current_part.len());
         for ((k_range, v_range), t, d) in self.current_part.drain(..) {
             updates.push(((&self.key_buf[k_range], &self.val_buf[v_range]), t, d));
@@ -517,7 +542,7 @@ where
         if updates.is_empty() {
             self.key_buf.clear();
             self.val_buf.clear();
-            return ColumnarRecordsBuilder::default().finish();
+            return (ColumnarRecordsBuilder::default().finish(), None);
         }
 
         let start = Instant::now();
@@ -545,7 +570,17 @@ where
         self.current_part_value_bytes = 0;
         assert_eq!(self.current_part.len(), 0);
 
-        columnar
+        (columnar, stats)
+    }
+
+    /// Returns the stats for the keys pushed since the last drain (if stats
+    /// are being collected) and resets the stats builder for the next part.
+    fn drain_stats(&mut self) -> Option<PartStats> {
+        let next = self.key_schema.as_ref().and_then(|x| x.stats_builder());
+        let key_stats = std::mem::replace(&mut self.key_stats, next)?;
+        Some(PartStats {
+            key: key_stats.finish(),
+        })
     }
 }
 
@@ -560,7 +595,7 @@ pub(crate) struct BatchParts<T> {
     lower: Antichain<T>,
     blob: Arc<dyn Blob + Send + Sync>,
     cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
-    writing_parts: VecDeque<(PartialBatchKey, JoinHandle<usize>)>,
+    writing_parts: VecDeque<(PartialBatchKey, Option<PartStats>, JoinHandle<usize>)>,
     finished_parts: Vec<HollowBatchPart>,
     batch_metrics: BatchWriteMetrics,
 }
@@ -593,6 +628,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
     pub(crate) async fn write(
         &mut self,
         updates: ColumnarRecords,
+        stats: Option<PartStats>,
         upper: Antichain<T>,
         since: Antichain<T>,
     ) {
@@ -652,11 +688,11 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
             }
             .instrument(write_span),
         );
-        self.writing_parts.push_back((partial_key, handle));
+        self.writing_parts.push_back((partial_key, stats, handle));
 
         while self.writing_parts.len() > self.max_outstanding {
             batch_metrics.write_stalls.inc();
-            let (key, handle) = self
+            let (key, stats, handle) = self
                 .writing_parts
                 .pop_front()
                 .expect("pop failed when len was just > some usize");
@@ -671,6 +707,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
             self.finished_parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                stats,
             });
         }
     }
@@ -678,7 +715,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
     #[instrument(level = "debug", name = "batch::finish_upload", skip_all, fields(shard = %self.shard_id))]
     pub(crate) async fn finish(self) -> Vec<HollowBatchPart> {
         let mut parts = self.finished_parts;
-        for (key, handle) in self.writing_parts {
+        for (key, stats, handle) in self.writing_parts {
             let encoded_size_bytes = match handle.await {
                 Ok(x) => x,
                 Err(err) if err.is_cancelled() => 0,
@@ -687,6 +724,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
             parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                stats,
             });
         }
         parts
@@ -716,6 +754,7 @@ mod tests {
     use crate::internal::paths::{BlobKey, PartialBlobKey};
     use crate::tests::all_ok;
     use crate::PersistLocation;
+    use mz_persist_types::stats::PrimitiveValue;
 
     use super::*;
 
@@ -834,4 +873,54 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn batch_builder_stats() {
+        mz_ore::test::init_logging();
+
+        let mut cache = PersistClientCache::new_no_metrics();
+        // Set blob_target_size to 0 so that each row gets forced into its own batch part
+        cache.cfg.blob_target_size = 0;
+        let client = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed");
+        let (mut write, _) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+
+        let batch = write
+            .expect_batch(
+                &[
+                    (("1".into(), "one".into()), 1, 1),
+                    (("2".into(), "two".into()), 2, 1),
+                ],
+                0,
+                3,
+            )
+            .await;
+
+        // Each part gets stats for exactly the keys that were written to it.
+        let bounds = batch
+            .batch
+            .parts
+            .iter()
+            .map(|part| {
+                let stats = part.stats.as_ref().expect("stats should be collected");
+                let col = stats.key.col("").expect("string keys have stats");
+                (col.lower.clone(), col.upper.clone())
+            })
+            .collect::<Vec<_>>();
+        let expected = ["1", "2"]
+            .iter()
+            .map(|x| {
+                let x = Some(PrimitiveValue::String((*x).to_owned()));
+                (x.clone(), x)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(bounds, expected);
+    }
 }
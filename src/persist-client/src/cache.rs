@@ -24,6 +24,7 @@ use tokio::task::JoinHandle;
 use tracing::instrument;
 
 use crate::async_runtime::CpuHeavyRuntime;
+use crate::internal::blob_cache::BlobCache;
 use crate::internal::machine::retry_external;
 use crate::internal::metrics::{Metrics, MetricsBlob, MetricsConsensus};
 use crate::{PersistClient, PersistConfig, PersistLocation};
@@ -160,6 +161,9 @@ impl PersistClientCache {
                     Self::PROMETHEUS_SCRAPE_INTERVAL,
                 )
                 .await;
+                // Layer the cache outside of MetricsBlob, so that the blob
+                // metrics only reflect requests that actually go to blob.
+                let blob = BlobCache::open(&self.cfg, Arc::clone(&self.metrics), blob).await?;
                 Arc::clone(&x.insert((RttLatencyTask(task), blob)).1)
             }
         };
@@ -11,6 +11,9 @@
 
 //! The tunable knobs for persist.
 
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use mz_build_info::BuildInfo;
@@ -155,6 +158,19 @@ pub struct PersistConfig {
     pub critical_downgrade_interval: Duration,
     /// Hostname of this persist user. Stored in state and used for debugging.
     pub hostname: String,
+    /// Configurations that can be dynamically updated.
+    pub dynamic: Arc<DynamicConfig>,
+    /// The maximum total size of batch parts to keep cached in memory after
+    /// fetching them from blob. Zero disables the in-memory cache.
+    pub blob_cache_mem_limit_bytes: usize,
+    /// A local directory in which to cache batch parts after fetching them
+    /// from blob. The cache is kept in a subdirectory of it, which is cleared
+    /// on startup, so the directory must not be shared between processes.
+    /// `None` disables the disk cache.
+    pub blob_cache_disk_path: Option<PathBuf>,
+    /// The maximum total size of batch parts to keep cached in
+    /// `blob_cache_disk_path`.
+    pub blob_cache_disk_limit_bytes: usize,
 }
 
 impl PersistConfig {
@@ -190,6 +206,13 @@ impl PersistConfig {
             // options, where the first is always provided and the second is
             // conditionally enabled by the process orchestrator.
             hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_owned()),
+            dynamic: Arc::new(DynamicConfig {
+                stats_collection_enabled: AtomicBool::new(true),
+                stats_filter_enabled: AtomicBool::new(true),
+            }),
+            blob_cache_mem_limit_bytes: 0,
+            blob_cache_disk_path: None,
+            blob_cache_disk_limit_bytes: Self::DEFAULT_BLOB_CACHE_DISK_LIMIT_BYTES,
         }
     }
 
@@ -212,6 +235,8 @@ impl PersistConfig {
     pub const DEFAULT_BLOB_TARGET_SIZE: usize = 128 * MB;
     /// Default value for [`PersistConfig::compaction_minimum_timeout`].
     pub const DEFAULT_COMPACTION_MINIMUM_TIMEOUT: Duration = Duration::from_secs(90);
+    /// Default value for [`PersistConfig::blob_cache_disk_limit_bytes`].
+    pub const DEFAULT_BLOB_CACHE_DISK_LIMIT_BYTES: usize = 10 * 1024 * MB;
 
     // Move this to a PersistConfig field when we actually have read leases.
     //
@@ -237,6 +262,35 @@ impl ConsensusKnobs for PersistConfig {
     }
 }
 
+/// Persist configurations that can be dynamically updated.
+///
+/// Parameters can be updated by calling [PersistParameters::apply]. All
+/// clones of a [PersistConfig] share the same [DynamicConfig], so updates are
+/// visible to every client created from it.
+#[derive(Debug)]
+pub struct DynamicConfig {
+    stats_collection_enabled: AtomicBool,
+    stats_filter_enabled: AtomicBool,
+}
+
+impl DynamicConfig {
+    // TODO: Decide if we can relax these.
+    const LOAD_ORDERING: Ordering = Ordering::SeqCst;
+    const STORE_ORDERING: Ordering = Ordering::SeqCst;
+
+    /// Whether to compute and store statistics about the contents of each
+    /// batch part when it is written.
+    pub fn stats_collection_enabled(&self) -> bool {
+        self.stats_collection_enabled.load(Self::LOAD_ORDERING)
+    }
+
+    /// Whether to use per-part statistics to skip fetching parts that a reader
+    /// has indicated it doesn't need.
+    pub fn stats_filter_enabled(&self) -> bool {
+        self.stats_filter_enabled.load(Self::LOAD_ORDERING)
+    }
+}
+
 // TODO: Replace with dynamic values when PersistConfig is integrated with LD
 impl BlobKnobs for PersistConfig {
     fn operation_timeout(&self) -> Duration {
@@ -270,6 +324,10 @@ pub struct PersistParameters {
     pub blob_target_size: Option<usize>,
     /// Configures [`PersistConfig::compaction_minimum_timeout`].
     pub compaction_minimum_timeout: Option<Duration>,
+    /// Configures [`DynamicConfig::stats_collection_enabled`].
+    pub stats_collection_enabled: Option<bool>,
+    /// Configures [`DynamicConfig::stats_filter_enabled`].
+    pub stats_filter_enabled: Option<bool>,
 }
 
 impl PersistParameters {
@@ -281,11 +339,40 @@ impl PersistParameters {
         if let Some(v) = other.compaction_minimum_timeout {
             self.compaction_minimum_timeout = Some(v);
         }
+        if let Some(v) = other.stats_collection_enabled {
+            self.stats_collection_enabled = Some(v);
+        }
+        if let Some(v) = other.stats_filter_enabled {
+            self.stats_filter_enabled = Some(v);
+        }
     }
 
     /// Return whether all parameters are unset.
     pub fn all_unset(&self) -> bool {
-        self.blob_target_size.is_none() && self.compaction_minimum_timeout.is_none()
+        self.blob_target_size.is_none()
+            && self.compaction_minimum_timeout.is_none()
+            && self.stats_collection_enabled.is_none()
+            && self.stats_filter_enabled.is_none()
+    }
+
+    /// Applies the set parameter values to the dynamic parts of `cfg`.
+    ///
+    /// The values take effect for every client that shares `cfg`'s
+    /// [DynamicConfig], including those that are already open.
+    ///
+    /// TODO(#16753): `blob_target_size` and `compaction_minimum_timeout` are
+    /// not yet dynamic and are ignored here.
+    pub fn apply(&self, cfg: &PersistConfig) {
+        if let Some(v) = self.stats_collection_enabled {
+            cfg.dynamic
+                .stats_collection_enabled
+                .store(v, DynamicConfig::STORE_ORDERING);
+        }
+        if let Some(v) = self.stats_filter_enabled {
+            cfg.dynamic
+                .stats_filter_enabled
+                .store(v, DynamicConfig::STORE_ORDERING);
+        }
     }
 }
 
@@ -294,6 +381,8 @@ impl RustType<ProtoPersistParameters> for PersistParameters {
         ProtoPersistParameters {
             blob_target_size: self.blob_target_size.into_proto(),
             compaction_minimum_timeout: self.compaction_minimum_timeout.into_proto(),
+            stats_collection_enabled: self.stats_collection_enabled.into_proto(),
+            stats_filter_enabled: self.stats_filter_enabled.into_proto(),
         }
     }
 
@@ -301,6 +390,8 @@ impl RustType<ProtoPersistParameters> for PersistParameters {
         Ok(Self {
             blob_target_size: proto.blob_target_size.into_rust()?,
             compaction_minimum_timeout: proto.compaction_minimum_timeout.into_rust()?,
+            stats_collection_enabled: proto.stats_collection_enabled.into_rust()?,
+            stats_filter_enabled: proto.stats_filter_enabled.into_rust()?,
         })
     }
 }
@@ -10,9 +10,11 @@
 //! CLI introspection tools for persist
 
 use std::any::Any;
+use std::collections::BTreeSet;
+use std::ops::ControlFlow::{Break, Continue};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -23,16 +25,26 @@ use mz_persist::cfg::{BlobConfig, ConsensusConfig};
 use mz_persist::location::{
     Atomicity, Blob, BlobMetadata, Consensus, ExternalError, SeqNo, VersionedData,
 };
+use mz_persist::retry::Retry;
+use mz_persist_types::codec_impls::VecU8Schema;
+use mz_persist_types::Codec64;
 use prometheus::proto::{MetricFamily, MetricType};
+use timely::progress::Antichain;
+use timely::PartialOrder;
 use tracing::{info, warn};
 
 use crate::async_runtime::CpuHeavyRuntime;
-use crate::cli::inspect::StateArgs;
+use crate::batch::BatchBuilder;
+use crate::cli::inspect::{StateArgs, K, KVTD_CODECS, V};
+use crate::fetch::fetch_batch_part;
 use crate::internal::compact::{CompactReq, Compactor};
 use crate::internal::gc::{GarbageCollector, GcReq};
 use crate::internal::machine::Machine;
 use crate::internal::metrics::{MetricsBlob, MetricsConsensus};
-use crate::internal::trace::{ApplyMergeResult, FueledMergeRes};
+use crate::internal::paths::{BlobKey, PartialBlobKey, PartialRollupKey, RollupId};
+use crate::internal::state::{HollowBatch, NoOpStateTransition, State};
+use crate::internal::state_diff::StateDiff;
+use crate::internal::trace::{ApplyMergeResult, FueledMergeRes, Trace};
 use crate::write::WriterId;
 use crate::{Metrics, PersistConfig, ShardId, StateVersions, BUILD_INFO};
 
@@ -54,6 +66,12 @@ pub(crate) enum Command {
     ForceCompaction(ForceCompactionArgs),
     /// Manually kick off a GC run for a shard.
     ForceGc(ForceGcArgs),
+    /// Copies a consistent snapshot of a shard's state, along with every blob
+    /// it references, to another blob location.
+    Backup(BackupArgs),
+    /// Rehydrates a shard from a backup, optionally under a new shard id and
+    /// as of a chosen timestamp.
+    Restore(RestoreArgs),
 }
 
 /// Manually completes all fueled compactions in a shard.
@@ -74,6 +92,49 @@ pub(crate) struct ForceGcArgs {
     state: StateArgs,
 }
 
+/// Copies a consistent snapshot of a shard's state to another blob location.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct BackupArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// Blob to write the backup to.
+    #[clap(long)]
+    backup_blob_uri: String,
+}
+
+/// Rehydrates a shard from a backup.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct RestoreArgs {
+    /// Shard to restore, as named in the backup.
+    #[clap(long)]
+    shard_id: String,
+
+    /// Blob the backup was written to.
+    #[clap(long)]
+    backup_blob_uri: String,
+
+    /// Consensus to restore the shard into.
+    #[clap(long, env = "CONSENSUS_URI")]
+    consensus_uri: String,
+
+    /// Blob to restore the shard into.
+    #[clap(long, env = "BLOB_URI")]
+    blob_uri: String,
+
+    /// Restore under this shard id instead of the original one, leaving the
+    /// original shard (if any) untouched.
+    #[clap(long)]
+    new_shard_id: Option<String>,
+
+    /// Only restore updates at times less than or equal to this timestamp.
+    ///
+    /// The restored shard has an upper of `as_of + 1`. Only supported for
+    /// shards with `u64` timestamps.
+    #[clap(long)]
+    as_of: Option<u64>,
+}
+
 /// Runs the given read-write admin command.
 pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
     match command.command {
@@ -112,6 +173,44 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
             .await?;
             info_log_non_zero_metrics(&metrics_registry.gather());
         }
+        Command::Backup(args) => {
+            let shard_id = ShardId::from_str(&args.state.shard_id).expect("invalid shard id");
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+            let metrics_registry = MetricsRegistry::new();
+            let () = backup(
+                cfg,
+                &metrics_registry,
+                shard_id,
+                &args.state.consensus_uri,
+                &args.state.blob_uri,
+                &args.backup_blob_uri,
+                command.commit,
+            )
+            .await?;
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
+        Command::Restore(args) => {
+            let shard_id = ShardId::from_str(&args.shard_id).expect("invalid shard id");
+            let new_shard_id = match args.new_shard_id {
+                Some(x) => ShardId::from_str(&x).expect("invalid new shard id"),
+                None => shard_id,
+            };
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+            let metrics_registry = MetricsRegistry::new();
+            let () = restore(
+                cfg,
+                &metrics_registry,
+                shard_id,
+                &args.backup_blob_uri,
+                new_shard_id,
+                &args.consensus_uri,
+                &args.blob_uri,
+                args.as_of,
+                command.commit,
+            )
+            .await?;
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
     }
     Ok(())
 }
@@ -263,6 +362,355 @@ pub async fn force_compaction(
     }
 }
 
+/// The number of times [backup] fetches the state of a shard before giving up
+/// on copying a consistent snapshot of it.
+const BACKUP_MAX_ATTEMPTS: usize = 10;
+
+/// Copies a consistent snapshot of a shard's state, along with every blob it
+/// references, to another blob location.
+///
+/// The backup is laid out exactly as the shard is in its own blob: each part
+/// under its original key and the state as a rollup at the seqno it was read
+/// at. The source shard is never modified. If parts keep being compacted away
+/// before they can be copied, this gives up after [BACKUP_MAX_ATTEMPTS]
+/// attempts, backing off between them.
+pub async fn backup(
+    cfg: PersistConfig,
+    metrics_registry: &MetricsRegistry,
+    shard_id: ShardId,
+    consensus_uri: &str,
+    blob_uri: &str,
+    backup_blob_uri: &str,
+    commit: bool,
+) -> Result<(), anyhow::Error> {
+    let metrics = Arc::new(Metrics::new(&cfg, metrics_registry));
+    let consensus = make_consensus(&cfg, consensus_uri, false, Arc::clone(&metrics)).await?;
+    let blob = make_blob(&cfg, blob_uri, false, Arc::clone(&metrics)).await?;
+    let backup_blob = make_blob(&cfg, backup_blob_uri, commit, Arc::clone(&metrics)).await?;
+    let state_versions = StateVersions::new(cfg.clone(), consensus, Arc::clone(&blob), metrics);
+
+    let mut copied = BTreeSet::new();
+    let mut retry = Retry::persist_defaults(SystemTime::now()).into_retry_stream();
+    'outer: loop {
+        let state = fetch_state(&state_versions, &shard_id).await?;
+        let batches = state.collections.trace.batches().into_iter();
+        let parts = batches.flat_map(|x| x.parts.iter());
+        for part in parts {
+            if copied.contains(&part.key) {
+                continue;
+            }
+            let key = part.key.complete(&shard_id);
+            let value = match blob.get(&key).await? {
+                Some(x) => x,
+                None => {
+                    // The part was compacted away and GC'd since we fetched
+                    // state. Anything we've already copied is still fine, so
+                    // grab a newer state and pick up where we left off.
+                    if retry.attempt() + 1 >= BACKUP_MAX_ATTEMPTS {
+                        return Err(anyhow!(
+                            "part {} no longer exists after {} attempts, the shard is changing \
+                             faster than it can be backed up",
+                            key,
+                            BACKUP_MAX_ATTEMPTS
+                        ));
+                    }
+                    info!(
+                        "attempt {}: part {} no longer exists, trying again in {:?}",
+                        retry.attempt(),
+                        key,
+                        retry.next_sleep()
+                    );
+                    retry = retry.sleep().await;
+                    continue 'outer;
+                }
+            };
+            backup_blob
+                .set(&key, value, Atomicity::RequireAtomic)
+                .await?;
+            copied.insert(part.key.clone());
+        }
+
+        let rollup_key = PartialRollupKey::new(state.seqno, &RollupId::new());
+        let mut buf = Vec::new();
+        state.encode(&mut buf);
+        backup_blob
+            .set(
+                &rollup_key.complete(&shard_id),
+                Bytes::from(buf),
+                Atomicity::RequireAtomic,
+            )
+            .await?;
+        info!(
+            "backed up shard {} at seqno {} with {} parts to {}",
+            shard_id,
+            state.seqno,
+            copied.len(),
+            rollup_key
+        );
+        return Ok(());
+    }
+}
+
+/// Rehydrates a shard from a backup written by [backup].
+///
+/// The shard is restored under `new_shard_id`, which must not already be
+/// initialized in the target consensus. If `as_of` is set, only updates at
+/// times less than or equal to it are restored. All readers and writers of the
+/// original shard are dropped.
+pub async fn restore(
+    cfg: PersistConfig,
+    metrics_registry: &MetricsRegistry,
+    shard_id: ShardId,
+    backup_blob_uri: &str,
+    new_shard_id: ShardId,
+    consensus_uri: &str,
+    blob_uri: &str,
+    as_of: Option<u64>,
+    commit: bool,
+) -> Result<(), anyhow::Error> {
+    let metrics = Arc::new(Metrics::new(&cfg, metrics_registry));
+    let backup_blob = make_blob(&cfg, backup_blob_uri, false, Arc::clone(&metrics)).await?;
+    let consensus = make_consensus(&cfg, consensus_uri, commit, Arc::clone(&metrics)).await?;
+    let blob = make_blob(&cfg, blob_uri, commit, Arc::clone(&metrics)).await?;
+    let state_versions = StateVersions::new(
+        cfg.clone(),
+        consensus,
+        Arc::clone(&blob),
+        Arc::clone(&metrics),
+    );
+
+    let backup = fetch_backup_state(&cfg, backup_blob.as_ref(), &shard_id).await?;
+    info!(
+        "restoring shard {} at seqno {} as {}",
+        shard_id, backup.seqno, new_shard_id
+    );
+
+    let existing = state_versions
+        .fetch_recent_live_diffs::<u64>(&new_shard_id)
+        .await;
+    if !existing.0.is_empty() {
+        return Err(anyhow!(
+            "shard {} already exists, refusing to overwrite it",
+            new_shard_id
+        ));
+    }
+
+    let since = backup.collections.trace.since().clone();
+    if let Some(as_of) = as_of {
+        if !since.less_equal(&as_of) {
+            return Err(anyhow!(
+                "as_of {} is not beyond the since {:?} of the backup",
+                as_of,
+                since.elements()
+            ));
+        }
+        if backup.collections.trace.upper().less_equal(&as_of) {
+            return Err(anyhow!(
+                "as_of {} is not before the upper {:?} of the backup",
+                as_of,
+                backup.collections.trace.upper().elements()
+            ));
+        }
+    }
+    let mut trace = Trace::default();
+    for batch in backup.collections.trace.batches() {
+        let batch = match as_of {
+            None => batch.clone(),
+            Some(as_of) => {
+                let as_of_upper = Antichain::from_elem(as_of + 1);
+                if !batch.desc.lower().less_equal(&as_of) {
+                    // Entirely after the as_of, so none of it is restored.
+                    continue;
+                }
+                if !PartialOrder::less_equal(batch.desc.upper(), &as_of_upper) {
+                    // Spans the as_of, so it has to be rewritten with only the
+                    // updates at or before it.
+                    let batch = truncate_batch(
+                        &cfg,
+                        &metrics,
+                        &backup_blob,
+                        shard_id,
+                        &blob,
+                        new_shard_id,
+                        batch,
+                        as_of,
+                    )
+                    .await?;
+                    let _merge_reqs = trace.push_batch(batch);
+                    continue;
+                }
+                batch.clone()
+            }
+        };
+        for part in batch.parts.iter() {
+            let value = backup_blob
+                .get(&part.key.complete(&shard_id))
+                .await?
+                .ok_or_else(|| anyhow!("backup is missing part {}", part.key))?;
+            blob.set(
+                &part.key.complete(&new_shard_id),
+                value,
+                Atomicity::RequireAtomic,
+            )
+            .await?;
+        }
+        let _merge_reqs = trace.push_batch(batch);
+    }
+    trace.downgrade_since(&since);
+
+    let shard_metrics = metrics.shards.shard(&new_shard_id);
+    let empty_state = State::<K, V, u64, i64>::new(
+        cfg.build_version.clone(),
+        new_shard_id,
+        cfg.hostname.clone(),
+        (cfg.now)(),
+    );
+    let rollup_seqno = empty_state.seqno.next();
+    let rollup_key = PartialRollupKey::new(rollup_seqno, &RollupId::new());
+    let restored = empty_state.clone_apply(&cfg, &mut |_, _, state| {
+        state.trace = trace.clone();
+        state.add_and_remove_rollups((rollup_seqno, &rollup_key), &[])
+    });
+    let restored = match restored {
+        Continue((_applied, state)) => state,
+        Break(NoOpStateTransition(_)) => {
+            return Err(anyhow!("restoring into the empty state was a no-op"))
+        }
+    };
+
+    let rollup = state_versions.encode_rollup_blob(&shard_metrics, &restored, rollup_key);
+    let () = state_versions.write_rollup_blob(&rollup).await;
+    let diff = StateDiff::from_diff(&empty_state, &restored);
+    let cas_res = state_versions
+        .try_compare_and_set_current("restore", &shard_metrics, None, &restored, &diff)
+        .await?;
+    match cas_res {
+        Ok(()) => {
+            info!(
+                "restored shard {} with since={:?} upper={:?}",
+                new_shard_id,
+                restored.collections.trace.since().elements(),
+                restored.collections.trace.upper().elements(),
+            );
+            Ok(())
+        }
+        Err(_) => Err(anyhow!(
+            "shard {} was concurrently initialized during restore",
+            new_shard_id
+        )),
+    }
+}
+
+/// Rewrites a batch from a backup into `blob` under `shard_id`, keeping only
+/// the updates at or before `as_of`.
+async fn truncate_batch(
+    cfg: &PersistConfig,
+    metrics: &Arc<Metrics>,
+    backup_blob: &Arc<dyn Blob + Send + Sync>,
+    backup_shard_id: ShardId,
+    blob: &Arc<dyn Blob + Send + Sync>,
+    shard_id: ShardId,
+    batch: &HollowBatch<u64>,
+    as_of: u64,
+) -> Result<HollowBatch<u64>, anyhow::Error> {
+    let upper = Antichain::from_elem(as_of + 1);
+    let mut builder = BatchBuilder::<Vec<u8>, Vec<u8>, u64, i64>::new(
+        cfg.clone(),
+        Arc::clone(metrics),
+        metrics.user.clone(),
+        batch.desc.lower().clone(),
+        Arc::clone(blob),
+        Arc::new(CpuHeavyRuntime::new()),
+        shard_id,
+        WriterId::new(),
+        Arc::new(VecU8Schema),
+        batch.desc.since().clone(),
+        Some(upper.clone()),
+        true,
+    );
+    for part in batch.parts.iter() {
+        let mut part = fetch_batch_part(
+            &backup_shard_id,
+            backup_blob.as_ref(),
+            metrics,
+            &metrics.read.snapshot,
+            &part.key,
+            &batch.desc,
+        )
+        .await?;
+        while let Some((k, v, t, d)) = part.next() {
+            if t <= as_of {
+                builder
+                    .add(&k.to_vec(), &v.to_vec(), &t, &i64::decode(d))
+                    .await?;
+            }
+        }
+    }
+    let batch = builder.finish(upper).await?;
+    Ok(batch.into_hollow_batch())
+}
+
+/// Fetches the current state of a shard, priming the K and V codec magic.
+async fn fetch_state(
+    state_versions: &StateVersions,
+    shard_id: &ShardId,
+) -> Result<State<K, V, u64, i64>, anyhow::Error> {
+    let versions = state_versions
+        .fetch_recent_live_diffs::<u64>(shard_id)
+        .await;
+    if versions.0.is_empty() {
+        return Err(anyhow!("shard {} is not initialized", shard_id));
+    }
+    loop {
+        let state_res = state_versions
+            .fetch_current_state::<K, V, u64, i64>(shard_id, versions.0.clone())
+            .await;
+        match state_res {
+            Ok(state) => return Ok(state),
+            Err(codec) => {
+                let mut kvtd = KVTD_CODECS.lock().expect("lockable");
+                *kvtd = codec.actual;
+            }
+        }
+    }
+}
+
+/// Fetches the state of the most recent backup of a shard, priming the K and V
+/// codec magic.
+async fn fetch_backup_state(
+    cfg: &PersistConfig,
+    backup_blob: &(dyn Blob + Send + Sync),
+    shard_id: &ShardId,
+) -> Result<State<K, V, u64, i64>, anyhow::Error> {
+    let mut latest: Option<(SeqNo, BlobKey)> = None;
+    backup_blob
+        .list_keys_and_metadata(&shard_id.to_string(), &mut |metadata| {
+            if let Ok((_, PartialBlobKey::Rollup(seqno, rollup_id))) =
+                BlobKey::parse_ids(metadata.key)
+            {
+                if latest.as_ref().map_or(true, |(x, _)| *x < seqno) {
+                    let key = PartialRollupKey::new(seqno, &rollup_id).complete(shard_id);
+                    latest = Some((seqno, key));
+                }
+            }
+        })
+        .await?;
+    let (_, key) = latest.ok_or_else(|| anyhow!("no backup of shard {} found", shard_id))?;
+    let buf = backup_blob
+        .get(&key)
+        .await?
+        .ok_or_else(|| anyhow!("backup rollup {} disappeared", key))?;
+    loop {
+        match State::<K, V, u64, i64>::decode(&cfg.build_version, &buf) {
+            Ok(state) => return Ok(state),
+            Err(codec) => {
+                let mut kvtd = KVTD_CODECS.lock().expect("lockable");
+                *kvtd = codec.actual;
+            }
+        }
+    }
+}
+
 /// Wrap a lower-level service (Blob or Consensus) to make it read only.
 /// This is probably not elaborate enough to work in general -- folks may expect to read
 /// their own writes, among other things -- but it should handle the case of GC, where
@@ -272,7 +720,7 @@ struct ReadOnly<T>(T);
 
 #[async_trait]
 impl Blob for ReadOnly<Arc<dyn Blob + Sync + Send>> {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         self.0.get(key).await
     }
 
@@ -324,6 +772,20 @@ impl Consensus for ReadOnly<Arc<dyn Consensus + Sync + Send>> {
         warn!("ignoring truncate({key}) in read-only mode");
         Ok(0)
     }
+
+    fn supports_multi(&self) -> bool {
+        self.0.supports_multi()
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        for (key, _, _) in ops {
+            warn!("ignoring cas({key}) in read-only mode");
+        }
+        Ok(Ok(()))
+    }
 }
 
 pub(super) async fn make_consensus(
@@ -447,3 +909,116 @@ async fn force_gc(
 
     Ok(Box::new(machine))
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::cache::PersistClientCache;
+    use crate::tests::all_ok;
+    use crate::PersistLocation;
+
+    use super::*;
+
+    // Verifies that a shard can be backed up and restored under a new shard
+    // id, either in full or truncated to an as_of.
+    #[tokio::test]
+    async fn backup_restore_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let uri = |name: &str| format!("file://{}", dir.path().join(name).display());
+        let (consensus_uri, blob_uri, backup_uri) = (uri("consensus"), uri("blob"), uri("backup"));
+        let cfg = PersistConfig::new_for_tests();
+        let client = PersistClientCache::new(cfg.clone(), &MetricsRegistry::new())
+            .open(PersistLocation {
+                blob_uri: blob_uri.clone(),
+                consensus_uri: consensus_uri.clone(),
+            })
+            .await
+            .expect("valid location");
+
+        let data = vec![
+            (("0".to_owned(), "zero".to_owned()), 0, 1),
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+        let shard_id = ShardId::new();
+        let (mut write, _read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        // The first batch spans the as_of restored below and the second is
+        // entirely after it.
+        write.expect_compare_and_append(&data[0..3], 0, 3).await;
+        write.expect_compare_and_append(&data[3..4], 3, 4).await;
+
+        backup(
+            cfg.clone(),
+            &MetricsRegistry::new(),
+            shard_id,
+            &consensus_uri,
+            &blob_uri,
+            &backup_uri,
+            true,
+        )
+        .await
+        .expect("backup succeeds");
+
+        let full_shard_id = ShardId::new();
+        restore(
+            cfg.clone(),
+            &MetricsRegistry::new(),
+            shard_id,
+            &backup_uri,
+            full_shard_id,
+            &consensus_uri,
+            &blob_uri,
+            None,
+            true,
+        )
+        .await
+        .expect("restore succeeds");
+        let (write, mut read) = client
+            .expect_open::<String, String, u64, i64>(full_shard_id)
+            .await;
+        assert_eq!(write.upper(), &Antichain::from_elem(4));
+        assert_eq!(read.expect_snapshot_and_fetch(3).await, all_ok(&data, 3));
+
+        let truncated_shard_id = ShardId::new();
+        restore(
+            cfg.clone(),
+            &MetricsRegistry::new(),
+            shard_id,
+            &backup_uri,
+            truncated_shard_id,
+            &consensus_uri,
+            &blob_uri,
+            Some(1),
+            true,
+        )
+        .await
+        .expect("restore succeeds");
+        let (write, mut read) = client
+            .expect_open::<String, String, u64, i64>(truncated_shard_id)
+            .await;
+        assert_eq!(write.upper(), &Antichain::from_elem(2));
+        assert_eq!(
+            read.expect_snapshot_and_fetch(1).await,
+            all_ok(&data[0..2], 1)
+        );
+
+        // Restoring over an existing shard is refused.
+        let res = restore(
+            cfg.clone(),
+            &MetricsRegistry::new(),
+            shard_id,
+            &backup_uri,
+            full_shard_id,
+            &consensus_uri,
+            &blob_uri,
+            None,
+            true,
+        )
+        .await;
+        assert!(res.is_err());
+    }
+}
@@ -254,7 +254,7 @@ pub async fn fetch_state_rollup(
         .get(&rollup_key.complete(&shard_id))
         .await?
         .expect("fetching the specified state rollup");
-    let proto = ProtoStateRollup::decode(rollup_buf.as_slice()).expect("invalid encoded state");
+    let proto = ProtoStateRollup::decode(rollup_buf).expect("invalid encoded state");
     Ok(proto)
 }
 
@@ -298,8 +298,7 @@ pub async fn fetch_state_rollups(args: &StateArgs) -> Result<impl serde::Seriali
             .await
             .unwrap();
         if let Some(rollup_buf) = rollup_buf {
-            let proto =
-                ProtoStateRollup::decode(rollup_buf.as_slice()).expect("invalid encoded state");
+            let proto = ProtoStateRollup::decode(rollup_buf).expect("invalid encoded state");
             rollup_states.insert(key.to_string(), proto);
         }
     }
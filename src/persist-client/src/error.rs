@@ -113,6 +113,20 @@ pub enum InvalidUsage<T> {
     CodecMismatch(Box<CodecMismatch>),
     /// An unregistered or expired [crate::write::WriterId] was used by [crate::write::WriteHandle]
     UnknownWriter(WriterId),
+    /// Shards were given to an atomic multi-shard operation but are not all
+    /// stored in the same [mz_persist::location::Consensus]
+    ConsensusMismatch {
+        /// The first shard
+        shard: ShardId,
+        /// A shard in a different consensus than the first
+        other_shard: ShardId,
+    },
+    /// Multiple shards were given to an atomic multi-shard operation but their
+    /// [mz_persist::location::Consensus] can't update more than one at a time
+    AtomicMultiShardUnsupported {
+        /// The first shard
+        shard: ShardId,
+    },
 }
 
 impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
@@ -167,6 +181,16 @@ impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
             InvalidUsage::UnknownWriter(writer_id) => {
                 write!(f, "writer id {} is not registered", writer_id)
             }
+            InvalidUsage::ConsensusMismatch { shard, other_shard } => write!(
+                f,
+                "shards {} and {} are not in the same consensus",
+                shard, other_shard
+            ),
+            InvalidUsage::AtomicMultiShardUnsupported { shard } => write!(
+                f,
+                "consensus of shard {} does not support atomic multi-shard updates",
+                shard
+            ),
         }
     }
 }
@@ -26,6 +26,7 @@ use tracing::{debug_span, trace_span, Instrument};
 
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::{Blob, SeqNo};
+use mz_persist_types::stats::PartStats;
 use mz_persist_types::{Codec, Codec64};
 
 use crate::error::InvalidUsage;
@@ -336,6 +337,7 @@ where
     pub(crate) desc: Description<T>,
     pub(crate) key: PartialBatchKey,
     pub(crate) encoded_size_bytes: usize,
+    pub(crate) stats: Option<PartStats>,
     /// The `SeqNo` from which this part originated; we track this value as
     /// long as necessary to ensure the `SeqNo` isn't garbage collected while a
     /// read still depends on it.
@@ -364,6 +366,7 @@ where
             since: self.desc.since().iter().map(T::encode).collect(),
             key: self.key.clone(),
             encoded_size_bytes: self.encoded_size_bytes,
+            stats: self.stats.clone(),
             leased_seqno: self.leased_seqno,
             reader_id: self.reader_id.clone(),
         };
@@ -394,6 +397,12 @@ where
     pub fn encoded_size_bytes(&self) -> usize {
         self.encoded_size_bytes
     }
+
+    /// Statistics about the contents of this part, if they were computed when
+    /// it was written.
+    pub fn stats(&self) -> Option<&PartStats> {
+        self.stats.as_ref()
+    }
 }
 
 impl<T> Drop for LeasedBatchPart<T>
@@ -585,6 +594,7 @@ pub struct SerdeLeasedBatchPart {
     since: Vec<[u8; 8]>,
     key: PartialBatchKey,
     encoded_size_bytes: usize,
+    stats: Option<PartStats>,
     leased_seqno: Option<SeqNo>,
     reader_id: LeasedReaderId,
 }
@@ -611,6 +621,7 @@ impl<T: Timestamp + Codec64> LeasedBatchPart<T> {
             ),
             key: x.key,
             encoded_size_bytes: x.encoded_size_bytes,
+            stats: x.stats,
             leased_seqno: x.leased_seqno,
             reader_id: x.reader_id,
         }
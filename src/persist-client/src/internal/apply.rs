@@ -325,6 +325,123 @@ where
         .await
     }
 
+    /// Like [Self::apply_unbatched_cmd], but atomically applies the command to
+    /// multiple shards: either every shard's new state is committed or none
+    /// are.
+    ///
+    /// `work_fn` is passed the index in `appliers` of the shard it's being
+    /// applied to. If it returns a Break for any shard, nothing is committed
+    /// and that index is returned along with the error. All of the shards must
+    /// be stored in the same [mz_persist::location::Consensus].
+    pub async fn apply_unbatched_cmd_multi<
+        R,
+        E,
+        WorkFn: FnMut(usize, SeqNo, &PersistConfig, &mut StateCollections<T>) -> ControlFlow<E, R>,
+    >(
+        appliers: &mut [&mut Self],
+        cmd: &CmdMetrics,
+        mut work_fn: WorkFn,
+    ) -> Result<Result<Vec<(SeqNo, R, RoutineMaintenance)>, (usize, E)>, Indeterminate> {
+        let (metrics, shard_metrics, state_versions) = match appliers.first() {
+            Some(x) => (
+                Arc::clone(&x.metrics),
+                Arc::clone(&x.shard_metrics),
+                Arc::clone(&x.state_versions),
+            ),
+            None => return Ok(Ok(Vec::new())),
+        };
+        for applier in appliers.iter() {
+            assert!(
+                state_versions.same_consensus(&applier.state_versions),
+                "shards {} and {} are not in the same consensus",
+                appliers[0].shard_metrics.shard_id,
+                applier.shard_metrics.shard_id,
+            );
+        }
+        cmd.run_cmd(&shard_metrics, |cas_mismatch_metric| async move {
+            loop {
+                let mut new_states = Vec::with_capacity(appliers.len());
+                for (idx, applier) in appliers.iter().enumerate() {
+                    let (work_ret, mut new_state) = match applier
+                        .state
+                        .clone_apply(&applier.cfg, &mut |seqno, cfg, state| {
+                            work_fn(idx, seqno, cfg, state)
+                        }) {
+                        Continue(x) => x,
+                        Break(err) => return Ok(Err((idx, err))),
+                    };
+                    let expiry_metrics = new_state.expire_at((applier.cfg.now)());
+                    let garbage_collection = new_state.maybe_gc(true);
+                    let diff = StateDiff::from_diff(&applier.state, &new_state);
+                    new_states.push((
+                        work_ret,
+                        new_state,
+                        diff,
+                        expiry_metrics,
+                        garbage_collection,
+                    ));
+                }
+
+                let updates = appliers
+                    .iter()
+                    .zip(new_states.iter())
+                    .map(|(applier, (_, new_state, diff, _, _))| {
+                        (
+                            applier.shard_metrics.as_ref(),
+                            applier.state.seqno(),
+                            new_state,
+                            diff,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let cas_res = state_versions
+                    .try_compare_and_set_current_multi(&cmd.name, &updates)
+                    .await?;
+                drop(updates);
+                match cas_res {
+                    Ok(()) => {
+                        let mut ret = Vec::with_capacity(appliers.len());
+                        for (applier, new_state) in appliers.iter_mut().zip(new_states) {
+                            let (work_ret, new_state, _, expiry_metrics, garbage_collection) =
+                                new_state;
+                            assert!(
+                                applier.state.seqno <= new_state.seqno,
+                                "state seqno regressed: {} vs {}",
+                                applier.state.seqno,
+                                new_state.seqno
+                            );
+                            applier.state = new_state;
+                            metrics
+                                .lease
+                                .timeout_read
+                                .inc_by(u64::cast_from(expiry_metrics.readers_expired));
+                            if let Some(gc) = garbage_collection.as_ref() {
+                                debug!("Assigned gc request: {:?}", gc);
+                            }
+                            let maintenance = RoutineMaintenance {
+                                garbage_collection,
+                                write_rollup: applier.state.need_rollup(),
+                            };
+                            ret.push((applier.state.seqno(), work_ret, maintenance));
+                        }
+                        return Ok(Ok(ret));
+                    }
+                    Err(_idx) => {
+                        cas_mismatch_metric.0.inc();
+                        // We don't get the diffs back from a multi-key CaS, so
+                        // just refetch everything. Intentionally don't
+                        // backoff, same as in apply_unbatched_cmd_locked.
+                        for applier in appliers.iter_mut() {
+                            applier.fetch_and_update_state().await;
+                        }
+                        continue;
+                    }
+                }
+            }
+        })
+        .await
+    }
+
     pub async fn fetch_and_update_state(&mut self) {
         let seqno_before = self.state.seqno;
         self.state_versions
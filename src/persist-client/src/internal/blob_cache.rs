@@ -0,0 +1,481 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A size-bounded local cache of batch parts, layered over [Blob].
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use mz_ore::cast::CastFrom;
+use mz_persist::location::{Atomicity, Blob, BlobMetadata, ExternalError};
+use tokio::fs;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::internal::metrics::Metrics;
+use crate::internal::paths::{BlobKey, PartialBlobKey};
+use crate::PersistConfig;
+
+/// A [Blob] that keeps recently fetched batch parts in memory and on local
+/// disk, falling back to the wrapped [Blob] on a miss.
+///
+/// Batch parts are immutable once written, so a cached copy never goes stale:
+/// entries are only removed by eviction or when the part is deleted through
+/// this [Blob]. All other keys (rollups, liveness checks, etc.) are passed
+/// straight through.
+///
+/// The in-memory tier shares its buffers with the values returned by
+/// [Blob::get], so a hit doesn't copy the part. The on-disk tier lives in a
+/// [Self::DISK_DIR_NAME] subdirectory of the configured path, which is owned by
+/// the cache and cleared when it is opened.
+#[derive(Debug)]
+pub struct BlobCache {
+    blob: Arc<dyn Blob + Send + Sync>,
+    metrics: Arc<Metrics>,
+    mem: Option<Mutex<Lru<Bytes>>>,
+    disk: Option<DiskCache>,
+}
+
+#[derive(Debug)]
+struct DiskCache {
+    dir: PathBuf,
+    index: Mutex<Lru<()>>,
+}
+
+impl BlobCache {
+    /// The name of the subdirectory of [PersistConfig::blob_cache_disk_path]
+    /// that holds the on-disk tier.
+    pub const DISK_DIR_NAME: &'static str = "persist-blob-cache";
+
+    /// Returns `blob` wrapped in a cache as configured by `cfg`, or `blob`
+    /// itself if caching is disabled.
+    pub async fn open(
+        cfg: &PersistConfig,
+        metrics: Arc<Metrics>,
+        blob: Arc<dyn Blob + Send + Sync>,
+    ) -> Result<Arc<dyn Blob + Send + Sync>, ExternalError> {
+        let disk = match cfg.blob_cache_disk_path.as_ref() {
+            Some(dir) if cfg.blob_cache_disk_limit_bytes > 0 => {
+                // The configured directory may hold other things, so only ever
+                // touch our own subdirectory of it.
+                let dir = dir.join(Self::DISK_DIR_NAME);
+                // We don't know the sizes or ages of anything left over from a
+                // previous process, so start from scratch.
+                match fs::remove_dir_all(&dir).await {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == ErrorKind::NotFound => {}
+                    Err(err) => return Err(err.into()),
+                }
+                fs::create_dir_all(&dir).await?;
+                info!(
+                    "caching up to {} bytes of batch parts in {}",
+                    cfg.blob_cache_disk_limit_bytes,
+                    dir.display()
+                );
+                Some(DiskCache {
+                    dir,
+                    index: Mutex::new(Lru::new(cfg.blob_cache_disk_limit_bytes)),
+                })
+            }
+            _ => None,
+        };
+        let mem = (cfg.blob_cache_mem_limit_bytes > 0)
+            .then(|| Mutex::new(Lru::new(cfg.blob_cache_mem_limit_bytes)));
+        if disk.is_none() && mem.is_none() {
+            return Ok(blob);
+        }
+        Ok(Arc::new(BlobCache {
+            blob,
+            metrics,
+            mem,
+            disk,
+        }))
+    }
+
+    /// Returns the cache key for `key` if it refers to a batch part.
+    fn cache_key(key: &str) -> Option<String> {
+        match BlobKey::parse_ids(key) {
+            Ok((_, PartialBlobKey::Batch(_, _))) => Some(key.to_owned()),
+            Ok((_, PartialBlobKey::Rollup(_, _))) | Err(_) => None,
+        }
+    }
+
+    fn mem_get(&self, key: &str) -> Option<Bytes> {
+        let mut mem = self.mem.as_ref()?.lock().expect("lock poisoned");
+        mem.get(key).cloned()
+    }
+
+    fn mem_insert(&self, key: String, value: Bytes) {
+        let Some(mem) = self.mem.as_ref() else {
+            return;
+        };
+        let mut mem = mem.lock().expect("lock poisoned");
+        let evicted = mem.insert(key, value.len(), value);
+        self.metrics
+            .blob_cache
+            .mem_evictions
+            .inc_by(u64::cast_from(evicted.len()));
+        self.metrics
+            .blob_cache
+            .mem_size_bytes
+            .set(u64::cast_from(mem.size_bytes));
+    }
+
+    async fn disk_get(&self, disk: &DiskCache, key: &str) -> Option<Bytes> {
+        let present = disk.index.lock().expect("lock poisoned").get(key).is_some();
+        if !present {
+            return None;
+        }
+        match fs::read(disk.path(key)).await {
+            Ok(value) => Some(Bytes::from(value)),
+            Err(err) => {
+                // Most likely raced with an eviction. Either way, forget about
+                // the entry and fall back to blob.
+                warn!("failed to read {} from blob cache: {}", key, err);
+                self.metrics.blob_cache.disk_errors.inc();
+                self.disk_remove(disk, key).await;
+                None
+            }
+        }
+    }
+
+    async fn disk_insert(&self, disk: &DiskCache, key: String, value: &[u8]) {
+        // Write the file before making it visible in the index, so readers
+        // never see a partially written part.
+        let tmp_path = disk.dir.join(format!(".tmp-{}", Uuid::new_v4()));
+        let write_res = match fs::write(&tmp_path, value).await {
+            Ok(()) => fs::rename(&tmp_path, disk.path(&key)).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = write_res {
+            warn!("failed to write {} to blob cache: {}", key, err);
+            self.metrics.blob_cache.disk_errors.inc();
+            let _ = fs::remove_file(&tmp_path).await;
+            return;
+        }
+
+        let evicted = {
+            let mut index = disk.index.lock().expect("lock poisoned");
+            let evicted = index.insert(key, value.len(), ());
+            self.metrics
+                .blob_cache
+                .disk_size_bytes
+                .set(u64::cast_from(index.size_bytes));
+            evicted
+        };
+        self.metrics
+            .blob_cache
+            .disk_evictions
+            .inc_by(u64::cast_from(evicted.len()));
+        for (key, ()) in evicted {
+            disk.remove_file(&key).await;
+        }
+    }
+
+    async fn disk_remove(&self, disk: &DiskCache, key: &str) {
+        {
+            let mut index = disk.index.lock().expect("lock poisoned");
+            index.remove(key);
+            self.metrics
+                .blob_cache
+                .disk_size_bytes
+                .set(u64::cast_from(index.size_bytes));
+        }
+        disk.remove_file(key).await;
+    }
+}
+
+impl DiskCache {
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key.replace('/', "_"))
+    }
+
+    async fn remove_file(&self, key: &str) {
+        match fs::remove_file(self.path(key)).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => warn!("failed to remove {} from blob cache: {}", key, err),
+        }
+    }
+}
+
+#[async_trait]
+impl Blob for BlobCache {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
+        let cache_key = match Self::cache_key(key) {
+            Some(x) => x,
+            None => return self.blob.get(key).await,
+        };
+
+        if let Some(value) = self.mem_get(&cache_key) {
+            self.metrics.blob_cache.mem_hits.inc();
+            return Ok(Some(value));
+        }
+        if let Some(disk) = self.disk.as_ref() {
+            if let Some(value) = self.disk_get(disk, &cache_key).await {
+                self.metrics.blob_cache.disk_hits.inc();
+                self.mem_insert(cache_key, value.clone());
+                return Ok(Some(value));
+            }
+        }
+
+        self.metrics.blob_cache.misses.inc();
+        let value = match self.blob.get(key).await? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        if let Some(disk) = self.disk.as_ref() {
+            self.disk_insert(disk, cache_key.clone(), &value).await;
+        }
+        self.mem_insert(cache_key, value.clone());
+        Ok(Some(value))
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        self.blob.list_keys_and_metadata(key_prefix, f).await
+    }
+
+    async fn set(&self, key: &str, value: Bytes, atomic: Atomicity) -> Result<(), ExternalError> {
+        self.blob.set(key, value, atomic).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        if let Some(cache_key) = Self::cache_key(key) {
+            if let Some(mem) = self.mem.as_ref() {
+                let mut mem = mem.lock().expect("lock poisoned");
+                mem.remove(&cache_key);
+                self.metrics
+                    .blob_cache
+                    .mem_size_bytes
+                    .set(u64::cast_from(mem.size_bytes));
+            }
+            if let Some(disk) = self.disk.as_ref() {
+                self.disk_remove(disk, &cache_key).await;
+            }
+        }
+        self.blob.delete(key).await
+    }
+}
+
+/// A least-recently-used index of entries, bounded by their total size.
+#[derive(Debug)]
+struct Lru<V> {
+    limit_bytes: usize,
+    size_bytes: usize,
+    next_tick: u64,
+    entries: BTreeMap<String, (u64, usize, V)>,
+    by_tick: BTreeMap<u64, String>,
+}
+
+impl<V> Lru<V> {
+    fn new(limit_bytes: usize) -> Self {
+        Lru {
+            limit_bytes,
+            size_bytes: 0,
+            next_tick: 0,
+            entries: BTreeMap::new(),
+            by_tick: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the entry for `key`, marking it as most recently used.
+    fn get(&mut self, key: &str) -> Option<&V> {
+        let tick = self.next_tick;
+        let (entry_tick, _, value) = self.entries.get_mut(key)?;
+        let key = self.by_tick.remove(entry_tick).expect("index in sync");
+        self.by_tick.insert(tick, key);
+        *entry_tick = tick;
+        self.next_tick += 1;
+        Some(value)
+    }
+
+    #[cfg(test)]
+    fn keys(&self) -> Vec<&str> {
+        self.by_tick.values().map(|x| x.as_str()).collect()
+    }
+
+    /// Inserts an entry, returning any that were evicted to make room for it.
+    ///
+    /// Entries larger than the limit are not inserted at all.
+    fn insert(&mut self, key: String, size_bytes: usize, value: V) -> Vec<(String, V)> {
+        if size_bytes > self.limit_bytes || self.entries.contains_key(&key) {
+            return Vec::new();
+        }
+        let mut evicted = Vec::new();
+        while self.size_bytes + size_bytes > self.limit_bytes {
+            let oldest = match self.by_tick.keys().next() {
+                Some(x) => *x,
+                None => break,
+            };
+            let oldest = self.by_tick.remove(&oldest).expect("key exists");
+            if let Some(value) = self.remove(&oldest) {
+                evicted.push((oldest, value));
+            }
+        }
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.size_bytes += size_bytes;
+        self.by_tick.insert(tick, key.clone());
+        self.entries.insert(key, (tick, size_bytes, value));
+        evicted
+    }
+
+    fn remove(&mut self, key: &str) -> Option<V> {
+        let (tick, size_bytes, value) = self.entries.remove(key)?;
+        self.by_tick.remove(&tick);
+        self.size_bytes -= size_bytes;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_persist::location::SeqNo;
+    use mz_persist::mem::{MemBlob, MemBlobConfig};
+
+    use crate::internal::paths::{PartId, PartialBatchKey, PartialRollupKey, RollupId};
+    use crate::write::WriterId;
+    use crate::ShardId;
+
+    use super::*;
+
+    #[test]
+    fn lru() {
+        let mut lru = Lru::new(10);
+        assert_eq!(lru.insert("a".into(), 4, ()).len(), 0);
+        assert_eq!(lru.insert("b".into(), 4, ()).len(), 0);
+        // Too big to ever fit.
+        assert_eq!(lru.insert("c".into(), 11, ()).len(), 0);
+        assert_eq!(lru.keys(), vec!["a", "b"]);
+        // Touching a makes b the eviction candidate.
+        assert!(lru.get("a").is_some());
+        let evicted = lru.insert("d".into(), 4, ());
+        assert_eq!(evicted, vec![("b".to_owned(), ())]);
+        assert_eq!(lru.keys(), vec!["a", "d"]);
+        assert_eq!(lru.size_bytes, 8);
+        assert_eq!(lru.remove("a"), Some(()));
+        assert_eq!(lru.size_bytes, 4);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    async fn blob_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut cfg = PersistConfig::new_for_tests();
+        cfg.blob_cache_mem_limit_bytes = 3;
+        cfg.blob_cache_disk_path = Some(dir.path().to_owned());
+        cfg.blob_cache_disk_limit_bytes = 6;
+        // Opening the cache only clears its own subdirectory.
+        let other = dir.path().join("other");
+        std::fs::write(&other, "other").expect("write");
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let mem = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        let cache = BlobCache::open(&cfg, Arc::clone(&metrics), mem)
+            .await
+            .expect("cache opens");
+        assert!(other.exists());
+        assert!(dir.path().join(BlobCache::DISK_DIR_NAME).is_dir());
+
+        let shard_id = ShardId::new();
+        let key = || {
+            PartialBatchKey::new(&WriterId::new(), &PartId::new())
+                .complete(&shard_id)
+                .to_string()
+        };
+        let (k1, k2, k3) = (key(), key(), key());
+        for (k, v) in [(&k1, "aaa"), (&k2, "bbb"), (&k3, "ccc")] {
+            cache
+                .set(k, Bytes::from(v), Atomicity::RequireAtomic)
+                .await
+                .expect("set");
+        }
+
+        let get = |k: &str| {
+            let cache = Arc::clone(&cache);
+            let k = k.to_owned();
+            async move { cache.get(&k).await.expect("get") }
+        };
+        let hits = |metrics: &Metrics| {
+            (
+                metrics.blob_cache.mem_hits.get(),
+                metrics.blob_cache.disk_hits.get(),
+                metrics.blob_cache.misses.get(),
+            )
+        };
+
+        // The first fetch is a miss, the next is served from memory.
+        assert_eq!(get(&k1).await, Some(Bytes::from("aaa")));
+        assert_eq!(get(&k1).await, Some(Bytes::from("aaa")));
+        assert_eq!(hits(&metrics), (1, 0, 1));
+
+        // k2 pushes k1 out of memory but both fit on disk.
+        assert_eq!(get(&k2).await, Some(Bytes::from("bbb")));
+        assert_eq!(get(&k1).await, Some(Bytes::from("aaa")));
+        assert_eq!(hits(&metrics), (1, 1, 2));
+
+        // k3 pushes k2 (the least recently used) off disk.
+        assert_eq!(get(&k3).await, Some(Bytes::from("ccc")));
+        assert_eq!(get(&k2).await, Some(Bytes::from("bbb")));
+        assert_eq!(hits(&metrics), (1, 1, 4));
+        assert!(metrics.blob_cache.disk_evictions.get() > 0);
+
+        // Deleting a part removes it from the cache too.
+        assert_eq!(cache.delete(&k2).await.expect("delete"), Some(3));
+        assert_eq!(get(&k2).await, None);
+
+        // Non-batch keys are passed straight through.
+        let rollup_key = PartialRollupKey::new(SeqNo(1), &RollupId::new())
+            .complete(&shard_id)
+            .to_string();
+        assert_eq!(get(&rollup_key).await, None);
+        assert_eq!(hits(&metrics), (1, 1, 5));
+    }
+
+    // Verifies that the memory tier is skipped entirely when its limit is 0.
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    async fn blob_cache_disk_only() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut cfg = PersistConfig::new_for_tests();
+        cfg.blob_cache_mem_limit_bytes = 0;
+        cfg.blob_cache_disk_path = Some(dir.path().to_owned());
+        cfg.blob_cache_disk_limit_bytes = 6;
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let mem = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        let cache = BlobCache::open(&cfg, Arc::clone(&metrics), mem)
+            .await
+            .expect("cache opens");
+
+        let key = PartialBatchKey::new(&WriterId::new(), &PartId::new())
+            .complete(&ShardId::new())
+            .to_string();
+        cache
+            .set(&key, Bytes::from("aaa"), Atomicity::RequireAtomic)
+            .await
+            .expect("set");
+        for _ in 0..2 {
+            assert_eq!(
+                cache.get(&key).await.expect("get"),
+                Some(Bytes::from("aaa"))
+            );
+        }
+        assert_eq!(metrics.blob_cache.mem_hits.get(), 0);
+        assert_eq!(metrics.blob_cache.disk_hits.get(), 1);
+        assert_eq!(metrics.blob_cache.mem_size_bytes.get(), 0);
+    }
+}
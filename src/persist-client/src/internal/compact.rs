@@ -24,6 +24,8 @@ use futures_util::TryFutureExt;
 use mz_ore::cast::CastFrom;
 use mz_ore::task::spawn;
 use mz_persist::location::Blob;
+use mz_persist_types::codec_impls::VecU8Schema;
+use mz_persist_types::stats::PartStats;
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::Timestamp;
 use timely::PartialOrder;
@@ -581,6 +583,14 @@ where
         // For now, invent some some extra budget out of thin air for prefetch.
         let prefetch_budget_bytes = 2 * cfg.blob_target_size;
 
+        // We compact the raw encoded keys, so we can't compute stats for the
+        // output directly. Instead, bound every output part with the stats of
+        // all the inputs, which is correct (if loose).
+        let input_stats = PartStats::merge_all(
+            runs.iter()
+                .flat_map(|(_, parts)| parts.iter().map(|x| x.stats.as_ref())),
+        );
+
         let mut sorted_updates = BinaryHeap::new();
 
         let mut remaining_updates_by_run = vec![0; runs.len()];
@@ -608,6 +618,7 @@ where
             cpu_heavy_runtime,
             shard_id.clone(),
             writer_id,
+            Arc::new(VecU8Schema),
             desc.since().clone(),
             Some(desc.upper().clone()),
             true,
@@ -688,7 +699,10 @@ where
         }
 
         let batch = batch.finish(desc.upper().clone()).await?;
-        let hollow_batch = batch.into_hollow_batch();
+        let mut hollow_batch = batch.into_hollow_batch();
+        for part in hollow_batch.parts.iter_mut() {
+            part.stats = input_stats.clone();
+        }
 
         timings.record(&metrics);
 
@@ -997,6 +1011,7 @@ mod tests {
             .map(|encoded_size_bytes| HollowBatchPart {
                 key: PartialBatchKey("".into()),
                 encoded_size_bytes,
+                stats: None,
             })
             .collect::<Vec<_>>();
         let parse = |x: &str| {
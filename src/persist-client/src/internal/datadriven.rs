@@ -106,6 +106,7 @@ impl<'a> DirectiveArgs<'a> {
                 .map(|x| HollowBatchPart {
                     key: PartialBatchKey((*x).to_owned()),
                     encoded_size_bytes: 0,
+                    stats: None,
                 })
                 .collect(),
             runs: vec![],
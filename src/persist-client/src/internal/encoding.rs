@@ -12,6 +12,7 @@ use std::marker::PhantomData;
 
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
+use mz_persist_types::stats::{ColumnStats, PartStats, PrimitiveValue, StructStats};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use prost::Message;
@@ -28,11 +29,12 @@ use crate::critical::CriticalReaderId;
 use crate::error::CodecMismatch;
 use crate::internal::paths::{PartialBatchKey, PartialRollupKey};
 use crate::internal::state::{
-    CriticalReaderState, HandleDebugState, HollowBatch, HollowBatchPart, IdempotencyToken,
-    LeasedReaderState, OpaqueState, ProtoCriticalReaderState, ProtoHandleDebugState,
-    ProtoHollowBatch, ProtoHollowBatchPart, ProtoLeasedReaderState, ProtoStateDiff,
-    ProtoStateField, ProtoStateFieldDiffType, ProtoStateFieldDiffs, ProtoStateRollup, ProtoTrace,
-    ProtoU64Antichain, ProtoU64Description, ProtoWriterState, State, StateCollections, WriterState,
+    proto_primitive_value, CriticalReaderState, HandleDebugState, HollowBatch, HollowBatchPart,
+    IdempotencyToken, LeasedReaderState, OpaqueState, ProtoColumnStats, ProtoCriticalReaderState,
+    ProtoHandleDebugState, ProtoHollowBatch, ProtoHollowBatchPart, ProtoLeasedReaderState,
+    ProtoPartStats, ProtoPrimitiveValue, ProtoStateDiff, ProtoStateField, ProtoStateFieldDiffType,
+    ProtoStateFieldDiffs, ProtoStateRollup, ProtoStructStats, ProtoTrace, ProtoU64Antichain,
+    ProtoU64Description, ProtoWriterState, State, StateCollections, WriterState,
 };
 use crate::internal::state_diff::{
     ProtoStateFieldDiff, StateDiff, StateFieldDiff, StateFieldValDiff,
@@ -811,6 +813,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatch> for HollowBatch<T> {
                 .map(|key| HollowBatchPart {
                     key: PartialBatchKey(key),
                     encoded_size_bytes: 0,
+                    stats: None,
                 }),
         );
         Ok(HollowBatch {
@@ -827,6 +830,7 @@ impl RustType<ProtoHollowBatchPart> for HollowBatchPart {
         ProtoHollowBatchPart {
             key: self.key.into_proto(),
             encoded_size_bytes: self.encoded_size_bytes.into_proto(),
+            stats: self.stats.into_proto(),
         }
     }
 
@@ -834,10 +838,95 @@ impl RustType<ProtoHollowBatchPart> for HollowBatchPart {
         Ok(HollowBatchPart {
             key: proto.key.into_rust()?,
             encoded_size_bytes: proto.encoded_size_bytes.into_rust()?,
+            stats: proto.stats.into_rust()?,
         })
     }
 }
 
+impl RustType<ProtoPartStats> for PartStats {
+    fn into_proto(&self) -> ProtoPartStats {
+        ProtoPartStats {
+            key: Some(self.key.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPartStats) -> Result<Self, TryFromProtoError> {
+        Ok(PartStats {
+            key: proto.key.into_rust_if_some("ProtoPartStats::key")?,
+        })
+    }
+}
+
+impl RustType<ProtoStructStats> for StructStats {
+    fn into_proto(&self) -> ProtoStructStats {
+        ProtoStructStats {
+            len: self.len.into_proto(),
+            cols: self
+                .cols
+                .iter()
+                .map(|(name, col)| (name.clone(), col.into_proto()))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoStructStats) -> Result<Self, TryFromProtoError> {
+        let mut cols = BTreeMap::new();
+        for (name, col) in proto.cols {
+            cols.insert(name, col.into_rust()?);
+        }
+        Ok(StructStats {
+            len: proto.len.into_rust()?,
+            cols,
+        })
+    }
+}
+
+impl RustType<ProtoColumnStats> for ColumnStats {
+    fn into_proto(&self) -> ProtoColumnStats {
+        ProtoColumnStats {
+            nulls: self.nulls.into_proto(),
+            lower: self.lower.into_proto(),
+            upper: self.upper.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoColumnStats) -> Result<Self, TryFromProtoError> {
+        Ok(ColumnStats {
+            nulls: proto.nulls.into_rust()?,
+            lower: proto.lower.into_rust()?,
+            upper: proto.upper.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoPrimitiveValue> for PrimitiveValue {
+    fn into_proto(&self) -> ProtoPrimitiveValue {
+        use proto_primitive_value::Kind;
+        let kind = match self {
+            PrimitiveValue::Bool(x) => Kind::Bool(*x),
+            PrimitiveValue::I64(x) => Kind::I64(*x),
+            PrimitiveValue::U64(x) => Kind::U64(*x),
+            PrimitiveValue::String(x) => Kind::String(x.clone()),
+            PrimitiveValue::Bytes(x) => Kind::Bytes(x.clone()),
+        };
+        ProtoPrimitiveValue { kind: Some(kind) }
+    }
+
+    fn from_proto(proto: ProtoPrimitiveValue) -> Result<Self, TryFromProtoError> {
+        use proto_primitive_value::Kind;
+        match proto.kind {
+            Some(Kind::Bool(x)) => Ok(PrimitiveValue::Bool(x)),
+            Some(Kind::I64(x)) => Ok(PrimitiveValue::I64(x)),
+            Some(Kind::U64(x)) => Ok(PrimitiveValue::U64(x)),
+            Some(Kind::String(x)) => Ok(PrimitiveValue::String(x)),
+            Some(Kind::Bytes(x)) => Ok(PrimitiveValue::Bytes(x)),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoPrimitiveValue::kind",
+            )),
+        }
+    }
+}
+
 impl<T: Timestamp + Codec64> RustType<ProtoU64Description> for Description<T> {
     fn into_proto(&self) -> ProtoU64Description {
         ProtoU64Description {
@@ -983,6 +1072,7 @@ mod tests {
             parts: vec![HollowBatchPart {
                 key: PartialBatchKey("a".into()),
                 encoded_size_bytes: 5,
+                stats: None,
             }],
             runs: vec![],
         };
@@ -1000,6 +1090,7 @@ mod tests {
         expected.parts.push(HollowBatchPart {
             key: PartialBatchKey("b".into()),
             encoded_size_bytes: 0,
+            stats: None,
         });
         assert_eq!(<HollowBatch<u64>>::from_proto(old).unwrap(), expected);
     }
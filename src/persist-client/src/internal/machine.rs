@@ -489,6 +489,172 @@ where
         }
     }
 
+    /// Atomically appends a batch to each of several shards: either all of
+    /// the appends commit or none of them do.
+    ///
+    /// `appends` contains, for each shard, the machine, the batch, and the
+    /// writer. All of the shards must be stored in the same
+    /// [mz_persist::location::Consensus]. On an upper mismatch, the index in
+    /// `appends` of the (first) offending shard is returned along with its
+    /// current upper.
+    pub async fn compare_and_append_multi(
+        appends: &mut [(&mut Self, &HollowBatch<T>, &WriterId)],
+        heartbeat_timestamp_ms: u64,
+    ) -> Result<Result<Vec<(SeqNo, WriterMaintenance<T>)>, InvalidUsage<T>>, (usize, Upper<T>)>
+    {
+        let idempotency_token = IdempotencyToken::new();
+        loop {
+            let res = Self::compare_and_append_multi_idempotent(
+                appends,
+                heartbeat_timestamp_ms,
+                &idempotency_token,
+            )
+            .await;
+            match res {
+                Ok(x) => return Ok(x),
+                Err((idx, _current_upper)) => {
+                    // Same as compare_and_append: our cached state for this
+                    // shard might have been outdated, so refresh it and retry
+                    // if that's all that was wrong.
+                    let (machine, batch, _) = &mut appends[idx];
+                    machine.applier.fetch_and_update_state().await;
+                    let current_upper = machine.upper();
+                    if current_upper != batch.desc.lower() {
+                        return Err((idx, Upper(current_upper.clone())));
+                    }
+                }
+            }
+        }
+    }
+
+    async fn compare_and_append_multi_idempotent(
+        appends: &mut [(&mut Self, &HollowBatch<T>, &WriterId)],
+        heartbeat_timestamp_ms: u64,
+        idempotency_token: &IdempotencyToken,
+    ) -> Result<Result<Vec<(SeqNo, WriterMaintenance<T>)>, InvalidUsage<T>>, (usize, Upper<T>)>
+    {
+        let Some((first, _, _)) = appends.first() else {
+            return Ok(Ok(Vec::new()));
+        };
+        let metrics = Arc::clone(&first.applier.metrics);
+        let inputs = appends
+            .iter()
+            .map(|(_, batch, writer_id)| (*batch, *writer_id))
+            .collect::<Vec<_>>();
+        // See compare_and_append_idempotent for a discussion of the retry
+        // logic here. The same IdempotencyToken is used for every shard and
+        // the shards are committed atomically, so if any of them reports
+        // AlreadyCommitted, all of them were committed.
+        let mut indeterminate = None;
+        let mut retry = metrics
+            .retries
+            .compare_and_append_idempotent
+            .stream(Retry::persist_defaults(SystemTime::now()).into_retry_stream());
+        loop {
+            let cmd_res = {
+                let mut appliers = appends
+                    .iter_mut()
+                    .map(|(machine, _, _)| &mut machine.applier)
+                    .collect::<Vec<_>>();
+                Applier::apply_unbatched_cmd_multi(
+                    &mut appliers,
+                    &metrics.cmds.compare_and_append_multi,
+                    |idx, _, _, state| {
+                        let (batch, writer_id) = inputs[idx];
+                        state.compare_and_append(
+                            batch,
+                            writer_id,
+                            heartbeat_timestamp_ms,
+                            idempotency_token,
+                        )
+                    },
+                )
+                .await
+            };
+            let res = match cmd_res {
+                Ok(x) => x,
+                Err(err) => {
+                    info!(
+                        "compare_and_append_multi received an indeterminate error, retrying in {:?}: {}",
+                        retry.next_sleep(),
+                        err
+                    );
+                    if indeterminate.is_none() {
+                        indeterminate = Some(err);
+                    }
+                    retry = retry.sleep().await;
+                    continue;
+                }
+            };
+            match res {
+                Ok(results) => {
+                    let mut ret = Vec::with_capacity(results.len());
+                    for ((machine, _, _), (seqno, merge_reqs, routine)) in
+                        appends.iter_mut().zip(results)
+                    {
+                        let compaction = merge_reqs
+                            .into_iter()
+                            .map(|req| CompactReq {
+                                shard_id: machine.shard_id(),
+                                desc: req.desc,
+                                inputs: req.inputs.iter().map(|b| b.as_ref().clone()).collect(),
+                            })
+                            .collect();
+                        let mut writer_maintenance = WriterMaintenance {
+                            routine,
+                            compaction,
+                        };
+                        if let Some(tombstone_maintenance) = machine.maybe_become_tombstone().await
+                        {
+                            writer_maintenance.routine.merge(tombstone_maintenance);
+                        }
+                        ret.push((seqno, writer_maintenance));
+                    }
+                    return Ok(Ok(ret));
+                }
+                Err((_, CompareAndAppendBreak::AlreadyCommitted)) => {
+                    assert!(indeterminate.is_some());
+                    metrics.cmds.compare_and_append_noop.inc();
+                    let ret = appends
+                        .iter()
+                        .map(|(machine, _, _)| (machine.seqno(), WriterMaintenance::default()))
+                        .collect();
+                    return Ok(Ok(ret));
+                }
+                Err((_, CompareAndAppendBreak::InvalidUsage(err))) => {
+                    assert!(indeterminate.is_none());
+                    return Ok(Err(err));
+                }
+                Err((
+                    idx,
+                    CompareAndAppendBreak::Upper {
+                        shard_upper,
+                        writer_upper,
+                    },
+                )) => {
+                    let batch = inputs[idx].0;
+                    assert!(
+                        PartialOrder::less_equal(&writer_upper, &shard_upper),
+                        "{:?} vs {:?}",
+                        &writer_upper,
+                        &shard_upper
+                    );
+                    if PartialOrder::less_than(&writer_upper, batch.desc.upper())
+                        || indeterminate.is_none()
+                    {
+                        return Err((idx, Upper(shard_upper)));
+                    }
+                    panic!(concat!(
+                        "cannot distinguish compare_and_append_multi success or failure ",
+                        "caa_lower={:?} caa_upper={:?} writer_upper={:?} shard_upper={:?} err={:?}"),
+                        batch.desc.lower().elements(), batch.desc.upper().elements(),
+                        writer_upper.elements(), shard_upper.elements(), indeterminate,
+                    );
+                }
+            }
+        }
+    }
+
     pub async fn merge_res(&mut self, res: &FueledMergeRes<T>) -> ApplyMergeResult {
         let metrics = Arc::clone(&self.applier.metrics);
 
@@ -1180,6 +1346,7 @@ pub mod datadriven {
             Arc::clone(&datadriven.client.cpu_heavy_runtime),
             datadriven.shard_id.clone(),
             WriterId::new(),
+            Arc::new(StringSchema),
             since,
             Some(upper.clone()),
             consolidate,
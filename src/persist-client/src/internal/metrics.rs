@@ -62,6 +62,10 @@ pub struct Metrics {
     pub gc: GcMetrics,
     /// Metrics for leasing and automatic lease expiry.
     pub lease: LeaseMetrics,
+    /// Metrics for filtering out parts using their stats.
+    pub pushdown: PushdownMetrics,
+    /// Metrics for the local cache of batch parts.
+    pub blob_cache: BlobCacheMetrics,
     /// Metrics for various encodings and decodings.
     pub codecs: CodecsMetrics,
     /// Metrics for (incremental) state updates and fetches.
@@ -104,6 +108,8 @@ impl Metrics {
             compaction: CompactionMetrics::new(registry),
             gc: GcMetrics::new(registry),
             lease: LeaseMetrics::new(registry),
+            pushdown: PushdownMetrics::new(registry),
+            blob_cache: BlobCacheMetrics::new(registry),
             state: StateMetrics::new(registry),
             shards: ShardsMetrics::new(registry),
             audit: UsageAuditMetrics::new(registry),
@@ -326,6 +332,7 @@ impl MetricsVecs {
             add_and_remove_rollups: self.cmd_metrics("add_and_remove_rollups"),
             register: self.cmd_metrics("register"),
             compare_and_append: self.cmd_metrics("compare_and_append"),
+            compare_and_append_multi: self.cmd_metrics("compare_and_append_multi"),
             compare_and_append_noop:             registry.register(metric!(
                 name: "mz_persist_cmd_compare_and_append_noop",
                 help: "count of compare_and_append retries that were discoverd to have already committed",
@@ -425,6 +432,7 @@ impl MetricsVecs {
         ConsensusMetrics {
             head: self.external_op_metrics("consensus_head", false),
             compare_and_set: self.external_op_metrics("consensus_cas", true),
+            compare_and_set_multi: self.external_op_metrics("consensus_cas_multi", true),
             scan: self.external_op_metrics("consensus_scan", false),
             truncate: self.external_op_metrics("consensus_truncate", false),
             truncated_count: self.external_consensus_truncated_count.clone(),
@@ -517,6 +525,7 @@ pub struct CmdsMetrics {
     pub(crate) add_and_remove_rollups: CmdMetrics,
     pub(crate) register: CmdMetrics,
     pub(crate) compare_and_append: CmdMetrics,
+    pub(crate) compare_and_append_multi: CmdMetrics,
     pub(crate) compare_and_append_noop: IntCounter,
     pub(crate) compare_and_downgrade_since: CmdMetrics,
     pub(crate) downgrade_since: CmdMetrics,
@@ -876,6 +885,88 @@ impl LeaseMetrics {
     }
 }
 
+#[derive(Debug)]
+pub struct PushdownMetrics {
+    pub(crate) parts_filtered_count: IntCounter,
+    pub(crate) parts_filtered_bytes: IntCounter,
+    pub(crate) parts_fetched_count: IntCounter,
+    pub(crate) parts_fetched_bytes: IntCounter,
+}
+
+impl PushdownMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        PushdownMetrics {
+            parts_filtered_count: registry.register(metric!(
+                name: "mz_persist_pushdown_parts_filtered_count",
+                help: "count of parts skipped because their stats didn't match the filter",
+            )),
+            parts_filtered_bytes: registry.register(metric!(
+                name: "mz_persist_pushdown_parts_filtered_bytes",
+                help: "total size of parts skipped because their stats didn't match the filter",
+            )),
+            parts_fetched_count: registry.register(metric!(
+                name: "mz_persist_pushdown_parts_fetched_count",
+                help: "count of parts fetched after being checked against the filter",
+            )),
+            parts_fetched_bytes: registry.register(metric!(
+                name: "mz_persist_pushdown_parts_fetched_bytes",
+                help: "total size of parts fetched after being checked against the filter",
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BlobCacheMetrics {
+    pub(crate) mem_hits: IntCounter,
+    pub(crate) disk_hits: IntCounter,
+    pub(crate) misses: IntCounter,
+    pub(crate) mem_evictions: IntCounter,
+    pub(crate) disk_evictions: IntCounter,
+    pub(crate) disk_errors: IntCounter,
+    pub(crate) mem_size_bytes: UIntGauge,
+    pub(crate) disk_size_bytes: UIntGauge,
+}
+
+impl BlobCacheMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        BlobCacheMetrics {
+            mem_hits: registry.register(metric!(
+                name: "mz_persist_blob_cache_mem_hits",
+                help: "count of batch part fetches served from the in-memory cache",
+            )),
+            disk_hits: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_hits",
+                help: "count of batch part fetches served from the local disk cache",
+            )),
+            misses: registry.register(metric!(
+                name: "mz_persist_blob_cache_misses",
+                help: "count of batch part fetches that missed both caches",
+            )),
+            mem_evictions: registry.register(metric!(
+                name: "mz_persist_blob_cache_mem_evictions",
+                help: "count of batch parts evicted from the in-memory cache",
+            )),
+            disk_evictions: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_evictions",
+                help: "count of batch parts evicted from the local disk cache",
+            )),
+            disk_errors: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_errors",
+                help: "count of local disk cache reads or writes that failed",
+            )),
+            mem_size_bytes: registry.register(metric!(
+                name: "mz_persist_blob_cache_mem_size_bytes",
+                help: "total size of batch parts in the in-memory cache",
+            )),
+            disk_size_bytes: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_size_bytes",
+                help: "total size of batch parts in the local disk cache",
+            )),
+        }
+    }
+}
+
 struct IncOnDrop(IntCounter);
 
 impl Drop for IncOnDrop {
@@ -1436,7 +1527,7 @@ impl MetricsBlob {
 
 #[async_trait]
 impl Blob for MetricsBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         let res = self
             .metrics
             .blob
@@ -1522,6 +1613,7 @@ impl Blob for MetricsBlob {
 pub struct ConsensusMetrics {
     head: ExternalOpMetrics,
     compare_and_set: ExternalOpMetrics,
+    compare_and_set_multi: ExternalOpMetrics,
     scan: ExternalOpMetrics,
     truncate: ExternalOpMetrics,
     truncated_count: IntCounter,
@@ -1645,6 +1737,31 @@ impl Consensus for MetricsConsensus {
             .inc_by(u64::cast_from(deleted));
         Ok(deleted)
     }
+
+    fn supports_multi(&self) -> bool {
+        self.consensus.supports_multi()
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        let bytes = ops.iter().map(|(_, _, new)| new.data.len()).sum::<usize>();
+        let res = self
+            .metrics
+            .consensus
+            .compare_and_set_multi
+            .run_op(|| self.consensus.compare_and_set_multi(ops), Self::on_err)
+            .await;
+        if let Ok(Ok(())) = res.as_ref() {
+            self.metrics
+                .consensus
+                .compare_and_set_multi
+                .bytes
+                .inc_by(u64::cast_from(bytes));
+        }
+        res
+    }
 }
 
 /// Encode a frontier into an i64 acceptable for use in metrics.
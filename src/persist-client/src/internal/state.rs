@@ -19,6 +19,7 @@ use differential_dataflow::trace::Description;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_persist::location::SeqNo;
+use mz_persist_types::stats::PartStats;
 use mz_persist_types::{Codec, Codec64, Opaque};
 use semver::Version;
 use timely::progress::{Antichain, Timestamp};
@@ -137,6 +138,9 @@ pub struct HollowBatchPart {
     pub key: PartialBatchKey,
     /// The encoded size of this part.
     pub encoded_size_bytes: usize,
+    /// Aggregate statistics about the contents of this part, if they were
+    /// computed when it was written.
+    pub stats: Option<PartStats>,
 }
 
 /// A [Batch] but with the updates themselves stored externally.
@@ -1234,6 +1238,7 @@ mod tests {
                 .map(|x| HollowBatchPart {
                     key: PartialBatchKey((*x).to_owned()),
                     encoded_size_bytes: 0,
+                    stats: None,
                 })
                 .collect(),
             len,
@@ -249,15 +249,7 @@ impl StateVersions {
                     new_state
                 );
 
-                shard_metrics.set_since(new_state.since());
-                shard_metrics.set_upper(new_state.upper());
-                shard_metrics.set_batch_part_count(new_state.batch_part_count());
-                shard_metrics.set_update_count(new_state.num_updates());
-                let (largest_batch_size, encoded_batch_size) = new_state.batch_size_metrics();
-                shard_metrics.set_largest_batch_size(largest_batch_size);
-                shard_metrics.set_encoded_batch_size(encoded_batch_size);
-                shard_metrics.set_seqnos_held(new_state.seqnos_held());
-                shard_metrics.inc_encoded_diff_size(payload_len);
+                Self::update_shard_metrics(shard_metrics, new_state, payload_len);
                 Ok(Ok(()))
             }
             Err(live_diffs) => {
@@ -273,6 +265,116 @@ impl StateVersions {
         }
     }
 
+    /// Atomically updates the state of multiple shards, each to a new `current`
+    /// iff its `expected` matches its `current`.
+    ///
+    /// Either every shard is updated or none are. On a mismatch, the index in
+    /// `updates` of (one of) the shards that didn't match is returned. All of
+    /// the shards must be stored in this [StateVersions]' [Consensus].
+    pub async fn try_compare_and_set_current_multi<K, V, T, D>(
+        &self,
+        cmd_name: &str,
+        updates: &[(&ShardMetrics, SeqNo, &State<K, V, T, D>, &StateDiff<T>)],
+    ) -> Result<Result<(), usize>, Indeterminate>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64,
+    {
+        let mut paths = Vec::with_capacity(updates.len());
+        let mut news = Vec::with_capacity(updates.len());
+        for (shard_metrics, _expected, new_state, diff) in updates {
+            assert_eq!(shard_metrics.shard_id, new_state.shard_id);
+            trace!(
+                "apply_unbatched_cmd_multi {} attempting {}\n  new_state={:?}",
+                cmd_name,
+                new_state.seqno(),
+                new_state
+            );
+            let new = self.metrics.codecs.state_diff.encode(|| {
+                let mut buf = Vec::new();
+                diff.encode(&mut buf);
+                VersionedData {
+                    seqno: new_state.seqno(),
+                    data: Bytes::from(buf),
+                }
+            });
+            assert_eq!(new.seqno, diff.seqno_to);
+            paths.push(new_state.shard_id.to_string());
+            news.push(new);
+        }
+        let ops = updates
+            .iter()
+            .zip(paths.iter().zip(news.iter()))
+            .map(|((_, expected, _, _), (path, new))| (path.as_str(), Some(*expected), new.clone()))
+            .collect::<Vec<_>>();
+
+        let payload_len = news.iter().map(|x| x.data.len()).sum::<usize>();
+        let cas_res = retry_determinate(
+            &self.metrics.retries.determinate.apply_unbatched_cmd_cas,
+            || async { self.consensus.compare_and_set_multi(&ops).await },
+        )
+        .instrument(debug_span!("apply_unbatched_cmd_multi::cas", payload_len))
+        .await
+        .map_err(|err| {
+            debug!("apply_unbatched_cmd_multi {} errored: {}", cmd_name, err);
+            err
+        })?;
+
+        match cas_res {
+            Ok(()) => {
+                for ((shard_metrics, _, new_state, _), new) in updates.iter().zip(news.iter()) {
+                    Self::update_shard_metrics(shard_metrics, new_state, new.data.len());
+                }
+                Ok(Ok(()))
+            }
+            Err(idx) => {
+                debug!(
+                    "apply_unbatched_cmd_multi {} {} lost the CaS race, retrying",
+                    updates[idx].2.shard_id(),
+                    cmd_name,
+                );
+                Ok(Err(idx))
+            }
+        }
+    }
+
+    fn update_shard_metrics<K, V, T, D>(
+        shard_metrics: &ShardMetrics,
+        new_state: &State<K, V, T, D>,
+        payload_len: usize,
+    ) where
+        K: Codec,
+        V: Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Codec64,
+    {
+        shard_metrics.set_since(new_state.since());
+        shard_metrics.set_upper(new_state.upper());
+        shard_metrics.set_batch_part_count(new_state.batch_part_count());
+        shard_metrics.set_update_count(new_state.num_updates());
+        let (largest_batch_size, encoded_batch_size) = new_state.batch_size_metrics();
+        shard_metrics.set_largest_batch_size(largest_batch_size);
+        shard_metrics.set_encoded_batch_size(encoded_batch_size);
+        shard_metrics.set_seqnos_held(new_state.seqnos_held());
+        shard_metrics.inc_encoded_diff_size(payload_len);
+    }
+
+    /// Returns whether `self` and `other` are backed by the same [Consensus],
+    /// which is required to atomically update shards from both.
+    pub fn same_consensus(&self, other: &StateVersions) -> bool {
+        let this = Arc::as_ptr(&self.consensus).cast::<()>();
+        let other = Arc::as_ptr(&other.consensus).cast::<()>();
+        this == other
+    }
+
+    /// Returns whether the backing [Consensus] can atomically update more than
+    /// one shard.
+    pub fn supports_multi(&self) -> bool {
+        self.consensus.supports_multi()
+    }
+
     /// Fetches the `current` state of the requested shard.
     ///
     /// Uses the provided hint (live_diffs), which is a possibly outdated
@@ -142,6 +142,7 @@ pub mod write;
 /// An implementation of the public crate interface.
 mod internal {
     pub mod apply;
+    pub mod blob_cache;
     pub mod compact;
     pub mod encoding;
     pub mod gc;
@@ -519,15 +520,16 @@ impl PersistClient {
     /// Use this to save latency and a bit of persist traffic if you're just
     /// going to immediately drop or expire the [ReadHandle].
     ///
-    /// The `_schema` parameter is currently unused, but should be an object
-    /// that represents the schema of the data in the shard. This will be required
-    /// in the future.
+    /// The `key_schema` is used to compute statistics about the keys in each
+    /// written part. The `_val_schema` parameter is currently unused, but
+    /// should be an object that represents the schema of the data in the shard.
+    /// This will be required in the future.
     #[instrument(level = "debug", skip_all, fields(shard = %shard_id))]
     pub async fn open_writer<K, V, T, D>(
         &self,
         shard_id: ShardId,
         purpose: &str,
-        _key_schema: Arc<K::Schema>,
+        key_schema: Arc<K::Schema>,
         _val_schema: Arc<V::Schema>,
     ) -> Result<WriteHandle<K, V, T, D>, InvalidUsage<T>>
     where
@@ -577,6 +579,7 @@ impl PersistClient {
             Arc::clone(&self.blob),
             Arc::clone(&self.cpu_heavy_runtime),
             writer_id,
+            key_schema,
             shard_upper.0,
             heartbeat_ts,
         )
@@ -609,6 +612,12 @@ impl PersistClient {
         .expect("codec mismatch")
     }
 
+    /// Returns whether [WriteHandle::compare_and_append_atomic] can be used
+    /// to append to more than one of the shards opened by this client.
+    pub fn supports_atomic_multi_shard(&self) -> bool {
+        self.consensus.supports_multi()
+    }
+
     /// Return the metrics being used by this client.
     ///
     /// Only exposed for tests, persistcli, and benchmarks.
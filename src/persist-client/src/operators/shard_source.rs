@@ -23,8 +23,8 @@ use differential_dataflow::Hashable;
 use futures::StreamExt;
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
-use mz_ore::vec::VecExt;
 use mz_persist::location::ExternalError;
+use mz_persist_types::stats::PartStats;
 use mz_persist_types::{Codec, Codec64};
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
@@ -39,7 +39,7 @@ use tracing::trace;
 
 use crate::cache::PersistClientCache;
 use crate::fetch::{FetchedPart, SerdeLeasedBatchPart};
-use crate::read::ListenEvent;
+use crate::read::{filter_parts, ListenEvent};
 use crate::{PersistLocation, ShardId};
 
 /// Creates a new source that reads from a persist shard, distributing the work
@@ -63,6 +63,11 @@ use crate::{PersistLocation, ShardId};
 /// to the empty antichain can be used. An easy easy of creating such stream is by
 /// using [`timely::dataflow::operators::generic::operator::empty`].
 ///
+/// The `should_fetch_part` argument is called with the stats of each part (for
+/// parts that have them) and may return false to indicate that nothing in the
+/// part is needed by the caller, in which case the part is not fetched. It must
+/// only return false if it's certain that the part contains no relevant data.
+///
 /// [advanced by]: differential_dataflow::lattice::Lattice::advance_by
 pub fn shard_source<K, V, D, G>(
     scope: &G,
@@ -75,6 +80,7 @@ pub fn shard_source<K, V, D, G>(
     flow_control: Option<FlowControl<G>>,
     key_schema: Arc<K::Schema>,
     val_schema: Arc<V::Schema>,
+    should_fetch_part: impl FnMut(&PartStats) -> bool + 'static,
 ) -> (Stream<G, FetchedPart<K, V, G::Timestamp, D>>, Rc<dyn Any>)
 where
     K: Debug + Codec,
@@ -123,6 +129,7 @@ where
         chosen_worker,
         Arc::clone(&key_schema),
         Arc::clone(&val_schema),
+        should_fetch_part,
     );
     let (parts, tokens) = shard_source_fetch(
         &descs, name, clients, location, shard_id, key_schema, val_schema,
@@ -159,6 +166,7 @@ pub(crate) fn shard_source_descs<K, V, D, G>(
     chosen_worker: usize,
     key_schema: Arc<K::Schema>,
     val_schema: Arc<V::Schema>,
+    mut should_fetch_part: impl FnMut(&PartStats) -> bool + 'static,
 ) -> (Stream<G, (usize, SerdeLeasedBatchPart)>, ShutdownButton<()>)
 where
     K: Debug + Codec,
@@ -198,6 +206,8 @@ where
             .await
             .expect("could not open persist shard");
         let as_of = as_of.unwrap_or_else(|| read.since().clone());
+        let dynamic_cfg = Arc::clone(&read.cfg.dynamic);
+        let metrics = Arc::clone(&read.metrics);
 
         // Eagerly yield the initial as_of. This makes sure that the output
         // frontier of the `persist_source` closely tracks the `upper` frontier
@@ -229,7 +239,15 @@ where
                     .return_leased_part(subscription.leased_part_from_exchangeable(leased_part));
             }
 
-            for event in subscription.next().await {
+            for mut event in subscription.next().await {
+                if let ListenEvent::Updates(parts) = &mut event {
+                    if dynamic_cfg.stats_filter_enabled() {
+                        let filtered = filter_parts(&metrics, parts, &mut should_fetch_part);
+                        for part in filtered {
+                            subscription.return_leased_part(part);
+                        }
+                    }
+                }
                 if let ListenEvent::Progress(ref progress) = event {
                     // If `until.less_equal(progress)`, it means that all subsequent batches will
                     // contain only times greater or equal to `until`, which means they can be
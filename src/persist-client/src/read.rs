@@ -18,10 +18,13 @@ use std::time::{Duration, SystemTime};
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use futures::Stream;
+use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_ore::task::RuntimeExt;
+use mz_ore::vec::VecExt;
 use mz_persist::location::{Blob, SeqNo};
 use mz_persist::retry::Retry;
+use mz_persist_types::stats::PartStats;
 use mz_persist_types::{Codec, Codec64};
 use serde::{Deserialize, Serialize};
 use timely::progress::{Antichain, Timestamp};
@@ -38,7 +41,7 @@ use crate::fetch::{
 use crate::internal::machine::Machine;
 use crate::internal::metrics::{Metrics, MetricsRetryStream};
 use crate::internal::state::{HollowBatch, Since};
-use crate::{parse_id, GarbageCollector, PersistConfig};
+use crate::{parse_id, GarbageCollector, PersistConfig, ShardId};
 
 /// An opaque identifier for a reader of a persist durable TVC (aka shard).
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -430,6 +433,56 @@ where
     }
 }
 
+/// Removes from `parts` those whose stats show that `should_fetch_part` would
+/// reject them and returns them, so the caller can give back their leases.
+///
+/// Parts without stats are always kept.
+pub(crate) fn filter_parts<T, F>(
+    metrics: &Metrics,
+    parts: &mut Vec<LeasedBatchPart<T>>,
+    should_fetch_part: &mut F,
+) -> Vec<LeasedBatchPart<T>>
+where
+    F: FnMut(&PartStats) -> bool,
+{
+    let filtered: Vec<_> = parts
+        .drain_filter_swapping(|part| match part.stats() {
+            Some(stats) => !should_fetch_part(stats),
+            None => false,
+        })
+        .collect();
+    for part in filtered.iter() {
+        metrics.pushdown.parts_filtered_count.inc();
+        metrics
+            .pushdown
+            .parts_filtered_bytes
+            .inc_by(u64::cast_from(part.encoded_size_bytes()));
+    }
+    for part in parts.iter().filter(|x| x.stats().is_some()) {
+        metrics.pushdown.parts_fetched_count.inc();
+        metrics
+            .pushdown
+            .parts_fetched_bytes
+            .inc_by(u64::cast_from(part.encoded_size_bytes()));
+    }
+    filtered
+}
+
+/// Aggregate statistics about the contents of a shard as of some time.
+///
+/// See [ReadHandle::snapshot_stats].
+#[derive(Debug, Clone)]
+pub struct SnapshotStats {
+    /// The shard these statistics are for.
+    pub shard_id: ShardId,
+    /// An upper bound on the number of records in the shard as of the
+    /// requested time, as the updates have not necessarily been consolidated.
+    pub num_updates: usize,
+    /// Statistics about the keys of the records, or None if they are missing
+    /// for any part.
+    pub stats: Option<PartStats>,
+}
+
 /// A "capability" granting the ability to read the state of some shard at times
 /// greater or equal to `self.since()`.
 ///
@@ -625,6 +678,51 @@ where
         Ok(leased_parts)
     }
 
+    /// Like [Self::snapshot], but omits the parts whose statistics show that
+    /// they don't contain anything the caller needs.
+    ///
+    /// `should_fetch_part` is called with the stats of each part and returns
+    /// false if the part can be skipped. Parts without stats are always
+    /// returned, as are all parts when
+    /// [crate::cfg::DynamicConfig::stats_filter_enabled] is off. The leases of
+    /// skipped parts are returned to this handle before this method returns.
+    #[instrument(level = "trace", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn snapshot_filtered(
+        &mut self,
+        as_of: Antichain<T>,
+        mut should_fetch_part: impl FnMut(&PartStats) -> bool,
+    ) -> Result<Vec<LeasedBatchPart<T>>, Since<T>> {
+        let mut parts = self.snapshot(as_of).await?;
+        if self.cfg.dynamic.stats_filter_enabled() {
+            let filtered = filter_parts(&self.metrics, &mut parts, &mut should_fetch_part);
+            for part in filtered {
+                self.process_returned_leased_part(part);
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Returns aggregate statistics about the contents of the shard TVC at
+    /// `as_of`, computed from the metadata of its batches and without fetching
+    /// them.
+    ///
+    /// This has the same blocking and error semantics as [Self::snapshot].
+    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn snapshot_stats(&mut self, as_of: Antichain<T>) -> Result<SnapshotStats, Since<T>> {
+        let batches = self.machine.snapshot(&as_of).await?;
+        let num_updates = batches.iter().map(|batch| batch.len).sum();
+        let stats = PartStats::merge_all(
+            batches
+                .iter()
+                .flat_map(|batch| batch.parts.iter().map(|part| part.stats.as_ref())),
+        );
+        Ok(SnapshotStats {
+            shard_id: self.machine.shard_id(),
+            num_updates,
+            stats,
+        })
+    }
+
     /// Generates a [Self::snapshot], and fetches all of the batches
     /// it contains.
     pub async fn snapshot_and_fetch(
@@ -649,6 +747,32 @@ where
         Ok(contents)
     }
 
+    /// Fetches the contents of `parts`, as returned by [Self::snapshot], and
+    /// passes each of the updates they contain to `f`.
+    ///
+    /// Unlike [Self::snapshot_and_fetch], this only holds the contents of one
+    /// part in memory at a time, which allows callers to filter the contents
+    /// of large shards as they are fetched.
+    pub async fn fetch_snapshot_parts<F>(&mut self, parts: Vec<LeasedBatchPart<T>>, mut f: F)
+    where
+        F: FnMut(((Result<K, String>, Result<V, String>), T, D)),
+    {
+        for part in parts {
+            let (part, fetched_part) = fetch_leased_part(
+                part,
+                self.blob.as_ref(),
+                Arc::clone(&self.metrics),
+                &self.metrics.read.snapshot,
+                Some(&self.reader_id),
+            )
+            .await;
+            self.process_returned_leased_part(part);
+            for update in fetched_part {
+                f(update);
+            }
+        }
+    }
+
     /// Returns a snapshot of all of a shard's data using `as_of`, followed by
     /// listening to any future updates.
     ///
@@ -677,6 +801,7 @@ where
             desc: batch.desc.clone(),
             key: part.key,
             encoded_size_bytes: part.encoded_size_bytes,
+            stats: part.stats,
             leased_seqno: Some(self.lease_seqno()),
         })
     }
@@ -956,6 +1081,7 @@ mod tests {
     use std::str::FromStr;
 
     use crate::async_runtime::CpuHeavyRuntime;
+    use crate::cfg::PersistParameters;
     use crate::internal::metrics::Metrics;
     use crate::tests::{all_ok, new_test_client};
     use crate::{PersistClient, PersistConfig, ShardId};
@@ -991,6 +1117,55 @@ mod tests {
         drop(subscribe);
     }
 
+    // Verifies that `snapshot_filtered` skips the parts whose stats are
+    // rejected, unless stats filtering is disabled.
+    #[tokio::test]
+    async fn snapshot_filtered() {
+        let data = vec![
+            (("0".to_owned(), "zero".to_owned()), 0, 1),
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+
+        let (mut write, mut read) = new_test_client()
+            .await
+            .expect_open::<String, String, u64, i64>(crate::ShardId::new())
+            .await;
+
+        write.expect_compare_and_append(&data[0..1], 0, 1).await;
+        write.expect_compare_and_append(&data[1..2], 1, 2).await;
+        write.expect_compare_and_append(&data[2..3], 2, 3).await;
+
+        let as_of = Antichain::from_elem(2);
+        let parts = read.snapshot(as_of.clone()).await.unwrap();
+        let num_parts = parts.len();
+        let num_parts_with_stats = parts.iter().filter(|x| x.stats().is_some()).count();
+        for part in parts {
+            read.process_returned_leased_part(part);
+        }
+
+        let parts = read
+            .snapshot_filtered(as_of.clone(), |_| false)
+            .await
+            .unwrap();
+        assert_eq!(parts.len(), num_parts - num_parts_with_stats);
+        for part in parts {
+            read.process_returned_leased_part(part);
+        }
+
+        PersistParameters {
+            stats_filter_enabled: Some(false),
+            ..Default::default()
+        }
+        .apply(&read.cfg);
+        let parts = read.snapshot_filtered(as_of, |_| false).await.unwrap();
+        assert_eq!(parts.len(), num_parts);
+        for part in parts {
+            read.process_returned_leased_part(part);
+        }
+        read.expire().await;
+    }
+
     // Verifies the semantics of `SeqNo` leases + checks dropping `LeasedBatchPart` semantics.
     #[tokio::test]
     async fn seqno_leases() {
@@ -133,6 +133,7 @@ where
     pub(crate) blob: Arc<dyn Blob + Send + Sync>,
     pub(crate) cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
     pub(crate) writer_id: WriterId,
+    pub(crate) key_schema: Arc<K::Schema>,
 
     pub(crate) upper: Antichain<T>,
     pub(crate) last_heartbeat: EpochMillis,
@@ -157,6 +158,7 @@ where
         blob: Arc<dyn Blob + Send + Sync>,
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         writer_id: WriterId,
+        key_schema: Arc<K::Schema>,
         upper: Antichain<T>,
         last_heartbeat: EpochMillis,
     ) -> Self {
@@ -169,6 +171,7 @@ where
             blob,
             cpu_heavy_runtime,
             writer_id: writer_id.clone(),
+            key_schema,
             upper,
             last_heartbeat,
             explicitly_expired: false,
@@ -489,6 +492,118 @@ where
         Ok(Ok(()))
     }
 
+    /// Returns whether [Self::compare_and_append_atomic] can be used to append
+    /// to more than one shard in the consensus that stores this one.
+    pub fn supports_atomic_multi_shard(&self) -> bool {
+        self.machine.applier.state_versions.supports_multi()
+    }
+
+    /// Atomically applies a [Self::compare_and_append_batch] to each of
+    /// several shards: either every append commits or none of them do.
+    ///
+    /// The innermost `Result` is `Ok` if all of the batches were successfully
+    /// written. If not, the index in `appends` of a shard whose upper didn't
+    /// match is returned with an `Upper` err containing that shard's current
+    /// global upper. In that case, none of the shards were modified.
+    ///
+    /// All of the shards must be stored in the same
+    /// [mz_persist::location::Consensus] (in practice, opened by
+    /// [crate::PersistClient]s with the same consensus location), otherwise an
+    /// [InvalidUsage::ConsensusMismatch] is returned. Callers that append to
+    /// more than one shard must check [Self::supports_atomic_multi_shard]
+    /// first, otherwise an [InvalidUsage::AtomicMultiShardUnsupported] is
+    /// returned.
+    ///
+    /// IMPORTANT: In case of an erroneous result the caller is responsible for
+    /// the lifecycle of the batches. See [Self::compare_and_append_batch].
+    #[instrument(level = "debug", skip_all)]
+    pub async fn compare_and_append_atomic(
+        appends: &mut [AtomicAppend<'_, K, V, T, D>],
+    ) -> Result<Result<(), (usize, UpperMismatch<T>)>, InvalidUsage<T>> {
+        if appends.len() > 1 && !appends[0].handle.supports_atomic_multi_shard() {
+            return Err(InvalidUsage::AtomicMultiShardUnsupported {
+                shard: appends[0].handle.machine.shard_id(),
+            });
+        }
+
+        let mut hollow_batches = Vec::with_capacity(appends.len());
+        for append in appends.iter() {
+            let shard_id = append.handle.machine.shard_id();
+            if shard_id != append.batch.shard_id() {
+                return Err(InvalidUsage::BatchNotFromThisShard {
+                    batch_shard: append.batch.shard_id(),
+                    handle_shard: shard_id,
+                });
+            }
+            let first = &appends[0].handle.machine;
+            if !first
+                .applier
+                .state_versions
+                .same_consensus(&append.handle.machine.applier.state_versions)
+            {
+                return Err(InvalidUsage::ConsensusMismatch {
+                    shard: first.shard_id(),
+                    other_shard: shard_id,
+                });
+            }
+
+            let since = Antichain::from_elem(T::minimum());
+            let desc = Description::new(
+                append.expected_upper.clone(),
+                append.new_upper.clone(),
+                since,
+            );
+            let () = validate_truncate_batch(&append.batch.batch.desc, &desc)?;
+            hollow_batches.push(HollowBatch {
+                desc,
+                parts: append.batch.batch.parts.clone(),
+                len: append.batch.batch.len,
+                runs: vec![],
+            });
+        }
+
+        let heartbeat_timestamp = appends
+            .first()
+            .map_or(0, |append| (append.handle.cfg.now)());
+        let res = {
+            let mut machine_appends = appends
+                .iter_mut()
+                .zip(hollow_batches.iter())
+                .map(|(append, batch)| {
+                    let handle = &mut *append.handle;
+                    (&mut handle.machine, batch, &handle.writer_id)
+                })
+                .collect::<Vec<_>>();
+            Machine::compare_and_append_multi(&mut machine_appends, heartbeat_timestamp).await
+        };
+
+        let maintenances = match res {
+            Ok(Ok(x)) => x,
+            Ok(Err(invalid_usage)) => return Err(invalid_usage),
+            Err((idx, Upper(current_upper))) => {
+                let append = &mut appends[idx];
+                append.handle.upper = current_upper.clone();
+                return Ok(Err((
+                    idx,
+                    UpperMismatch {
+                        current: current_upper,
+                        expected: append.expected_upper.clone(),
+                    },
+                )));
+            }
+        };
+
+        for (append, (_seqno, maintenance)) in appends.iter_mut().zip(maintenances) {
+            append.handle.upper = append.new_upper.clone();
+            append.handle.last_heartbeat = heartbeat_timestamp;
+            append.batch.mark_consumed();
+            let handle = &*append.handle;
+            maintenance.start_performing(&handle.machine, &handle.gc, handle.compact.as_ref());
+        }
+
+        Ok(Ok(()))
+    }
+
     /// Turns the given [`WriterEnrichedHollowBatch`] back into a [`Batch`]
     /// which can be used to append it to this shard.
     pub fn batch_from_hollow_batch(
@@ -532,6 +647,7 @@ where
             Arc::clone(&self.cpu_heavy_runtime),
             self.machine.shard_id().clone(),
             self.writer_id.clone(),
+            Arc::clone(&self.key_schema),
             Antichain::from_elem(T::minimum()),
             None,
             false,
@@ -710,6 +826,25 @@ where
     }
 }
 
+/// One of the shards in a [WriteHandle::compare_and_append_atomic].
+#[derive(Debug)]
+pub struct AtomicAppend<'a, K, V, T, D>
+where
+    T: Timestamp + Lattice + Codec64,
+    K: Debug + Codec,
+    V: Debug + Codec,
+    D: Semigroup + Codec64 + Send + Sync,
+{
+    /// The handle of the shard being appended to.
+    pub handle: &'a mut WriteHandle<K, V, T, D>,
+    /// The updates to append.
+    pub batch: &'a mut Batch<K, V, T, D>,
+    /// The expected current upper of the shard.
+    pub expected_upper: Antichain<T>,
+    /// The new upper of the shard, if the append succeeds.
+    pub new_upper: Antichain<T>,
+}
+
 impl<K, V, T, D> Drop for WriteHandle<K, V, T, D>
 where
     T: Timestamp + Lattice + Codec64,
@@ -838,6 +973,92 @@ mod tests {
         assert_eq!(actual, all_ok(&expected, 3));
     }
 
+    #[tokio::test]
+    async fn compare_and_append_atomic() {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+
+        let client = new_test_client().await;
+        let (mut write0, mut read0) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+        let (mut write1, mut read1) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+
+        assert!(write0.supports_atomic_multi_shard());
+
+        // Both shards are at upper 0, so the atomic append succeeds.
+        let mut batch0 = write0.expect_batch(&data[..1], 0, 2).await;
+        let mut batch1 = write1.expect_batch(&data[1..], 0, 3).await;
+        let res = WriteHandle::compare_and_append_atomic(&mut [
+            AtomicAppend {
+                handle: &mut write0,
+                batch: &mut batch0,
+                expected_upper: Antichain::from_elem(0),
+                new_upper: Antichain::from_elem(2),
+            },
+            AtomicAppend {
+                handle: &mut write1,
+                batch: &mut batch1,
+                expected_upper: Antichain::from_elem(0),
+                new_upper: Antichain::from_elem(3),
+            },
+        ])
+        .await
+        .expect("invalid usage");
+        assert_eq!(res, Ok(()));
+        assert_eq!(write0.upper(), &Antichain::from_elem(2));
+        assert_eq!(write1.upper(), &Antichain::from_elem(3));
+
+        // The expected upper of the second shard is wrong, so neither shard is
+        // appended to.
+        let mut batch0 = write0.expect_batch(&data[..1], 2, 4).await;
+        let mut batch1 = write1.expect_batch(&data[1..], 2, 4).await;
+        let res = WriteHandle::compare_and_append_atomic(&mut [
+            AtomicAppend {
+                handle: &mut write0,
+                batch: &mut batch0,
+                expected_upper: Antichain::from_elem(2),
+                new_upper: Antichain::from_elem(4),
+            },
+            AtomicAppend {
+                handle: &mut write1,
+                batch: &mut batch1,
+                expected_upper: Antichain::from_elem(2),
+                new_upper: Antichain::from_elem(4),
+            },
+        ])
+        .await
+        .expect("invalid usage");
+        assert_eq!(
+            res,
+            Err((
+                1,
+                UpperMismatch {
+                    current: Antichain::from_elem(3),
+                    expected: Antichain::from_elem(2),
+                }
+            ))
+        );
+        assert_eq!(write0.fetch_recent_upper().await, &Antichain::from_elem(2));
+        batch0.delete().await;
+        batch1.delete().await;
+
+        assert_eq!(
+            read0.expect_snapshot_and_fetch(1).await,
+            all_ok(&data[..1], 1)
+        );
+        assert_eq!(
+            read1.expect_snapshot_and_fetch(2).await,
+            all_ok(&data[1..], 2)
+        );
+    }
+
     #[test]
     fn writer_id_human_readable_serde() {
         #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +28,7 @@ use crate::columnar::{
     ColumnFormat, ColumnGet, ColumnPush, Data, DataType, PartDecoder, PartEncoder, Schema,
 };
 use crate::part::{ColumnsMut, ColumnsRef};
+use crate::stats::{SimpleStatsBuilder, StatsBuilder};
 use crate::{Codec, Codec64, Opaque};
 
 /// An implementation of [Schema] for [()].
@@ -150,6 +151,13 @@ impl Schema<String> for StringSchema {
             },
         })
     }
+
+    fn stats_builder(&self) -> Option<Box<dyn StatsBuilder<String>>> {
+        Some(Box::new(SimpleStatsBuilder::new(
+            "",
+            |col, val: &String| col.push_str(val),
+        )))
+    }
 }
 
 impl Codec for String {
@@ -53,6 +53,7 @@ use std::fmt::Debug;
 use crate::codec_impls::UnitSchema;
 use crate::columnar::sealed::ColumnRef;
 use crate::part::{ColumnsMut, ColumnsRef, PartBuilder};
+use crate::stats::StatsBuilder;
 use crate::Codec;
 
 /// A type understood by persist.
@@ -223,6 +224,12 @@ pub trait Schema<T>: Debug {
 
     /// Returns a [Self::Encoder<'a>] for the given columns.
     fn encoder<'a>(&self, cols: ColumnsMut<'a>) -> Result<Self::Encoder<'a>, String>;
+
+    /// Returns a [StatsBuilder] for computing statistics about values of this
+    /// type, or None if this schema doesn't support statistics.
+    fn stats_builder(&self) -> Option<Box<dyn StatsBuilder<T>>> {
+        None
+    }
 }
 
 /// A helper for writing tests that validate that a piece of data roundtrips
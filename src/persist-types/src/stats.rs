@@ -0,0 +1,430 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Aggregate statistics about data stored in persist.
+//!
+//! Statistics are computed over the keys of each part (blob) as it is written
+//! and stored alongside the part's metadata in state. Readers can use them to
+//! skip fetching parts that provably contain no data they care about (aka
+//! filter pushdown).
+//!
+//! All statistics are conservative: the true min of a column is always greater
+//! or equal to [ColumnStats::lower] and the true max is always less or equal to
+//! [ColumnStats::upper]. A column that is missing from [StructStats::cols] has
+//! unknown contents and must not be used to prune.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of bytes retained for a string or bytes bound.
+///
+/// Larger lower bounds are truncated (a prefix always sorts before the value
+/// it was taken from). Larger upper bounds cannot be truncated without
+/// incrementing them, so the column's stats are dropped instead.
+pub const STATS_MAX_BYTES: usize = 100;
+
+/// A single value used as a bound in [ColumnStats].
+///
+/// Integers of all widths are widened into [PrimitiveValue::I64] and
+/// [PrimitiveValue::U64]. Because a given column always has a single type, the
+/// widening doesn't affect comparisons.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PrimitiveValue {
+    /// A [bool] value.
+    Bool(bool),
+    /// A signed integer value.
+    I64(i64),
+    /// An unsigned integer value.
+    U64(u64),
+    /// A [String] value.
+    String(String),
+    /// A byte string value.
+    Bytes(Vec<u8>),
+}
+
+/// Statistics about a single column of a part.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ColumnStats {
+    /// The number of null values in the column.
+    pub nulls: usize,
+    /// A lower bound on the non-null values in the column, or None if every
+    /// value is null.
+    pub lower: Option<PrimitiveValue>,
+    /// An upper bound on the non-null values in the column, or None if every
+    /// value is null.
+    pub upper: Option<PrimitiveValue>,
+}
+
+impl ColumnStats {
+    /// Widens `self` to also bound the values described by `other`.
+    pub fn merge(&mut self, other: &ColumnStats) {
+        self.nulls += other.nulls;
+        match (&mut self.lower, &other.lower) {
+            (Some(s), Some(o)) if *o < *s => *s = o.clone(),
+            (s @ None, Some(o)) => *s = Some(o.clone()),
+            _ => {}
+        }
+        match (&mut self.upper, &other.upper) {
+            (Some(s), Some(o)) if *o > *s => *s = o.clone(),
+            (s @ None, Some(o)) => *s = Some(o.clone()),
+            _ => {}
+        }
+    }
+}
+
+/// Statistics about a struct of named columns.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StructStats {
+    /// An upper bound on the number of values described by these stats.
+    pub len: usize,
+    /// Statistics for each column, keyed by column name.
+    ///
+    /// Columns without statistics are omitted.
+    pub cols: BTreeMap<String, ColumnStats>,
+}
+
+impl StructStats {
+    /// Widens `self` to also bound the values described by `other`.
+    ///
+    /// Only columns with statistics in both inputs retain them.
+    pub fn merge(&mut self, other: &StructStats) {
+        self.len += other.len;
+        self.cols.retain(|name, stats| match other.cols.get(name) {
+            Some(other) => {
+                stats.merge(other);
+                true
+            }
+            None => false,
+        });
+    }
+
+    /// Returns the stats for the named column, if any.
+    pub fn col(&self, name: &str) -> Option<&ColumnStats> {
+        self.cols.get(name)
+    }
+}
+
+/// Statistics about the contents of a part (blob) of updates.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PartStats {
+    /// Statistics about the keys in the part.
+    pub key: StructStats,
+}
+
+impl PartStats {
+    /// Returns stats that bound the contents of every one of `parts`, or None
+    /// if any of them is missing stats (or there are none).
+    pub fn merge_all<'a, I: IntoIterator<Item = Option<&'a PartStats>>>(
+        parts: I,
+    ) -> Option<PartStats> {
+        let mut parts = parts.into_iter();
+        let mut merged = parts.next()??.clone();
+        for part in parts {
+            merged.key.merge(&part?.key);
+        }
+        Some(merged)
+    }
+}
+
+/// An incremental constructor of [StructStats] for values of some type.
+///
+/// See [crate::columnar::Schema::stats_builder].
+pub trait StatsBuilder<T>: Debug + Send {
+    /// Includes the given value in the statistics.
+    fn push(&mut self, val: &T);
+
+    /// Completes construction of the statistics.
+    fn finish(self: Box<Self>) -> StructStats;
+}
+
+/// An incremental constructor of [ColumnStats].
+///
+/// If values of different types are pushed into the same builder, or an upper
+/// bound would exceed [STATS_MAX_BYTES], no stats are produced for the column.
+#[derive(Debug, Default)]
+pub struct ColumnStatsBuilder {
+    nulls: usize,
+    bounds: Option<(PrimitiveValue, PrimitiveValue)>,
+    invalid: bool,
+}
+
+macro_rules! push_primitive {
+    ($fn:ident, $variant:ident, $typ:ty) => {
+        /// Includes the given non-null value in the statistics.
+        pub fn $fn(&mut self, val: $typ) {
+            match &mut self.bounds {
+                bounds @ None => {
+                    *bounds = Some((PrimitiveValue::$variant(val), PrimitiveValue::$variant(val)))
+                }
+                Some((PrimitiveValue::$variant(lower), PrimitiveValue::$variant(upper))) => {
+                    if val < *lower {
+                        *lower = val;
+                    }
+                    if val > *upper {
+                        *upper = val;
+                    }
+                }
+                Some(_) => self.invalid = true,
+            }
+        }
+    };
+}
+
+impl ColumnStatsBuilder {
+    /// Includes a null value in the statistics.
+    pub fn push_null(&mut self) {
+        self.nulls += 1;
+    }
+
+    push_primitive!(push_bool, Bool, bool);
+    push_primitive!(push_i64, I64, i64);
+    push_primitive!(push_u64, U64, u64);
+
+    /// Includes the given non-null value in the statistics.
+    pub fn push_str(&mut self, val: &str) {
+        match &mut self.bounds {
+            bounds @ None => {
+                *bounds = Some((
+                    PrimitiveValue::String(val.to_owned()),
+                    PrimitiveValue::String(val.to_owned()),
+                ))
+            }
+            Some((PrimitiveValue::String(lower), PrimitiveValue::String(upper))) => {
+                if val < lower.as_str() {
+                    lower.clear();
+                    lower.push_str(val);
+                }
+                if val > upper.as_str() {
+                    upper.clear();
+                    upper.push_str(val);
+                }
+            }
+            Some(_) => self.invalid = true,
+        }
+    }
+
+    /// Includes the given non-null value in the statistics.
+    pub fn push_bytes(&mut self, val: &[u8]) {
+        match &mut self.bounds {
+            bounds @ None => {
+                *bounds = Some((
+                    PrimitiveValue::Bytes(val.to_owned()),
+                    PrimitiveValue::Bytes(val.to_owned()),
+                ))
+            }
+            Some((PrimitiveValue::Bytes(lower), PrimitiveValue::Bytes(upper))) => {
+                if val < lower.as_slice() {
+                    lower.clear();
+                    lower.extend_from_slice(val);
+                }
+                if val > upper.as_slice() {
+                    upper.clear();
+                    upper.extend_from_slice(val);
+                }
+            }
+            Some(_) => self.invalid = true,
+        }
+    }
+
+    /// Marks the column as unsupported, so that no stats are produced for it.
+    pub fn invalidate(&mut self) {
+        self.invalid = true;
+    }
+
+    /// Completes construction of the statistics, returning None if they could
+    /// not be computed.
+    pub fn finish(self) -> Option<ColumnStats> {
+        if self.invalid {
+            return None;
+        }
+        let (lower, upper) = match self.bounds {
+            Some((lower, upper)) => (Some(truncate_lower(lower)), Some(truncate_upper(upper)?)),
+            None => (None, None),
+        };
+        Some(ColumnStats {
+            nulls: self.nulls,
+            lower,
+            upper,
+        })
+    }
+}
+
+fn truncate_lower(val: PrimitiveValue) -> PrimitiveValue {
+    match val {
+        PrimitiveValue::String(mut x) if x.len() > STATS_MAX_BYTES => {
+            let mut idx = STATS_MAX_BYTES;
+            while !x.is_char_boundary(idx) {
+                idx -= 1;
+            }
+            x.truncate(idx);
+            PrimitiveValue::String(x)
+        }
+        PrimitiveValue::Bytes(mut x) if x.len() > STATS_MAX_BYTES => {
+            x.truncate(STATS_MAX_BYTES);
+            PrimitiveValue::Bytes(x)
+        }
+        x => x,
+    }
+}
+
+fn truncate_upper(val: PrimitiveValue) -> Option<PrimitiveValue> {
+    match val {
+        PrimitiveValue::String(x) if x.len() > STATS_MAX_BYTES => None,
+        PrimitiveValue::Bytes(x) if x.len() > STATS_MAX_BYTES => None,
+        x => Some(x),
+    }
+}
+
+/// A [StatsBuilder] for types with a single column.
+#[derive(Debug)]
+pub struct SimpleStatsBuilder<T> {
+    name: String,
+    len: usize,
+    col: ColumnStatsBuilder,
+    push: fn(&mut ColumnStatsBuilder, &T),
+}
+
+impl<T> SimpleStatsBuilder<T> {
+    /// Returns a new [SimpleStatsBuilder] for a column with the given name.
+    pub fn new(name: &str, push: fn(&mut ColumnStatsBuilder, &T)) -> Self {
+        SimpleStatsBuilder {
+            name: name.to_owned(),
+            len: 0,
+            col: ColumnStatsBuilder::default(),
+            push,
+        }
+    }
+}
+
+impl<T: Debug> StatsBuilder<T> for SimpleStatsBuilder<T> {
+    fn push(&mut self, val: &T) {
+        self.len += 1;
+        (self.push)(&mut self.col, val)
+    }
+
+    fn finish(self: Box<Self>) -> StructStats {
+        let mut cols = BTreeMap::new();
+        if let Some(col) = self.col.finish() {
+            cols.insert(self.name, col);
+        }
+        StructStats {
+            len: self.len,
+            cols,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_stats_builder() {
+        let mut b = ColumnStatsBuilder::default();
+        b.push_i64(3);
+        b.push_null();
+        b.push_i64(-1);
+        b.push_i64(7);
+        assert_eq!(
+            b.finish(),
+            Some(ColumnStats {
+                nulls: 1,
+                lower: Some(PrimitiveValue::I64(-1)),
+                upper: Some(PrimitiveValue::I64(7)),
+            })
+        );
+
+        let mut b = ColumnStatsBuilder::default();
+        b.push_null();
+        assert_eq!(
+            b.finish(),
+            Some(ColumnStats {
+                nulls: 1,
+                lower: None,
+                upper: None,
+            })
+        );
+
+        // Mixed types produce no stats.
+        let mut b = ColumnStatsBuilder::default();
+        b.push_i64(1);
+        b.push_str("a");
+        assert_eq!(b.finish(), None);
+    }
+
+    #[test]
+    fn column_stats_truncation() {
+        let long = "a".repeat(STATS_MAX_BYTES + 1);
+
+        // A long lower bound is truncated.
+        let mut b = ColumnStatsBuilder::default();
+        b.push_str(&long);
+        b.push_str("b");
+        let stats = b.finish().expect("lower is truncatable");
+        assert_eq!(
+            stats.lower,
+            Some(PrimitiveValue::String("a".repeat(STATS_MAX_BYTES)))
+        );
+        assert_eq!(stats.upper, Some(PrimitiveValue::String("b".to_owned())));
+
+        // A long upper bound drops the stats.
+        let mut b = ColumnStatsBuilder::default();
+        b.push_str(&long);
+        b.push_str("0");
+        assert_eq!(b.finish(), None);
+
+        // Truncation respects char boundaries.
+        let mut b = ColumnStatsBuilder::default();
+        b.push_str(&format!("a{}", "é".repeat(STATS_MAX_BYTES)));
+        b.push_str("b");
+        let lower = b.finish().and_then(|x| x.lower);
+        assert_eq!(
+            lower,
+            Some(PrimitiveValue::String(format!(
+                "a{}",
+                "é".repeat(STATS_MAX_BYTES / 2 - 1)
+            )))
+        );
+    }
+
+    #[test]
+    fn struct_stats_merge() {
+        let col = |nulls, lower, upper| ColumnStats {
+            nulls,
+            lower: Some(PrimitiveValue::U64(lower)),
+            upper: Some(PrimitiveValue::U64(upper)),
+        };
+        let mut a = StructStats {
+            len: 3,
+            cols: BTreeMap::from([
+                ("a".to_owned(), col(0, 2, 5)),
+                ("b".to_owned(), col(1, 0, 1)),
+            ]),
+        };
+        let b = StructStats {
+            len: 2,
+            cols: BTreeMap::from([("a".to_owned(), col(1, 0, 3))]),
+        };
+        a.merge(&b);
+        assert_eq!(
+            a,
+            StructStats {
+                len: 5,
+                cols: BTreeMap::from([("a".to_owned(), col(1, 0, 5))]),
+            }
+        );
+
+        let p = PartStats { key: b };
+        assert_eq!(PartStats::merge_all([Some(&p), None]), None);
+        assert_eq!(PartStats::merge_all([]), None);
+        assert_eq!(PartStats::merge_all([Some(&p)]), Some(p.clone()));
+    }
+}
@@ -17,7 +17,7 @@ use anyhow::anyhow;
 use tracing::warn;
 use url::Url;
 
-use crate::file::{FileBlob, FileBlobConfig};
+use crate::file::{FileBlob, FileBlobConfig, FileConsensus, FileConsensusConfig};
 use crate::location::{Blob, Consensus, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::metrics::{PostgresConsensusMetrics, S3BlobMetrics};
@@ -139,6 +139,8 @@ impl BlobConfig {
 /// Config for an implementation of [Consensus].
 #[derive(Debug, Clone)]
 pub enum ConsensusConfig {
+    /// Config for [FileConsensus].
+    File(FileConsensusConfig),
     /// Config for [PostgresConsensus].
     Postgres(PostgresConsensusConfig),
     /// Config for [MemConsensus], only available in testing.
@@ -161,6 +163,7 @@ impl ConsensusConfig {
     /// Opens the associated implementation of [Consensus].
     pub async fn open(self) -> Result<Arc<dyn Consensus + Send + Sync>, ExternalError> {
         match self {
+            ConsensusConfig::File(config) => Ok(Arc::new(FileConsensus::open(config).await?)),
             ConsensusConfig::Postgres(config) => {
                 Ok(Arc::new(PostgresConsensus::open(config).await?))
             }
@@ -183,6 +186,7 @@ impl ConsensusConfig {
         })?;
 
         let config = match url.scheme() {
+            "file" => Ok(ConsensusConfig::File(FileConsensusConfig::from(url.path()))),
             "postgres" | "postgresql" => Ok(ConsensusConfig::Postgres(
                 PostgresConsensusConfig::new(value, knobs, metrics)?,
             )),
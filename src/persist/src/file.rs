@@ -7,9 +7,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! File backed implementations for testing and benchmarking.
+//! File backed implementations for testing, benchmarking, and single-node
+//! deployments.
 
 use std::io::ErrorKind;
+use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
@@ -19,9 +21,12 @@ use fail::fail_point;
 use mz_ore::cast::CastFrom;
 use tokio::fs::{self, File, OpenOptions};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
 
 use crate::error::Error;
-use crate::location::{Atomicity, Blob, BlobMetadata, ExternalError};
+use crate::location::{
+    Atomicity, Blob, BlobMetadata, Consensus, ExternalError, SeqNo, VersionedData,
+};
 
 /// Configuration for opening a [FileBlob].
 #[derive(Debug, Clone)]
@@ -75,7 +80,7 @@ impl FileBlob {
 
 #[async_trait]
 impl Blob for FileBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         let file_path = self.blob_path(&FileBlob::replace_forward_slashes(key));
         let mut file = match File::open(file_path).await {
             Ok(file) => file,
@@ -84,7 +89,7 @@ impl Blob for FileBlob {
         };
         let mut buf = Vec::new();
         file.read_to_end(&mut buf).await?;
-        Ok(Some(buf))
+        Ok(Some(Bytes::from(buf)))
     }
 
     async fn list_keys_and_metadata(
@@ -238,9 +243,495 @@ impl Blob for FileBlob {
     }
 }
 
+/// Configuration for opening a [FileConsensus].
+#[derive(Debug, Clone)]
+pub struct FileConsensusConfig {
+    base_dir: PathBuf,
+}
+
+impl<P: AsRef<Path>> From<P> for FileConsensusConfig {
+    fn from(base_dir: P) -> Self {
+        FileConsensusConfig {
+            base_dir: base_dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// Implementation of [Consensus] backed by files.
+///
+/// Each key is a directory containing one file per version of the data. The
+/// versions of a key are numbered by a dense "slot", which is encoded into the
+/// file name along with the version's [SeqNo]. A compare_and_set that observed
+/// slot `n` as the current version writes the new version to a temp file, then
+/// atomically links it into place as slot `n+1`. The link fails if some other
+/// writer has already created that slot. Truncation only ever removes versions
+/// older than the current one, so the slot numbering is never reused.
+///
+/// Every operation holds an advisory lock on the directory, shared for reads
+/// and exclusive for writes, which serializes writers even between processes
+/// sharing the same directory. A compare_and_set_multi first durably records
+/// all of its new versions in an intent file and removes it once every version
+/// has been linked into place. If a writer crashes in between, the next
+/// operation to take the lock finishes linking the versions before it
+/// proceeds, so either all of them become visible or none do.
+///
+/// This is intended for single-node deployments where running a Postgres
+/// server is undesirable. The directory must be on a local filesystem that
+/// supports hard links and `flock`.
+#[derive(Debug)]
+pub struct FileConsensus {
+    base_dir: PathBuf,
+}
+
+/// An advisory lock on the directory of a [FileConsensus], released on drop.
+#[derive(Debug)]
+struct ConsensusLock {
+    _file: std::fs::File,
+}
+
+/// A version of the data at some key, as identified by its file name.
+#[derive(Debug, Clone, Copy)]
+struct FileVersion {
+    slot: u64,
+    seqno: SeqNo,
+}
+
+impl FileVersion {
+    fn file_name(&self) -> String {
+        format!("{:020}-{:020}", self.slot, self.seqno.0)
+    }
+
+    fn parse(file_name: &str) -> Option<Self> {
+        let (slot, seqno) = file_name.split_once('-')?;
+        Some(FileVersion {
+            slot: slot.parse().ok()?,
+            seqno: SeqNo(seqno.parse().ok()?),
+        })
+    }
+}
+
+impl FileConsensus {
+    const LOCK_FILE: &'static str = ".lock";
+    const INTENT_FILE: &'static str = ".multi";
+    const INTENT_TMP_FILE: &'static str = ".multi.tmp";
+    const TMP_PREFIX: &'static str = ".tmp-";
+
+    /// Opens the given location for non-exclusive read-write access.
+    ///
+    /// This removes any temp files left behind by writers that crashed.
+    pub async fn open(config: FileConsensusConfig) -> Result<Self, ExternalError> {
+        let base_dir = config.base_dir;
+        fs::create_dir_all(&base_dir).await.map_err(Error::from)?;
+        let consensus = FileConsensus { base_dir };
+        let _lock = consensus.lock(true).await?;
+        consensus.remove_tmp_files().await?;
+        Ok(consensus)
+    }
+
+    /// Takes a lock on the directory, exclusive if `exclusive` and shared
+    /// otherwise.
+    ///
+    /// Before returning, this finishes any compare_and_set_multi that a crashed
+    /// writer left behind, so the caller never observes it partially applied.
+    async fn lock(&self, exclusive: bool) -> Result<ConsensusLock, ExternalError> {
+        loop {
+            let lock = self.flock(exclusive).await?;
+            match fs::metadata(self.base_dir.join(Self::INTENT_FILE)).await {
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(lock),
+                Err(err) => return Err(err.into()),
+                Ok(_) => {}
+            }
+            if exclusive {
+                self.apply_intent().await?;
+                return Ok(lock);
+            }
+            // Holders of a shared lock can't write, so trade ours for an
+            // exclusive one to finish the operation, then start over.
+            drop(lock);
+            let _lock = self.flock(true).await?;
+            self.apply_intent().await?;
+        }
+    }
+
+    async fn flock(&self, exclusive: bool) -> Result<ConsensusLock, ExternalError> {
+        let path = self.base_dir.join(Self::LOCK_FILE);
+        let lock = mz_ore::task::spawn_blocking(
+            || "FileConsensus::flock",
+            move || -> Result<ConsensusLock, std::io::Error> {
+                let file = std::fs::OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .open(&path)?;
+                let operation = if exclusive {
+                    libc::LOCK_EX
+                } else {
+                    libc::LOCK_SH
+                };
+                // SAFETY: `file` is an open file descriptor for the duration of
+                // the call.
+                if unsafe { libc::flock(file.as_raw_fd(), operation) } != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(ConsensusLock { _file: file })
+            },
+        )
+        .await
+        .map_err(|err| ExternalError::from(anyhow!(err)))??;
+        Ok(lock)
+    }
+
+    /// Removes the temp files of writers that crashed before linking them
+    /// into place. Must be called with the exclusive lock held.
+    async fn remove_tmp_files(&self) -> Result<(), ExternalError> {
+        match fs::remove_file(self.base_dir.join(Self::INTENT_TMP_FILE)).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+        let mut key_dirs = fs::read_dir(&self.base_dir).await?;
+        while let Some(key_dir) = key_dirs.next_entry().await? {
+            if !key_dir.file_type().await?.is_dir() {
+                continue;
+            }
+            let mut entries = fs::read_dir(key_dir.path()).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let is_tmp = entry
+                    .file_name()
+                    .to_str()
+                    .map_or(false, |x| x.starts_with(Self::TMP_PREFIX));
+                if is_tmp {
+                    fs::remove_file(entry.path()).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Links every version recorded in the intent file into place, then
+    /// removes the intent file. Must be called with the exclusive lock held.
+    async fn apply_intent(&self) -> Result<(), ExternalError> {
+        let intent_path = self.base_dir.join(Self::INTENT_FILE);
+        let intent = match fs::read(&intent_path).await {
+            Ok(intent) => intent,
+            // Someone else already applied it.
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        for (key, version, data) in Self::decode_intent(&intent)? {
+            // The version may already have been linked before the crash.
+            let _ = self.write_version(&key, &version, &data).await?;
+            File::open(self.key_dir(&key)).await?.sync_all().await?;
+        }
+        fs::remove_file(&intent_path).await?;
+        File::open(&self.base_dir).await?.sync_all().await?;
+        Ok(())
+    }
+
+    fn encode_intent(ops: &[(&str, FileVersion, &Bytes)]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (key, version, data) in ops {
+            buf.extend(u64::cast_from(key.len()).to_le_bytes());
+            buf.extend(key.as_bytes());
+            buf.extend(version.slot.to_le_bytes());
+            buf.extend(version.seqno.0.to_le_bytes());
+            buf.extend(u64::cast_from(data.len()).to_le_bytes());
+            buf.extend(&data[..]);
+        }
+        buf
+    }
+
+    fn decode_intent(mut buf: &[u8]) -> Result<Vec<(String, FileVersion, Bytes)>, ExternalError> {
+        fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], ExternalError> {
+            if buf.len() < len {
+                return Err(ExternalError::from(anyhow!(
+                    "FileConsensus intent file is truncated"
+                )));
+            }
+            let (head, tail) = buf.split_at(len);
+            *buf = tail;
+            Ok(head)
+        }
+        fn take_u64(buf: &mut &[u8]) -> Result<u64, ExternalError> {
+            let bytes = take(buf, 8)?;
+            Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+        }
+        let mut ops = Vec::new();
+        while !buf.is_empty() {
+            let key_len = usize::cast_from(take_u64(&mut buf)?);
+            let key = String::from_utf8(take(&mut buf, key_len)?.to_vec())
+                .map_err(|err| ExternalError::from(anyhow!(err)))?;
+            let slot = take_u64(&mut buf)?;
+            let seqno = SeqNo(take_u64(&mut buf)?);
+            let data_len = usize::cast_from(take_u64(&mut buf)?);
+            let data = Bytes::copy_from_slice(take(&mut buf, data_len)?);
+            ops.push((key, FileVersion { slot, seqno }, data));
+        }
+        Ok(ops)
+    }
+
+    /// Durably writes `data` as the given version of `key`, or returns
+    /// `Err(())` if that slot is already taken.
+    ///
+    /// The caller is responsible for fsync-ing the key's directory.
+    async fn write_version(
+        &self,
+        key: &str,
+        version: &FileVersion,
+        data: &Bytes,
+    ) -> Result<Result<(), ()>, ExternalError> {
+        let key_dir = self.key_dir(key);
+        fs::create_dir_all(&key_dir).await?;
+
+        // Write the data to a temp file and make it durable before linking it
+        // into place, so that the version is never observed partially written.
+        let tmp_path = key_dir.join(format!("{}{}", Self::TMP_PREFIX, Uuid::new_v4()));
+        let mut file = File::create(&tmp_path).await?;
+        file.write_all(&data[..]).await?;
+        file.sync_all().await?;
+
+        let linked = fs::hard_link(&tmp_path, key_dir.join(version.file_name())).await;
+        fs::remove_file(&tmp_path).await?;
+        match linked {
+            Ok(()) => Ok(Ok(())),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => Ok(Err(())),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn check_seqnos(expected: Option<SeqNo>, new: &VersionedData) -> Result<(), ExternalError> {
+        if let Some(expected) = expected {
+            if new.seqno <= expected {
+                return Err(ExternalError::from(
+                        anyhow!("new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                                 new.seqno, expected)));
+            }
+        }
+
+        if new.seqno.0 > i64::MAX.try_into().expect("i64::MAX known to fit in u64") {
+            return Err(ExternalError::from(anyhow!(
+                "sequence numbers must fit within [0, i64::MAX], received: {:?}",
+                new.seqno
+            )));
+        }
+        Ok(())
+    }
+
+    fn key_dir(&self, key: &str) -> PathBuf {
+        self.base_dir.join(FileBlob::replace_forward_slashes(key))
+    }
+
+    /// Returns the versions currently stored for `key`, in ascending order.
+    async fn versions(&self, key: &str) -> Result<Vec<FileVersion>, ExternalError> {
+        let mut entries = match fs::read_dir(self.key_dir(key)).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut versions = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            // Temp files (and anything else unexpected) don't parse as a
+            // version and are ignored.
+            if let Some(version) = entry.file_name().to_str().and_then(FileVersion::parse) {
+                versions.push(version);
+            }
+        }
+        versions.sort_by_key(|x| x.slot);
+        Ok(versions)
+    }
+
+    /// Reads the data for the given version, or None if it has been
+    /// concurrently truncated.
+    async fn read_version(
+        &self,
+        key: &str,
+        version: &FileVersion,
+    ) -> Result<Option<VersionedData>, ExternalError> {
+        let path = self.key_dir(key).join(version.file_name());
+        match fs::read(path).await {
+            Ok(data) => Ok(Some(VersionedData {
+                seqno: version.seqno,
+                data: Bytes::from(data),
+            })),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn scan_versions(
+        &self,
+        key: &str,
+        versions: &[FileVersion],
+        from: SeqNo,
+        limit: usize,
+    ) -> Result<Vec<VersionedData>, ExternalError> {
+        let mut results = Vec::new();
+        for version in versions.iter().filter(|x| x.seqno >= from) {
+            if results.len() >= limit {
+                break;
+            }
+            // Truncation only removes a prefix of the versions, so a missing
+            // file can safely be skipped.
+            if let Some(data) = self.read_version(key, version).await? {
+                results.push(data);
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl Consensus for FileConsensus {
+    async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+        let _lock = self.lock(false).await?;
+        let versions = self.versions(key).await?;
+        match versions.last() {
+            // The current version is never truncated, so it must still exist.
+            Some(version) => match self.read_version(key, version).await? {
+                Some(data) => Ok(Some(data)),
+                None => Err(ExternalError::from(anyhow!(
+                    "FileConsensus head {} for key {} unexpectedly missing",
+                    version.seqno,
+                    key
+                ))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    async fn compare_and_set(
+        &self,
+        key: &str,
+        expected: Option<SeqNo>,
+        new: VersionedData,
+    ) -> Result<Result<(), Vec<VersionedData>>, ExternalError> {
+        Self::check_seqnos(expected, &new)?;
+
+        let _lock = self.lock(true).await?;
+        let from = expected.map_or_else(SeqNo::minimum, |x| x.next());
+        let versions = self.versions(key).await?;
+        let current = versions.last();
+        if current.map(|x| x.seqno) != expected {
+            return Ok(Err(self
+                .scan_versions(key, &versions, from, usize::MAX)
+                .await?));
+        }
+
+        let version = FileVersion {
+            slot: current.map_or(0, |x| x.slot + 1),
+            seqno: new.seqno,
+        };
+        if let Err(()) = self.write_version(key, &version, &new.data).await? {
+            // Someone else wrote the next version first.
+            let versions = self.versions(key).await?;
+            return Ok(Err(self
+                .scan_versions(key, &versions, from, usize::MAX)
+                .await?));
+        }
+
+        // fsync the directory so that the new version is durably visible.
+        File::open(self.key_dir(key)).await?.sync_all().await?;
+        Ok(Ok(()))
+    }
+
+    async fn scan(
+        &self,
+        key: &str,
+        from: SeqNo,
+        limit: usize,
+    ) -> Result<Vec<VersionedData>, ExternalError> {
+        let _lock = self.lock(false).await?;
+        let versions = self.versions(key).await?;
+        self.scan_versions(key, &versions, from, limit).await
+    }
+
+    async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError> {
+        let _lock = self.lock(true).await?;
+        let versions = self.versions(key).await?;
+        if versions.last().map_or(true, |x| x.seqno < seqno) {
+            return Err(ExternalError::from(anyhow!(
+                "upper bound too high for truncate: {:?}",
+                seqno
+            )));
+        }
+
+        let key_dir = self.key_dir(key);
+        let mut deleted = 0;
+        for version in versions.iter().filter(|x| x.seqno < seqno) {
+            match fs::remove_file(key_dir.join(version.file_name())).await {
+                Ok(()) => deleted += 1,
+                // Truncate is idempotent, someone else may have beaten us to it.
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        if deleted > 0 {
+            File::open(&key_dir).await?.sync_all().await?;
+        }
+        Ok(deleted)
+    }
+
+    fn supports_multi(&self) -> bool {
+        true
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        for (_, expected, new) in ops {
+            Self::check_seqnos(*expected, new)?;
+        }
+
+        let _lock = self.lock(true).await?;
+        let mut versions = Vec::with_capacity(ops.len());
+        for (idx, (key, expected, new)) in ops.iter().enumerate() {
+            let current = self.versions(key).await?.last().copied();
+            if current.map(|x| x.seqno) != *expected {
+                return Ok(Err(idx));
+            }
+            let version = FileVersion {
+                slot: current.map_or(0, |x| x.slot + 1),
+                seqno: new.seqno,
+            };
+            versions.push((*key, version, &new.data));
+        }
+        match versions.as_slice() {
+            [] => return Ok(Ok(())),
+            // A single version is linked into place atomically, no need for
+            // an intent file.
+            [(key, version, data)] => {
+                self.write_version(key, version, data)
+                    .await?
+                    .map_err(|()| {
+                        ExternalError::from(anyhow!(
+                            "FileConsensus version {:?} of key {} unexpectedly exists",
+                            version,
+                            key
+                        ))
+                    })?;
+                File::open(self.key_dir(key)).await?.sync_all().await?;
+                return Ok(Ok(()));
+            }
+            _ => {}
+        }
+
+        // Durably record all of the new versions before linking any of them,
+        // so a crash in between can be rolled forward by `apply_intent`.
+        let tmp_path = self.base_dir.join(Self::INTENT_TMP_FILE);
+        let mut file = File::create(&tmp_path).await?;
+        file.write_all(&Self::encode_intent(&versions)).await?;
+        file.sync_all().await?;
+        fs::rename(&tmp_path, self.base_dir.join(Self::INTENT_FILE)).await?;
+        File::open(&self.base_dir).await?.sync_all().await?;
+
+        self.apply_intent().await?;
+        Ok(Ok(()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::blob_impl_test;
+    use crate::location::tests::{blob_impl_test, consensus_impl_test, consensus_multi_impl_test};
 
     use super::*;
 
@@ -253,4 +744,62 @@ mod tests {
         })
         .await
     }
+
+    // Verifies that opening a FileConsensus finishes a compare_and_set_multi
+    // and removes the temp files that a crashed writer left behind.
+    #[tokio::test]
+    async fn file_consensus_crash_recovery() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let consensus = FileConsensus::open(temp_dir.path().into()).await?;
+        let data = |x: &'static str| VersionedData {
+            seqno: SeqNo(1),
+            data: Bytes::from(x),
+        };
+        assert_eq!(
+            consensus.compare_and_set("k0", None, data("a")).await,
+            Ok(Ok(()))
+        );
+
+        // Simulate a writer that crashed after recording its intent and
+        // linking only the first of its versions.
+        let version = |slot| FileVersion {
+            slot,
+            seqno: SeqNo(2),
+        };
+        let (a, b) = (Bytes::from("b"), Bytes::from("c"));
+        let intent =
+            FileConsensus::encode_intent(&[("k0", version(1), &a), ("k1", version(0), &b)]);
+        fs::write(temp_dir.path().join(FileConsensus::INTENT_FILE), intent).await?;
+        assert_eq!(
+            consensus.write_version("k0", &version(1), &a).await?,
+            Ok(())
+        );
+        let tmp_path = consensus.key_dir("k0").join(".tmp-crashed");
+        fs::write(&tmp_path, "partial").await?;
+        drop(consensus);
+
+        let consensus = FileConsensus::open(temp_dir.path().into()).await?;
+        assert_eq!(
+            consensus.head("k0").await?.map(|x| x.data),
+            Some(Bytes::from("b"))
+        );
+        assert_eq!(
+            consensus.head("k1").await?.map(|x| x.data),
+            Some(Bytes::from("c"))
+        );
+        assert!(fs::metadata(&tmp_path).await.is_err());
+        assert!(
+            fs::metadata(temp_dir.path().join(FileConsensus::INTENT_FILE))
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_consensus() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        consensus_impl_test(|| FileConsensus::open(temp_dir.path().into())).await?;
+        consensus_multi_impl_test(|| FileConsensus::open(temp_dir.path().into())).await
+    }
 }
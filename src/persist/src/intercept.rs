@@ -76,7 +76,7 @@ impl InterceptBlob {
 
 #[async_trait]
 impl Blob for InterceptBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         self.blob.get(key).await
     }
 
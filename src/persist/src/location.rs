@@ -359,6 +359,41 @@ pub trait Consensus: std::fmt::Debug {
     /// `seqno` is greater than the current sequence number, or if there is no
     /// data at this key.
     async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError>;
+
+    /// Atomically performs a [Consensus::compare_and_set] for each `(key,
+    /// expected, new)` in `ops`: either every update is applied or none are.
+    ///
+    /// The keys must be distinct. If the current seqno of any key does not
+    /// equal its `expected`, nothing is applied and the index in `ops` of
+    /// (one of) the mismatched keys is returned. Callers are expected to
+    /// [Consensus::scan] for the current data themselves in that case.
+    ///
+    /// The default implementation only supports a single op. Implementations
+    /// that can atomically update multiple keys should override it, as well as
+    /// [Consensus::supports_multi]. Callers must check
+    /// [Consensus::supports_multi] before passing more than one op.
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        match ops {
+            [] => Ok(Ok(())),
+            [(key, expected, new)] => {
+                let res = self.compare_and_set(key, *expected, new.clone()).await?;
+                Ok(res.map_err(|_current| 0))
+            }
+            _ => Err(ExternalError::Determinate(Determinate::new(anyhow!(
+                "{:?} does not support atomically updating multiple keys",
+                self
+            )))),
+        }
+    }
+
+    /// Whether [Consensus::compare_and_set_multi] can atomically update more
+    /// than one key.
+    fn supports_multi(&self) -> bool {
+        false
+    }
 }
 
 /// Metadata about a particular blob stored by persist
@@ -387,7 +422,7 @@ pub const BLOB_GET_LIVENESS_KEY: &str = "LIVENESS";
 #[async_trait]
 pub trait Blob: std::fmt::Debug {
     /// Returns a reference to the value corresponding to the key.
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError>;
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError>;
 
     /// List all of the keys in the map with metadata about the entry.
     ///
@@ -455,7 +490,7 @@ pub mod tests {
     >(
         new_fn: NewFn,
     ) -> Result<(), ExternalError> {
-        let values = vec!["v0".as_bytes().to_vec(), "v1".as_bytes().to_vec()];
+        let values = vec![Bytes::from("v0"), Bytes::from("v1")];
 
         let blob0 = new_fn("path0").await?;
 
@@ -478,16 +513,12 @@ pub mod tests {
         assert_eq!(empty_keys, Vec::<String>::new());
 
         // Set a key with AllowNonAtomic and get it back.
-        blob0
-            .set(k0, values[0].clone().into(), AllowNonAtomic)
-            .await?;
+        blob0.set(k0, values[0].clone(), AllowNonAtomic).await?;
         assert_eq!(blob0.get(k0).await?, Some(values[0].clone()));
         assert_eq!(blob1.get(k0).await?, Some(values[0].clone()));
 
         // Set a key with RequireAtomic and get it back.
-        blob0
-            .set("k0a", values[0].clone().into(), RequireAtomic)
-            .await?;
+        blob0.set("k0a", values[0].clone(), RequireAtomic).await?;
         assert_eq!(blob0.get("k0a").await?, Some(values[0].clone()));
         assert_eq!(blob1.get("k0a").await?, Some(values[0].clone()));
 
@@ -500,15 +531,11 @@ pub mod tests {
         assert_eq!(blob_keys, keys(&empty_keys, &[k0, "k0a"]));
 
         // Can overwrite a key with AllowNonAtomic.
-        blob0
-            .set(k0, values[1].clone().into(), AllowNonAtomic)
-            .await?;
+        blob0.set(k0, values[1].clone(), AllowNonAtomic).await?;
         assert_eq!(blob0.get(k0).await?, Some(values[1].clone()));
         assert_eq!(blob1.get(k0).await?, Some(values[1].clone()));
         // Can overwrite a key with RequireAtomic.
-        blob0
-            .set("k0a", values[1].clone().into(), RequireAtomic)
-            .await?;
+        blob0.set("k0a", values[1].clone(), RequireAtomic).await?;
         assert_eq!(blob0.get("k0a").await?, Some(values[1].clone()));
         assert_eq!(blob1.get("k0a").await?, Some(values[1].clone()));
 
@@ -535,9 +562,7 @@ pub mod tests {
         blob_keys.sort();
         assert_eq!(blob_keys, empty_keys);
         // Can reset a deleted key to some other value.
-        blob0
-            .set(k0, values[1].clone().into(), AllowNonAtomic)
-            .await?;
+        blob0.set(k0, values[1].clone(), AllowNonAtomic).await?;
         assert_eq!(blob1.get(k0).await?, Some(values[1].clone()));
         assert_eq!(blob0.get(k0).await?, Some(values[1].clone()));
 
@@ -546,9 +571,7 @@ pub mod tests {
         let mut expected_keys = empty_keys;
         for i in 1..=5 {
             let key = format!("k{}", i);
-            blob0
-                .set(&key, values[0].clone().into(), AllowNonAtomic)
-                .await?;
+            blob0.set(&key, values[0].clone(), AllowNonAtomic).await?;
             expected_keys.push(key);
         }
 
@@ -565,9 +588,7 @@ pub mod tests {
         let mut expected_prefix_keys = vec![];
         for i in 1..=3 {
             let key = format!("k-prefix-{}", i);
-            blob0
-                .set(&key, values[0].clone().into(), AllowNonAtomic)
-                .await?;
+            blob0.set(&key, values[0].clone(), AllowNonAtomic).await?;
             expected_prefix_keys.push(key);
         }
         let mut blob_keys = get_keys_with_prefix(&blob0, "k-prefix").await?;
@@ -914,6 +935,72 @@ pub mod tests {
         Ok(())
     }
 
+    pub async fn consensus_multi_impl_test<
+        C: Consensus,
+        F: Future<Output = Result<C, ExternalError>>,
+        NewFn: FnMut() -> F,
+    >(
+        mut new_fn: NewFn,
+    ) -> Result<(), ExternalError> {
+        let consensus = new_fn().await?;
+        assert!(consensus.supports_multi());
+
+        let (k0, k1) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string());
+        let v = |seqno, data: &'static str| VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(data),
+        };
+
+        // Initializing both keys at once works.
+        assert_eq!(
+            consensus
+                .compare_and_set_multi(&[(&k0, None, v(1, "a")), (&k1, None, v(1, "b"))])
+                .await,
+            Ok(Ok(()))
+        );
+        assert_eq!(consensus.head(&k0).await, Ok(Some(v(1, "a"))));
+        assert_eq!(consensus.head(&k1).await, Ok(Some(v(1, "b"))));
+
+        // A mismatch on any key means nothing is applied.
+        assert_eq!(
+            consensus
+                .compare_and_set_multi(&[
+                    (&k0, Some(SeqNo(1)), v(2, "c")),
+                    (&k1, Some(SeqNo(0)), v(2, "d")),
+                ])
+                .await,
+            Ok(Err(1))
+        );
+        assert_eq!(consensus.head(&k0).await, Ok(Some(v(1, "a"))));
+        assert_eq!(consensus.head(&k1).await, Ok(Some(v(1, "b"))));
+
+        // Keys can advance by different amounts.
+        assert_eq!(
+            consensus
+                .compare_and_set_multi(&[
+                    (&k0, Some(SeqNo(1)), v(2, "c")),
+                    (&k1, Some(SeqNo(1)), v(5, "d")),
+                ])
+                .await,
+            Ok(Ok(()))
+        );
+        assert_eq!(consensus.head(&k0).await, Ok(Some(v(2, "c"))));
+        assert_eq!(consensus.head(&k1).await, Ok(Some(v(5, "d"))));
+
+        // The single key compare_and_set still sees the results.
+        assert_eq!(
+            consensus
+                .compare_and_set(&k1, Some(SeqNo(1)), v(6, "e"))
+                .await,
+            Ok(Err(vec![v(5, "d")]))
+        );
+
+        // An empty set of ops is trivially successful.
+        assert_eq!(consensus.compare_and_set_multi(&[]).await, Ok(Ok(())));
+
+        Ok(())
+    }
+
     #[test]
     fn timeout_error() {
         assert!(ExternalError::new_timeout(Instant::now()).is_timeout());
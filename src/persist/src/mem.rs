@@ -63,8 +63,8 @@ struct MemBlobCore {
 }
 
 impl MemBlobCore {
-    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
-        Ok(self.dataz.get(key).map(|x| x.to_vec()))
+    fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
+        Ok(self.dataz.get(key).cloned())
     }
 
     fn set(&mut self, key: &str, value: Bytes) -> Result<(), ExternalError> {
@@ -118,7 +118,7 @@ impl MemBlob {
 
 #[async_trait]
 impl Blob for MemBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         self.core.lock().await.get(key)
     }
 
@@ -236,6 +236,47 @@ impl Consensus for MemConsensus {
         Self::scan_store(&store, key, from, limit)
     }
 
+    fn supports_multi(&self) -> bool {
+        true
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        for (_, expected, new) in ops {
+            if let Some(expected) = expected {
+                if new.seqno <= *expected {
+                    return Err(ExternalError::from(
+                        anyhow!("new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                                 new.seqno, expected)));
+                }
+            }
+            if new.seqno.0 > i64::MAX.try_into().expect("i64::MAX known to fit in u64") {
+                return Err(ExternalError::from(anyhow!(
+                    "sequence numbers must fit within [0, i64::MAX], received: {:?}",
+                    new.seqno
+                )));
+            }
+        }
+
+        let mut store = self.data.lock().map_err(Error::from)?;
+        for (idx, (key, expected, _)) in ops.iter().enumerate() {
+            let seqno = store
+                .get(*key)
+                .and_then(|values| values.last())
+                .map(|data| data.seqno);
+            if seqno != *expected {
+                return Ok(Err(idx));
+            }
+        }
+        for (key, _, new) in ops {
+            store.entry(key.to_string()).or_default().push(new.clone());
+        }
+
+        Ok(Ok(()))
+    }
+
     async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError> {
         let current = self.head(key).await?;
         if current.map_or(true, |data| data.seqno < seqno) {
@@ -260,7 +301,7 @@ impl Consensus for MemConsensus {
 
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::{blob_impl_test, consensus_impl_test};
+    use crate::location::tests::{blob_impl_test, consensus_impl_test, consensus_multi_impl_test};
 
     use super::*;
 
@@ -277,6 +318,7 @@ mod tests {
 
     #[tokio::test]
     async fn mem_consensus() -> Result<(), ExternalError> {
-        consensus_impl_test(|| async { Ok(MemConsensus::default()) }).await
+        consensus_impl_test(|| async { Ok(MemConsensus::default()) }).await?;
+        consensus_multi_impl_test(|| async { Ok(MemConsensus::default()) }).await
     }
 }
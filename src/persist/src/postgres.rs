@@ -334,6 +334,25 @@ fn make_tls(config: &Config) -> Result<MakeTlsConnector, anyhow::Error> {
     Ok(tls_connector)
 }
 
+// This query has been written to execute within a single network round-trip.
+// The insert performance has been tuned against CockroachDB, ensuring it goes
+// through the fast-path 1-phase commit of CRDB. Any changes to this query
+// should confirm an EXPLAIN ANALYZE (VERBOSE) query plan contains `auto commit`
+const CAS_QUERY: &str = r#"
+    INSERT INTO consensus (shard, sequence_number, data)
+    SELECT $1, $2, $3
+    WHERE (SELECT sequence_number FROM consensus
+           WHERE shard = $1
+           ORDER BY sequence_number DESC LIMIT 1) = $4;
+"#;
+
+// Insert the new row as long as no other row exists for the same shard.
+const CAS_INIT_QUERY: &str = "INSERT INTO consensus SELECT $1, $2, $3 WHERE
+         NOT EXISTS (
+             SELECT * FROM consensus WHERE shard = $1
+         )
+         ON CONFLICT DO NOTHING";
+
 #[async_trait]
 impl Consensus for PostgresConsensus {
     async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
@@ -373,21 +392,8 @@ impl Consensus for PostgresConsensus {
         }
 
         let result = if let Some(expected) = expected {
-            // This query has been written to execute within a single
-            // network round-trip. The insert performance has been tuned
-            // against CockroachDB, ensuring it goes through the fast-path
-            // 1-phase commit of CRDB. Any changes to this query should
-            // confirm an EXPLAIN ANALYZE (VERBOSE) query plan contains
-            // `auto commit`
-            let q = r#"
-                INSERT INTO consensus (shard, sequence_number, data)
-                SELECT $1, $2, $3
-                WHERE (SELECT sequence_number FROM consensus
-                       WHERE shard = $1
-                       ORDER BY sequence_number DESC LIMIT 1) = $4;
-            "#;
             let client = self.get_connection().await?;
-            let statement = client.prepare_cached(q).await?;
+            let statement = client.prepare_cached(CAS_QUERY).await?;
             client
                 .execute(
                     &statement,
@@ -395,14 +401,8 @@ impl Consensus for PostgresConsensus {
                 )
                 .await?
         } else {
-            // Insert the new row as long as no other row exists for the same shard.
-            let q = "INSERT INTO consensus SELECT $1, $2, $3 WHERE
-                     NOT EXISTS (
-                         SELECT * FROM consensus WHERE shard = $1
-                     )
-                     ON CONFLICT DO NOTHING";
             let client = self.get_connection().await?;
-            let statement = client.prepare_cached(q).await?;
+            let statement = client.prepare_cached(CAS_INIT_QUERY).await?;
             client
                 .execute(&statement, &[&key, &new.seqno, &new.data.as_ref()])
                 .await?
@@ -490,11 +490,56 @@ impl Consensus for PostgresConsensus {
 
         Ok(usize::cast_from(result))
     }
+
+    fn supports_multi(&self) -> bool {
+        true
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        for (_, expected, new) in ops {
+            if let Some(expected) = expected {
+                if new.seqno <= *expected {
+                    return Err(Error::from(
+                        format!("new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                                 new.seqno, expected)).into());
+                }
+            }
+        }
+
+        // Unlike compare_and_set, this can't go through the 1-phase commit
+        // fast-path: each op is its own statement in one transaction, which
+        // we roll back as soon as one of them doesn't match.
+        let mut client = self.get_connection().await?;
+        let txn = client.transaction().await?;
+        for (idx, (key, expected, new)) in ops.iter().enumerate() {
+            let result = match expected {
+                Some(expected) => {
+                    let statement = txn.prepare_cached(CAS_QUERY).await?;
+                    txn.execute(&statement, &[key, &new.seqno, &new.data.as_ref(), expected])
+                        .await?
+                }
+                None => {
+                    let statement = txn.prepare_cached(CAS_INIT_QUERY).await?;
+                    txn.execute(&statement, &[key, &new.seqno, &new.data.as_ref()])
+                        .await?
+                }
+            };
+            if result != 1 {
+                txn.rollback().await?;
+                return Ok(Err(idx));
+            }
+        }
+        txn.commit().await?;
+        Ok(Ok(()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::consensus_impl_test;
+    use crate::location::tests::{consensus_impl_test, consensus_multi_impl_test};
     use tracing::info;
     use uuid::Uuid;
 
@@ -514,6 +559,7 @@ mod tests {
         };
 
         consensus_impl_test(|| PostgresConsensus::open(config.clone())).await?;
+        consensus_multi_impl_test(|| PostgresConsensus::open(config.clone())).await?;
 
         // and now verify the implementation-specific `drop_and_recreate` works as intended
         let consensus = PostgresConsensus::open(config.clone()).await?;
@@ -306,7 +306,7 @@ impl S3Blob {
 
 #[async_trait]
 impl Blob for S3Blob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         let start_overall = Instant::now();
         let path = self.get_path(key);
 
@@ -521,7 +521,7 @@ impl Blob for S3Blob {
             start_overall.elapsed(),
             num_parts
         );
-        Ok(Some(val))
+        Ok(Some(Bytes::from(val)))
     }
 
     async fn list_keys_and_metadata(
@@ -139,7 +139,7 @@ impl UnreliableBlob {
 
 #[async_trait]
 impl Blob for UnreliableBlob {
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, ExternalError> {
         self.handle.run_op("get", || self.blob.get(key)).await
     }
 
@@ -217,6 +217,21 @@ impl Consensus for UnreliableConsensus {
             .run_op("truncate", || self.consensus.truncate(key, seqno))
             .await
     }
+
+    fn supports_multi(&self) -> bool {
+        self.consensus.supports_multi()
+    }
+
+    async fn compare_and_set_multi(
+        &self,
+        ops: &[(&str, Option<SeqNo>, VersionedData)],
+    ) -> Result<Result<(), usize>, ExternalError> {
+        self.handle
+            .run_op("compare_and_set_multi", || {
+                self.consensus.compare_and_set_multi(ops)
+            })
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -344,6 +344,10 @@ impl ErrorResponse {
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            AdapterError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
+            AdapterError::UniqueViolation { .. } => SqlState::UNIQUE_VIOLATION,
+            AdapterError::TableAlteredConcurrently { .. } => SqlState::T_R_SERIALIZATION_FAILURE,
+            AdapterError::ConcurrentTableWrite { .. } => SqlState::T_R_SERIALIZATION_FAILURE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             AdapterError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
@@ -370,6 +374,7 @@ impl ErrorResponse {
             AdapterError::UnknownCursor(_) => SqlState::INVALID_CURSOR_NAME,
             AdapterError::UnknownParameter(_) => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnknownPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
+            AdapterError::UnknownSavepoint(_) => SqlState::S_E_INVALID_SPECIFICATION,
             AdapterError::UnknownLoginRole(_) => SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
             AdapterError::UnknownClusterReplica { .. } => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnmaterializableFunction(_) => SqlState::FEATURE_NOT_SUPPORTED,
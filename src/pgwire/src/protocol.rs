@@ -1276,6 +1276,7 @@ where
             | ExecuteResponse::CreatedSources
             | ExecuteResponse::CreatedTable { .. }
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedSavepoint
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
             | ExecuteResponse::Deallocate { .. }
@@ -1299,6 +1300,8 @@ where
             | ExecuteResponse::Inserted(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
+            | ExecuteResponse::ReleasedSavepoint
+            | ExecuteResponse::RolledBackToSavepoint
             | ExecuteResponse::StartedTransaction { .. }
             | ExecuteResponse::TransactionCommitted
             | ExecuteResponse::TransactionRolledBack
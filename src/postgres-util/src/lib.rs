@@ -186,6 +186,33 @@ pub fn make_tls(config: &tokio_postgres::Config) -> Result<MakeTlsConnector, Pos
     Ok(tls_connector)
 }
 
+/// Returns whether a publication only publishes `INSERT`s, i.e. was created
+/// `WITH (publish = 'insert')`, so that none of its tables' rows are ever
+/// updated or deleted in the replication stream.
+///
+/// # Errors
+///
+/// - Invalid connection string, user information, or user permissions.
+/// - Upstream publication does not exist.
+pub async fn publication_publishes_only_inserts(
+    config: &Config,
+    publication: &str,
+) -> Result<bool, PostgresError> {
+    let client = config
+        .connect("postgres_publication_publishes_only_inserts")
+        .await?;
+    let row = client
+        .query_opt(
+            "SELECT pubupdate, pubdelete FROM pg_publication WHERE pubname = $1",
+            &[&publication],
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("publication {:?} does not exist", publication))?;
+    let pubupdate: bool = row.get("pubupdate");
+    let pubdelete: bool = row.get("pubdelete");
+    Ok(!pubupdate && !pubdelete)
+}
+
 /// Fetches table schema information from an upstream Postgres source for all
 /// tables that are part of a publication, given a connection string and the
 /// publication name.
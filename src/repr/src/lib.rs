@@ -103,6 +103,7 @@ pub mod antichain;
 pub mod chrono;
 pub mod explain;
 pub mod global_id;
+pub mod spill;
 pub mod strconv;
 pub mod url;
 
@@ -114,8 +115,8 @@ pub use crate::relation::{
     ProtoRelationType, RelationDesc, RelationType,
 };
 pub use crate::row::{
-    datum_list_size, datum_size, datums_size, row_size, DatumList, DatumMap, ProtoRow, Row,
-    RowArena, RowPacker, RowRef,
+    datum_list_size, datum_size, datum_stats_value, datums_size, row_size, DatumList, DatumMap,
+    ProtoRow, Row, RowArena, RowPacker, RowRef, RowStatsBuilder,
 };
 pub use crate::scalar::{
     arb_datum, arb_range_type, AsColumnType, Datum, DatumType, PropArray, PropDatum, PropDict,
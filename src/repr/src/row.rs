@@ -46,6 +46,8 @@ use crate::{Datum, Timestamp};
 
 mod encoding;
 
+pub use encoding::{datum_stats_value, RowStatsBuilder};
+
 include!(concat!(env!("OUT_DIR"), "/mz_repr.row.rs"));
 
 /// A packed representation for `Datum`s.
@@ -169,15 +171,17 @@ impl Ord for Row {
 mod columnation {
 
     use super::Row;
-    use columnation::{Columnation, Region, StableRegion};
+    use crate::spill::SpillRegion;
+    use columnation::{Columnation, Region};
 
     /// Region allocation for `Row` data.
     ///
     /// Content bytes are stored in stable contiguous memory locations,
-    /// and then a `Row` referencing them is falsified.
+    /// and then a `Row` referencing them is falsified. The memory locations
+    /// may be backed by disk, see [`crate::spill`].
     #[derive(Default)]
     pub struct RowStack {
-        region: StableRegion<u8>,
+        region: SpillRegion,
     }
 
     impl Columnation for Row {
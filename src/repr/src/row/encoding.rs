@@ -11,6 +11,8 @@
 //!
 //! See row.proto for details.
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use bytes::BufMut;
 use chrono::Timelike;
 use dec::Decimal;
@@ -18,6 +20,7 @@ use mz_persist_types::columnar::{
     ColumnFormat, ColumnGet, ColumnPush, Data, DataType, PartDecoder, PartEncoder, Schema,
 };
 use mz_persist_types::part::{ColumnsMut, ColumnsRef};
+use mz_persist_types::stats::{ColumnStatsBuilder, PrimitiveValue, StatsBuilder, StructStats};
 use mz_persist_types::Codec;
 use prost::Message;
 use uuid::Uuid;
@@ -249,6 +252,125 @@ impl Schema<Row> for RelationDesc {
         let () = part.finish()?;
         Ok(RowEncoder(encoders))
     }
+
+    fn stats_builder(&self) -> Option<Box<dyn StatsBuilder<Row>>> {
+        Some(Box::new(RowStatsBuilder::new(self)))
+    }
+}
+
+/// A [StatsBuilder] for the columns of [Row]s described by a [RelationDesc].
+///
+/// Stats are keyed by column name. Columns whose names are not unique within
+/// the relation and columns of types without a total order that is preserved
+/// by [datum_stats_value] get no stats.
+#[derive(Debug)]
+pub struct RowStatsBuilder {
+    len: usize,
+    cols: Vec<(String, ColumnStatsBuilder)>,
+}
+
+impl RowStatsBuilder {
+    /// Returns a new [RowStatsBuilder] for rows described by `desc`.
+    pub fn new(desc: &RelationDesc) -> Self {
+        let cols = desc
+            .iter_names()
+            .map(|name| (name.as_str().to_owned(), ColumnStatsBuilder::default()))
+            .collect();
+        RowStatsBuilder { len: 0, cols }
+    }
+
+    /// Includes the given row in the statistics.
+    pub fn push_row(&mut self, row: &Row) {
+        self.len += 1;
+        for (datum, (_name, col)) in row.iter().zip(self.cols.iter_mut()) {
+            match datum {
+                Datum::Null => col.push_null(),
+                Datum::True => col.push_bool(true),
+                Datum::False => col.push_bool(false),
+                Datum::Int16(x) => col.push_i64(i64::from(x)),
+                Datum::Int32(x) => col.push_i64(i64::from(x)),
+                Datum::Int64(x) => col.push_i64(x),
+                Datum::UInt8(x) => col.push_u64(u64::from(x)),
+                Datum::UInt16(x) => col.push_u64(u64::from(x)),
+                Datum::UInt32(x) => col.push_u64(u64::from(x)),
+                Datum::UInt64(x) => col.push_u64(x),
+                Datum::Date(x) => col.push_i64(i64::from(x.unix_epoch_days())),
+                Datum::Timestamp(x) => col.push_i64(x.timestamp_micros()),
+                Datum::TimestampTz(x) => col.push_i64(x.timestamp_micros()),
+                Datum::MzTimestamp(x) => col.push_u64(u64::from(x)),
+                Datum::String(x) => col.push_str(x),
+                Datum::Bytes(x) => col.push_bytes(x),
+                _ => col.invalidate(),
+            }
+        }
+    }
+
+    /// Completes construction of the statistics.
+    pub fn finish_row_stats(self) -> StructStats {
+        let mut cols = BTreeMap::new();
+        let mut dup_names = BTreeSet::new();
+        for (name, col) in self.cols {
+            if dup_names.contains(&name) {
+                continue;
+            }
+            if cols.contains_key(&name) {
+                cols.remove(&name);
+                dup_names.insert(name);
+                continue;
+            }
+            match col.finish() {
+                Some(col) => {
+                    cols.insert(name, col);
+                }
+                None => {
+                    // Remember the name so that a later column with the same
+                    // name isn't mistaken for this one.
+                    dup_names.insert(name);
+                }
+            }
+        }
+        StructStats {
+            len: self.len,
+            cols,
+        }
+    }
+}
+
+impl StatsBuilder<Row> for RowStatsBuilder {
+    fn push(&mut self, val: &Row) {
+        self.push_row(val)
+    }
+
+    fn finish(self: Box<Self>) -> StructStats {
+        self.finish_row_stats()
+    }
+}
+
+/// Returns the representation of `datum` used as a bound in persist stats, if
+/// its type supports stats.
+///
+/// This matches the representation used by [RowStatsBuilder] so that a datum
+/// can be compared against the stats of the column it belongs to.
+pub fn datum_stats_value(datum: Datum) -> Option<PrimitiveValue> {
+    let val = match datum {
+        Datum::True => PrimitiveValue::Bool(true),
+        Datum::False => PrimitiveValue::Bool(false),
+        Datum::Int16(x) => PrimitiveValue::I64(i64::from(x)),
+        Datum::Int32(x) => PrimitiveValue::I64(i64::from(x)),
+        Datum::Int64(x) => PrimitiveValue::I64(x),
+        Datum::UInt8(x) => PrimitiveValue::U64(u64::from(x)),
+        Datum::UInt16(x) => PrimitiveValue::U64(u64::from(x)),
+        Datum::UInt32(x) => PrimitiveValue::U64(u64::from(x)),
+        Datum::UInt64(x) => PrimitiveValue::U64(x),
+        Datum::Date(x) => PrimitiveValue::I64(i64::from(x.unix_epoch_days())),
+        Datum::Timestamp(x) => PrimitiveValue::I64(x.timestamp_micros()),
+        Datum::TimestampTz(x) => PrimitiveValue::I64(x.timestamp_micros()),
+        Datum::MzTimestamp(x) => PrimitiveValue::U64(u64::from(x)),
+        Datum::String(x) => PrimitiveValue::String(x.to_owned()),
+        Datum::Bytes(x) => PrimitiveValue::Bytes(x.to_owned()),
+        _ => return None,
+    };
+    Some(val)
 }
 
 impl<'a> From<Datum<'a>> for ProtoDatum {
@@ -685,4 +807,73 @@ mod tests {
             Ok(())
         );
     }
+
+    #[test]
+    fn row_stats() {
+        use mz_persist_types::stats::{ColumnStats, PrimitiveValue};
+
+        let typ = |scalar_type| ColumnType {
+            nullable: true,
+            scalar_type,
+        };
+        let desc = RelationDesc::from_names_and_types(vec![
+            ("a", typ(ScalarType::Int32)),
+            ("b", typ(ScalarType::String)),
+            ("c", typ(ScalarType::Float64)),
+            ("d", typ(ScalarType::Int64)),
+            ("d", typ(ScalarType::Int64)),
+        ]);
+        let rows = [
+            Row::pack(vec![
+                Datum::Int32(3),
+                Datum::String("b"),
+                Datum::Float64(1f64.into()),
+                Datum::Int64(1),
+                Datum::Int64(1),
+            ]),
+            Row::pack(vec![
+                Datum::Int32(-1),
+                Datum::Null,
+                Datum::Float64(2f64.into()),
+                Datum::Int64(1),
+                Datum::Int64(1),
+            ]),
+            Row::pack(vec![
+                Datum::Int32(2),
+                Datum::String("a"),
+                Datum::Null,
+                Datum::Int64(1),
+                Datum::Int64(1),
+            ]),
+        ];
+        let mut builder = super::RowStatsBuilder::new(&desc);
+        for row in rows.iter() {
+            builder.push_row(row);
+        }
+        let stats = builder.finish_row_stats();
+        assert_eq!(stats.len, 3);
+        assert_eq!(
+            stats.col("a"),
+            Some(&ColumnStats {
+                nulls: 0,
+                lower: Some(PrimitiveValue::I64(-1)),
+                upper: Some(PrimitiveValue::I64(3)),
+            })
+        );
+        assert_eq!(
+            stats.col("b"),
+            Some(&ColumnStats {
+                nulls: 1,
+                lower: Some(PrimitiveValue::String("a".into())),
+                upper: Some(PrimitiveValue::String("b".into())),
+            })
+        );
+        // Floats are unsupported and duplicate names are ambiguous.
+        assert_eq!(stats.col("c"), None);
+        assert_eq!(stats.col("d"), None);
+        assert_eq!(
+            super::datum_stats_value(Datum::Int32(3)),
+            Some(PrimitiveValue::I64(3))
+        );
+    }
 }
@@ -0,0 +1,378 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Spilling of row data to disk.
+//!
+//! Columnar containers of [`Row`]s, which back the batches of arrangements,
+//! store row contents in a [`SpillRegion`]. A region allocates its memory in
+//! chunks. Chunks are allocated on the heap until the process has allocated
+//! the configured memory limit, after which they are backed by memory-mapped
+//! files in a scratch directory. The operating system is free to write the
+//! pages of these files to disk and evict them from memory, so a process that
+//! exceeds its memory limit keeps working, more slowly, rather than running
+//! out of memory.
+//!
+//! Spilling is disabled until [`configure`] is called. Only the contents of
+//! rows that are too large to be stored inline, i.e., rows whose encoding
+//! exceeds 24 bytes, are subject to spilling. Rows of at most 24 bytes, as
+//! well as all other state of an arrangement, e.g., timestamps, diffs, and the
+//! offsets of each batch, stay on the heap and do not count towards the
+//! memory limit.
+//!
+//! The files that back spilled chunks are allocated eagerly, so that running
+//! out of disk space is detected when a chunk is allocated, in which case the
+//! chunk falls back to the heap, rather than when writing to its pages, which
+//! would crash the process.
+//!
+//! [`Row`]: crate::Row
+
+use std::fmt;
+use std::fs::File;
+use std::io;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use memmap2::MmapMut;
+use once_cell::sync::OnceCell;
+
+use mz_ore::cast::CastFrom;
+
+/// The largest chunk a region allocates, unless a single item requires more.
+///
+/// Bounding the chunk size keeps the memory limit from being overshot by
+/// much, and lets a large region spill only part of its contents.
+const MAX_CHUNK_BYTES: usize = 8 << 20;
+
+/// The smallest chunk that is backed by a file.
+///
+/// Mapping a file has a fixed cost, which is not worth paying for the small
+/// chunks that regions start out with.
+const MIN_SPILLED_CHUNK_BYTES: usize = 1 << 20;
+
+static CONFIG: OnceCell<SpillConfig> = OnceCell::new();
+
+static PROCESS_STATS: SpillStats = SpillStats::new();
+
+thread_local! {
+    static THREAD_STATS: Arc<SpillStats> = Arc::new(SpillStats::new());
+}
+
+/// Configures when and where row data is spilled to disk.
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    /// The directory in which to create the files that back spilled chunks.
+    ///
+    /// The files are unlinked immediately after creation, so nothing is left
+    /// behind in the directory if the process crashes.
+    pub directory: PathBuf,
+    /// The number of bytes of row data to keep on the heap, across all
+    /// regions of the process, before spilling new chunks to disk.
+    pub memory_limit: usize,
+}
+
+/// Enables spilling for the current process.
+///
+/// Only the first call has an effect: regions may already have allocated
+/// chunks under the first configuration.
+pub fn configure(config: SpillConfig) {
+    let _ = CONFIG.set(config);
+}
+
+/// Returns the statistics for the chunks allocated by all threads of the
+/// current process.
+pub fn process_stats() -> &'static SpillStats {
+    &PROCESS_STATS
+}
+
+/// Returns the statistics for the chunks allocated by the current thread.
+///
+/// Chunks remain accounted to the thread that allocated them, even if they are
+/// dropped by another thread.
+pub fn thread_stats() -> Arc<SpillStats> {
+    THREAD_STATS.with(Arc::clone)
+}
+
+/// The number of bytes allocated for chunks of [`SpillRegion`]s.
+#[derive(Debug)]
+pub struct SpillStats {
+    heap_bytes: AtomicUsize,
+    spilled_bytes: AtomicUsize,
+}
+
+impl SpillStats {
+    const fn new() -> Self {
+        Self {
+            heap_bytes: AtomicUsize::new(0),
+            spilled_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// The number of bytes allocated for chunks on the heap.
+    pub fn heap_bytes(&self) -> usize {
+        self.heap_bytes.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes allocated for chunks backed by files.
+    pub fn spilled_bytes(&self) -> usize {
+        self.spilled_bytes.load(Ordering::Relaxed)
+    }
+
+    fn counter(&self, spilled: bool) -> &AtomicUsize {
+        if spilled {
+            &self.spilled_bytes
+        } else {
+            &self.heap_bytes
+        }
+    }
+}
+
+/// A region of bytes whose allocations do not move once made.
+///
+/// This mirrors `columnation::StableRegion<u8>`, but may back its chunks with
+/// files rather than the heap. See the [module documentation](self) for
+/// details.
+#[derive(Debug, Default)]
+pub struct SpillRegion {
+    /// The chunk that new items are copied into.
+    local: Option<Chunk>,
+    /// Filled chunks, which are retained because items may point into them.
+    stash: Vec<Chunk>,
+}
+
+impl SpillRegion {
+    /// Copies `items` into the region and returns the stable copy.
+    pub fn copy_slice(&mut self, items: &[u8]) -> &mut [u8] {
+        if items.is_empty() {
+            return &mut [];
+        }
+        self.reserve(items.len());
+        self.local
+            .as_mut()
+            .expect("reserved space for items")
+            .extend_from_slice(items)
+    }
+
+    /// Ensures that `count` bytes can be copied into the region without
+    /// allocating a new chunk.
+    pub fn reserve(&mut self, count: usize) {
+        let (len, capacity) = match &self.local {
+            Some(chunk) => (chunk.len, chunk.capacity()),
+            None => (0, 0),
+        };
+        if len + count > capacity {
+            let next_capacity = std::cmp::min((capacity + 1).next_power_of_two(), MAX_CHUNK_BYTES);
+            let next = Chunk::allocate(std::cmp::max(count, next_capacity));
+            if let Some(prev) = self.local.replace(next) {
+                if prev.len > 0 {
+                    self.stash.push(prev);
+                }
+            }
+        }
+    }
+
+    /// Removes all items from the region, retaining the current chunk.
+    pub fn clear(&mut self) {
+        if let Some(local) = &mut self.local {
+            local.clear();
+        }
+        self.stash.clear();
+    }
+
+    /// The number of bytes copied into the region.
+    pub fn len(&self) -> usize {
+        self.local.iter().chain(&self.stash).map(|c| c.len).sum()
+    }
+
+    /// Reports whether the region holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A fixed-capacity allocation of a [`SpillRegion`].
+struct Chunk {
+    backing: Backing,
+    /// The number of bytes in use.
+    len: usize,
+    /// The statistics of the thread that allocated the chunk.
+    stats: Arc<SpillStats>,
+}
+
+enum Backing {
+    /// A vector whose capacity is never exceeded, so that it never reallocates.
+    Heap(Vec<u8>),
+    Mapped(MmapMut),
+}
+
+impl Chunk {
+    fn allocate(capacity: usize) -> Chunk {
+        let backing = match CONFIG.get() {
+            Some(config) if PROCESS_STATS.heap_bytes() + capacity > config.memory_limit => {
+                // Running out of disk is no reason to fail: fall back to the
+                // heap, which is what would have happened without spilling.
+                match map_file(
+                    &config.directory,
+                    std::cmp::max(capacity, MIN_SPILLED_CHUNK_BYTES),
+                ) {
+                    Ok(map) => Backing::Mapped(map),
+                    Err(_) => Backing::Heap(Vec::with_capacity(capacity)),
+                }
+            }
+            _ => Backing::Heap(Vec::with_capacity(capacity)),
+        };
+        let chunk = Chunk {
+            backing,
+            len: 0,
+            stats: thread_stats(),
+        };
+        chunk.account(|counter, bytes| counter.fetch_add(bytes, Ordering::Relaxed));
+        chunk
+    }
+
+    fn capacity(&self) -> usize {
+        match &self.backing {
+            Backing::Heap(vec) => vec.capacity(),
+            Backing::Mapped(map) => map.len(),
+        }
+    }
+
+    fn is_spilled(&self) -> bool {
+        matches!(self.backing, Backing::Mapped(_))
+    }
+
+    fn extend_from_slice(&mut self, items: &[u8]) -> &mut [u8] {
+        let start = self.len;
+        let end = start + items.len();
+        assert!(end <= self.capacity(), "chunk capacity exceeded");
+        self.len = end;
+        match &mut self.backing {
+            Backing::Heap(vec) => {
+                vec.extend_from_slice(items);
+                &mut vec[start..end]
+            }
+            Backing::Mapped(map) => {
+                let slice = &mut map[start..end];
+                slice.copy_from_slice(items);
+                slice
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        if let Backing::Heap(vec) = &mut self.backing {
+            vec.clear();
+        }
+    }
+
+    /// Applies `f` to the counters that track this chunk's allocation.
+    fn account(&self, f: impl Fn(&AtomicUsize, usize) -> usize) {
+        let spilled = self.is_spilled();
+        let bytes = self.capacity();
+        f(PROCESS_STATS.counter(spilled), bytes);
+        f(self.stats.counter(spilled), bytes);
+    }
+}
+
+impl fmt::Debug for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunk")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("spilled", &self.is_spilled())
+            .finish()
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        self.account(|counter, bytes| counter.fetch_sub(bytes, Ordering::Relaxed));
+    }
+}
+
+/// Maps a new, unlinked file of `len` bytes in `directory`.
+///
+/// The file's blocks are allocated up front: writing to a page of a sparse
+/// file whose block cannot be allocated raises `SIGBUS`, whereas failing to
+/// allocate the file here lets the caller fall back to the heap.
+fn map_file(directory: &Path, len: usize) -> io::Result<MmapMut> {
+    let file = tempfile::tempfile_in(directory)?;
+    allocate_file(&file, len)?;
+    // SAFETY: the file is unlinked and private to this process, so it cannot
+    // be modified or truncated by anyone else while it is mapped.
+    unsafe { MmapMut::map_mut(&file) }
+}
+
+/// Allocates the blocks for the first `len` bytes of `file`.
+#[cfg(target_os = "linux")]
+fn allocate_file(file: &File, len: usize) -> io::Result<()> {
+    let len = libc::off_t::try_from(len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file too large"))?;
+    // SAFETY: `file` is an open file descriptor for the duration of the call.
+    // Unlike most libc functions, `posix_fallocate` returns the error number
+    // rather than setting `errno`.
+    match unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, len) } {
+        0 => Ok(()),
+        errno => Err(io::Error::from_raw_os_error(errno)),
+    }
+}
+
+/// Allocates the blocks for the first `len` bytes of `file`.
+///
+/// `posix_fallocate` is not available on this platform, so the file is only
+/// extended and remains sparse. Spilling is only meant for development here.
+#[cfg(not(target_os = "linux"))]
+fn allocate_file(file: &File, len: usize) -> io::Result<()> {
+    file.set_len(u64::cast_from(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_copies_are_stable() {
+        let mut region = SpillRegion::default();
+        let mut copies = Vec::new();
+        for i in 0..1000u32 {
+            let item = i.to_le_bytes().repeat(usize::cast_from(i % 17) + 1);
+            let copy = region.copy_slice(&item);
+            copies.push((copy.as_ptr(), item));
+        }
+        assert_eq!(
+            region.len(),
+            copies.iter().map(|(_, item)| item.len()).sum::<usize>()
+        );
+        for (ptr, item) in &copies {
+            // SAFETY: the region has not been cleared, so the copy is live.
+            let copy = unsafe { std::slice::from_raw_parts(*ptr, item.len()) };
+            assert_eq!(copy, &item[..]);
+        }
+
+        region.clear();
+        assert!(region.is_empty());
+    }
+
+    #[test]
+    fn test_chunks_are_accounted_to_the_allocating_thread() {
+        let stats = thread_stats();
+        assert_eq!(stats.heap_bytes(), 0);
+
+        let mut region = SpillRegion::default();
+        region.copy_slice(&[1; 100]);
+        assert!(stats.heap_bytes() >= 100);
+        assert_eq!(stats.spilled_bytes(), 0);
+
+        std::thread::spawn(move || drop(region)).join().unwrap();
+        assert_eq!(stats.heap_bytes(), 0);
+    }
+}
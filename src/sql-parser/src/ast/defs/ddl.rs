@@ -377,6 +377,9 @@ pub enum SourceIncludeMetadataType {
     Topic,
     Offset,
     Headers,
+    ObjectKey,
+    LineNumber,
+    Lsn,
 }
 
 impl AstDisplay for SourceIncludeMetadataType {
@@ -388,6 +391,9 @@ impl AstDisplay for SourceIncludeMetadataType {
             SourceIncludeMetadataType::Topic => f.write_str("TOPIC"),
             SourceIncludeMetadataType::Offset => f.write_str("OFFSET"),
             SourceIncludeMetadataType::Headers => f.write_str("HEADERS"),
+            SourceIncludeMetadataType::ObjectKey => f.write_str("OBJECT KEY"),
+            SourceIncludeMetadataType::LineNumber => f.write_str("LINE NUMBER"),
+            SourceIncludeMetadataType::Lsn => f.write_str("LSN"),
         }
     }
 }
@@ -1448,6 +1454,10 @@ pub enum ColumnOption<T: AstInfo> {
     },
     // `CHECK (<expr>)`
     Check(Expr<T>),
+    /// `DROPPED`, which marks a column removed by `ALTER TABLE ... DROP
+    /// COLUMN`. The column's values remain in the table's storage but are
+    /// no longer visible.
+    Dropped,
 }
 
 impl<T: AstInfo> AstDisplay for ColumnOption<T> {
@@ -1482,6 +1492,7 @@ impl<T: AstInfo> AstDisplay for ColumnOption<T> {
                 f.write_node(expr);
                 f.write_str(")");
             }
+            Dropped => f.write_str("DROPPED"),
         }
     }
 }
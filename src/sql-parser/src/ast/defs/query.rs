@@ -186,12 +186,18 @@ impl_display!(SetOperator);
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SelectOptionName {
     ExpectedGroupSize,
+    JoinAlgorithm,
+    JoinIndex,
+    JoinOrder,
 }
 
 impl AstDisplay for SelectOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             SelectOptionName::ExpectedGroupSize => "EXPECTED GROUP SIZE",
+            SelectOptionName::JoinAlgorithm => "JOIN ALGORITHM",
+            SelectOptionName::JoinIndex => "JOIN INDEX",
+            SelectOptionName::JoinOrder => "JOIN ORDER",
         })
     }
 }
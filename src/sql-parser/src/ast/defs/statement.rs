@@ -61,10 +61,13 @@ pub enum Statement<T: AstInfo> {
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
+    AlterCluster(AlterClusterStatement<T>),
     AlterIndex(AlterIndexStatement<T>),
+    AlterMaterializedView(AlterMaterializedViewStatement<T>),
     AlterSecret(AlterSecretStatement<T>),
     AlterSink(AlterSinkStatement<T>),
     AlterSource(AlterSourceStatement<T>),
+    AlterTable(AlterTableStatement<T>),
     AlterSystemSet(AlterSystemSetStatement),
     AlterSystemReset(AlterSystemResetStatement),
     AlterSystemResetAll(AlterSystemResetAllStatement),
@@ -83,8 +86,11 @@ pub enum Statement<T: AstInfo> {
     SetTransaction(SetTransactionStatement),
     Commit(CommitStatement),
     Rollback(RollbackStatement),
+    Savepoint(SavepointStatement),
+    ReleaseSavepoint(ReleaseSavepointStatement),
     Subscribe(SubscribeStatement<T>),
     Explain(ExplainStatement<T>),
+    ExplainAnalyze(ExplainAnalyzeStatement<T>),
     Declare(DeclareStatement<T>),
     Fetch(FetchStatement<T>),
     Close(CloseStatement),
@@ -118,10 +124,13 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
+            Statement::AlterCluster(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
+            Statement::AlterMaterializedView(stmt) => f.write_node(stmt),
             Statement::AlterSecret(stmt) => f.write_node(stmt),
             Statement::AlterSink(stmt) => f.write_node(stmt),
             Statement::AlterSource(stmt) => f.write_node(stmt),
+            Statement::AlterTable(stmt) => f.write_node(stmt),
             Statement::AlterSystemSet(stmt) => f.write_node(stmt),
             Statement::AlterSystemReset(stmt) => f.write_node(stmt),
             Statement::AlterSystemResetAll(stmt) => f.write_node(stmt),
@@ -140,8 +149,11 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::SetTransaction(stmt) => f.write_node(stmt),
             Statement::Commit(stmt) => f.write_node(stmt),
             Statement::Rollback(stmt) => f.write_node(stmt),
+            Statement::Savepoint(stmt) => f.write_node(stmt),
+            Statement::ReleaseSavepoint(stmt) => f.write_node(stmt),
             Statement::Subscribe(stmt) => f.write_node(stmt),
             Statement::Explain(stmt) => f.write_node(stmt),
+            Statement::ExplainAnalyze(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
             Statement::Close(stmt) => f.write_node(stmt),
             Statement::Fetch(stmt) => f.write_node(stmt),
@@ -916,6 +928,9 @@ pub struct CreateTableStatement<T: AstInfo> {
     /// Optional schema
     pub columns: Vec<ColumnDef<T>>,
     pub constraints: Vec<TableConstraint<T>>,
+    /// The `EXPIRE ROWS AFTER` expression, which computes when each row
+    /// expires.
+    pub expire_rows_after: Option<Expr<T>>,
     pub if_not_exists: bool,
     pub temporary: bool,
 }
@@ -938,6 +953,10 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             f.write_node(&display::comma_separated(&self.constraints));
         }
         f.write_str(")");
+        if let Some(expr) = &self.expire_rows_after {
+            f.write_str(" EXPIRE ROWS AFTER ");
+            f.write_node(expr);
+        }
     }
 }
 impl_display_t!(CreateTableStatement);
@@ -1146,18 +1165,24 @@ impl_display_t!(CreateTypeStatement);
 pub enum ClusterOptionName {
     /// The `REPLICAS` option.
     Replicas,
+    /// The `SIZE [[=] <size>]` option.
+    Size,
+    /// The `REPLICATION FACTOR [[=] <factor>]` option.
+    ReplicationFactor,
 }
 
 impl AstDisplay for ClusterOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             ClusterOptionName::Replicas => f.write_str("REPLICAS"),
+            ClusterOptionName::Size => f.write_str("SIZE"),
+            ClusterOptionName::ReplicationFactor => f.write_str("REPLICATION FACTOR"),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-/// An option in a `CREATE CLUSTER` ostatement.
+/// An option in a `CREATE CLUSTER` or `ALTER CLUSTER` statement.
 pub struct ClusterOption<T: AstInfo> {
     pub name: ClusterOptionName,
     pub value: Option<WithOptionValue<T>>,
@@ -1167,7 +1192,10 @@ impl<T: AstInfo> AstDisplay for ClusterOption<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_node(&self.name);
         if let Some(v) = &self.value {
-            f.write_str(" ");
+            match self.name {
+                ClusterOptionName::Replicas => f.write_str(" "),
+                _ => f.write_str(" = "),
+            }
             f.write_node(v);
         }
     }
@@ -1441,6 +1469,115 @@ impl<T: AstInfo> AstDisplay for AlterIndexStatement<T> {
 
 impl_display_t!(AlterIndexStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableAction<T: AstInfo> {
+    /// `ADD [COLUMN] [IF NOT EXISTS] <column_def>`
+    AddColumn {
+        if_not_exists: bool,
+        column: ColumnDef<T>,
+    },
+    /// `DROP [COLUMN] [IF EXISTS] <name> [CASCADE | RESTRICT]`
+    DropColumn {
+        if_exists: bool,
+        name: Ident,
+        cascade: bool,
+    },
+}
+
+/// `ALTER TABLE ... {ADD, DROP} COLUMN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableStatement<T: AstInfo> {
+    pub table_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterTableAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER TABLE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.table_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterTableAction::AddColumn {
+                if_not_exists,
+                column,
+            } => {
+                f.write_str("ADD COLUMN ");
+                if *if_not_exists {
+                    f.write_str("IF NOT EXISTS ");
+                }
+                f.write_node(column);
+            }
+            AlterTableAction::DropColumn {
+                if_exists,
+                name,
+                cascade,
+            } => {
+                f.write_str("DROP COLUMN ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(name);
+                if *cascade {
+                    f.write_str(" CASCADE");
+                }
+            }
+        }
+    }
+}
+
+impl_display_t!(AlterTableStatement);
+
+/// `ALTER MATERIALIZED VIEW ... SET DEFINITION AS ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterMaterializedViewStatement<T: AstInfo> {
+    pub name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub query: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterMaterializedViewStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER MATERIALIZED VIEW ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" SET DEFINITION AS ");
+        f.write_node(&self.query);
+    }
+}
+
+impl_display_t!(AlterMaterializedViewStatement);
+
+/// `ALTER CLUSTER .. SET (..)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterClusterStatement<T: AstInfo> {
+    /// The name of the cluster to alter.
+    pub name: Ident,
+    pub if_exists: bool,
+    /// The options to set on the cluster.
+    pub options: Vec<ClusterOption<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterClusterStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER CLUSTER ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" SET (");
+        f.write_node(&display::comma_separated(&self.options));
+        f.write_str(")");
+    }
+}
+impl_display_t!(AlterClusterStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterSinkAction<T: AstInfo> {
     SetOptions(Vec<CreateSinkOption<T>>),
@@ -2094,14 +2231,21 @@ impl AstDisplay for CommitStatement {
 impl_display!(CommitStatement);
 
 /// `ROLLBACK [ TRANSACTION | WORK ] [ AND [ NO ] CHAIN ]`
+/// or `ROLLBACK [ TRANSACTION | WORK ] TO [ SAVEPOINT ] name`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RollbackStatement {
     pub chain: bool,
+    /// The savepoint to roll back to, if any.
+    pub savepoint: Option<Ident>,
 }
 
 impl AstDisplay for RollbackStatement {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("ROLLBACK");
+        if let Some(savepoint) = &self.savepoint {
+            f.write_str(" TO SAVEPOINT ");
+            f.write_node(savepoint);
+        }
         if self.chain {
             f.write_str(" AND CHAIN");
         }
@@ -2109,6 +2253,34 @@ impl AstDisplay for RollbackStatement {
 }
 impl_display!(RollbackStatement);
 
+/// `SAVEPOINT name`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SavepointStatement {
+    pub name: Ident,
+}
+
+impl AstDisplay for SavepointStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SAVEPOINT ");
+        f.write_node(&self.name);
+    }
+}
+impl_display!(SavepointStatement);
+
+/// `RELEASE [ SAVEPOINT ] name`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReleaseSavepointStatement {
+    pub name: Ident,
+}
+
+impl AstDisplay for ReleaseSavepointStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("RELEASE SAVEPOINT ");
+        f.write_node(&self.name);
+    }
+}
+impl_display!(ReleaseSavepointStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SubscribeOptionName {
     Snapshot,
@@ -2217,6 +2389,25 @@ impl<T: AstInfo> AstDisplay for ExplainStatement<T> {
 }
 impl_display_t!(ExplainStatement);
 
+/// `EXPLAIN ANALYZE { INDEX | MATERIALIZED VIEW } name`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainAnalyzeStatement<T: AstInfo> {
+    /// The type of the object to analyze. Either `Index` or
+    /// `MaterializedView`.
+    pub object_type: ObjectType,
+    pub name: T::ObjectName,
+}
+
+impl<T: AstInfo> AstDisplay for ExplainAnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN ANALYZE ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        f.write_node(&self.name);
+    }
+}
+impl_display_t!(ExplainAnalyzeStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InsertSource<T: AstInfo> {
     Query(Query<T>),
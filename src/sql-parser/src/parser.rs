@@ -284,6 +284,8 @@ impl<'a> Parser<'a> {
                 Token::Keyword(BEGIN) => Ok(self.parse_begin()?),
                 Token::Keyword(COMMIT) => Ok(self.parse_commit()?),
                 Token::Keyword(ROLLBACK) => Ok(self.parse_rollback()?),
+                Token::Keyword(SAVEPOINT) => Ok(self.parse_savepoint()?),
+                Token::Keyword(RELEASE) => Ok(self.parse_release_savepoint()?),
                 Token::Keyword(TAIL) => Ok(self.parse_tail()?),
                 Token::Keyword(SUBSCRIBE) => Ok(self.parse_subscribe()?),
                 Token::Keyword(EXPLAIN) => Ok(self.parse_explain()?),
@@ -3126,7 +3128,20 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_cluster_option(&mut self) -> Result<ClusterOption<Raw>, ParserError> {
-        self.expect_keyword(REPLICAS)?;
+        let name = match self.expect_one_of_keywords(&[REPLICAS, REPLICATION, SIZE])? {
+            REPLICAS => return self.parse_cluster_replicas_option(),
+            REPLICATION => {
+                self.expect_keyword(FACTOR)?;
+                ClusterOptionName::ReplicationFactor
+            }
+            SIZE => ClusterOptionName::Size,
+            _ => unreachable!(),
+        };
+        let value = self.parse_optional_option_value()?;
+        Ok(ClusterOption { name, value })
+    }
+
+    fn parse_cluster_replicas_option(&mut self) -> Result<ClusterOption<Raw>, ParserError> {
         self.expect_token(&Token::LParen)?;
         let replicas = if self.consume_token(&Token::RParen) {
             vec![]
@@ -3228,15 +3243,24 @@ impl<'a> Parser<'a> {
     fn parse_source_include_metadata(&mut self) -> Result<Vec<SourceIncludeMetadata>, ParserError> {
         if self.parse_keyword(INCLUDE) {
             self.parse_comma_separated(|parser| {
-                let ty = match parser
-                    .expect_one_of_keywords(&[KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET, HEADERS])?
-                {
+                let ty = match parser.expect_one_of_keywords(&[
+                    KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET, HEADERS, OBJECT, LINE, LSN,
+                ])? {
                     KEY => SourceIncludeMetadataType::Key,
                     TIMESTAMP => SourceIncludeMetadataType::Timestamp,
                     PARTITION => SourceIncludeMetadataType::Partition,
                     TOPIC => SourceIncludeMetadataType::Topic,
                     OFFSET => SourceIncludeMetadataType::Offset,
                     HEADERS => SourceIncludeMetadataType::Headers,
+                    OBJECT => {
+                        parser.expect_keyword(KEY)?;
+                        SourceIncludeMetadataType::ObjectKey
+                    }
+                    LINE => {
+                        parser.expect_keyword(NUMBER)?;
+                        SourceIncludeMetadataType::LineNumber
+                    }
+                    LSN => SourceIncludeMetadataType::Lsn,
                     _ => unreachable!("only explicitly allowed items can be parsed"),
                 };
                 let alias = parser
@@ -3383,11 +3407,17 @@ impl<'a> Parser<'a> {
         let table_name = self.parse_object_name()?;
         // parse optional column list (schema)
         let (columns, constraints) = self.parse_columns(Mandatory)?;
+        let expire_rows_after = if self.parse_keywords(&[EXPIRE, ROWS, AFTER]) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
 
         Ok(Statement::CreateTable(CreateTableStatement {
             name: table_name,
             columns,
             constraints,
+            expire_rows_after,
             if_not_exists,
             temporary,
         }))
@@ -3420,26 +3450,7 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier() {
-                let data_type = self.parse_data_type()?;
-                let collation = if self.parse_keyword(COLLATE) {
-                    Some(self.parse_object_name()?)
-                } else {
-                    None
-                };
-                let mut options = vec![];
-                loop {
-                    match self.peek_token() {
-                        None | Some(Token::Comma) | Some(Token::RParen) => break,
-                        _ => options.push(self.parse_column_option_def()?),
-                    }
-                }
-
-                columns.push(ColumnDef {
-                    name: column_name,
-                    data_type,
-                    collation,
-                    options,
-                });
+                columns.push(self.parse_column_def(column_name)?);
             } else {
                 return self.expected(
                     self.peek_pos(),
@@ -3463,6 +3474,30 @@ impl<'a> Parser<'a> {
         Ok((columns, constraints))
     }
 
+    /// Parses the remainder of a column definition whose name is `name`.
+    fn parse_column_def(&mut self, name: Ident) -> Result<ColumnDef<Raw>, ParserError> {
+        let data_type = self.parse_data_type()?;
+        let collation = if self.parse_keyword(COLLATE) {
+            Some(self.parse_object_name()?)
+        } else {
+            None
+        };
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) => break,
+                _ => options.push(self.parse_column_option_def()?),
+            }
+        }
+
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        })
+    }
+
     fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef<Raw>, ParserError> {
         let name = if self.parse_keyword(CONSTRAINT) {
             Some(self.parse_identifier()?)
@@ -3492,6 +3527,8 @@ impl<'a> Parser<'a> {
             let expr = self.parse_expr()?;
             self.expect_token(&Token::RParen)?;
             ColumnOption::Check(expr)
+        } else if self.parse_keyword(DROPPED) {
+            ColumnOption::Dropped
         } else {
             return self.expected(self.peek_pos(), "column option", self.peek_token());
         };
@@ -3630,6 +3667,7 @@ impl<'a> Parser<'a> {
         let object_type = match self.expect_one_of_keywords(&[
             SINK,
             SOURCE,
+            CLUSTER,
             VIEW,
             MATERIALIZED,
             TABLE,
@@ -3640,12 +3678,13 @@ impl<'a> Parser<'a> {
         ])? {
             SINK => return self.parse_alter_sink(),
             SOURCE => return self.parse_alter_source(),
+            CLUSTER => return self.parse_alter_cluster(),
             VIEW => ObjectType::View,
             MATERIALIZED => {
                 self.expect_keyword(VIEW)?;
-                ObjectType::MaterializedView
+                return self.parse_alter_materialized_view();
             }
-            TABLE => ObjectType::Table,
+            TABLE => return self.parse_alter_table(),
             INDEX => return self.parse_alter_index(),
             SECRET => return self.parse_alter_secret(),
             SYSTEM => return self.parse_alter_system(),
@@ -3708,6 +3747,91 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_alter_materialized_view(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_object_name()?;
+
+        Ok(match self.expect_one_of_keywords(&[RENAME, SET])? {
+            RENAME => {
+                self.expect_keyword(TO)?;
+                let to_item_name = self.parse_identifier()?;
+
+                Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type: ObjectType::MaterializedView,
+                    if_exists,
+                    name,
+                    to_item_name,
+                })
+            }
+            SET => {
+                self.expect_keywords(&[DEFINITION, AS])?;
+                let query = self.parse_query()?;
+
+                Statement::AlterMaterializedView(AlterMaterializedViewStatement {
+                    name,
+                    if_exists,
+                    query,
+                })
+            }
+            _ => unreachable!(),
+        })
+    }
+
+    fn parse_alter_table(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_object_name()?;
+
+        Ok(match self.expect_one_of_keywords(&[ADD, DROP, RENAME])? {
+            ADD => {
+                let _ = self.parse_keyword(COLUMN);
+                let if_not_exists = self.parse_if_not_exists()?;
+                let column_name = self.parse_identifier()?;
+                let column = self.parse_column_def(column_name)?;
+                Statement::AlterTable(AlterTableStatement {
+                    table_name: name,
+                    if_exists,
+                    action: AlterTableAction::AddColumn {
+                        if_not_exists,
+                        column,
+                    },
+                })
+            }
+            DROP => {
+                let _ = self.parse_keyword(COLUMN);
+                let column_if_exists = self.parse_if_exists()?;
+                let column_name = self.parse_identifier()?;
+                let cascade = matches!(
+                    self.parse_at_most_one_keyword(
+                        &[CASCADE, RESTRICT],
+                        "ALTER TABLE DROP COLUMN"
+                    )?,
+                    Some(CASCADE),
+                );
+                Statement::AlterTable(AlterTableStatement {
+                    table_name: name,
+                    if_exists,
+                    action: AlterTableAction::DropColumn {
+                        if_exists: column_if_exists,
+                        name: column_name,
+                        cascade,
+                    },
+                })
+            }
+            RENAME => {
+                self.expect_keyword(TO)?;
+                let to_item_name = self.parse_identifier()?;
+
+                Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type: ObjectType::Table,
+                    if_exists,
+                    name,
+                    to_item_name,
+                })
+            }
+            _ => unreachable!(),
+        })
+    }
+
     fn parse_alter_index(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
@@ -3777,6 +3901,21 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parse an ALTER CLUSTER statement.
+    fn parse_alter_cluster(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_identifier()?;
+        self.expect_keyword(SET)?;
+        self.expect_token(&Token::LParen)?;
+        let options = self.parse_comma_separated(Parser::parse_cluster_option)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(Statement::AlterCluster(AlterClusterStatement {
+            name,
+            if_exists,
+            options,
+        }))
+    }
+
     /// Parse an ALTER SINK statement.
     fn parse_alter_sink(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
@@ -4800,8 +4939,32 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_select_option(&mut self) -> Result<SelectOption<Raw>, ParserError> {
-        self.expect_keywords(&[EXPECTED, GROUP, SIZE])?;
-        let name = SelectOptionName::ExpectedGroupSize;
+        let name = match self.expect_one_of_keywords(&[EXPECTED, JOIN])? {
+            EXPECTED => {
+                self.expect_keywords(&[GROUP, SIZE])?;
+                SelectOptionName::ExpectedGroupSize
+            }
+            JOIN => match self.expect_one_of_keywords(&[ALGORITHM, INDEX, ORDER])? {
+                ALGORITHM => SelectOptionName::JoinAlgorithm,
+                INDEX => {
+                    // Index names are resolved, so that the query depends on them.
+                    let _ = self.consume_token(&Token::Eq);
+                    let names = match self.parse_option_sequence(Parser::parse_raw_name)? {
+                        Some(names) => names,
+                        None => vec![self.parse_raw_name()?],
+                    };
+                    return Ok(SelectOption {
+                        name: SelectOptionName::JoinIndex,
+                        value: Some(WithOptionValue::Sequence(
+                            names.into_iter().map(WithOptionValue::Object).collect(),
+                        )),
+                    });
+                }
+                ORDER => SelectOptionName::JoinOrder,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
         Ok(SelectOption {
             name,
             value: self.parse_optional_option_value()?,
@@ -5529,8 +5692,34 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_rollback(&mut self) -> Result<Statement<Raw>, ParserError> {
+        if self.peek_keywords(&[TRANSACTION, TO])
+            || self.peek_keywords(&[WORK, TO])
+            || self.peek_keyword(TO)
+        {
+            let _ = self.parse_one_of_keywords(&[TRANSACTION, WORK]);
+            self.expect_keyword(TO)?;
+            let _ = self.parse_keyword(SAVEPOINT);
+            return Ok(Statement::Rollback(RollbackStatement {
+                chain: false,
+                savepoint: Some(self.parse_identifier()?),
+            }));
+        }
         Ok(Statement::Rollback(RollbackStatement {
             chain: self.parse_commit_rollback_chain()?,
+            savepoint: None,
+        }))
+    }
+
+    fn parse_savepoint(&mut self) -> Result<Statement<Raw>, ParserError> {
+        Ok(Statement::Savepoint(SavepointStatement {
+            name: self.parse_identifier()?,
+        }))
+    }
+
+    fn parse_release_savepoint(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let _ = self.parse_keyword(SAVEPOINT);
+        Ok(Statement::ReleaseSavepoint(ReleaseSavepointStatement {
+            name: self.parse_identifier()?,
         }))
     }
 
@@ -5595,6 +5784,10 @@ impl<'a> Parser<'a> {
     /// Parse an `EXPLAIN` statement, assuming that the `EXPLAIN` token
     /// has already been consumed.
     fn parse_explain(&mut self) -> Result<Statement<Raw>, ParserError> {
+        if self.parse_keyword(ANALYZE) {
+            return self.parse_explain_analyze();
+        }
+
         let stage = match self.parse_one_of_keywords(&[
             RAW,
             DECORRELATED,
@@ -5677,6 +5870,27 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `EXPLAIN ANALYZE` statement, assuming that the `EXPLAIN
+    /// ANALYZE` tokens have already been consumed.
+    fn parse_explain_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let object_type = if self.parse_keyword(INDEX) {
+            ObjectType::Index
+        } else if self.parse_keywords(&[MATERIALIZED, VIEW]) {
+            ObjectType::MaterializedView
+        } else {
+            return self.expected(
+                self.peek_pos(),
+                "INDEX or MATERIALIZED VIEW",
+                self.peek_token(),
+            );
+        };
+        let name = self.parse_raw_name()?;
+        Ok(Statement::ExplainAnalyze(ExplainAnalyzeStatement {
+            object_type,
+            name,
+        }))
+    }
+
     /// Parse a `DECLARE` statement, assuming that the `DECLARE` token
     /// has already been consumed.
     fn parse_declare(&mut self) -> Result<Statement<Raw>, ParserError> {
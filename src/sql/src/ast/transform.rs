@@ -19,8 +19,8 @@ use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
     AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
     CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
-    CreateViewStatement, Expr, Ident, Query, Raw, RawObjectName, Statement, UnresolvedObjectName,
-    ViewDefinition,
+    CreateViewStatement, Expr, Ident, Query, Raw, RawObjectName, Select, SelectItem, Statement,
+    TableAlias, TableFactor, TableWithJoins, UnresolvedObjectName, ViewDefinition,
 };
 use crate::names::FullObjectName;
 
@@ -381,3 +381,62 @@ impl<'ast> VisitMut<'ast, Raw> for CreateSqlIdReplacer<'_> {
         }
     }
 }
+
+/// Replaces each reference to the table `id` within `create_stmt` with a
+/// subquery that selects only the table's `columns`, so that the statement
+/// keeps its meaning after columns are added to the table.
+pub fn create_stmt_pin_table_columns(
+    create_stmt: &mut Statement<Raw>,
+    id: GlobalId,
+    columns: &[Ident],
+) {
+    let mut pinner = CreateSqlColumnPinner {
+        id: id.to_string(),
+        columns,
+    };
+    pinner.visit_statement_mut(create_stmt);
+}
+
+struct CreateSqlColumnPinner<'a> {
+    id: String,
+    columns: &'a [Ident],
+}
+
+impl<'ast> VisitMut<'ast, Raw> for CreateSqlColumnPinner<'_> {
+    fn visit_table_factor_mut(&mut self, table_factor: &'ast mut TableFactor<Raw>) {
+        match table_factor {
+            TableFactor::Table {
+                name: RawObjectName::Id(id, name),
+                alias,
+            } if *id == self.id => {
+                // References to the table's columns that are qualified by the
+                // table's name continue to work against the subquery.
+                let alias = alias.take().unwrap_or_else(|| TableAlias {
+                    name: name.0.last().expect("object names are non-empty").clone(),
+                    columns: vec![],
+                    strict: false,
+                });
+                let table = TableFactor::Table {
+                    name: RawObjectName::Id(id.clone(), name.clone()),
+                    alias: None,
+                };
+                let mut select = Select::default().from(TableWithJoins {
+                    relation: table,
+                    joins: vec![],
+                });
+                for column in self.columns {
+                    select = select.project(SelectItem::Expr {
+                        expr: Expr::Identifier(vec![column.clone()]),
+                        alias: None,
+                    });
+                }
+                *table_factor = TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(Query::select(select)),
+                    alias: Some(alias),
+                };
+            }
+            _ => visit_mut::visit_table_factor_mut(self, table_factor),
+        }
+    }
+}
@@ -44,7 +44,7 @@ use crate::names::{
 };
 use crate::normalize;
 use crate::plan::statement::StatementDesc;
-use crate::plan::PlanError;
+use crate::plan::{ManagedClusterConfig, PlanError};
 
 /// A catalog keeps track of SQL objects and session state available to the
 /// planner.
@@ -75,6 +75,10 @@ pub trait SessionCatalog: fmt::Debug + ExprHumanizer + Send + Sync {
     /// Returns the name of the user who is issuing the query.
     fn active_user(&self) -> &str;
 
+    /// Reports whether statements are planned from the definitions the catalog
+    /// stores for its items, rather than issued by a session.
+    fn is_planning_stored_definitions(&self) -> bool;
+
     /// Returns the database to use if one is not explicitly specified.
     fn active_database_name(&self) -> Option<&str> {
         self.active_database()
@@ -296,6 +300,10 @@ pub trait CatalogCluster<'a> {
     /// Returns the replicas of the cluster as a map from replica name to
     /// replica ID.
     fn replicas(&self) -> &BTreeMap<String, ReplicaId>;
+
+    /// Returns the configuration of the cluster, if its replicas are managed
+    /// by Materialize.
+    fn managed(&self) -> Option<&ManagedClusterConfig>;
 }
 
 /// An item in a [`SessionCatalog`].
@@ -356,6 +364,10 @@ pub trait CatalogItem {
     /// catalog item is an index.
     fn index_details(&self) -> Option<(&[MirScalarExpr], GlobalId)>;
 
+    /// Returns the ID of the cluster that maintains the catalog item, if the
+    /// catalog item is maintained by a cluster.
+    fn cluster_id(&self) -> Option<ClusterId>;
+
     /// Returns the column defaults associated with the catalog item, if the
     /// catalog item is a table.
     fn table_details(&self) -> Option<&[Expr<Aug>]>;
@@ -835,6 +847,10 @@ impl SessionCatalog for DummyCatalog {
         "dummy"
     }
 
+    fn is_planning_stored_definitions(&self) -> bool {
+        false
+    }
+
     fn active_database(&self) -> Option<&DatabaseId> {
         Some(&DatabaseId(0))
     }
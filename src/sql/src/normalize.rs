@@ -335,6 +335,7 @@ pub fn create_statement(
             name,
             columns,
             constraints: _,
+            expire_rows_after,
             if_not_exists,
             temporary,
         }) => {
@@ -347,6 +348,9 @@ pub fn create_statement(
             for c in columns {
                 normalizer.visit_column_def_mut(c);
             }
+            if let Some(expr) = expire_rows_after {
+                normalizer.visit_expr_mut(expr);
+            }
             if let Some(err) = normalizer.err {
                 return Err(err);
             }
@@ -44,6 +44,7 @@ use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
+use mz_storage_client::controller::CollectionLayout;
 use mz_storage_client::types::instances::StorageInstanceId;
 use mz_storage_client::types::sinks::{SinkEnvelope, StorageSinkConnectionBuilder};
 use mz_storage_client::types::sources::{SourceDesc, Timeline};
@@ -115,6 +116,9 @@ pub enum Plan {
     StartTransaction(StartTransactionPlan),
     CommitTransaction,
     AbortTransaction,
+    Savepoint(SavepointPlan),
+    ReleaseSavepoint(ReleaseSavepointPlan),
+    RollbackToSavepoint(RollbackToSavepointPlan),
     Peek(PeekPlan),
     Subscribe(SubscribePlan),
     SendRows(SendRowsPlan),
@@ -123,12 +127,15 @@ pub enum Plan {
     SendDiffs(SendDiffsPlan),
     Insert(InsertPlan),
     AlterNoop(AlterNoopPlan),
+    AlterCluster(AlterClusterPlan),
     AlterIndexSetOptions(AlterIndexSetOptionsPlan),
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterSink(AlterSinkPlan),
     AlterSource(AlterSourcePlan),
     AlterItemRename(AlterItemRenamePlan),
     AlterSecret(AlterSecretPlan),
+    AlterTable(AlterTablePlan),
+    AlterMaterializedView(AlterMaterializedViewPlan),
     AlterSystemSet(AlterSystemSetPlan),
     AlterSystemReset(AlterSystemResetPlan),
     AlterSystemResetAll(AlterSystemResetAllPlan),
@@ -148,18 +155,23 @@ impl Plan {
     /// [`PlanKind`].
     pub fn generated_from(stmt: StatementKind) -> Vec<PlanKind> {
         match stmt {
+            StatementKind::AlterCluster => vec![PlanKind::AlterCluster, PlanKind::AlterNoop],
             StatementKind::AlterConnection => vec![PlanKind::AlterNoop, PlanKind::RotateKeys],
             StatementKind::AlterIndex => vec![
                 PlanKind::AlterIndexResetOptions,
                 PlanKind::AlterIndexSetOptions,
                 PlanKind::AlterNoop,
             ],
+            StatementKind::AlterMaterializedView => {
+                vec![PlanKind::AlterMaterializedView, PlanKind::AlterNoop]
+            }
             StatementKind::AlterObjectRename => {
                 vec![PlanKind::AlterItemRename, PlanKind::AlterNoop]
             }
             StatementKind::AlterSecret => vec![PlanKind::AlterNoop, PlanKind::AlterSecret],
             StatementKind::AlterSink => vec![PlanKind::AlterNoop, PlanKind::AlterSink],
             StatementKind::AlterSource => vec![PlanKind::AlterNoop, PlanKind::AlterSource],
+            StatementKind::AlterTable => vec![PlanKind::AlterNoop, PlanKind::AlterTable],
             StatementKind::AlterSystemReset => {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSystemReset]
             }
@@ -180,7 +192,10 @@ impl Plan {
             StatementKind::CreateConnection => vec![PlanKind::CreateConnection],
             StatementKind::CreateDatabase => vec![PlanKind::CreateDatabase],
             StatementKind::CreateIndex => vec![PlanKind::CreateIndex],
-            StatementKind::CreateMaterializedView => vec![PlanKind::CreateMaterializedView],
+            StatementKind::CreateMaterializedView => vec![
+                PlanKind::AlterMaterializedView,
+                PlanKind::CreateMaterializedView,
+            ],
             StatementKind::CreateRole => vec![PlanKind::CreateRole],
             StatementKind::CreateSchema => vec![PlanKind::CreateSchema],
             StatementKind::CreateSecret => vec![PlanKind::CreateSecret],
@@ -203,12 +218,17 @@ impl Plan {
             StatementKind::DropSchema => vec![PlanKind::DropSchema],
             StatementKind::Execute => vec![PlanKind::Execute],
             StatementKind::Explain => vec![PlanKind::Explain],
+            StatementKind::ExplainAnalyze => vec![PlanKind::Peek],
             StatementKind::Fetch => vec![PlanKind::Fetch],
             StatementKind::Insert => vec![PlanKind::Insert],
             StatementKind::Prepare => vec![PlanKind::Prepare],
             StatementKind::Raise => vec![PlanKind::Raise],
             StatementKind::ResetVariable => vec![PlanKind::ResetVariable],
-            StatementKind::Rollback => vec![PlanKind::AbortTransaction],
+            StatementKind::ReleaseSavepoint => vec![PlanKind::ReleaseSavepoint],
+            StatementKind::Rollback => {
+                vec![PlanKind::AbortTransaction, PlanKind::RollbackToSavepoint]
+            }
+            StatementKind::Savepoint => vec![PlanKind::Savepoint],
             StatementKind::Select => vec![PlanKind::Peek],
             StatementKind::SetTransaction => vec![],
             StatementKind::SetVariable => vec![PlanKind::SetVariable],
@@ -231,6 +251,21 @@ pub struct StartTransactionPlan {
     pub isolation_level: Option<TransactionIsolationLevel>,
 }
 
+#[derive(Debug)]
+pub struct SavepointPlan {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct ReleaseSavepointPlan {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct RollbackToSavepointPlan {
+    pub name: String,
+}
+
 #[derive(Debug)]
 pub struct CreateDatabasePlan {
     pub name: String,
@@ -253,6 +288,19 @@ pub struct CreateRolePlan {
 pub struct CreateClusterPlan {
     pub name: String,
     pub replicas: Vec<(String, ReplicaConfig)>,
+    /// The configuration of the cluster, if its replicas are managed by
+    /// Materialize.
+    pub managed: Option<ManagedClusterConfig>,
+}
+
+/// The configuration of a managed cluster, whose replicas are provisioned
+/// by Materialize rather than by the user.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
+pub struct ManagedClusterConfig {
+    /// The size of each replica.
+    pub size: String,
+    /// The number of replicas.
+    pub replication_factor: u32,
 }
 
 #[derive(Debug)]
@@ -519,6 +567,16 @@ pub struct AlterNoopPlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterClusterPlan {
+    pub id: ClusterId,
+    pub name: String,
+    /// The configuration of the cluster after the alteration.
+    pub config: ManagedClusterConfig,
+    /// The configuration of any replicas provisioned by the alteration.
+    pub replica_config: ReplicaConfig,
+}
+
 #[derive(Debug)]
 pub struct AlterIndexSetOptionsPlan {
     pub id: GlobalId,
@@ -565,6 +623,23 @@ pub struct AlterSecretPlan {
     pub secret_as: MirScalarExpr,
 }
 
+#[derive(Debug)]
+pub struct AlterTablePlan {
+    pub id: GlobalId,
+    /// The table's definition after the alteration.
+    pub table: Table,
+}
+
+#[derive(Debug)]
+pub struct AlterMaterializedViewPlan {
+    pub id: GlobalId,
+    /// The materialized view's new definition.
+    pub materialized_view: MaterializedView,
+    /// Whether the plan stems from `CREATE OR REPLACE MATERIALIZED VIEW`,
+    /// rather than `ALTER MATERIALIZED VIEW`.
+    pub or_replace: bool,
+}
+
 #[derive(Debug)]
 pub struct AlterSystemSetPlan {
     pub name: String,
@@ -629,10 +704,38 @@ pub struct RaisePlan {
 pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
+    /// Describes the rows stored for the table, which retain the columns
+    /// removed by `ALTER TABLE ... DROP COLUMN`.
+    pub storage_desc: RelationDesc,
+    /// How rows described by `storage_desc` map to rows described by `desc`.
+    pub layout: CollectionLayout,
     pub defaults: Vec<Expr<Aug>>,
+    pub constraints: Vec<Constraint>,
+    /// Computes the time at which each row of the table expires, for tables
+    /// created with `EXPIRE ROWS AFTER`.
+    pub expire_rows_after: Option<MirScalarExpr>,
     pub temporary: bool,
 }
 
+/// A constraint on the contents of a table, enforced whenever the table is
+/// written to.
+#[derive(Clone, Debug)]
+pub enum Constraint {
+    /// `PRIMARY KEY` or `UNIQUE`: no two rows may have equal values in
+    /// `columns`, unless one of those values is null.
+    Unique { name: String, columns: Vec<usize> },
+    /// `CHECK`: `expr`, evaluated on a row, must not be false.
+    Check { name: String, expr: MirScalarExpr },
+}
+
+impl Constraint {
+    pub fn name(&self) -> &str {
+        match self {
+            Constraint::Unique { name, .. } | Constraint::Check { name, .. } => name,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
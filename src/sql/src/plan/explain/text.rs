@@ -165,6 +165,7 @@ impl HirRelationExpr {
                 right,
                 on,
                 kind,
+                hints: _,
             } => {
                 if on.is_literal_true() && kind == &JoinKind::Inner {
                     write!(f, "{}CrossJoin", ctx.indent)?;
@@ -210,6 +211,7 @@ impl HirRelationExpr {
                 limit,
                 offset,
                 input,
+                expected_group_size,
             } => {
                 write!(f, "{}TopK", ctx.indent)?;
                 if group_key.len() > 0 {
@@ -226,6 +228,9 @@ impl HirRelationExpr {
                 if offset > &0 {
                     write!(f, " offset={}", offset)?
                 }
+                if let Some(expected_group_size) = expected_group_size {
+                    write!(f, " exp_group_size={}", expected_group_size)?;
+                }
                 writeln!(f)?;
                 ctx.indented(|ctx| input.as_ref().fmt_text(f, ctx))?;
             }
@@ -37,8 +37,8 @@ use crate::plan::Params;
 
 // these happen to be unchanged at the moment, but there might be additions later
 pub use mz_expr::{
-    BinaryFunc, ColumnOrder, TableFunc, UnaryFunc, UnmaterializableFunc, VariadicFunc, WindowFrame,
-    WindowFrameBound, WindowFrameUnits,
+    BinaryFunc, ColumnOrder, JoinAlgorithm, JoinHints, JoinIndexHint, TableFunc, UnaryFunc,
+    UnmaterializableFunc, VariadicFunc, WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
 
 #[allow(missing_debug_implementations)]
@@ -141,6 +141,8 @@ pub enum HirRelationExpr {
         right: Box<HirRelationExpr>,
         on: HirScalarExpr,
         kind: JoinKind,
+        /// User hints that constrain the implementation of the join.
+        hints: JoinHints,
     },
     /// Unlike MirRelationExpr, when `key` is empty AND `input` is empty this returns
     /// a single row with the aggregates evaluated over empty groups, rather than returning zero
@@ -166,6 +168,8 @@ pub enum HirRelationExpr {
         limit: Option<usize>,
         /// Number of records to skip
         offset: usize,
+        /// User hint: expected number of values per group key. Used to optimize physical rendering.
+        expected_group_size: Option<usize>,
     },
     Negate {
         input: Box<HirRelationExpr>,
@@ -1211,6 +1215,7 @@ impl HirRelationExpr {
         order_key: Vec<ColumnOrder>,
         limit: Option<usize>,
         offset: usize,
+        expected_group_size: Option<usize>,
     ) -> Self {
         HirRelationExpr::TopK {
             input: Box::new(self),
@@ -1218,6 +1223,7 @@ impl HirRelationExpr {
             order_key,
             limit,
             offset,
+            expected_group_size,
         }
     }
 
@@ -1280,6 +1286,7 @@ impl HirRelationExpr {
                 right: Box::new(right),
                 on,
                 kind,
+                hints: JoinHints::default(),
             }
         }
     }
@@ -1640,6 +1647,7 @@ impl HirRelationExpr {
                     order_key: finishing.order_by,
                     limit: finishing.limit,
                     offset: finishing.offset,
+                    expected_group_size: None,
                 }),
                 outputs: finishing.project,
             }
@@ -1697,6 +1705,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left);
                 f(right);
@@ -1716,6 +1725,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1779,6 +1789,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left);
                 f(right);
@@ -1798,6 +1809,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1861,6 +1873,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left)?;
                 f(right)?;
@@ -1880,6 +1893,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1944,6 +1958,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left)?;
                 f(right)?;
@@ -1963,6 +1978,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -2025,6 +2041,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on),
             Reduce {
                 input: _,
@@ -2043,6 +2060,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2095,6 +2113,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on),
             Reduce {
                 input: _,
@@ -2113,6 +2132,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2166,6 +2186,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on)?,
             Reduce {
                 input: _,
@@ -2184,6 +2205,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2238,6 +2260,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on)?,
             Reduce {
                 input: _,
@@ -2256,6 +2279,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
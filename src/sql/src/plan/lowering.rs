@@ -470,6 +470,7 @@ impl HirRelationExpr {
                     right,
                     on,
                     kind,
+                    hints: _,
                 } if right.is_correlated() => {
                     // A correlated join is a join in which the right expression has
                     // access to the columns in the left expression. It turns out
@@ -529,6 +530,7 @@ impl HirRelationExpr {
                     right,
                     on,
                     kind,
+                    hints,
                 } => {
                     // Both join expressions should be decorrelated, and then joined by their
                     // leading columns to form only those pairs corresponding to the same row
@@ -554,6 +556,7 @@ impl HirRelationExpr {
                                 vec![get_left.clone(), get_right.clone()],
                                 (0..oa).map(|i| vec![(0, i), (1, i)]).collect(),
                             )
+                            .with_join_hints(hints.clone())
                             // Project away the repeated copy of get_outer's columns.
                             .project(
                                 (0..(oa + la))
@@ -698,6 +701,7 @@ impl HirRelationExpr {
                     order_key,
                     limit,
                     offset,
+                    expected_group_size,
                 } => {
                     // TopK is uncomplicated, except that we must group by the columns of `get_outer` as well.
                     let input = input.applied_to(id_gen, get_outer.clone(), col_map, cte_map);
@@ -712,7 +716,13 @@ impl HirRelationExpr {
                             nulls_last: column_order.nulls_last,
                         })
                         .collect();
-                    input.top_k(applied_group_key, applied_order_key, limit, offset)
+                    input.top_k(
+                        applied_group_key,
+                        applied_order_key,
+                        limit,
+                        offset,
+                        expected_group_size,
+                    )
                 }
                 Negate { input } => {
                     // Negate is uncomplicated.
@@ -73,15 +73,15 @@ use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
     AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
-    CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr, JoinKind,
-    ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc, WindowExpr,
-    WindowExprType,
+    CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr, JoinHints,
+    JoinIndexHint, JoinKind, ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr,
+    VariadicFunc, WindowExpr, WindowExprType,
 };
 use crate::plan::plan_utils::{self, JoinSide};
 use crate::plan::scope::{Scope, ScopeItem};
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::typeconv::{self, CastContext};
-use crate::plan::with_options::TryFromValue;
+use crate::plan::with_options::{Object, TryFromValue};
 use crate::plan::{transform_ast, PlanContext, SendRowsPlan};
 use crate::plan::{Params, QueryWhen};
 
@@ -910,6 +910,24 @@ pub fn plan_default_expr(
     Ok(hir)
 }
 
+/// Plans a `DEFAULT` expression and, if its value does not depend on when it
+/// is evaluated, returns that value as a single-datum row.
+pub fn plan_constant_default_expr(
+    scx: &StatementContext,
+    expr: &Expr<Aug>,
+    target_ty: &ScalarType,
+) -> Result<Option<Row>, PlanError> {
+    let mut expr = plan_default_expr(scx, expr, target_ty)?.lower_uncorrelated()?;
+    if expr.contains_unmaterializable() || expr.contains_temporal() {
+        return Ok(None);
+    }
+    expr.reduce(&[]);
+    Ok(match expr.as_literal() {
+        Some(Ok(datum)) => Some(Row::pack_slice(&[datum])),
+        _ => None,
+    })
+}
+
 pub fn plan_params<'a>(
     scx: &'a StatementContext,
     params: Vec<Expr<Aug>>,
@@ -988,6 +1006,63 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+    let mut expr = expr.lower_uncorrelated()?;
+    if expr.contains_unmaterializable() || expr.contains_temporal() {
+        sql_bail!("CHECK constraint expressions must be immutable");
+    }
+    expr.reduce(&desc.typ().column_types);
+    Ok(expr)
+}
+
+/// Plans the `EXPIRE ROWS AFTER` expression of a table, which computes the
+/// time at which each row of the table expires.
+pub fn plan_expire_rows_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "EXPIRE ROWS AFTER",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr =
+        plan_expr(ecx, &expr)?.cast_to(ecx, CastContext::Implicit, &ScalarType::MzTimestamp)?;
+    let mut expr = expr.lower_uncorrelated()?;
+    if expr.contains_unmaterializable() || expr.contains_temporal() {
+        sql_bail!("EXPIRE ROWS AFTER expressions must be immutable");
+    }
+    expr.reduce(&desc.typ().column_types);
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1230,17 +1305,35 @@ pub fn plan_nested_query(
 ) -> Result<(HirRelationExpr, Scope), PlanError> {
     let (mut expr, scope, finishing) = qcx.checked_recur_mut(|qcx| plan_query(qcx, q))?;
     if finishing.limit.is_some() || finishing.offset > 0 {
+        // Once decorrelated, the TopK is grouped by the outer columns, so the size of
+        // its groups is that of the result of the query for each outer row.
         expr = HirRelationExpr::TopK {
             input: Box::new(expr),
             group_key: vec![],
             order_key: finishing.order_by,
             limit: finishing.limit,
             offset: finishing.offset,
+            expected_group_size: plan_expected_group_size(q)?,
         };
     }
     Ok((expr.project(finishing.project), scope))
 }
 
+/// Returns the `EXPECTED GROUP SIZE` option of the `SELECT` that forms the body
+/// of `q`, if any.
+fn plan_expected_group_size(q: &Query<Aug>) -> Result<Option<usize>, PlanError> {
+    match &q.body {
+        SetExpr::Select(s) => {
+            let SelectOptionExtracted {
+                expected_group_size,
+                ..
+            } = SelectOptionExtracted::try_from(s.options.clone())?;
+            Ok(expected_group_size.map(usize::cast_from))
+        }
+        _ => Ok(None),
+    }
+}
+
 fn plan_set_expr(
     qcx: &mut QueryContext,
     q: &SetExpr<Aug>,
@@ -1609,6 +1702,133 @@ fn plan_values_insert(
     })
 }
 
+/// Plans the join hints of a `SELECT` from its `JOIN ALGORITHM`, `JOIN INDEX`
+/// and `JOIN ORDER` options.
+///
+/// The collections named in `JOIN ORDER` must be tables, sources or
+/// materialized views that appear in `from`, as views are inlined into the
+/// query before the join is planned. They may be named by their aliases.
+fn plan_join_hints(
+    qcx: &QueryContext,
+    from: &[TableWithJoins<Aug>],
+    algorithm: Option<String>,
+    indexes: Option<Vec<Object>>,
+    order: Option<Vec<String>>,
+) -> Result<JoinHints, PlanError> {
+    fn collect_from_items(twj: &TableWithJoins<Aug>, items: &mut Vec<(String, GlobalId)>) {
+        for relation in std::iter::once(&twj.relation).chain(twj.joins.iter().map(|j| &j.relation))
+        {
+            match relation {
+                TableFactor::Table {
+                    name: ResolvedObjectName::Object { id, full_name, .. },
+                    alias,
+                } => {
+                    let name = match alias {
+                        Some(alias) => alias.name.as_str().to_string(),
+                        None => full_name.item.clone(),
+                    };
+                    items.push((name, *id));
+                }
+                TableFactor::NestedJoin { join, .. } => collect_from_items(join, items),
+                _ => {}
+            }
+        }
+    }
+
+    let mut hints = JoinHints::default();
+    if algorithm.is_none() && indexes.is_none() && order.is_none() {
+        return Ok(hints);
+    }
+    let mut from_items = vec![];
+    for twj in from {
+        collect_from_items(twj, &mut from_items);
+    }
+
+    // The algorithm is not imported by name, as it would clash with the
+    // option of the same name.
+    hints.algorithm = match algorithm.as_deref().map(str::to_lowercase).as_deref() {
+        None => None,
+        Some("delta") => Some(mz_expr::JoinAlgorithm::Delta),
+        Some("differential") => Some(mz_expr::JoinAlgorithm::Differential),
+        Some(other) => sql_bail!(
+            "invalid JOIN ALGORITHM {}: expected DELTA or DIFFERENTIAL",
+            other.quoted()
+        ),
+    };
+
+    for name in order.unwrap_or_default() {
+        let Some((_, id)) = from_items.iter().find(|(item, _)| *item == name) else {
+            sql_bail!(
+                "JOIN ORDER refers to {}, which is not in the FROM clause",
+                name.quoted()
+            );
+        };
+        let item = qcx.scx.get_item(id);
+        match item.item_type() {
+            CatalogItemType::Table
+            | CatalogItemType::Source
+            | CatalogItemType::MaterializedView => {}
+            typ => sql_bail!(
+                "JOIN ORDER cannot refer to {} {}, only to tables, sources and materialized views",
+                typ,
+                name.quoted()
+            ),
+        }
+        // The hint refers to the relation rather than to the alias, so it cannot tell
+        // apart the inputs of a self-join.
+        if from_items.iter().filter(|(_, other)| other == id).count() > 1 {
+            sql_bail!(
+                "JOIN ORDER cannot refer to {}, as its relation appears more than once in the \
+                 FROM clause",
+                name.quoted()
+            );
+        }
+        hints.order.push(*id);
+    }
+
+    for index in indexes.unwrap_or_default() {
+        let id = GlobalId::from(index);
+        let item = qcx.scx.get_item(&id);
+        let name = qcx.scx.catalog.resolve_full_name(item.name());
+        let Some((key, on_id)) = item.index_details() else {
+            sql_bail!(
+                "JOIN INDEX refers to {}, which is not an index",
+                name.to_string().quoted()
+            );
+        };
+        if !from_items.iter().any(|(_, id)| *id == on_id) {
+            sql_bail!(
+                "JOIN INDEX refers to {}, which is not on a relation in the FROM clause",
+                name.to_string().quoted()
+            );
+        }
+        hints.indexes.push(JoinIndexHint {
+            id,
+            name: name.to_string(),
+            on_id,
+            key: key.to_vec(),
+        });
+    }
+
+    Ok(hints)
+}
+
+/// Attaches `hints` to the joins at the root of `expr`, which together join
+/// the items of a `FROM` clause.
+fn set_join_hints(expr: &mut HirRelationExpr, hints: &JoinHints) {
+    if let HirRelationExpr::Join {
+        left,
+        right,
+        hints: join_hints,
+        ..
+    } = expr
+    {
+        *join_hints = hints.clone();
+        set_join_hints(left, hints);
+        set_join_hints(right, hints);
+    }
+}
+
 fn plan_join_identity() -> (HirRelationExpr, Scope) {
     let typ = RelationType::new(vec![]);
     let expr = HirRelationExpr::constant(vec![vec![]], typ);
@@ -1629,7 +1849,13 @@ struct SelectPlan {
     project: Vec<usize>,
 }
 
-generate_extracted_config!(SelectOption, (ExpectedGroupSize, u64));
+generate_extracted_config!(
+    SelectOption,
+    (ExpectedGroupSize, u64),
+    (JoinAlgorithm, String),
+    (JoinIndex, Vec<Object>),
+    (JoinOrder, Vec<String>)
+);
 
 /// Plans a SELECT query. The SELECT query may contain an intrusive ORDER BY clause.
 ///
@@ -1661,8 +1887,12 @@ fn plan_view_select(
     // Extract query options.
     let SelectOptionExtracted {
         expected_group_size,
+        join_algorithm,
+        join_index,
+        join_order,
         seen: _,
     } = SelectOptionExtracted::try_from(s.options.clone())?;
+    let join_hints = plan_join_hints(qcx, &s.from, join_algorithm, join_index, join_order)?;
 
     // Step 1. Handle FROM clause, including joins.
     let (mut relation_expr, mut from_scope) =
@@ -1681,6 +1911,9 @@ fn plan_view_select(
                 },
             )
         })?;
+    if !join_hints.is_empty() {
+        set_join_hints(&mut relation_expr, &join_hints);
+    }
 
     // Step 2. Handle WHERE clause.
     if let Some(selection) = &s.selection {
@@ -2032,6 +2265,7 @@ fn plan_view_select(
                     group_key: distinct_key,
                     limit: Some(1),
                     offset: 0,
+                    expected_group_size: expected_group_size.map(usize::cast_from),
                 }
             }
         }
@@ -3775,6 +4009,7 @@ where
             order_key: finishing.order_by.clone(),
             limit: finishing.limit,
             offset: finishing.offset,
+            expected_group_size: None,
         };
     }
 
@@ -109,11 +109,16 @@ pub fn describe(
 
     let desc = match stmt {
         // DDL statements.
+        Statement::AlterCluster(stmt) => ddl::describe_alter_cluster(&scx, stmt)?,
         Statement::AlterConnection(stmt) => ddl::describe_alter_connection(&scx, stmt)?,
         Statement::AlterIndex(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
+        Statement::AlterMaterializedView(stmt) => {
+            ddl::describe_alter_materialized_view(&scx, stmt)?
+        }
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterSecret(stmt) => ddl::describe_alter_secret_options(&scx, stmt)?,
         Statement::AlterSink(stmt) => ddl::describe_alter_sink(&scx, stmt)?,
+        Statement::AlterTable(stmt) => ddl::describe_alter_table(&scx, stmt)?,
         Statement::AlterSource(stmt) => ddl::describe_alter_source(&scx, stmt)?,
         Statement::AlterSystemSet(stmt) => ddl::describe_alter_system_set(&scx, stmt)?,
         Statement::AlterSystemReset(stmt) => ddl::describe_alter_system_reset(&scx, stmt)?,
@@ -195,6 +200,7 @@ pub fn describe(
         Statement::Copy(stmt) => dml::describe_copy(&scx, stmt)?,
         Statement::Delete(stmt) => dml::describe_delete(&scx, stmt)?,
         Statement::Explain(stmt) => dml::describe_explain(&scx, stmt)?,
+        Statement::ExplainAnalyze(stmt) => show::explain_analyze(&scx, stmt)?.describe()?,
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
         Statement::Select(stmt) => dml::describe_select(&scx, stmt)?,
         Statement::Subscribe(stmt) => dml::describe_subscribe(&scx, stmt)?,
@@ -203,6 +209,8 @@ pub fn describe(
         // TCL statements.
         Statement::Commit(stmt) => tcl::describe_commit(&scx, stmt)?,
         Statement::Rollback(stmt) => tcl::describe_rollback(&scx, stmt)?,
+        Statement::Savepoint(stmt) => tcl::describe_savepoint(&scx, stmt)?,
+        Statement::ReleaseSavepoint(stmt) => tcl::describe_release_savepoint(&scx, stmt)?,
         Statement::SetTransaction(stmt) => tcl::describe_set_transaction(&scx, stmt)?,
         Statement::StartTransaction(stmt) => tcl::describe_start_transaction(&scx, stmt)?,
 
@@ -248,11 +256,16 @@ pub fn plan(
 
     let plan = match stmt {
         // DDL statements.
+        Statement::AlterCluster(stmt) => ddl::plan_alter_cluster(scx, stmt),
         Statement::AlterConnection(stmt) => ddl::plan_alter_connection(scx, stmt),
         Statement::AlterIndex(stmt) => ddl::plan_alter_index_options(scx, stmt),
+        Statement::AlterMaterializedView(stmt) => {
+            ddl::plan_alter_materialized_view(scx, stmt, params)
+        }
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
         Statement::AlterSecret(stmt) => ddl::plan_alter_secret(scx, stmt),
         Statement::AlterSink(stmt) => ddl::plan_alter_sink(scx, stmt),
+        Statement::AlterTable(stmt) => ddl::plan_alter_table(scx, stmt),
         Statement::AlterSource(stmt) => ddl::plan_alter_source(scx, stmt),
         Statement::AlterSystemSet(stmt) => ddl::plan_alter_system_set(scx, stmt),
         Statement::AlterSystemReset(stmt) => ddl::plan_alter_system_reset(scx, stmt),
@@ -285,6 +298,7 @@ pub fn plan(
         Statement::Copy(stmt) => dml::plan_copy(scx, stmt),
         Statement::Delete(stmt) => dml::plan_delete(scx, stmt, params),
         Statement::Explain(stmt) => dml::plan_explain(scx, stmt, params),
+        Statement::ExplainAnalyze(stmt) => show::explain_analyze(scx, stmt)?.plan(),
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
         Statement::Select(stmt) => dml::plan_select(scx, stmt, params, None),
         Statement::Subscribe(stmt) => dml::plan_subscribe(scx, stmt, None),
@@ -334,6 +348,8 @@ pub fn plan(
         // TCL statements.
         Statement::Commit(stmt) => tcl::plan_commit(scx, stmt),
         Statement::Rollback(stmt) => tcl::plan_rollback(scx, stmt),
+        Statement::Savepoint(stmt) => tcl::plan_savepoint(scx, stmt),
+        Statement::ReleaseSavepoint(stmt) => tcl::plan_release_savepoint(scx, stmt),
         Statement::SetTransaction(stmt) => tcl::plan_set_transaction(scx, stmt),
         Statement::StartTransaction(stmt) => tcl::plan_start_transaction(scx, stmt),
 
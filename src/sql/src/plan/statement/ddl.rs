@@ -24,7 +24,8 @@ use tracing::warn;
 
 use mz_controller::clusters::DEFAULT_REPLICA_LOGGING_INTERVAL_MICROS;
 use mz_expr::CollectionPlan;
-use mz_interchange::avro::AvroSchemaGenerator;
+use mz_interchange::avro::{cdc_v2, AvroSchemaGenerator};
+use mz_interchange::encode::column_names_and_types;
 use mz_ore::cast::{self, TryCastFrom};
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
@@ -32,7 +33,9 @@ use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::system::Oid;
 use mz_repr::strconv;
-use mz_repr::{ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, Datum, GlobalId, RelationDesc, RelationType, Row, ScalarType,
+};
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
     AlterSinkAction, AlterSinkStatement, AlterSourceAction, AlterSourceStatement,
@@ -40,6 +43,7 @@ use mz_sql_parser::ast::{
     CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
     DeferredObjectName, SetVariableValue, SshConnectionOption, UnresolvedObjectName,
 };
+use mz_storage_client::controller::CollectionLayout;
 use mz_storage_client::types::connections::aws::{AwsAssumeRole, AwsConfig, AwsCredentials};
 use mz_storage_client::types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, Connection, CsrConnectionHttpAuth, KafkaConnection,
@@ -54,24 +58,27 @@ use mz_storage_client::types::sources::encoding::{
     ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
 };
 use mz_storage_client::types::sources::{
-    GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KeyEnvelope,
-    KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
+    GenericSourceConnection, IncludedColumnPos, IncludedColumnSource, KafkaSourceConnection,
+    KeyEnvelope, KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
     PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails, S3SourceConnection, SourceDesc, SourceEnvelope,
     TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStyle,
 };
 
 use crate::ast::display::AstDisplay;
+use crate::ast::visit::{self, Visit};
 use crate::ast::{
-    AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
-    AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption,
-    AwsConnectionOptionName, AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName,
-    ClusterOption, ClusterOptionName, ColumnOption, Compression, CreateClusterReplicaStatement,
-    CreateClusterStatement, CreateConnection, CreateConnectionStatement, CreateDatabaseStatement,
-    CreateIndexStatement, CreateMaterializedViewStatement, CreateRoleOption, CreateRoleStatement,
-    CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection, CreateSinkOption,
-    CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection, CreateSourceFormat,
-    CreateSourceOption, CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
+    AlterClusterStatement, AlterConnectionStatement, AlterIndexAction, AlterIndexStatement,
+    AlterMaterializedViewStatement, AlterObjectRenameStatement, AlterSecretStatement,
+    AlterTableAction, AlterTableStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName,
+    AwsConnectionOption, AwsConnectionOptionName, AwsPrivatelinkConnectionOption,
+    AwsPrivatelinkConnectionOptionName, ClusterOption, ClusterOptionName, ColumnDef, ColumnOption,
+    ColumnOptionDef, Compression, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnection, CreateConnectionStatement, CreateDatabaseStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateRoleOption, CreateRoleStatement, CreateSchemaStatement,
+    CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
+    CreateSinkStatement, CreateSourceConnection, CreateSourceFormat, CreateSourceOption,
+    CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
     CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
     CreateTypeStatement, CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
     CsrConnectionAvro, CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf,
@@ -84,17 +91,18 @@ use crate::ast::{
     PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
     ReferencedSubsources, ReplicaDefinition, ReplicaOption, ReplicaOptionName,
     SourceIncludeMetadata, SourceIncludeMetadataType, SshConnectionOptionName, Statement,
-    TableConstraint, UnresolvedDatabaseName, Value, ViewDefinition,
+    TableConstraint, UnresolvedDatabaseName, Value, ViewDefinition, WithOptionValue,
 };
 use crate::catalog::{
     CatalogCluster, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails,
 };
 use crate::kafka_util::{self, KafkaConfigOptionExtracted, KafkaStartOffsetType};
 use crate::names::{
-    Aug, FullSchemaName, QualifiedObjectName, RawDatabaseSpecifier, ResolvedClusterName,
+    self, Aug, FullSchemaName, QualifiedObjectName, RawDatabaseSpecifier, ResolvedClusterName,
     ResolvedDataType, ResolvedDatabaseSpecifier, ResolvedObjectName, SchemaSpecifier,
 };
 use crate::normalize::{self, ident};
+use crate::parse;
 use crate::plan::error::PlanError;
 use crate::plan::expr::ColumnRef;
 use crate::plan::query::{ExprContext, QueryLifetime};
@@ -103,15 +111,16 @@ use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{self, OptionalInterval, TryFromValue};
 use crate::plan::{
-    plan_utils, query, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
-    AlterNoopPlan, AlterOptionParameter, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan,
-    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, ComputeReplicaConfig,
-    ComputeReplicaIntrospectionConfig, CreateClusterPlan, CreateClusterReplicaPlan,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropClusterReplicasPlan,
-    DropClustersPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
-    FullObjectName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext,
+    plan_utils, query, AlterClusterPlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
+    AlterItemRenamePlan, AlterMaterializedViewPlan, AlterNoopPlan, AlterOptionParameter,
+    AlterSecretPlan, AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan, AlterSystemResetPlan,
+    AlterSystemSetPlan, AlterTablePlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig,
+    Constraint, CreateClusterPlan, CreateClusterReplicaPlan, CreateConnectionPlan,
+    CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan,
+    CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, DataSourceDesc, DropClusterReplicasPlan, DropClustersPlan,
+    DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, FullObjectName, HirScalarExpr,
+    Index, Ingestion, ManagedClusterConfig, MaterializedView, Params, Plan, QueryContext,
     ReplicaConfig, RotateKeysPlan, Secret, Sink, Source, SourceSinkClusterConfig, Table, Type,
     View,
 };
@@ -190,11 +199,37 @@ pub fn plan_create_table(
         name,
         columns,
         constraints,
+        expire_rows_after,
         if_not_exists,
         temporary,
     } = &stmt;
 
-    let names: Vec<_> = columns
+    // Columns removed by `ALTER TABLE ... DROP COLUMN` remain in the
+    // definition so that the rows already stored for the table can still be
+    // interpreted, but are otherwise invisible.
+    let is_dropped = |c: &ColumnDef<Aug>| {
+        c.options
+            .iter()
+            .any(|o| matches!(o.option, ColumnOption::Dropped))
+    };
+    // Only `ALTER TABLE ... DROP COLUMN` may mark columns as dropped.
+    if !scx.catalog.is_planning_stored_definitions() {
+        if let Some(c) = columns.iter().find(|c| is_dropped(c)) {
+            sql_bail!(
+                "column {} cannot be declared DROPPED",
+                normalize::column_name(c.name.clone()).as_str().quoted()
+            );
+        }
+    }
+    let projection: Vec<_> = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !is_dropped(c))
+        .map(|(i, _)| i)
+        .collect();
+    let live_columns: Vec<_> = projection.iter().map(|i| &columns[*i]).collect();
+
+    let names: Vec<_> = live_columns
         .iter()
         .map(|c| normalize::column_name(c.name.clone()))
         .collect();
@@ -203,32 +238,71 @@ pub fn plan_create_table(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    // Constraints that don't specify a name get one derived from the table
+    // and column names, using the same scheme as PostgreSQL.
+    let table_name = normalize::unresolved_object_name(name.to_owned())?.item;
+    let mut constraint_names = BTreeSet::new();
+    let mut constraint_name = |name: &Option<Ident>, columns: &[usize], suffix: &str| {
+        let base = match name {
+            Some(name) => normalize::ident(name.clone()),
+            None => std::iter::once(table_name.as_str())
+                .chain(columns.iter().map(|i| names[*i].as_str()))
+                .chain(std::iter::once(suffix))
+                .join("_"),
+        };
+        let mut name = base.clone();
+        let mut i = 0;
+        while !constraint_names.insert(name.clone()) {
+            i += 1;
+            name = format!("{}{}", base, i);
+        }
+        name
+    };
+
     // Build initial relation type that handles declared data types
     // and NOT NULL constraints.
-    let mut column_types = Vec::with_capacity(columns.len());
-    let mut defaults = Vec::with_capacity(columns.len());
+    let mut column_types = Vec::with_capacity(live_columns.len());
+    let mut defaults = Vec::with_capacity(live_columns.len());
+    let mut default_values = Vec::with_capacity(live_columns.len());
     let mut keys = Vec::new();
+    let mut unique_constraints = Vec::new();
+    let mut check_exprs = Vec::new();
 
-    for (i, c) in columns.into_iter().enumerate() {
+    for (i, c) in live_columns.iter().enumerate() {
         let aug_data_type = &c.data_type;
         let ty = query::scalar_type_from_sql(scx, aug_data_type)?;
         let mut nullable = true;
         let mut default = Expr::null();
+        let mut default_value = None;
         for option in &c.options {
             match &option.option {
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::Default(expr) => {
                     // Ensure expression can be planned and yields the correct
-                    // type.
-                    let _ = query::plan_default_expr(scx, expr, &ty)?;
+                    // type. Constant defaults also stand in for the column in
+                    // rows stored before the column was added.
+                    default_value = query::plan_constant_default_expr(scx, expr, &ty)?;
                     default = expr.clone();
                 }
                 ColumnOption::Unique { is_primary } => {
                     keys.push(vec![i]);
+                    let name = if *is_primary {
+                        constraint_name(&None, &[], "pkey")
+                    } else {
+                        constraint_name(&None, &[i], "key")
+                    };
+                    unique_constraints.push(Constraint::Unique {
+                        name,
+                        columns: vec![i],
+                    });
                     if *is_primary {
                         nullable = false;
                     }
                 }
+                ColumnOption::Check(expr) => {
+                    let name = constraint_name(&None, &[i], "check");
+                    check_exprs.push((name, expr.clone()));
+                }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
@@ -236,12 +310,13 @@ pub fn plan_create_table(
         }
         column_types.push(ty.nullable(nullable));
         defaults.push(default);
+        default_values.push(default_value);
     }
 
     for constraint in constraints {
         match constraint {
             TableConstraint::Unique {
-                name: _,
+                name,
                 columns,
                 is_primary,
             } => {
@@ -258,6 +333,15 @@ pub fn plan_create_table(
                         }
                     }
                 }
+                let name = if *is_primary {
+                    constraint_name(name, &[], "pkey")
+                } else {
+                    constraint_name(name, &key, "key")
+                };
+                unique_constraints.push(Constraint::Unique {
+                    name,
+                    columns: key.clone(),
+                });
                 keys.push(key);
             }
             TableConstraint::ForeignKey { .. } => {
@@ -265,19 +349,42 @@ pub fn plan_create_table(
                 // them in unsafe mode for sqllogictest's sake.
                 scx.require_unsafe_mode("CREATE TABLE with a foreign key")?
             }
-            TableConstraint::Check { .. } => {
-                // Check constraints are not presently enforced. We allow them
-                // in unsafe mode for sqllogictest's sake.
-                scx.require_unsafe_mode("CREATE TABLE with a check constraint")?
+            TableConstraint::Check { name, expr } => {
+                let name = constraint_name(name, &[], "check");
+                check_exprs.push((name, (**expr).clone()));
             }
         }
     }
 
-    if !keys.is_empty() {
-        // Unique constraints are not presently enforced. We allow them in
-        // unsafe mode for sqllogictest's sake.
-        scx.require_unsafe_mode("CREATE TABLE with a primary key or unique constraint")?;
+    // The rows stored for the table include dropped columns, which are always
+    // nullable, as the values written after they are dropped are null.
+    let mut storage_types = Vec::with_capacity(columns.len());
+    let mut storage_names = Vec::with_capacity(columns.len());
+    let mut storage_defaults = Row::default();
+    let mut live = column_types.iter().zip(default_values.iter());
+    {
+        let mut packer = storage_defaults.packer();
+        for c in columns {
+            if is_dropped(c) {
+                let ty = query::scalar_type_from_sql(scx, &c.data_type)?;
+                storage_types.push(ty.nullable(true));
+                packer.push(Datum::Null);
+            } else {
+                let (typ, value) = live.next().expect("one type per live column");
+                storage_types.push(typ.clone());
+                match value {
+                    Some(value) => packer.extend(value.iter()),
+                    None => packer.push(Datum::Null),
+                }
+            }
+            storage_names.push(normalize::column_name(c.name.clone()));
+        }
     }
+    let storage_desc = RelationDesc::new(RelationType::new(storage_types), storage_names);
+    let layout = CollectionLayout {
+        defaults: storage_defaults,
+        projection,
+    };
 
     let typ = RelationType::new(column_types).with_keys(keys);
 
@@ -289,11 +396,25 @@ pub fn plan_create_table(
     };
     let desc = RelationDesc::new(typ, names);
 
+    let mut constraints = unique_constraints;
+    for (name, expr) in check_exprs {
+        let expr = query::plan_check_expr(scx, &desc, expr)?;
+        constraints.push(Constraint::Check { name, expr });
+    }
+    let expire_rows_after = match expire_rows_after {
+        Some(expr) => Some(query::plan_expire_rows_expr(scx, &desc, expr.clone())?),
+        None => None,
+    };
+
     let create_sql = normalize::create_statement(scx, Statement::CreateTable(stmt.clone()))?;
     let table = Table {
         create_sql,
         desc,
+        storage_desc,
+        layout,
         defaults,
+        constraints,
+        expire_rows_after,
         temporary,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
@@ -370,18 +491,6 @@ pub fn plan_create_source(
         ))?;
     }
 
-    if !matches!(connection, CreateSourceConnection::Kafka { .. })
-        && include_metadata
-            .iter()
-            .any(|sic| sic.ty == SourceIncludeMetadataType::Headers)
-    {
-        // TODO(guswynn): should this be `bail_unsupported!`?
-        sql_bail!("INCLUDE HEADERS with non-Kafka sources not supported");
-    }
-    if !matches!(connection, CreateSourceConnection::Kafka { .. }) && !include_metadata.is_empty() {
-        bail_unsupported!("INCLUDE metadata with non-Kafka sources");
-    }
-
     let (mut external_connection, encoding, available_subsources) = match connection {
         CreateSourceConnection::Kafka(mz_sql_parser::ast::KafkaSourceConnection {
             connection:
@@ -448,7 +557,7 @@ pub fn plan_create_source(
 
             let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
 
-            let mut connection = KafkaSourceConnection {
+            let connection = KafkaSourceConnection {
                 connection: kafka_connection,
                 connection_id: connection_item.id(),
                 topic,
@@ -462,15 +571,6 @@ pub fn plan_create_source(
                 include_headers: None,
             };
 
-            let unwrap_name = |alias: Option<Ident>, default, pos| {
-                Some(IncludedColumnPos {
-                    name: alias
-                        .map(|a| a.to_string())
-                        .unwrap_or_else(|| String::from(default)),
-                    pos,
-                })
-            };
-
             if !matches!(envelope, Envelope::Upsert | Envelope::None)
                 && include_metadata
                     .iter()
@@ -480,32 +580,6 @@ pub fn plan_create_source(
                 sql_bail!("INCLUDE HEADERS requires ENVELOPE UPSERT or no ENVELOPE");
             }
 
-            for (pos, item) in include_metadata.iter().cloned().enumerate() {
-                match item.ty {
-                    SourceIncludeMetadataType::Timestamp => {
-                        connection.include_timestamp = unwrap_name(item.alias, "timestamp", pos);
-                    }
-                    SourceIncludeMetadataType::Partition => {
-                        connection.include_partition = unwrap_name(item.alias, "partition", pos);
-                    }
-                    SourceIncludeMetadataType::Topic => {
-                        // TODO(bwm): This requires deeper thought, the current structure of the
-                        // code requires us to clone the topic name around all over the place
-                        // whether or not anyone ever uses it. Considering we expect the
-                        // overwhelming majority of people will *not* want topics in dataflows that
-                        // is an unnacceptable cost.
-                        bail_unsupported!("INCLUDE TOPIC");
-                    }
-                    SourceIncludeMetadataType::Offset => {
-                        connection.include_offset = unwrap_name(item.alias, "offset", pos);
-                    }
-                    SourceIncludeMetadataType::Headers => {
-                        connection.include_headers = unwrap_name(item.alias, "headers", pos);
-                    }
-                    SourceIncludeMetadataType::Key => {} // handled below
-                }
-            }
-
             let connection = GenericSourceConnection::Kafka(connection);
 
             (connection, encoding, None)
@@ -592,6 +666,8 @@ pub fn plan_create_source(
                     Compression::Gzip => mz_storage_client::types::sources::Compression::Gzip,
                     Compression::None => mz_storage_client::types::sources::Compression::None,
                 },
+                include_object_key: None,
+                include_line_number: None,
             });
             (connection, encoding, None)
         }
@@ -793,6 +869,8 @@ pub fn plan_create_source(
                 table_casts,
                 publication: publication.expect("validated exists during purification"),
                 publication_details,
+                include_lsn: None,
+                include_timestamp: None,
             });
             // The postgres source only outputs data to its subsources. The catalog object
             // representing the source itself is just an empty relation with no columns
@@ -822,6 +900,7 @@ pub fn plan_create_source(
                 GenericSourceConnection::LoadGenerator(LoadGeneratorSourceConnection {
                     load_generator,
                     tick_micros,
+                    include_offset: None,
                 });
 
             (connection, encoding, available_subsources)
@@ -837,6 +916,65 @@ pub fn plan_create_source(
         }
     };
 
+    // Each type of source declares the metadata it can supply; configure the
+    // connection to produce the requested metadata columns.
+    for (pos, item) in include_metadata.iter().enumerate() {
+        let (source, default_name) = match item.ty {
+            SourceIncludeMetadataType::Key => {
+                if !matches!(external_connection, GenericSourceConnection::Kafka(_)) {
+                    sql_bail!(
+                        "INCLUDE KEY with {} sources not supported",
+                        external_connection.name()
+                    );
+                }
+                // handled below
+                continue;
+            }
+            SourceIncludeMetadataType::Timestamp => (IncludedColumnSource::Timestamp, "timestamp"),
+            SourceIncludeMetadataType::Partition => (IncludedColumnSource::Partition, "partition"),
+            SourceIncludeMetadataType::Topic => {
+                // TODO(bwm): This requires deeper thought, the current structure of the
+                // code requires us to clone the topic name around all over the place
+                // whether or not anyone ever uses it. Considering we expect the
+                // overwhelming majority of people will *not* want topics in dataflows that
+                // is an unnacceptable cost.
+                bail_unsupported!("INCLUDE TOPIC");
+            }
+            SourceIncludeMetadataType::Offset => (IncludedColumnSource::Offset, "offset"),
+            SourceIncludeMetadataType::Headers => (IncludedColumnSource::Headers, "headers"),
+            SourceIncludeMetadataType::ObjectKey => (IncludedColumnSource::ObjectKey, "object_key"),
+            SourceIncludeMetadataType::LineNumber => {
+                (IncludedColumnSource::LineNumber, "line_number")
+            }
+            SourceIncludeMetadataType::Lsn => (IncludedColumnSource::Lsn, "lsn"),
+        };
+        if !external_connection
+            .supported_metadata_columns()
+            .contains(&source)
+        {
+            sql_bail!(
+                "INCLUDE {} with {} sources not supported",
+                item.ty,
+                external_connection.name()
+            );
+        }
+        // Offsets only identify rows that are never retracted; a TPCH generator
+        // with a tick interval updates and deletes rows it generated earlier.
+        if let GenericSourceConnection::LoadGenerator(LoadGeneratorSourceConnection {
+            load_generator: LoadGenerator::Tpch { .. },
+            tick_micros: Some(_),
+            ..
+        }) = &external_connection
+        {
+            bail_unsupported!("INCLUDE OFFSET with TPCH load generators with a TICK INTERVAL");
+        }
+        let name = match &item.alias {
+            Some(alias) => alias.to_string(),
+            None => default_name.into(),
+        };
+        external_connection.include_metadata_column(source, IncludedColumnPos { name, pos });
+    }
+
     let (available_subsources, requested_subsources) = match (
         available_subsources,
         referenced_subsources,
@@ -953,9 +1091,14 @@ pub fn plan_create_source(
         }
     };
 
-    let metadata_columns = external_connection.metadata_columns();
     let metadata_column_types = external_connection.metadata_column_types();
-    let metadata_desc = included_column_desc(metadata_columns.clone());
+    // Sources that output to subsources append their metadata columns to each
+    // subsource (see purification), rather than to the source itself.
+    let metadata_desc = if subsource_exports.is_empty() {
+        included_column_desc(external_connection.metadata_columns())
+    } else {
+        RelationDesc::empty()
+    };
     let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
 
     let CreateSourceOptionExtracted {
@@ -1017,6 +1160,16 @@ pub fn plan_create_source(
     }
 
     let cluster_config = source_sink_cluster_config(scx, "source", in_cluster.as_ref(), size)?;
+    if let SourceSinkClusterConfig::Existing { id } = &cluster_config {
+        if scx.catalog.get_cluster(*id).replicas().len() > 1
+            && !external_connection.supports_active_replication()
+        {
+            sql_bail!(
+                "cannot create {} source in cluster with more than one replica",
+                external_connection.name()
+            );
+        }
+    }
 
     let timestamp_interval = match timestamp_interval {
         Some(timestamp_interval) => timestamp_interval.duration()?,
@@ -1353,9 +1506,6 @@ fn source_sink_cluster_config(
         (None, None) => Ok(SourceSinkClusterConfig::Undefined),
         (Some(in_cluster), None) => {
             let cluster = scx.catalog.get_cluster(in_cluster.id);
-            if cluster.replicas().len() > 1 {
-                sql_bail!("cannot create {ty} in cluster with more than one replica")
-            }
             if !is_storage_cluster(scx, cluster) {
                 sql_bail!("cannot create {ty} in cluster containing indexes or materialized views");
             }
@@ -1751,12 +1901,31 @@ pub fn plan_create_materialized_view(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    let materialized_view = MaterializedView {
+        create_sql,
+        expr,
+        column_names,
+        cluster_id,
+    };
+
     let mut replace = None;
     let mut if_not_exists = false;
     match stmt.if_exists {
         IfExistsBehavior::Replace => {
             if let Ok(item) = scx.catalog.resolve_item(&partial_name) {
-                if expr.depends_on().contains(&item.id()) {
+                // Dropping a materialized view that other objects depend on
+                // would fail, so replace its definition in place instead.
+                if item.item_type() == CatalogItemType::MaterializedView
+                    && !item.used_by().is_empty()
+                {
+                    check_materialized_view_replacement(scx, item, &materialized_view)?;
+                    return Ok(Plan::AlterMaterializedView(AlterMaterializedViewPlan {
+                        id: item.id(),
+                        materialized_view,
+                        or_replace: true,
+                    }));
+                }
+                if materialized_view.expr.depends_on().contains(&item.id()) {
                     sql_bail!(
                         "cannot replace materialized view {0}: depended upon by new {0} definition",
                         scx.catalog.resolve_full_name(item.name())
@@ -1772,17 +1941,59 @@ pub fn plan_create_materialized_view(
 
     Ok(Plan::CreateMaterializedView(CreateMaterializedViewPlan {
         name,
-        materialized_view: MaterializedView {
-            create_sql,
-            expr,
-            column_names,
-            cluster_id,
-        },
+        materialized_view,
         replace,
         if_not_exists,
     }))
 }
 
+/// Checks that `materialized_view` can replace the definition of the
+/// materialized view `item` without affecting the objects that depend on it.
+///
+/// The new definition must be maintained by the same cluster and produce the
+/// same columns. Columns may become non-nullable, but not nullable.
+fn check_materialized_view_replacement(
+    scx: &StatementContext,
+    item: &dyn CatalogItem,
+    materialized_view: &MaterializedView,
+) -> Result<(), PlanError> {
+    let full_name = scx.catalog.resolve_full_name(item.name());
+    if materialized_view.expr.depends_on().contains(&item.id()) {
+        sql_bail!(
+            "cannot replace materialized view {0}: depended upon by new {0} definition",
+            full_name
+        );
+    }
+    if item.cluster_id() != Some(materialized_view.cluster_id) {
+        sql_bail!(
+            "cannot replace materialized view {}: new definition must be maintained by the same \
+             cluster",
+            full_name
+        );
+    }
+
+    let old_desc = item.desc(&full_name)?;
+    let new_typ = materialized_view.expr.typ();
+    let compatible = old_desc.arity() == new_typ.arity()
+        && old_desc
+            .iter()
+            .zip(&materialized_view.column_names)
+            .zip(&new_typ.column_types)
+            .all(|(((old_name, old_typ), new_name), new_typ)| {
+                old_name == new_name
+                    && old_typ.scalar_type == new_typ.scalar_type
+                    && (old_typ.nullable || !new_typ.nullable)
+            });
+    if !compatible {
+        sql_bail!(
+            "cannot replace materialized view {}: new definition must produce columns with the \
+             same names and types",
+            full_name
+        );
+    }
+    Ok(())
+}
+
 pub fn describe_create_sink(
     _: &StatementContext,
     _: CreateSinkStatement<Aug>,
@@ -1825,8 +2036,8 @@ pub fn plan_create_sink(
         None => sql_bail!("ENVELOPE clause is required"),
         Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
         Some(Envelope::Upsert) => SinkEnvelope::Upsert,
-        Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+        Some(Envelope::CdcV2) => SinkEnvelope::CdcV2,
+        Some(Envelope::None) => SinkEnvelope::None,
     };
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let from = scx.get_item_by_resolved_name(&from)?;
@@ -1898,6 +2109,10 @@ pub fn plan_create_sink(
         return Err(PlanError::UpsertSinkWithoutKey);
     }
 
+    if key_desc_and_indices.is_some() && envelope == SinkEnvelope::CdcV2 {
+        sql_bail!("ENVELOPE MATERIALIZE sinks do not support KEY");
+    }
+
     let connection_builder = match connection {
         CreateSinkConnection::Kafka { connection, .. } => kafka_sink_builder(
             scx,
@@ -2087,19 +2302,28 @@ fn kafka_sink_builder(
                 sql_bail!("Must specify both AVRO KEY FULLNAME and AVRO VALUE FULLNAME when specifying generated schema names");
             }
 
-            let schema_generator = AvroSchemaGenerator::new(
-                avro_key_fullname.as_deref(),
-                avro_value_fullname.as_deref(),
-                key_desc_and_indices
-                    .as_ref()
-                    .map(|(desc, _indices)| desc.clone()),
-                value_desc.clone(),
-                matches!(envelope, SinkEnvelope::Debezium),
-            )?;
-            let value_schema = schema_generator.value_writer_schema().to_string();
-            let key_schema = schema_generator
-                .key_writer_schema()
-                .map(|key_schema| key_schema.to_string());
+            let (key_schema, value_schema) = if envelope == SinkEnvelope::CdcV2 {
+                if avro_value_fullname.is_some() {
+                    sql_bail!("AVRO VALUE FULLNAME is not supported with ENVELOPE MATERIALIZE");
+                }
+                let columns = column_names_and_types(value_desc.clone());
+                (None, cdc_v2::writer_schema(&columns)?.to_string())
+            } else {
+                let schema_generator = AvroSchemaGenerator::new(
+                    avro_key_fullname.as_deref(),
+                    avro_value_fullname.as_deref(),
+                    key_desc_and_indices
+                        .as_ref()
+                        .map(|(desc, _indices)| desc.clone()),
+                    value_desc.clone(),
+                    matches!(envelope, SinkEnvelope::Debezium),
+                )?;
+                let value_schema = schema_generator.value_writer_schema().to_string();
+                let key_schema = schema_generator
+                    .key_writer_schema()
+                    .map(|key_schema| key_schema.to_string());
+                (key_schema, value_schema)
+            };
 
             KafkaSinkFormat::Avro {
                 key_schema,
@@ -2107,6 +2331,9 @@ fn kafka_sink_builder(
                 csr_connection,
             }
         }
+        Some(Format::Json) if envelope == SinkEnvelope::CdcV2 => {
+            bail_unsupported!("non-Avro-encoded ENVELOPE MATERIALIZE sinks")
+        }
         Some(Format::Json) => KafkaSinkFormat::Json,
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
@@ -2457,27 +2684,54 @@ pub fn describe_create_cluster(
     Ok(StatementDesc::new(None))
 }
 
-generate_extracted_config!(ClusterOption, (Replicas, Vec<ReplicaDefinition<Aug>>));
+generate_extracted_config!(
+    ClusterOption,
+    (Replicas, Vec<ReplicaDefinition<Aug>>),
+    (Size, String),
+    (ReplicationFactor, u32)
+);
 
 pub fn plan_create_cluster(
     scx: &StatementContext,
     CreateClusterStatement { name, options }: CreateClusterStatement<Aug>,
 ) -> Result<Plan, PlanError> {
-    let ClusterOptionExtracted { replicas, .. }: ClusterOptionExtracted = options.try_into()?;
+    let ClusterOptionExtracted {
+        replicas,
+        size,
+        replication_factor,
+        ..
+    }: ClusterOptionExtracted = options.try_into()?;
 
-    let replica_defs = match replicas {
-        Some(replica_defs) => replica_defs,
-        None => sql_bail!("REPLICAS option is required"),
+    let (replicas, managed) = match (replicas, size) {
+        (Some(replica_defs), None) => {
+            if replication_factor.is_some() {
+                sql_bail!("REPLICATION FACTOR cannot be specified with REPLICAS");
+            }
+            let mut replicas = vec![];
+            for ReplicaDefinition { name, options } in replica_defs {
+                replicas.push((normalize::ident(name), plan_replica_config(scx, options)?));
+            }
+            (replicas, None)
+        }
+        (None, Some(size)) => {
+            let config = ManagedClusterConfig {
+                size,
+                replication_factor: replication_factor.unwrap_or(1),
+            };
+            let replica_config = plan_managed_replica_config(scx, config.size.clone())?;
+            let replicas = (1..=config.replication_factor)
+                .map(|i| (format!("r{i}"), replica_config.clone()))
+                .collect();
+            (replicas, Some(config))
+        }
+        (Some(_), Some(_)) => sql_bail!("SIZE cannot be specified with REPLICAS"),
+        (None, None) => sql_bail!("either REPLICAS or SIZE option is required"),
     };
 
-    let mut replicas = vec![];
-    for ReplicaDefinition { name, options } in replica_defs {
-        replicas.push((normalize::ident(name), plan_replica_config(scx, options)?));
-    }
-
     Ok(Plan::CreateCluster(CreateClusterPlan {
         name: normalize::ident(name),
         replicas,
+        managed,
     }))
 }
 
@@ -2596,6 +2850,21 @@ fn plan_replica_config(
     }
 }
 
+/// Plans the configuration of a replica of a managed cluster. Only the size of
+/// such replicas is configurable; all other options take their default values.
+fn plan_managed_replica_config(
+    scx: &StatementContext,
+    size: String,
+) -> Result<ReplicaConfig, PlanError> {
+    plan_replica_config(
+        scx,
+        vec![ReplicaOption {
+            name: ReplicaOptionName::Size,
+            value: Some(WithOptionValue::Value(Value::String(size))),
+        }],
+    )
+}
+
 pub fn describe_create_cluster_replica(
     _: &StatementContext,
     _: CreateClusterReplicaStatement<Aug>,
@@ -2611,11 +2880,15 @@ pub fn plan_create_cluster_replica(
     }: CreateClusterReplicaStatement<Aug>,
 ) -> Result<Plan, PlanError> {
     let cluster = scx.catalog.resolve_cluster(Some(&of_cluster.to_string()))?;
-    if is_storage_cluster(scx, cluster)
-        && cluster.bound_objects().len() > 0
-        && cluster.replicas().len() > 0
-    {
-        sql_bail!("cannot create more than one replica of a cluster containing sources or sinks");
+    if cluster.managed().is_some() {
+        sql_bail!(
+            "cannot create replicas of managed cluster {}; use ALTER CLUSTER to change its \
+            REPLICATION FACTOR instead",
+            cluster.name().quoted()
+        );
+    }
+    if cluster.replicas().len() > 0 {
+        ensure_sources_support_replication(scx, cluster)?;
     }
     Ok(Plan::CreateClusterReplica(CreateClusterReplicaPlan {
         name: normalize::ident(name),
@@ -3347,6 +3620,26 @@ pub fn plan_drop_cluster(
     Ok(Plan::DropClusters(DropClustersPlan { ids }))
 }
 
+/// Ensures that all sources bound to `cluster` can be ingested by more than one
+/// replica.
+fn ensure_sources_support_replication(
+    scx: &StatementContext,
+    cluster: &dyn CatalogCluster,
+) -> Result<(), PlanError> {
+    for id in cluster.bound_objects() {
+        let item = scx.catalog.get_item(id);
+        if let Some(source_desc) = item.source_desc()? {
+            if !source_desc.connection.supports_active_replication() {
+                sql_bail!(
+                    "cannot create more than one replica of a cluster containing {} sources",
+                    source_desc.connection.name()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 fn is_storage_cluster(scx: &StatementContext, cluster: &dyn CatalogCluster) -> bool {
     cluster.bound_objects().iter().all(|id| {
         matches!(
@@ -3375,6 +3668,13 @@ pub fn plan_drop_cluster_replica(
             Err(e) => return Err(e.into()),
         };
         let replica_name = replica.into_string();
+        if cluster.managed().is_some() {
+            sql_bail!(
+                "cannot drop replicas of managed cluster {}; use ALTER CLUSTER to change its \
+                REPLICATION FACTOR instead",
+                cluster.name().quoted()
+            );
+        }
         // Check to see if name exists
         if let Some(replica_id) = cluster.replicas().get(&replica_name) {
             ids.push((cluster.id(), *replica_id));
@@ -3705,6 +4005,70 @@ pub fn describe_alter_sink(
     Ok(StatementDesc::new(None))
 }
 
+pub fn describe_alter_cluster(
+    _: &StatementContext,
+    _: AlterClusterStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_cluster(
+    scx: &StatementContext,
+    AlterClusterStatement {
+        name,
+        if_exists,
+        options,
+    }: AlterClusterStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let cluster = match scx.catalog.resolve_cluster(Some(name.as_str())) {
+        Ok(cluster) => cluster,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Cluster,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let Some(current) = cluster.managed() else {
+        sql_bail!(
+            "cannot alter cluster {} because it was not created with the SIZE option",
+            cluster.name().quoted()
+        );
+    };
+
+    let ClusterOptionExtracted {
+        replicas,
+        size,
+        replication_factor,
+        ..
+    }: ClusterOptionExtracted = options.try_into()?;
+    if replicas.is_some() {
+        sql_bail!("cannot set REPLICAS of a managed cluster");
+    }
+    let config = ManagedClusterConfig {
+        size: size.unwrap_or_else(|| current.size.clone()),
+        replication_factor: replication_factor.unwrap_or(current.replication_factor),
+    };
+
+    // When the size changes, the new replicas run alongside the old replicas
+    // until they have hydrated.
+    let max_replicas = if config.size == current.size {
+        config.replication_factor
+    } else {
+        config.replication_factor + u32::try_from(cluster.replicas().len()).unwrap_or(u32::MAX)
+    };
+    if max_replicas > 1 {
+        ensure_sources_support_replication(scx, cluster)?;
+    }
+
+    Ok(Plan::AlterCluster(AlterClusterPlan {
+        id: cluster.id(),
+        name: cluster.name().to_string(),
+        replica_config: plan_managed_replica_config(scx, config.size.clone())?,
+        config,
+    }))
+}
+
 pub fn plan_alter_sink(
     scx: &StatementContext,
     stmt: AlterSinkStatement<Aug>,
@@ -3857,6 +4221,274 @@ pub fn plan_alter_source(
     Ok(Plan::AlterSource(AlterSourcePlan { id, size }))
 }
 
+pub fn describe_alter_table(
+    _: &StatementContext,
+    _: AlterTableStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_table(
+    scx: &StatementContext,
+    stmt: AlterTableStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let AlterTableStatement {
+        table_name,
+        if_exists,
+        action,
+    } = stmt;
+    let table_name = normalize::unresolved_object_name(table_name)?;
+    let entry = match scx.catalog.resolve_item(&table_name) {
+        Ok(table) => table,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Table,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+    if entry.item_type() != CatalogItemType::Table {
+        sql_bail!("\"{}\" is a {} not a table", full_name, entry.item_type())
+    }
+    if entry.id().is_system() {
+        sql_bail!(
+            "cannot alter system table {}",
+            full_name.to_string().quoted()
+        );
+    }
+    if entry.name().qualifiers.schema_spec == SchemaSpecifier::Temporary {
+        bail_unsupported!("ALTER TABLE on temporary tables");
+    }
+    let id = entry.id();
+
+    // The table's new definition is its original definition with the column
+    // appended or marked as dropped. Rows stored under the original definition
+    // are reinterpreted rather than rewritten.
+    let create_stmt = parse::parse(entry.create_sql())?.into_element();
+    let (create_stmt, _) = names::resolve(scx.catalog, create_stmt)?;
+    let mut create_stmt = match create_stmt {
+        Statement::CreateTable(stmt) => stmt,
+        _ => unreachable!("tables are created by CREATE TABLE"),
+    };
+    let is_live = |c: &ColumnDef<Aug>| {
+        !c.options
+            .iter()
+            .any(|o| matches!(o.option, ColumnOption::Dropped))
+    };
+
+    match action {
+        AlterTableAction::AddColumn {
+            if_not_exists,
+            column,
+        } => {
+            let name = normalize::column_name(column.name.clone());
+            let exists = create_stmt
+                .columns
+                .iter()
+                .filter(|c| is_live(c))
+                .any(|c| normalize::column_name(c.name.clone()) == name);
+            if exists {
+                if if_not_exists {
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                sql_bail!(
+                    "column {} of relation {} already exists",
+                    name.as_str().quoted(),
+                    full_name.to_string().quoted()
+                );
+            }
+            let ty = query::scalar_type_from_sql(scx, &column.data_type)?;
+            let mut has_default = false;
+            let mut not_null = false;
+            for option in &column.options {
+                match &option.option {
+                    ColumnOption::NotNull => not_null = true,
+                    ColumnOption::Default(expr) => {
+                        // Rows that were stored before the column existed
+                        // take on its default, which therefore cannot change.
+                        if query::plan_constant_default_expr(scx, expr, &ty)?.is_none() {
+                            bail_unsupported!("ALTER TABLE ADD COLUMN with a non-constant default");
+                        }
+                        has_default = true;
+                    }
+                    other => {
+                        bail_unsupported!(format!(
+                            "ALTER TABLE ADD COLUMN with column constraint: {}",
+                            other
+                        ))
+                    }
+                }
+            }
+            if not_null && !has_default {
+                sql_bail!(
+                    "column {} of relation {} must have a default to be NOT NULL",
+                    name.as_str().quoted(),
+                    full_name.to_string().quoted()
+                );
+            }
+            create_stmt.columns.push(column);
+        }
+        AlterTableAction::DropColumn {
+            if_exists,
+            name,
+            cascade,
+        } => {
+            if cascade {
+                bail_unsupported!("ALTER TABLE DROP COLUMN with CASCADE");
+            }
+            let name = normalize::column_name(name);
+            let column = create_stmt
+                .columns
+                .iter_mut()
+                .filter(|c| is_live(c))
+                .find(|c| normalize::column_name(c.name.clone()) == name);
+            match column {
+                Some(column) => {
+                    column.options = vec![ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::Dropped,
+                    }];
+                }
+                None if if_exists => {
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                None => sql_bail!(
+                    "column {} of relation {} does not exist",
+                    name.as_str().quoted(),
+                    full_name.to_string().quoted()
+                ),
+            }
+
+            // Like PostgreSQL, drop the constraints that involve the column.
+            let mentions_column = |expr: &Expr<Aug>| {
+                let mut visitor = ColumnMentionVisitor {
+                    name: &name,
+                    found: false,
+                };
+                visitor.visit_expr(expr);
+                visitor.found
+            };
+            create_stmt
+                .constraints
+                .retain(|constraint| match constraint {
+                    TableConstraint::Unique { columns, .. } => !columns
+                        .iter()
+                        .any(|c| normalize::column_name(c.clone()) == name),
+                    TableConstraint::Check { expr, .. } => !mentions_column(expr),
+                    TableConstraint::ForeignKey { .. } => true,
+                });
+            for column in &mut create_stmt.columns {
+                column.options.retain(|option| match &option.option {
+                    ColumnOption::Check(expr) => !mentions_column(expr),
+                    _ => true,
+                });
+            }
+            if create_stmt
+                .expire_rows_after
+                .as_ref()
+                .map_or(false, |expr| mentions_column(expr))
+            {
+                sql_bail!(
+                    "cannot drop column {} of relation {} because its EXPIRE ROWS AFTER \
+                     expression refers to it",
+                    name.as_str().quoted(),
+                    full_name.to_string().quoted()
+                );
+            }
+        }
+    }
+
+    let table = match plan_create_table(scx, create_stmt)? {
+        Plan::CreateTable(CreateTablePlan { table, .. }) => table,
+        _ => unreachable!("plan_create_table produces a CreateTablePlan"),
+    };
+    Ok(Plan::AlterTable(AlterTablePlan { id, table }))
+}
+
+pub fn describe_alter_materialized_view(
+    _: &StatementContext,
+    _: AlterMaterializedViewStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_materialized_view(
+    scx: &StatementContext,
+    stmt: AlterMaterializedViewStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let AlterMaterializedViewStatement {
+        name,
+        if_exists,
+        query,
+    } = stmt;
+    let name = normalize::unresolved_object_name(name)?;
+    let entry = match scx.catalog.resolve_item(&name) {
+        Ok(entry) => entry,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::MaterializedView,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::MaterializedView {
+        sql_bail!(
+            "\"{}\" is a {} not a materialized view",
+            scx.catalog.resolve_full_name(entry.name()),
+            entry.item_type()
+        )
+    }
+
+    // The view's new definition is its original definition with the query
+    // swapped out, so it retains its name, column names, and cluster.
+    let create_stmt = parse::parse(entry.create_sql())?.into_element();
+    let (create_stmt, _) = names::resolve(scx.catalog, create_stmt)?;
+    let mut create_stmt = match create_stmt {
+        Statement::CreateMaterializedView(stmt) => stmt,
+        _ => unreachable!("materialized views are created by CREATE MATERIALIZED VIEW"),
+    };
+    create_stmt.query = query;
+
+    let materialized_view = match plan_create_materialized_view(scx, create_stmt, params)? {
+        Plan::CreateMaterializedView(plan) => plan.materialized_view,
+        _ => unreachable!("plan_create_materialized_view produces a CreateMaterializedViewPlan"),
+    };
+    check_materialized_view_replacement(scx, entry, &materialized_view)?;
+
+    Ok(Plan::AlterMaterializedView(AlterMaterializedViewPlan {
+        id: entry.id(),
+        materialized_view,
+        or_replace: false,
+    }))
+}
+
+/// Determines whether an expression refers to the column `name`.
+struct ColumnMentionVisitor<'a> {
+    name: &'a ColumnName,
+    found: bool,
+}
+
+impl<'a, 'ast> Visit<'ast, Aug> for ColumnMentionVisitor<'a> {
+    fn visit_expr(&mut self, expr: &'ast Expr<Aug>) {
+        match expr {
+            Expr::Identifier(idents) => {
+                if let Some(ident) = idents.last() {
+                    if normalize::column_name(ident.clone()) == *self.name {
+                        self.found = true;
+                    }
+                }
+            }
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+}
+
 pub fn describe_alter_system_set(
     _: &StatementContext,
     _: AlterSystemSetStatement,
@@ -11,7 +11,8 @@
 //!
 //! This module houses the handlers for the `SHOW` suite of statements, like
 //! `SHOW CREATE TABLE` and `SHOW VIEWS`. Note that `SHOW <var>` is considered
-//! an SCL statement.
+//! an SCL statement. It also houses `EXPLAIN ANALYZE`, which, like the `SHOW`
+//! statements, is answered by a query against the system catalog.
 
 use std::fmt::Write;
 
@@ -19,7 +20,8 @@ use mz_ore::collections::CollectionExt;
 use mz_repr::{Datum, RelationDesc, Row, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    ShowCreateConnectionStatement, ShowCreateMaterializedViewStatement, ShowObjectType,
+    ExplainAnalyzeStatement, ObjectType, ShowCreateConnectionStatement,
+    ShowCreateMaterializedViewStatement, ShowObjectType,
 };
 use query::QueryContext;
 
@@ -452,6 +454,87 @@ fn show_all_objects<'a>(
     ShowSelect::new(scx, query, filter, None, None)
 }
 
+/// Plans `EXPLAIN ANALYZE` as a query that attributes the introspection data
+/// of the operators in the dataflow maintaining an index or materialized view
+/// to the nodes of its physical plan.
+///
+/// Introspection data is only available for the active cluster, so the object
+/// must be maintained by that cluster.
+pub fn explain_analyze<'a>(
+    scx: &'a StatementContext<'a>,
+    ExplainAnalyzeStatement { object_type, name }: ExplainAnalyzeStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let item = scx.get_item_by_resolved_name(&name)?;
+    match (object_type, item.item_type()) {
+        (ObjectType::Index, CatalogItemType::Index) => (),
+        (ObjectType::MaterializedView, CatalogItemType::MaterializedView) => (),
+        (ObjectType::Index, _) => sql_bail!("{} is not an index", name.full_name_str()),
+        (_, _) => sql_bail!("{} is not a materialized view", name.full_name_str()),
+    }
+    let cluster_id = item
+        .cluster_id()
+        .expect("indexes and materialized views are maintained by a cluster");
+    let active_cluster = scx.resolve_cluster(None)?;
+    if active_cluster.id() != cluster_id {
+        sql_bail!(
+            "cannot explain {} because it is maintained by cluster {}, \
+            but the active cluster is {}",
+            name.full_name_str(),
+            scx.catalog.get_cluster(cluster_id).name(),
+            active_cluster.name(),
+        );
+    }
+
+    // Every operator is attributed to the innermost plan node whose operator
+    // ID range contains it.
+    let query = format!(
+        "WITH
+            mappings AS (
+                SELECT lir_id, worker_id, nesting, operator, operator_id_start, operator_id_end
+                FROM mz_internal.mz_lir_mapping
+                WHERE export_id = '{}'
+            ),
+            operator_nodes AS (
+                SELECT DISTINCT ON (o.id, o.worker_id) o.id, o.worker_id, m.lir_id
+                FROM mz_internal.mz_dataflow_operators o
+                JOIN mappings m
+                    ON o.worker_id = m.worker_id
+                    AND o.id > m.operator_id_start
+                    AND o.id < m.operator_id_end
+                ORDER BY o.id, o.worker_id, m.operator_id_end - m.operator_id_start
+            ),
+            node_stats AS (
+                SELECT
+                    n.lir_id,
+                    sum(e.elapsed_ns) AS elapsed_ns,
+                    sum(s.records) AS records,
+                    sum(s.batches) AS batches
+                FROM operator_nodes n
+                LEFT JOIN mz_internal.mz_scheduling_elapsed e
+                    ON n.id = e.id AND n.worker_id = e.worker_id
+                LEFT JOIN mz_internal.mz_arrangement_sizes s
+                    ON n.id = s.operator_id AND n.worker_id = s.worker_id
+                GROUP BY n.lir_id
+            )
+        SELECT DISTINCT
+            m.lir_id,
+            repeat('  ', m.nesting::int4) || m.operator AS operator,
+            st.elapsed_ns,
+            st.records,
+            st.batches
+        FROM mappings m
+        LEFT JOIN node_stats st ON m.lir_id = st.lir_id",
+        item.id(),
+    );
+    ShowSelect::new(
+        scx,
+        query,
+        None,
+        Some("lir_id"),
+        Some(&["operator", "elapsed_ns", "records", "batches"]),
+    )
+}
+
 pub fn show_indexes<'a>(
     scx: &'a StatementContext<'a>,
     from_schema: Option<ResolvedSchemaName>,
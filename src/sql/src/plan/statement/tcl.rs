@@ -13,11 +13,15 @@
 //! like `BEGIN` and `COMMIT`.
 
 use crate::ast::{
-    CommitStatement, RollbackStatement, SetTransactionStatement, StartTransactionStatement,
-    TransactionAccessMode, TransactionMode,
+    CommitStatement, ReleaseSavepointStatement, RollbackStatement, SavepointStatement,
+    SetTransactionStatement, StartTransactionStatement, TransactionAccessMode, TransactionMode,
 };
+use crate::normalize;
 use crate::plan::statement::{StatementContext, StatementDesc};
-use crate::plan::{Plan, PlanError, StartTransactionPlan};
+use crate::plan::{
+    Plan, PlanError, ReleaseSavepointPlan, RollbackToSavepointPlan, SavepointPlan,
+    StartTransactionPlan,
+};
 use mz_sql_parser::ast::TransactionIsolationLevel;
 
 pub fn describe_start_transaction(
@@ -83,10 +87,47 @@ pub fn describe_rollback(
 
 pub fn plan_rollback(
     _: &StatementContext,
-    RollbackStatement { chain }: RollbackStatement,
+    RollbackStatement { chain, savepoint }: RollbackStatement,
 ) -> Result<Plan, PlanError> {
     verify_chain(chain)?;
-    Ok(Plan::AbortTransaction)
+    match savepoint {
+        Some(name) => Ok(Plan::RollbackToSavepoint(RollbackToSavepointPlan {
+            name: normalize::ident(name),
+        })),
+        None => Ok(Plan::AbortTransaction),
+    }
+}
+
+pub fn describe_savepoint(
+    _: &StatementContext,
+    _: SavepointStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_savepoint(
+    _: &StatementContext,
+    SavepointStatement { name }: SavepointStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::Savepoint(SavepointPlan {
+        name: normalize::ident(name),
+    }))
+}
+
+pub fn describe_release_savepoint(
+    _: &StatementContext,
+    _: ReleaseSavepointStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_release_savepoint(
+    _: &StatementContext,
+    ReleaseSavepointStatement { name }: ReleaseSavepointStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::ReleaseSavepoint(ReleaseSavepointPlan {
+        name: normalize::ident(name),
+    }))
 }
 
 pub fn describe_commit(
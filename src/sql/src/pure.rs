@@ -46,7 +46,8 @@ use mz_storage_client::types::sources::PostgresSourcePublicationDetails;
 use crate::ast::{
     AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
     CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf,
-    CsvColumns, Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
+    CsvColumns, Format, ProtobufSchema, ReferencedSubsources, SourceIncludeMetadataType, Value,
+    WithOptionValue,
 };
 use crate::catalog::{ErsatzCatalog, SessionCatalog};
 use crate::kafka_util;
@@ -109,7 +110,7 @@ pub async fn purify_create_source(
         connection,
         format,
         envelope,
-        include_metadata: _,
+        include_metadata,
         referenced_subsources: requested_subsources,
         progress_subsource,
         ..
@@ -272,6 +273,31 @@ pub async fn purify_create_source(
                     cause: Arc::new(cause),
                 })?;
 
+            // The metadata of a row that's updated or deleted upstream isn't known when its
+            // retraction is replicated, so it can only be included for publications that
+            // never replicate updates or deletes.
+            let includes_commit_metadata = include_metadata.iter().any(|item| {
+                matches!(
+                    item.ty,
+                    SourceIncludeMetadataType::Lsn | SourceIncludeMetadataType::Timestamp
+                )
+            });
+            if includes_commit_metadata {
+                let only_inserts =
+                    mz_postgres_util::publication_publishes_only_inserts(&config, &publication)
+                        .await
+                        .map_err(|cause| PlanError::FetchingPostgresPublicationInfoFailed {
+                            cause: Arc::new(cause),
+                        })?;
+                if !only_inserts {
+                    sql_bail!(
+                        "INCLUDE LSN and INCLUDE TIMESTAMP require publication {} to only \
+                         publish inserts; create it WITH (publish = 'insert')",
+                        publication.quoted()
+                    );
+                }
+            }
+
             // An index from table name -> schema name -> database name -> PostgresTableDesc
             let mut tables_by_name = BTreeMap::new();
             for table in &publication_tables {
@@ -429,6 +455,28 @@ pub async fn purify_create_source(
                     });
                 }
 
+                // The source itself produces no rows, so any metadata it was asked to include
+                // is appended to each of its subsources. Unsupported metadata is rejected
+                // during planning.
+                for item in include_metadata.iter() {
+                    let (default_name, ty) = match item.ty {
+                        SourceIncludeMetadataType::Lsn => ("lsn", mz_pgrepr::Type::UInt8),
+                        SourceIncludeMetadataType::Timestamp => {
+                            ("timestamp", mz_pgrepr::Type::Timestamp { precision: None })
+                        }
+                        _ => continue,
+                    };
+                    columns.push(ColumnDef {
+                        name: item
+                            .alias
+                            .clone()
+                            .unwrap_or_else(|| Ident::new(default_name)),
+                        data_type: scx.resolve_type(ty)?,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
+
                 // Create the targeted AST node for the original CREATE SOURCE statement
                 let transient_id = GlobalId::Transient(u64::cast_from(i));
                 let subsource =
@@ -550,7 +598,21 @@ pub async fn purify_create_source(
             for (i, (upstream_name, subsource_name, desc)) in
                 validated_requested_subsources.into_iter().enumerate()
             {
-                let (columns, table_constraints) = scx.relation_desc_into_table_defs(desc)?;
+                let (mut columns, table_constraints) = scx.relation_desc_into_table_defs(desc)?;
+
+                // As with Postgres sources, the requested metadata is appended to each
+                // subsource. Unsupported metadata is rejected during planning.
+                for item in include_metadata.iter() {
+                    if item.ty != SourceIncludeMetadataType::Offset {
+                        continue;
+                    }
+                    columns.push(ColumnDef {
+                        name: item.alias.clone().unwrap_or_else(|| Ident::new("offset")),
+                        data_type: scx.resolve_type(mz_pgrepr::Type::UInt8)?,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
 
                 // Create the targeted AST node for the original CREATE SOURCE statement
                 let transient_id = GlobalId::Transient(u64::cast_from(i));
@@ -825,6 +825,7 @@ impl RunnerInner {
                 orchestrator,
                 clusterd_image: "clusterd".into(),
                 init_container_image: None,
+                arrangement_spill_directory: None,
                 persist_location: PersistLocation {
                     blob_uri: format!("file://{}/persist/blob", temp_dir.path().display()),
                     consensus_uri,
@@ -37,6 +37,7 @@ use mz_timely_util::progress::any_antichain;
 use crate::client::proto_storage_server::ProtoStorage;
 use crate::controller::CollectionMetadata;
 use crate::metrics::RehydratingStorageClientMetrics;
+use crate::types::instances::ReplicaId;
 use crate::types::parameters::StorageParameters;
 use crate::types::sinks::{MetadataFilled, StorageSinkDesc};
 use crate::types::sources::IngestionDescription;
@@ -280,7 +281,8 @@ impl Arbitrary for StorageCommand<mz_repr::Timestamp> {
 }
 
 // These structure represents a full set up updates for the `mz_source_statistics`
-// and `mz_sink_statistics` tables for a specific source-worker/sink-worker pair.
+// and `mz_sink_statistics` tables for a specific source-worker/sink-worker pair
+// of a replica; the controller tracks which replica reported them.
 // They are structured like this for simplicity
 // and efficiency: Each storage worker can individually collect and consolidate metrics,
 // then control how much `StorageResponse` traffic is produced when sending updates
@@ -310,13 +312,14 @@ pub struct SinkStatisticsUpdate {
 /// A trait that abstracts over user-facing statistics objects, used
 /// by `spawn_statistics_scraper`.
 pub trait PackableStats {
-    /// Pack `self` into the `Row`.
-    fn pack(&self, packer: mz_repr::RowPacker<'_>);
+    /// Pack `self`, as reported by the replica `replica_id`, into the `Row`.
+    fn pack(&self, replica_id: ReplicaId, packer: mz_repr::RowPacker<'_>);
 }
 impl PackableStats for SourceStatisticsUpdate {
-    fn pack(&self, mut packer: mz_repr::RowPacker<'_>) {
+    fn pack(&self, replica_id: ReplicaId, mut packer: mz_repr::RowPacker<'_>) {
         use mz_repr::Datum;
         packer.push(Datum::from(self.id.to_string().as_str()));
+        packer.push(Datum::from(replica_id));
         packer.push(Datum::from(u64::cast_from(self.worker_id)));
         packer.push(Datum::from(self.snapshot_committed));
         packer.push(Datum::from(self.messages_received));
@@ -326,9 +329,10 @@ impl PackableStats for SourceStatisticsUpdate {
     }
 }
 impl PackableStats for SinkStatisticsUpdate {
-    fn pack(&self, mut packer: mz_repr::RowPacker<'_>) {
+    fn pack(&self, replica_id: ReplicaId, mut packer: mz_repr::RowPacker<'_>) {
         use mz_repr::Datum;
         packer.push(Datum::from(self.id.to_string().as_str()));
+        packer.push(Datum::from(replica_id));
         packer.push(Datum::from(u64::cast_from(self.worker_id)));
         packer.push(Datum::from(self.messages_staged));
         packer.push(Datum::from(self.messages_committed));
@@ -31,6 +31,7 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
+use futures::future::BoxFuture;
 use itertools::Itertools;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
@@ -43,17 +44,21 @@ use tokio_stream::StreamMap;
 use tracing::{debug, info};
 
 use mz_build_info::BuildInfo;
+use mz_expr::SafeMfpPlan;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::soft_assert;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::critical::SinceHandle;
+use mz_persist_client::read::SnapshotStats;
 use mz_persist_client::write::WriteHandle;
 use mz_persist_client::{PersistClient, PersistLocation, ShardId};
 use mz_persist_types::codec_impls::UnitSchema;
 use mz_persist_types::{Codec64, Opaque};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
-use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, TimestampManipulation};
+use mz_repr::{
+    Datum, DatumVec, Diff, GlobalId, RelationDesc, Row, RowArena, TimestampManipulation,
+};
 use mz_stash::{self, AppendBatch, StashError, StashFactory, TypedCollection};
 
 use crate::client::{
@@ -63,8 +68,9 @@ use crate::client::{
 use crate::controller::rehydration::RehydratingStorageClient;
 use crate::healthcheck;
 use crate::metrics::StorageControllerMetrics;
+use crate::source::persist_source::should_fetch_part;
 use crate::types::errors::DataflowError;
-use crate::types::instances::StorageInstanceId;
+use crate::types::instances::{ReplicaId, StorageInstanceId};
 use crate::types::parameters::StorageParameters;
 use crate::types::sinks::{
     MetadataUnfilled, ProtoDurableExportMetadata, SinkAsOf, StorageSinkDesc,
@@ -115,6 +121,9 @@ pub enum IntrospectionType {
     SinkStatusHistory,
     SourceStatusHistory,
     ShardMapping,
+    /// Like the status histories, the adapter appends to this collection
+    /// through [`StorageController::append_introspection_updates`].
+    StatementExecutionHistory,
 
     // Note that this single-shard introspection source will be changed to per-replica,
     // once we allow multiplexing multiple sources/sinks on a single cluster.
@@ -148,6 +157,9 @@ pub struct CollectionDescription<T> {
     /// A GlobalId to use for this collection to use for the status collection.
     /// Used to keep track of source status/error information.
     pub status_collection_id: Option<GlobalId>,
+    /// How rows of `desc` map to rows of the collection's current schema, if
+    /// the collection's schema can change.
+    pub layout: Option<CollectionLayout>,
 }
 
 impl<T> From<RelationDesc> for CollectionDescription<T> {
@@ -157,6 +169,7 @@ impl<T> From<RelationDesc> for CollectionDescription<T> {
             data_source: DataSource::Other,
             since: None,
             status_collection_id: None,
+            layout: None,
         }
     }
 }
@@ -203,7 +216,7 @@ pub trait StorageController: Debug + Send {
 
     /// Creates a storage instance with the specified ID.
     ///
-    /// A storage instance can have zero or one replicas. The instance is
+    /// A storage instance can have zero or more replicas. The instance is
     /// created with zero replicas.
     ///
     /// Panics if a storage instance with the given ID already exists.
@@ -211,21 +224,25 @@ pub trait StorageController: Debug + Send {
 
     /// Drops the storage instance with the given ID.
     ///
-    /// If you call this method while the storage instance has a replica
-    /// attached, that replica will be leaked. Call `drop_replica` first.
+    /// If you call this method while the storage instance has replicas
+    /// attached, those replicas will be leaked. Call `drop_replica` first.
     ///
     /// Panics if a storage instance with the given ID does not exist.
     fn drop_instance(&mut self, id: StorageInstanceId);
 
     /// Connects the storage instance to the specified replica.
     ///
-    /// If the storage instance is already attached to a replica, communication
-    /// with that replica is severed in favor of the new replica.
+    /// Every replica of a storage instance ingests all of the instance's
+    /// sources, while its sinks are exported by a single replica. If the
+    /// storage instance is already attached to a replica with the same ID,
+    /// communication with that replica is severed in favor of the new address.
+    fn connect_replica(&mut self, id: StorageInstanceId, replica_id: ReplicaId, addr: String);
+
+    /// Disconnects the storage instance from the specified replica.
     ///
-    /// In the future, this API will be adjusted to support active replication
-    /// of storage instances (i.e., multiple replicas attached to a given
-    /// storage instance).
-    fn connect_replica(&mut self, id: StorageInstanceId, addr: String);
+    /// If the replica was exporting the instance's sinks, they are moved to one
+    /// of the instance's remaining replicas.
+    fn drop_replica(&mut self, id: StorageInstanceId, replica_id: ReplicaId);
 
     /// Acquire a mutable reference to the collection state, should it exist.
     fn collection_mut(
@@ -256,6 +273,19 @@ pub trait StorageController: Debug + Send {
         collections: Vec<(GlobalId, CollectionDescription<Self::Timestamp>)>,
     ) -> Result<(), StorageError>;
 
+    /// Changes the schema of the collection `id`, which must have been created
+    /// with a layout, to `desc`. `layout` describes how the rows already in the
+    /// collection's data shard map to rows of `desc`.
+    ///
+    /// Dataflows that were reading the collection before the change continue
+    /// to see its previous schema.
+    fn alter_collection_desc(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        layout: CollectionLayout,
+    ) -> Result<(), StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
@@ -322,6 +352,21 @@ pub trait StorageController: Debug + Send {
         commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
     ) -> Result<tokio::sync::oneshot::Receiver<Result<(), StorageError>>, StorageError>;
 
+    /// Like [`Self::append`], but the updates to the local inputs become
+    /// visible atomically: either all of them are committed or none are.
+    ///
+    /// If more than one input receives updates and
+    /// [`Self::supports_atomic_appends`] is false, the oneshot returns
+    /// [`StorageError::AtomicAppendsUnsupported`] and nothing is committed.
+    fn append_atomic(
+        &mut self,
+        commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
+    ) -> Result<tokio::sync::oneshot::Receiver<Result<(), StorageError>>, StorageError>;
+
+    /// Reports whether [`Self::append_atomic`] can commit updates to more
+    /// than one local input.
+    fn supports_atomic_appends(&self) -> bool;
+
     /// Returns the snapshot of the contents of the local input named `id` at `as_of`.
     async fn snapshot(
         &self,
@@ -329,6 +374,45 @@ pub trait StorageController: Debug + Send {
         as_of: Self::Timestamp,
     ) -> Result<Vec<(Row, Diff)>, StorageError>;
 
+    /// Returns a future that reads the snapshot of the contents of the local
+    /// input named `id` at `as_of`, applying `mfp` to each row as it is
+    /// fetched.
+    ///
+    /// The read is validated against the collection's since before this
+    /// method returns, and the parts of the snapshot are leased until the
+    /// returned future completes. The contents are only fetched when the
+    /// future is awaited, which does not require access to the controller.
+    async fn snapshot_and_map_filter(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+        mfp: SafeMfpPlan,
+    ) -> Result<BoxFuture<'static, Result<Vec<(Row, Diff)>, StorageError>>, StorageError>;
+
+    /// Returns a future that fetches aggregate statistics about the contents
+    /// of the local input named `id` at `as_of`, without reading the contents
+    /// themselves.
+    ///
+    /// The future does not require access to the controller. The read handle
+    /// it opens is expired when the future completes, or when it is dropped
+    /// before completing, e.g. because it timed out.
+    fn snapshot_stats(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxFuture<'static, Result<SnapshotStats, StorageError>>, StorageError>;
+
+    /// Appends `updates` to the collection responsible for the introspection
+    /// type `type_`, effective as of the system time.
+    ///
+    /// # Panics
+    /// - If no collection is responsible for `type_`.
+    async fn append_introspection_updates(
+        &mut self,
+        type_: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    );
+
     /// Assigns a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -473,6 +557,39 @@ pub struct CollectionMetadata {
     pub status_shard: Option<ShardId>,
     /// The `RelationDesc` that describes the contents of the `data_shard`.
     pub relation_desc: RelationDesc,
+    /// How rows of `relation_desc` map to rows of the collection's current
+    /// schema, if the collection's schema can change.
+    pub layout: Option<CollectionLayout>,
+}
+
+impl CollectionMetadata {
+    /// Returns a description of the rows read from this collection, with each
+    /// column named after the data shard column it's read from, for use in
+    /// interpreting the stats of the data shard's parts.
+    ///
+    /// Returns `None` if the data shard's column names are ambiguous, which
+    /// happens when a dropped column's name is reused. In that case, the stats
+    /// of older parts might describe a different column than the current one
+    /// of that name.
+    pub fn stats_desc(&self) -> Option<RelationDesc> {
+        let desc = &self.relation_desc;
+        match &self.layout {
+            Some(layout) => {
+                if desc.iter_names().duplicates().next().is_some() {
+                    return None;
+                }
+                Some(RelationDesc::from_names_and_types(
+                    layout.projection.iter().map(|c| {
+                        (
+                            desc.get_name(*c).clone(),
+                            desc.typ().column_types[*c].clone(),
+                        )
+                    }),
+                ))
+            }
+            None => Some(desc.clone()),
+        }
+    }
 }
 
 impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
@@ -484,6 +601,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             remap_shard: self.remap_shard.to_string(),
             status_shard: self.status_shard.map(|s| s.to_string()),
             relation_desc: Some(self.relation_desc.into_proto()),
+            layout: self.layout.into_proto(),
         }
     }
 
@@ -508,6 +626,110 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             relation_desc: value
                 .relation_desc
                 .into_rust_if_some("ProtoCollectionMetadata::relation_desc")?,
+            layout: value.layout.into_rust()?,
+        })
+    }
+}
+
+/// Describes how the rows in a collection's data shard, some of which may have
+/// been written under earlier versions of the collection's schema, map to rows
+/// of its current schema.
+///
+/// This lets tables be altered without rewriting their data shard: rows
+/// written before a column was added are shorter than the shard's
+/// `RelationDesc`, and dropped columns are kept in the shard but hidden from
+/// readers.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionLayout {
+    /// For each column of the data shard, the value to read for that column
+    /// from rows that were written before it was added.
+    pub defaults: Row,
+    /// The columns of the data shard that make up the collection's current
+    /// schema, in order.
+    pub projection: Vec<usize>,
+}
+
+impl CollectionLayout {
+    /// Returns a [`LayoutConverter`] for converting rows read from the data
+    /// shard into rows of the collection's current schema.
+    pub fn converter(&self) -> LayoutConverter {
+        LayoutConverter {
+            defaults: self
+                .defaults
+                .iter()
+                .map(|datum| Row::pack_slice(&[datum]))
+                .collect(),
+            identity: self.projection.iter().enumerate().all(|(i, c)| i == *c),
+            projection: self.projection.clone(),
+        }
+    }
+
+    /// Converts a row of the collection's current schema into a row to write
+    /// to the data shard.
+    pub fn to_physical(&self, row: &Row) -> Row {
+        let mut datums = vec![Datum::Null; self.defaults.iter().count()];
+        for (datum, c) in row.iter().zip(self.projection.iter()) {
+            datums[*c] = datum;
+        }
+        Row::pack_slice(&datums)
+    }
+}
+
+/// Converts rows read from the data shard of a collection into rows of its
+/// current schema, as described by a [`CollectionLayout`].
+#[derive(Clone, Debug)]
+pub struct LayoutConverter {
+    /// For each column of the data shard, a row that contains only its
+    /// default.
+    defaults: Vec<Row>,
+    /// See [`CollectionLayout::projection`].
+    projection: Vec<usize>,
+    /// Whether `projection` selects every column of the data shard, in order.
+    identity: bool,
+}
+
+impl LayoutConverter {
+    /// Converts a row read from the data shard into a row of the collection's
+    /// current schema. `row_buf` is scratch space that's reused across calls.
+    pub fn to_logical(&self, mut row: Row, datum_vec: &mut DatumVec, row_buf: &mut Row) -> Row {
+        let datums = datum_vec.borrow_with(&row);
+        // The common case of a row that was written under the current schema
+        // of a table with no dropped columns needs no conversion.
+        if self.identity && datums.len() == self.projection.len() {
+            drop(datums);
+            return row;
+        }
+        row_buf.packer().extend(self.projection.iter().map(|c| {
+            match datums.get(*c) {
+                Some(datum) => *datum,
+                None => self
+                    .defaults
+                    .get(*c)
+                    .map_or(Datum::Null, |default| default.unpack_first()),
+            }
+        }));
+        drop(datums);
+        // Return the converted row and keep the allocation of the original one
+        // around for the next conversion.
+        std::mem::swap(&mut row, row_buf);
+        row
+    }
+}
+
+impl RustType<ProtoCollectionLayout> for CollectionLayout {
+    fn into_proto(&self) -> ProtoCollectionLayout {
+        ProtoCollectionLayout {
+            defaults: Some(self.defaults.into_proto()),
+            projection: self.projection.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoCollectionLayout) -> Result<Self, TryFromProtoError> {
+        Ok(CollectionLayout {
+            defaults: proto
+                .defaults
+                .into_rust_if_some("ProtoCollectionLayout::defaults")?,
+            projection: proto.projection.into_rust()?,
         })
     }
 }
@@ -646,7 +868,9 @@ pub struct StorageControllerState<T: Timestamp + Lattice + Codec64 + TimestampMa
     /// These handles are on the other end of a Tokio task, so that work can be done asynchronously
     /// without blocking the storage controller.
     persist_read_handles: persist_handles::PersistReadWorker<T>,
-    stashed_response: Option<StorageResponse<T>>,
+    /// A response to handle on the next call to `StorageController::process`,
+    /// along with the replica that produced it, if any.
+    stashed_response: Option<(Option<ReplicaId>, StorageResponse<T>)>,
     /// IDs of sources that were dropped whose statuses should be
     /// updated during the next call to `StorageController::process`.
     pending_source_drops: Vec<GlobalId>,
@@ -669,12 +893,16 @@ pub struct StorageControllerState<T: Timestamp + Lattice + Codec64 + TimestampMa
 
     /// Consolidated metrics updates to periodically write. We do not eagerly initialize this,
     /// and its contents are entirely driven by `StorageResponse::StatisticsUpdates`'s.
-    source_statistics:
-        Arc<std::sync::Mutex<BTreeMap<GlobalId, BTreeMap<usize, SourceStatisticsUpdate>>>>,
+    ///
+    /// Statistics are keyed by replica and worker, as every replica ingests each source.
+    source_statistics: Arc<
+        std::sync::Mutex<BTreeMap<GlobalId, BTreeMap<(ReplicaId, usize), SourceStatisticsUpdate>>>,
+    >,
     /// Consolidated metrics updates to periodically write. We do not eagerly initialize this,
     /// and its contents are entirely driven by `StorageResponse::StatisticsUpdates`'s.
-    sink_statistics:
-        Arc<std::sync::Mutex<BTreeMap<GlobalId, BTreeMap<usize, SinkStatisticsUpdate>>>>,
+    sink_statistics: Arc<
+        std::sync::Mutex<BTreeMap<GlobalId, BTreeMap<(ReplicaId, usize), SinkStatisticsUpdate>>>,
+    >,
 
     /// Clients for all known storage instances.
     clients: BTreeMap<StorageInstanceId, RehydratingStorageClient<T>>,
@@ -699,6 +927,9 @@ pub struct Controller<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + Tim
     persist_location: PersistLocation,
     /// A persist client used to write to storage collections
     persist: Arc<PersistClientCache>,
+    /// Whether the persist consensus can commit appends to multiple storage
+    /// collections atomically.
+    supports_atomic_appends: bool,
     /// Metrics of the Storage controller
     metrics: StorageControllerMetrics,
 }
@@ -716,6 +947,9 @@ pub enum StorageError {
     ReadBeforeSince(GlobalId),
     /// The expected upper of one or more appends was different from the actual upper of the collection
     InvalidUppers(Vec<GlobalId>),
+    /// The appends to the collections had to be committed atomically, which the persist
+    /// consensus does not support
+    AtomicAppendsUnsupported(Vec<GlobalId>),
     /// An error from the underlying client.
     ClientError(anyhow::Error),
     /// An operation failed to read or write state
@@ -732,6 +966,7 @@ impl Error for StorageError {
             Self::UpdateBeyondUpper(_) => None,
             Self::ReadBeforeSince(_) => None,
             Self::InvalidUppers(_) => None,
+            Self::AtomicAppendsUnsupported(_) => None,
             Self::ClientError(_) => None,
             Self::IOError(err) => Some(err),
             Self::DataflowError(err) => Some(err),
@@ -764,6 +999,13 @@ impl fmt::Display for StorageError {
                     id.iter().map(|id| id.to_string()).join(", ")
                 )
             }
+            Self::AtomicAppendsUnsupported(id) => {
+                write!(
+                    f,
+                    "cannot atomically append to multiple collections: {}",
+                    id.iter().map(|id| id.to_string()).join(", ")
+                )
+            }
             Self::ClientError(err) => write!(f, "underlying client error: {:#}", err),
             Self::IOError(err) => write!(f, "failed to read or write state: {err}"),
             Self::DataflowError(err) => write!(f, "dataflow failed to process request: {err}"),
@@ -910,7 +1152,7 @@ where
     }
 
     fn update_configuration(&mut self, config_params: StorageParameters) {
-        // TODO(#16753): apply config to `self.persist`
+        config_params.persist.apply(self.persist.cfg());
 
         for client in self.state.clients.values_mut() {
             client.send(StorageCommand::UpdateConfiguration(config_params.clone()));
@@ -962,13 +1204,36 @@ where
         assert!(client.is_some(), "storage instance {id} does not exist");
     }
 
-    fn connect_replica(&mut self, id: StorageInstanceId, addr: String) {
+    fn connect_replica(&mut self, id: StorageInstanceId, replica_id: ReplicaId, addr: String) {
         let client = self
             .state
             .clients
             .get_mut(&id)
             .unwrap_or_else(|| panic!("instance {id} does not exist"));
-        client.connect(addr);
+        client.connect(replica_id, addr);
+    }
+
+    fn drop_replica(&mut self, id: StorageInstanceId, replica_id: ReplicaId) {
+        let client = self
+            .state
+            .clients
+            .get_mut(&id)
+            .unwrap_or_else(|| panic!("instance {id} does not exist"));
+        client.drop_replica(replica_id);
+
+        for collection in self.state.collections.values_mut() {
+            collection.replica_write_frontiers.remove(&replica_id);
+        }
+
+        // The statistics of the replica's workers are no longer current.
+        let mut source_statistics = self.state.source_statistics.lock().expect("poisoned");
+        for stats in source_statistics.values_mut() {
+            stats.retain(|(stats_replica_id, _), _| *stats_replica_id != replica_id);
+        }
+        let mut sink_statistics = self.state.sink_statistics.lock().expect("poisoned");
+        for stats in sink_statistics.values_mut() {
+            stats.retain(|(stats_replica_id, _), _| *stats_replica_id != replica_id);
+        }
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
@@ -1041,6 +1306,7 @@ where
                 data_shard: collection_shards.data_shard,
                 status_shard,
                 relation_desc: description.desc.clone(),
+                layout: description.layout.clone(),
             };
 
             Ok((id, description, metadata))
@@ -1225,7 +1491,8 @@ where
                             self.state.introspection_tokens.insert(id, scraper_token);
                         }
                         IntrospectionType::SourceStatusHistory
-                        | IntrospectionType::SinkStatusHistory => {
+                        | IntrospectionType::SinkStatusHistory
+                        | IntrospectionType::StatementExecutionHistory => {
                             // nothing to do: these collections are append only
                         }
                     }
@@ -1237,6 +1504,24 @@ where
         Ok(())
     }
 
+    fn alter_collection_desc(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        layout: CollectionLayout,
+    ) -> Result<(), StorageError> {
+        let collection = self.collection_mut(id)?;
+        assert!(
+            collection.collection_metadata.layout.is_some(),
+            "collection {id} cannot change its schema"
+        );
+        collection.description.desc = desc.clone();
+        collection.description.layout = Some(layout.clone());
+        collection.collection_metadata.relation_desc = desc;
+        collection.collection_metadata.layout = Some(layout);
+        Ok(())
+    }
+
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError> {
         self.state
             .exports
@@ -1423,16 +1708,19 @@ where
         &mut self,
         commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
     ) -> Result<tokio::sync::oneshot::Receiver<Result<(), StorageError>>, StorageError> {
-        // TODO(petrosagg): validate appends against the expected RelationDesc of the collection
-        for (id, updates, batch_upper) in commands.iter() {
-            for update in updates.iter() {
-                if !update.timestamp.less_than(batch_upper) {
-                    return Err(StorageError::UpdateBeyondUpper(*id));
-                }
-            }
-        }
+        self.append_inner(commands, false)
+    }
 
-        Ok(self.state.persist_write_handles.append(commands))
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn append_atomic(
+        &mut self,
+        commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
+    ) -> Result<tokio::sync::oneshot::Receiver<Result<(), StorageError>>, StorageError> {
+        self.append_inner(commands, true)
+    }
+
+    fn supports_atomic_appends(&self) -> bool {
+        self.supports_atomic_appends
     }
 
     // TODO(petrosagg): This signature is not very useful in the context of partially ordered times
@@ -1471,10 +1759,16 @@ where
         match read_handle.snapshot_and_fetch(as_of).await {
             Ok(contents) => {
                 let mut snapshot = Vec::with_capacity(contents.len());
+                let mut datum_vec = DatumVec::new();
+                let mut row_buf = Row::default();
+                let converter = metadata.layout.as_ref().map(|layout| layout.converter());
                 for ((data, _), _, diff) in contents {
                     // TODO(petrosagg): We should accumulate the errors too and let the user
                     // interprret the result
-                    let row = data.expect("invalid protobuf data").0?;
+                    let mut row = data.expect("invalid protobuf data").0?;
+                    if let Some(converter) = &converter {
+                        row = converter.to_logical(row, &mut datum_vec, &mut row_buf);
+                    }
                     snapshot.push((row, diff));
                 }
                 Ok(snapshot)
@@ -1483,6 +1777,136 @@ where
         }
     }
 
+    async fn snapshot_and_map_filter(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+        mfp: SafeMfpPlan,
+    ) -> Result<BoxFuture<'static, Result<Vec<(Row, Diff)>, StorageError>>, StorageError> {
+        let as_of = Antichain::from_elem(as_of);
+        let metadata = self.collection(id)?.collection_metadata.clone();
+
+        let persist_client = self
+            .persist
+            .open(metadata.persist_location.clone())
+            .await
+            .unwrap();
+
+        // As with `snapshot`, we create a short-lived read handle rather than
+        // keeping one around. It is moved into the returned future, which
+        // expires it once all parts have been fetched.
+        let mut read_handle = persist_client
+            .open_leased_reader::<SourceData, (), _, _>(
+                metadata.data_shard,
+                &format!("snapshot_and_map_filter {}", id),
+                Arc::new(metadata.relation_desc.clone()),
+                Arc::new(UnitSchema),
+            )
+            .await
+            .expect("invalid persist usage");
+
+        // Skip the parts whose stats prove that none of their rows pass the
+        // filter. The stats describe the physical columns, so this is only
+        // sound when they match the logical ones the filter refers to.
+        let relation_desc = metadata.relation_desc.clone();
+        let prune = metadata.layout.is_none();
+        let parts = match read_handle
+            .snapshot_filtered(as_of, |stats| {
+                !prune || should_fetch_part(&relation_desc, &mfp, stats)
+            })
+            .await
+        {
+            Ok(parts) => parts,
+            Err(_) => {
+                read_handle.expire().await;
+                return Err(StorageError::ReadBeforeSince(id));
+            }
+        };
+
+        Ok(Box::pin(async move {
+            let mut result = Ok(Vec::new());
+            let mut datum_vec = DatumVec::new();
+            let mut row_buf = Row::default();
+            let mut layout_buf = Row::default();
+            let converter = metadata.layout.as_ref().map(|layout| layout.converter());
+            read_handle
+                .fetch_snapshot_parts(parts, |((data, _), _, diff)| {
+                    let Ok(snapshot) = &mut result else {
+                        return;
+                    };
+                    let row = match data.expect("invalid protobuf data").0 {
+                        Ok(row) => row,
+                        Err(err) => {
+                            result = Err(StorageError::DataflowError(err));
+                            return;
+                        }
+                    };
+                    let row = match &converter {
+                        Some(converter) => {
+                            converter.to_logical(row, &mut datum_vec, &mut layout_buf)
+                        }
+                        None => row,
+                    };
+                    let arena = RowArena::new();
+                    let mut datums = datum_vec.borrow_with(&row);
+                    match mfp.evaluate_into(&mut datums, &arena, &mut row_buf) {
+                        Ok(Some(row)) => snapshot.push((row, diff)),
+                        Ok(None) => (),
+                        Err(err) => result = Err(StorageError::DataflowError(err.into())),
+                    }
+                })
+                .await;
+            read_handle.expire().await;
+            result
+        }))
+    }
+
+    fn snapshot_stats(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxFuture<'static, Result<SnapshotStats, StorageError>>, StorageError> {
+        let as_of = Antichain::from_elem(as_of);
+        let metadata = self.collection(id)?.collection_metadata.clone();
+        let persist = Arc::clone(&self.persist);
+
+        Ok(Box::pin(async move {
+            let persist_client = persist
+                .open(metadata.persist_location.clone())
+                .await
+                .unwrap();
+
+            // As with `snapshot`, we create a short-lived read handle rather
+            // than keeping one around. If this future is dropped, dropping the
+            // handle expires it.
+            let mut read_handle = persist_client
+                .open_leased_reader::<SourceData, (), _, _>(
+                    metadata.data_shard,
+                    &format!("snapshot_stats {}", id),
+                    Arc::new(metadata.relation_desc),
+                    Arc::new(UnitSchema),
+                )
+                .await
+                .expect("invalid persist usage");
+
+            let result = read_handle
+                .snapshot_stats(as_of)
+                .await
+                .map_err(|_| StorageError::ReadBeforeSince(id));
+            read_handle.expire().await;
+            result
+        }))
+    }
+
+    async fn append_introspection_updates(
+        &mut self,
+        type_: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    ) {
+        let id = self.state.introspection_ids[&type_];
+        self.append_to_managed_collection(id, updates).await;
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     fn set_read_policy(&mut self, policies: Vec<(GlobalId, ReadPolicy<Self::Timestamp>)>) {
         let mut read_capability_changes = BTreeMap::default();
@@ -1606,8 +2030,8 @@ where
             // before processing external commands.
             biased;
 
-            Some(m) = self.internal_response_queue.recv() => m,
-            Some((_id, m)) = clients.next() => m,
+            Some(m) = self.internal_response_queue.recv() => (None, m),
+            Some((_id, (replica_id, m))) = clients.next() => (Some(replica_id), m),
         };
 
         self.state.stashed_response = Some(msg);
@@ -1616,14 +2040,24 @@ where
     async fn process(&mut self) -> Result<(), anyhow::Error> {
         match self.state.stashed_response.take() {
             None => (),
-            Some(StorageResponse::FrontierUppers(updates)) => {
+            Some((replica_id, StorageResponse::FrontierUppers(updates))) => {
+                if let Some(replica_id) = replica_id {
+                    for (id, upper) in &updates {
+                        if let Some(collection) = self.state.collections.get_mut(id) {
+                            collection
+                                .replica_write_frontiers
+                                .insert(replica_id, upper.clone());
+                        }
+                    }
+                }
                 self.update_write_frontiers(&updates);
             }
-            Some(StorageResponse::DroppedIds(_ids)) => {
+            Some((_, StorageResponse::DroppedIds(_ids))) => {
                 // TODO(petrosagg): It looks like the storage controller never cleans up GlobalIds
                 // from its state. It should probably be done as a reaction to this response.
             }
-            Some(StorageResponse::StatisticsUpdates(source_stats, sink_stats)) => {
+            Some((replica_id, StorageResponse::StatisticsUpdates(source_stats, sink_stats))) => {
+                let replica_id = replica_id.expect("statistics are reported by replicas");
                 // Note we only hold the locks while moving some plain-old-data around here.
 
                 let mut shared_stats = self.state.source_statistics.lock().expect("poisoned");
@@ -1631,7 +2065,7 @@ where
                     let shared_stats = shared_stats.entry(stat.id).or_default();
                     // We just write the whole object, as the update from storage represents the
                     // current values.
-                    shared_stats.insert(stat.worker_id, stat);
+                    shared_stats.insert((replica_id, stat.worker_id), stat);
                 }
 
                 let mut shared_stats = self.state.sink_statistics.lock().expect("poisoned");
@@ -1639,7 +2073,7 @@ where
                     let shared_stats = shared_stats.entry(stat.id).or_default();
                     // We just write the whole object, as the update from storage represents the
                     // current values.
-                    shared_stats.insert(stat.worker_id, stat);
+                    shared_stats.insert((replica_id, stat.worker_id), stat);
                 }
             }
         }
@@ -1755,6 +2189,12 @@ where
     ) -> Self {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
+        let supports_atomic_appends = persist_clients
+            .open(persist_location.clone())
+            .await
+            .expect("invalid persist location")
+            .supports_atomic_multi_shard();
+
         Self {
             build_info,
             state: StorageControllerState::new(postgres_url, tx, now, postgres_factory, envd_epoch)
@@ -1762,10 +2202,35 @@ where
             internal_response_queue: rx,
             persist_location,
             persist: persist_clients,
+            supports_atomic_appends,
             metrics: StorageControllerMetrics::new(metrics_registry),
         }
     }
 
+    /// Validates `commands` and hands them to the persist write worker, which
+    /// commits the updates atomically if `atomic` is set.
+    fn append_inner(
+        &mut self,
+        mut commands: Vec<(GlobalId, Vec<Update<T>>, T)>,
+        atomic: bool,
+    ) -> Result<tokio::sync::oneshot::Receiver<Result<(), StorageError>>, StorageError> {
+        // TODO(petrosagg): validate appends against the expected RelationDesc of the collection
+        for (id, updates, batch_upper) in commands.iter_mut() {
+            for update in updates.iter() {
+                if !update.timestamp.less_than(batch_upper) {
+                    return Err(StorageError::UpdateBeyondUpper(*id));
+                }
+            }
+            if let Some(layout) = &self.collection(*id)?.collection_metadata.layout {
+                for update in updates.iter_mut() {
+                    update.row = layout.to_physical(&update.row);
+                }
+            }
+        }
+
+        Ok(self.state.persist_write_handles.append(commands, atomic))
+    }
+
     /// Validate that a collection exists for all identifiers, and error if any do not.
     fn validate_collection_ids(
         &self,
@@ -2241,6 +2706,9 @@ pub struct CollectionState<T> {
 
     /// Reported write frontier.
     pub write_frontier: Antichain<T>,
+    /// The write frontier reported by each replica that ingests the
+    /// collection.
+    pub replica_write_frontiers: BTreeMap<ReplicaId, Antichain<T>>,
 
     pub collection_metadata: CollectionMetadata,
 }
@@ -2261,6 +2729,7 @@ impl<T: Timestamp> CollectionState<T> {
             implied_capability: since.clone(),
             read_policy: ReadPolicy::ValidFrom(since),
             write_frontier,
+            replica_write_frontiers: BTreeMap::new(),
             collection_metadata: metadata,
         }
     }
@@ -2300,6 +2769,47 @@ impl<T: Timestamp> ExportState<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn layout_to_logical() {
+        // The shard has columns a, b, c, of which b was dropped and c added
+        // with a default.
+        let layout = CollectionLayout {
+            defaults: Row::pack_slice(&[Datum::Null, Datum::Null, Datum::Int32(42)]),
+            projection: vec![0, 2],
+        };
+        let converter = layout.converter();
+        let mut datum_vec = DatumVec::new();
+        let mut row_buf = Row::default();
+
+        let old_row = Row::pack_slice(&[Datum::Int32(1), Datum::String("one")]);
+        assert_eq!(
+            converter.to_logical(old_row, &mut datum_vec, &mut row_buf),
+            Row::pack_slice(&[Datum::Int32(1), Datum::Int32(42)])
+        );
+        let new_row = layout.to_physical(&Row::pack_slice(&[Datum::Int32(2), Datum::Int32(3)]));
+        assert_eq!(
+            converter.to_logical(new_row, &mut datum_vec, &mut row_buf),
+            Row::pack_slice(&[Datum::Int32(2), Datum::Int32(3)])
+        );
+
+        // Rows of a table with no dropped columns are returned as is.
+        let layout = CollectionLayout {
+            defaults: Row::pack_slice(&[Datum::Null, Datum::Int32(42)]),
+            projection: vec![0, 1],
+        };
+        let converter = layout.converter();
+        let row = Row::pack_slice(&[Datum::Int32(1), Datum::Int32(2)]);
+        assert_eq!(
+            converter.to_logical(row.clone(), &mut datum_vec, &mut row_buf),
+            row
+        );
+        let old_row = Row::pack_slice(&[Datum::Int32(1)]);
+        assert_eq!(
+            converter.to_logical(old_row, &mut datum_vec, &mut row_buf),
+            Row::pack_slice(&[Datum::Int32(1), Datum::Int32(42)])
+        );
+    }
+
     #[test]
     fn lag_writes_by_zero() {
         let policy = ReadPolicy::lag_writes_by(mz_repr::Timestamp::default());
@@ -21,7 +21,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use tracing::Instrument;
 
 use mz_persist_client::critical::SinceHandle;
-use mz_persist_client::write::WriteHandle;
+use mz_persist_client::write::{AtomicAppend, WriteHandle};
 use mz_persist_types::Codec64;
 use mz_repr::{Diff, GlobalId, TimestampManipulation};
 
@@ -180,8 +180,11 @@ where
 enum PersistWriteWorkerCmd<T: Timestamp + Lattice + Codec64> {
     Register(GlobalId, WriteHandle<SourceData, (), T, Diff>),
     Update(GlobalId, WriteHandle<SourceData, (), T, Diff>),
+    /// Appends updates to collections, committing them atomically if the
+    /// `bool` is set.
     Append(
         Vec<(GlobalId, Vec<Update<T>>, T)>,
+        bool,
         tokio::sync::oneshot::Sender<Result<(), StorageError>>,
     ),
     /// Appends `Vec<TimelessUpdate>` to `GlobalId` at, essentially,
@@ -193,6 +196,16 @@ enum PersistWriteWorkerCmd<T: Timestamp + Lattice + Codec64> {
     Shutdown,
 }
 
+/// Why [PersistWriteWorker] failed to commit the updates to a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AppendFailure {
+    /// The upper of the collection was not the expected one.
+    InvalidUpper,
+    /// The updates had to be committed atomically with those to other
+    /// collections, which the consensus does not support.
+    AtomicUnsupported,
+}
+
 impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorker<T> {
     pub(crate) fn new(
         mut frontier_responses: tokio::sync::mpsc::UnboundedSender<StorageResponse<T>>,
@@ -230,6 +243,8 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                             // Accumulated updates and upper frontier.
                             let mut all_updates = BTreeMap::default();
                             let mut all_responses = Vec::default();
+                            // Collections whose updates must be committed atomically.
+                            let mut atomic_ids = BTreeSet::new();
 
                             while let Some((span, command)) = commands.pop_front() {
                                 match command {
@@ -245,7 +260,7 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                                     PersistWriteWorkerCmd::Update(id, write_handle) => {
                                         write_handles.insert(id, write_handle).expect("PersistWriteWorkerCmd::Update only valid for updating extant write handles");
                                     }
-                                    PersistWriteWorkerCmd::Append(updates, response) => {
+                                    PersistWriteWorkerCmd::Append(updates, atomic, response) => {
                                         let mut ids = BTreeSet::new();
                                         for (id, update, upper) in updates {
                                             ids.insert(id);
@@ -271,6 +286,9 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                                             updates.extend(update);
                                             old_upper.join_assign(&Antichain::from_elem(upper));
                                         }
+                                        if atomic {
+                                            atomic_ids.extend(ids.iter().copied());
+                                        }
                                         all_responses.push((ids, response));
                                     }
                                     PersistWriteWorkerCmd::MonotonicAppend(updates, response) => {
@@ -309,7 +327,7 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                                         }
                                         commands.push_front((
                                             span,
-                                            PersistWriteWorkerCmd::Append(updates_outer, response),
+                                            PersistWriteWorkerCmd::Append(updates_outer, false, response),
                                         ));
                                     }
                                     PersistWriteWorkerCmd::Shutdown => {
@@ -330,7 +348,8 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                                     GlobalId,
                                     (tracing::Span, Vec<Update<T2>>, Antichain<T2>),
                                 >,
-                            ) -> Result<(), Vec<GlobalId>> {
+                                atomic_ids: &BTreeSet<GlobalId>,
+                            ) -> Result<(), BTreeMap<GlobalId, AppendFailure>> {
                                 let futs = FuturesUnordered::new();
 
                                 // We cannot iterate through the updates and then set off a persist call
@@ -339,39 +358,118 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                                 //
                                 // Instead, we first group the update by ID above and then iterate
                                 // through all available write handles and see if there are any updates
-                                // for it. If yes, we send them all in one go.
+                                // for it. If yes, we write them all out as one batch.
                                 for (id, write) in write_handles.iter_mut() {
                                     if let Some((span, updates, new_upper)) = commands.remove(id) {
                                         let persist_upper = write.upper().clone();
+                                        let has_updates = !updates.is_empty();
                                         let updates = updates
                                             .into_iter()
                                             .map(|u| ((SourceData(Ok(u.row)), ()), u.timestamp, u.diff));
 
                                         futs.push(async move {
-                                            let persist_upper = persist_upper.clone();
-                                            write
-                                                .compare_and_append(
-                                                    updates.clone(),
-                                                    persist_upper.clone(),
-                                                    new_upper.clone(),
-                                                )
-                                                .instrument(span.clone())
+                                            let batch = write
+                                                .batch(updates, persist_upper.clone(), new_upper.clone())
+                                                .instrument(span)
                                                 .await
-                                                .expect("cannot append updates")
-                                                .or(Err(*id))?;
-
-                                            Ok::<_, GlobalId>((*id, new_upper))
+                                                .expect("cannot append updates");
+                                            (*id, write, batch, persist_upper, new_upper, has_updates)
                                         })
                                     }
                                 }
 
                                 use futures::StreamExt;
-                                // Ensure all futures run to completion, and track status of each of them individually
-                                let (new_uppers, failed_appends): (Vec<_>, Vec<_>) = futs
+                                let batches = futs.collect::<Vec<_>>().await;
+
+                                // Only the batches of appends that asked for atomicity, e.g.
+                                // those of a transaction that writes to multiple tables, are
+                                // committed together, so that they are either entirely visible or
+                                // not at all. Everything else, including the appends that merely
+                                // advance the upper of an idle table, is committed shard by shard.
+                                let (mut atomic, mut singles): (Vec<_>, Vec<_>) =
+                                    batches.into_iter().partition(|(id, _, _, _, _, has_updates)| {
+                                        *has_updates && atomic_ids.contains(id)
+                                    });
+
+                                let mut new_uppers = Vec::new();
+                                let mut failed_appends = BTreeMap::new();
+
+                                if atomic.len() < 2 {
+                                    singles.append(&mut atomic);
+                                } else if !atomic[0].1.supports_atomic_multi_shard() {
+                                    // Committing the batches shard by shard could leave them
+                                    // partially visible, so don't commit any of them.
+                                    for (id, _, batch, _, _, _) in atomic.drain(..) {
+                                        batch.delete().await;
+                                        failed_appends.insert(id, AppendFailure::AtomicUnsupported);
+                                    }
+                                }
+
+                                if !atomic.is_empty() {
+                                    let mut appends = atomic
+                                        .iter_mut()
+                                        .map(|(_, write, batch, persist_upper, new_upper, _)| {
+                                            AtomicAppend {
+                                                handle: &mut **write,
+                                                batch,
+                                                expected_upper: persist_upper.clone(),
+                                                new_upper: new_upper.clone(),
+                                            }
+                                        })
+                                        .collect::<Vec<_>>();
+                                    let res = WriteHandle::compare_and_append_atomic(&mut appends)
+                                        .await
+                                        .expect("cannot append updates");
+                                    drop(appends);
+
+                                    match res {
+                                        Ok(()) => new_uppers.extend(
+                                            atomic
+                                                .into_iter()
+                                                .map(|(id, _, _, _, new_upper, _)| (id, new_upper)),
+                                        ),
+                                        Err(_) => {
+                                            // Nothing was committed, so every append in this group
+                                            // failed.
+                                            for (id, _, batch, _, _, _) in atomic {
+                                                batch.delete().await;
+                                                failed_appends.insert(id, AppendFailure::InvalidUpper);
+                                            }
+                                        }
+                                    }
+                                }
+
+                                let futs = FuturesUnordered::new();
+                                for (id, write, mut batch, persist_upper, new_upper, _) in singles {
+                                    futs.push(async move {
+                                        let res = write
+                                            .compare_and_append_batch(
+                                                &mut [&mut batch],
+                                                persist_upper,
+                                                new_upper.clone(),
+                                            )
+                                            .await
+                                            .expect("cannot append updates");
+                                        match res {
+                                            Ok(()) => Ok((id, new_upper)),
+                                            Err(_) => {
+                                                batch.delete().await;
+                                                Err(id)
+                                            }
+                                        }
+                                    });
+                                }
+                                let (singles_uppers, singles_failed): (Vec<_>, Vec<_>) = futs
                                     .collect::<Vec<_>>()
                                     .await
                                     .into_iter()
                                     .partition_result();
+                                new_uppers.extend(singles_uppers);
+                                failed_appends.extend(
+                                    singles_failed
+                                        .into_iter()
+                                        .map(|id| (id, AppendFailure::InvalidUpper)),
+                                );
 
                                 // It is not strictly an error for the controller to hang up.
                                 let _ =
@@ -385,16 +483,26 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                             }
 
                             let result =
-                                append_work(&mut frontier_responses, &mut write_handles, all_updates).await;
+                                append_work(&mut frontier_responses, &mut write_handles, all_updates, &atomic_ids).await;
 
                             for (ids, response) in all_responses {
                                 let result = match &result {
-                                    Err(bad_ids) => {
-                                        let filtered: Vec<_> = bad_ids.iter().filter(|id| ids.contains(id)).copied().collect();
-                                        if filtered.is_empty() {
-                                            Ok(())
+                                    Err(failures) => {
+                                        let filtered_ids = |failure| -> Vec<_> {
+                                            failures
+                                                .iter()
+                                                .filter(|(id, f)| ids.contains(id) && **f == failure)
+                                                .map(|(id, _)| *id)
+                                                .collect()
+                                        };
+                                        let unsupported = filtered_ids(AppendFailure::AtomicUnsupported);
+                                        let invalid_uppers = filtered_ids(AppendFailure::InvalidUpper);
+                                        if !unsupported.is_empty() {
+                                            Err(StorageError::AtomicAppendsUnsupported(unsupported))
+                                        } else if !invalid_uppers.is_empty() {
+                                            Err(StorageError::InvalidUppers(invalid_uppers))
                                         } else {
-                                            Err(StorageError::InvalidUppers(filtered))
+                                            Ok(())
                                         }
                                     }
                                     Ok(()) => Ok(()),
@@ -438,9 +546,17 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
         self.send(PersistWriteWorkerCmd::Update(id, write_handle))
     }
 
+    /// Appends values to collections associated with `GlobalId`.
+    ///
+    /// If `atomic` is set, the updates to all of the collections are either
+    /// committed together or not at all. This fails with
+    /// [StorageError::AtomicAppendsUnsupported], rather than committing the
+    /// updates one collection at a time, if more than one collection receives
+    /// updates and the consensus can't commit to multiple shards at once.
     pub(crate) fn append(
         &self,
         updates: Vec<(GlobalId, Vec<Update<T>>, T)>,
+        atomic: bool,
     ) -> tokio::sync::oneshot::Receiver<Result<(), StorageError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         if updates.is_empty() {
@@ -448,7 +564,7 @@ impl<T: Timestamp + Lattice + Codec64 + TimestampManipulation> PersistWriteWorke
                 .expect("rx has not been dropped at this point");
             rx
         } else {
-            self.send(PersistWriteWorkerCmd::Append(updates, tx));
+            self.send(PersistWriteWorkerCmd::Append(updates, atomic, tx));
             rx
         }
     }
@@ -14,9 +14,16 @@
 //! minimal form. If it observes a send or receive failure while communicating
 //! with the underlying client, it will reconnect the client and replay the
 //! command stream.
+//!
+//! A storage instance can have several replicas. Sources are ingested by every
+//! replica, each of which writes the same data to the same persist shards, so
+//! the loss of a replica does not interrupt ingestion. Sinks are exported by
+//! only one replica at a time, and are moved to another replica when that
+//! replica fails or is dropped.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
+use std::task::Poll;
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -29,7 +36,6 @@ use tokio::pin;
 use tokio::select;
 use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
-use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::warn;
 
 use mz_build_info::BuildInfo;
@@ -46,6 +52,7 @@ use crate::client::{
 };
 use crate::controller::ResumptionFrontierCalculator;
 use crate::metrics::RehydratingStorageClientMetrics;
+use crate::types::instances::ReplicaId;
 use crate::types::parameters::StorageParameters;
 use crate::types::sources::SourceData;
 
@@ -54,9 +61,21 @@ use crate::types::sources::SourceData;
 /// See the [module documentation](self) for details.
 #[derive(Debug)]
 pub struct RehydratingStorageClient<T> {
-    command_tx: UnboundedSender<RehydrationCommand<T>>,
-    response_rx: UnboundedReceiverStream<StorageResponse<T>>,
-    _task: AbortOnDropHandle<()>,
+    build_info: &'static BuildInfo,
+    persist: Arc<PersistClientCache>,
+    metrics: RehydratingStorageClientMetrics,
+    /// The commands observed so far, in a minimal form, used to hydrate newly
+    /// connected replicas.
+    history: CommandHistory<T>,
+    /// The connected replicas.
+    replicas: BTreeMap<ReplicaId, ReplicaClient<T>>,
+    /// The replicas whose communication is live, i.e., that have been hydrated
+    /// and not failed since.
+    healthy_replicas: BTreeSet<ReplicaId>,
+    /// The replica that exports the sinks of the instance, if any.
+    sink_replica: Option<ReplicaId>,
+    response_tx: UnboundedSender<(ReplicaId, ReplicaResponse<T>)>,
+    response_rx: UnboundedReceiver<(ReplicaId, ReplicaResponse<T>)>,
 }
 
 impl<T> RehydratingStorageClient<T>
@@ -65,15 +84,259 @@ where
     StorageGrpcClient: StorageClient<T>,
 {
     /// Creates a `RehydratingStorageClient` that is not yet connected to
-    /// a storage replica.
+    /// any storage replica.
     pub fn new(
         build_info: &'static BuildInfo,
         persist: Arc<PersistClientCache>,
         metrics: RehydratingStorageClientMetrics,
     ) -> RehydratingStorageClient<T> {
-        let (command_tx, command_rx) = unbounded_channel();
         let (response_tx, response_rx) = unbounded_channel();
+        RehydratingStorageClient {
+            build_info,
+            persist,
+            metrics,
+            history: CommandHistory::default(),
+            replicas: BTreeMap::new(),
+            healthy_replicas: BTreeSet::new(),
+            sink_replica: None,
+            response_tx,
+            response_rx,
+        }
+    }
+
+    /// Connects to the storage replica with the specified ID at the specified
+    /// network address.
+    ///
+    /// If a replica with the same ID is already connected, communication with
+    /// it is severed in favor of the new address.
+    pub fn connect(&mut self, replica_id: ReplicaId, addr: String) {
+        if let Some(replica) = self.replicas.get(&replica_id) {
+            replica.send(RehydrationCommand::Connect { addr });
+            return;
+        }
+
+        // The first replica to connect exports the sinks.
+        let exports_sinks = self.sink_replica.is_none();
+        if exports_sinks {
+            self.sink_replica = Some(replica_id);
+        }
+
+        let replica = ReplicaClient::new(
+            replica_id,
+            self.build_info,
+            Arc::clone(&self.persist),
+            self.metrics.clone(),
+            self.response_tx.clone(),
+        );
+        for command in self.history.commands(exports_sinks) {
+            replica.send(RehydrationCommand::Send(command));
+        }
+        replica.send(RehydrationCommand::Connect { addr });
+        self.replicas.insert(replica_id, replica);
+    }
+
+    /// Disconnects from the storage replica with the specified ID.
+    ///
+    /// If the replica was exporting the sinks of the instance, the sinks are
+    /// moved to one of the remaining replicas, preferring a healthy one.
+    pub fn drop_replica(&mut self, replica_id: ReplicaId) {
+        self.replicas.remove(&replica_id);
+        self.healthy_replicas.remove(&replica_id);
+        if self.sink_replica != Some(replica_id) {
+            return;
+        }
+
+        self.sink_replica = None;
+        let next = self
+            .healthy_replicas
+            .iter()
+            .chain(self.replicas.keys())
+            .next()
+            .copied();
+        if let Some(next) = next {
+            self.move_sinks(next);
+        }
+    }
+
+    /// Moves the export of the instance's sinks to the replica `to`.
+    ///
+    /// The replica that previously exported the sinks forgets about them, so
+    /// that it stops exporting them once it reconnects.
+    fn move_sinks(&mut self, to: ReplicaId) {
+        if let Some(from) = self.sink_replica.replace(to) {
+            if let Some(replica) = self.replicas.get(&from) {
+                replica.send(RehydrationCommand::ReleaseSinks);
+            }
+        }
+        let sinks: Vec<_> = self.history.sinks.values().cloned().collect();
+        if !sinks.is_empty() {
+            self.replicas[&to].send(RehydrationCommand::AdoptSinks(sinks));
+        }
+    }
+
+    /// Records that communication with the replica is live.
+    ///
+    /// If the sinks are stranded on a replica that has failed, they move to
+    /// this replica.
+    fn replica_connected(&mut self, replica_id: ReplicaId) {
+        self.healthy_replicas.insert(replica_id);
+        match self.sink_replica {
+            Some(sink_replica)
+                if sink_replica != replica_id && !self.healthy_replicas.contains(&sink_replica) =>
+            {
+                self.move_sinks(replica_id)
+            }
+            _ => {}
+        }
+    }
+
+    /// Records that communication with the replica has failed.
+    ///
+    /// If the replica was exporting the sinks of the instance, the sinks move
+    /// to a healthy replica, if there is one. Otherwise they stay put, and move
+    /// to the first replica that becomes healthy.
+    fn replica_disconnected(&mut self, replica_id: ReplicaId) {
+        self.healthy_replicas.remove(&replica_id);
+        if self.sink_replica == Some(replica_id) {
+            if let Some(next) = self.healthy_replicas.iter().next().copied() {
+                self.move_sinks(next);
+            }
+        }
+    }
+
+    /// Sends a command to the underlying replicas.
+    pub fn send(&mut self, cmd: StorageCommand<T>) {
+        self.history.absorb(&cmd);
+        for (replica_id, replica) in &self.replicas {
+            if matches!(cmd, StorageCommand::CreateSinks(_))
+                && self.sink_replica != Some(*replica_id)
+            {
+                continue;
+            }
+            replica.send(RehydrationCommand::Send(cmd.clone()));
+        }
+    }
+
+    /// Returns a stream that produces responses from the underlying replicas,
+    /// along with the ID of the replica that produced them.
+    ///
+    /// Polling the stream also reacts to replicas failing and recovering.
+    pub fn response_stream(&mut self) -> impl Stream<Item = (ReplicaId, StorageResponse<T>)> + '_ {
+        futures::stream::poll_fn(move |cx| loop {
+            let (replica_id, response) = match self.response_rx.poll_recv(cx) {
+                Poll::Ready(Some(message)) => message,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            // Messages from replicas that have been dropped since are stale.
+            if !self.replicas.contains_key(&replica_id) {
+                continue;
+            }
+            match response {
+                ReplicaResponse::Response(response) => {
+                    return Poll::Ready(Some((replica_id, response)))
+                }
+                ReplicaResponse::Connected => self.replica_connected(replica_id),
+                ReplicaResponse::Disconnected => self.replica_disconnected(replica_id),
+            }
+        })
+    }
+}
+
+/// The commands sent to a storage instance, in a minimal form.
+#[derive(Debug)]
+struct CommandHistory<T> {
+    /// The sources that have been observed.
+    sources: BTreeMap<GlobalId, CreateSourceCommand<T>>,
+    /// The exports that have been observed.
+    sinks: BTreeMap<GlobalId, CreateSinkCommand<T>>,
+    /// Set to `true` once [`StorageCommand::InitializationComplete`] has been
+    /// observed.
+    initialized: bool,
+    /// Storage configuration that has been observed.
+    config: StorageParameters,
+}
+
+impl<T> Default for CommandHistory<T> {
+    fn default() -> Self {
+        CommandHistory {
+            sources: BTreeMap::new(),
+            sinks: BTreeMap::new(),
+            initialized: false,
+            config: Default::default(),
+        }
+    }
+}
+
+impl<T: Clone> CommandHistory<T> {
+    fn absorb(&mut self, command: &StorageCommand<T>) {
+        match command {
+            StorageCommand::InitializationComplete => self.initialized = true,
+            StorageCommand::UpdateConfiguration(params) => {
+                self.config.update(params.clone());
+            }
+            StorageCommand::CreateSources(ingestions) => {
+                for ingestion in ingestions {
+                    self.sources.insert(ingestion.id, ingestion.clone());
+                }
+            }
+            StorageCommand::CreateSinks(exports) => {
+                for export in exports {
+                    self.sinks.insert(export.id, export.clone());
+                }
+            }
+            StorageCommand::AllowCompaction(frontiers) => {
+                for (id, frontier) in frontiers {
+                    if frontier.is_empty() {
+                        self.sources.remove(id);
+                        self.sinks.remove(id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the commands that bring a new replica up to date.
+    fn commands(&self, include_sinks: bool) -> Vec<StorageCommand<T>> {
+        let mut commands = vec![
+            StorageCommand::UpdateConfiguration(self.config.clone()),
+            StorageCommand::CreateSources(self.sources.values().cloned().collect()),
+        ];
+        if include_sinks {
+            commands.push(StorageCommand::CreateSinks(
+                self.sinks.values().cloned().collect(),
+            ));
+        }
+        if self.initialized {
+            commands.push(StorageCommand::InitializationComplete);
+        }
+        commands
+    }
+}
+
+/// A client for a single storage replica that replays the command stream on
+/// failure.
+#[derive(Debug)]
+struct ReplicaClient<T> {
+    command_tx: UnboundedSender<RehydrationCommand<T>>,
+    _task: AbortOnDropHandle<()>,
+}
+
+impl<T> ReplicaClient<T>
+where
+    T: Timestamp + Lattice + Codec64,
+    StorageGrpcClient: StorageClient<T>,
+{
+    fn new(
+        replica_id: ReplicaId,
+        build_info: &'static BuildInfo,
+        persist: Arc<PersistClientCache>,
+        metrics: RehydratingStorageClientMetrics,
+        response_tx: UnboundedSender<(ReplicaId, ReplicaResponse<T>)>,
+    ) -> ReplicaClient<T> {
+        let (command_tx, command_rx) = unbounded_channel();
         let mut task = RehydrationTask {
+            replica_id,
             build_info,
             command_rx,
             response_tx,
@@ -86,31 +349,17 @@ where
             metrics,
         };
         let task = mz_ore::task::spawn(|| "rehydration", async move { task.run().await });
-        RehydratingStorageClient {
+        ReplicaClient {
             command_tx,
-            response_rx: UnboundedReceiverStream::new(response_rx),
             _task: task.abort_on_drop(),
         }
     }
 
-    /// Connects to the storage replica at the specified network address.
-    pub fn connect(&mut self, addr: String) {
+    fn send(&self, command: RehydrationCommand<T>) {
         self.command_tx
-            .send(RehydrationCommand::Connect { addr })
+            .send(command)
             .expect("rehydration task should not drop first");
     }
-
-    /// Sends a command to the underlying client.
-    pub fn send(&mut self, cmd: StorageCommand<T>) {
-        self.command_tx
-            .send(RehydrationCommand::Send(cmd))
-            .expect("rehydration task should not drop first");
-    }
-
-    /// Returns a stream that produces responses from the underlying client.
-    pub fn response_stream(&mut self) -> impl Stream<Item = StorageResponse<T>> + '_ {
-        &mut self.response_rx
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -122,17 +371,36 @@ enum RehydrationCommand<T> {
     },
     /// Send the contained storage command to the replica.
     Send(StorageCommand<T>),
+    /// Start exporting the contained sinks, which were previously exported by
+    /// another replica.
+    AdoptSinks(Vec<CreateSinkCommand<T>>),
+    /// Stop exporting sinks, which have moved to another replica.
+    ReleaseSinks,
+}
+
+/// A message from the task that manages a storage replica.
+#[derive(Debug)]
+enum ReplicaResponse<T> {
+    /// A response from the storage replica.
+    Response(StorageResponse<T>),
+    /// Communication with the storage replica was (re)established.
+    Connected,
+    /// Communication with the storage replica failed, and is being
+    /// reestablished.
+    Disconnected,
 }
 
 /// A task that manages rehydration.
 struct RehydrationTask<T> {
+    /// The ID of the storage replica.
+    replica_id: ReplicaId,
     /// The build information for this process.
     build_info: &'static BuildInfo,
     /// A channel upon which commands intended for the storage replica are
     /// delivered.
     command_rx: UnboundedReceiver<RehydrationCommand<T>>,
     /// A channel upon which responses from the storage replica are delivered.
-    response_tx: UnboundedSender<StorageResponse<T>>,
+    response_tx: UnboundedSender<(ReplicaId, ReplicaResponse<T>)>,
     /// The sources that have been observed.
     sources: BTreeMap<GlobalId, CreateSourceCommand<T>>,
     /// The exports that have been observed.
@@ -198,6 +466,10 @@ where
                 Some(RehydrationCommand::Send(command)) => {
                     self.absorb_command(&command);
                 }
+                Some(RehydrationCommand::AdoptSinks(exports)) => {
+                    self.absorb_command(&StorageCommand::CreateSinks(exports));
+                }
+                Some(RehydrationCommand::ReleaseSinks) => self.sinks.clear(),
             }
         }
     }
@@ -221,6 +493,10 @@ where
                     Ok(RehydrationCommand::Send(command)) => {
                         self.absorb_command(&command);
                     }
+                    Ok(RehydrationCommand::AdoptSinks(exports)) => {
+                        self.absorb_command(&StorageCommand::CreateSinks(exports));
+                    }
+                    Ok(RehydrationCommand::ReleaseSinks) => self.sinks.clear(),
                     Err(TryRecvError::Disconnected) => return RehydrationTaskState::Done,
                     Err(TryRecvError::Empty) => break,
                 }
@@ -299,6 +575,14 @@ where
             export.description.as_of = cached_as_of.maybe_fast_forward(from_since);
         }
 
+        if self
+            .response_tx
+            .send((self.replica_id, ReplicaResponse::Connected))
+            .is_err()
+        {
+            return RehydrationTaskState::Done;
+        }
+
         // Rehydrate all commands.
         let mut commands = vec![
             StorageCommand::UpdateConfiguration(self.config.clone()),
@@ -325,6 +609,19 @@ where
                     self.absorb_command(&command);
                     self.send_commands(addr, client, vec![command]).await
                 }
+                // Adopted sinks need their `as_of` brought up to date, which
+                // happens during rehydration. The replica reconciles the
+                // replayed commands with the dataflows it is already running.
+                Some(RehydrationCommand::AdoptSinks(exports)) => {
+                    self.absorb_command(&StorageCommand::CreateSinks(exports));
+                    RehydrationTaskState::Rehydrate { addr }
+                }
+                // Likewise, the replica drops the released sinks when it
+                // reconciles the replayed commands.
+                Some(RehydrationCommand::ReleaseSinks) => {
+                    self.sinks.clear();
+                    RehydrationTaskState::Rehydrate { addr }
+                }
             },
             // Response from storage cluster to forward to controller.
             response = client.recv() => {
@@ -367,6 +664,7 @@ where
         match response {
             Ok(response) => {
                 if let Some(response) = self.absorb_response(response) {
+                    let response = (self.replica_id, ReplicaResponse::Response(response));
                     if self.response_tx.send(response).is_err() {
                         RehydrationTaskState::Done
                     } else {
@@ -378,6 +676,13 @@ where
             }
             Err(e) => {
                 warn!("storage cluster produced error, reconnecting: {e}");
+                if self
+                    .response_tx
+                    .send((self.replica_id, ReplicaResponse::Disconnected))
+                    .is_err()
+                {
+                    return RehydrationTaskState::Done;
+                }
                 RehydrationTaskState::Rehydrate { addr }
             }
         }
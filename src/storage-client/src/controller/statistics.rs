@@ -22,13 +22,14 @@ use mz_repr::{GlobalId, Row};
 
 use crate::client::PackableStats;
 use crate::controller::collection_mgmt::CollectionManager;
+use crate::types::instances::ReplicaId;
 
 /// Spawns a task that continually (at an interval) writes statistics from storaged's
 /// that are consolidated in shared memory in the controller.
 pub(super) fn spawn_statistics_scraper<Stats: PackableStats + Send + 'static>(
     statistics_collection_id: GlobalId,
     collection_mgmt: CollectionManager,
-    shared_stats: Arc<Mutex<BTreeMap<GlobalId, BTreeMap<usize, Stats>>>>,
+    shared_stats: Arc<Mutex<BTreeMap<GlobalId, BTreeMap<(ReplicaId, usize), Stats>>>>,
 ) -> Box<dyn Any + Send + Sync> {
     // TODO(guswynn): Should this be configurable? Maybe via LaunchDarkly?
     const STATISTICS_INTERVAL: Duration = Duration::from_secs(30);
@@ -69,8 +70,8 @@ pub(super) fn spawn_statistics_scraper<Stats: PackableStats + Send + 'static>(
                         let shared_stats = shared_stats.lock().expect("poisoned");
 
                         for (_, items) in shared_stats.iter() {
-                            for (_, stats) in items.iter() {
-                                stats.pack(row_buf.packer());
+                            for ((replica_id, _), stats) in items.iter() {
+                                stats.pack(*replica_id, row_buf.packer());
                                 correction.push((row_buf.clone(), 1));
                             }
                         }
@@ -25,12 +25,13 @@ use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use timely::scheduling::Activator;
 
-use mz_expr::MfpPlan;
+use mz_expr::{BinaryFunc, MfpPlan, MirScalarExpr, SafeMfpPlan, UnaryFunc, VariadicFunc};
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::fetch::FetchedPart;
-use mz_repr::{DatumVec, Diff, GlobalId, Row, Timestamp};
+use mz_persist_types::stats::{ColumnStats, PartStats, StructStats};
+use mz_repr::{datum_stats_value, DatumVec, Diff, GlobalId, RelationDesc, Row, Timestamp};
 
-use crate::controller::CollectionMetadata;
+use crate::controller::{CollectionLayout, CollectionMetadata, LayoutConverter};
 use crate::types::errors::DataflowError;
 use crate::types::sources::SourceData;
 
@@ -122,6 +123,16 @@ where
     YFn: Fn(Instant, usize) -> bool + 'static,
 {
     let name = source_id.to_string();
+    // Use the non-temporal predicates of the MFP to skip parts whose stats
+    // prove that none of their rows would survive the filter.
+    let desc = metadata.stats_desc();
+    let filter_plan = map_filter_project
+        .as_ref()
+        .map(|mfp| mfp.nontemporal().clone());
+    let should_fetch_part = move |stats: &PartStats| match (&desc, &filter_plan) {
+        (Some(desc), Some(mfp)) => should_fetch_part(desc, mfp, stats),
+        _ => true,
+    };
     let (fetched, token) = shard_source(
         scope,
         &name,
@@ -133,15 +144,140 @@ where
         flow_control,
         Arc::new(metadata.relation_desc),
         Arc::new(UnitSchema),
+        should_fetch_part,
+    );
+    let rows = decode_and_mfp(
+        &fetched,
+        &name,
+        until,
+        metadata.layout,
+        map_filter_project,
+        yield_fn,
     );
-    let rows = decode_and_mfp(&fetched, &name, until, map_filter_project, yield_fn);
     (rows, token)
 }
 
+/// Returns whether a part with the given stats might contain a row that isn't
+/// filtered out by `mfp`.
+///
+/// This is conservative: it only returns false if the stats prove that every
+/// row in the part fails some predicate, and that no error could have been
+/// produced while evaluating the predicates up to that one.
+pub(crate) fn should_fetch_part(desc: &RelationDesc, mfp: &SafeMfpPlan, stats: &PartStats) -> bool {
+    for (support, predicate) in mfp.predicates.iter() {
+        // Predicates are evaluated in order, with map expressions evaluated as
+        // needed in between them. Stop at the first thing that could error, so
+        // that we never skip a part that would have produced an error.
+        if *support > mfp.input_arity || predicate.could_error() {
+            break;
+        }
+        if !may_be_true(desc, &stats.key, predicate) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns whether `expr` might evaluate to true for some row described by
+/// `stats`.
+fn may_be_true(desc: &RelationDesc, stats: &StructStats, expr: &MirScalarExpr) -> bool {
+    match expr {
+        MirScalarExpr::CallVariadic {
+            func: VariadicFunc::And,
+            exprs,
+        } => exprs.iter().all(|x| may_be_true(desc, stats, x)),
+        MirScalarExpr::CallVariadic {
+            func: VariadicFunc::Or,
+            exprs,
+        } => exprs.iter().any(|x| may_be_true(desc, stats, x)),
+        MirScalarExpr::CallUnary {
+            func: UnaryFunc::IsNull(_),
+            expr,
+        } => column_stats(desc, stats, expr).map_or(true, |col| col.nulls > 0),
+        MirScalarExpr::CallUnary {
+            func: UnaryFunc::Not(_),
+            expr,
+        } => match &**expr {
+            MirScalarExpr::CallUnary {
+                func: UnaryFunc::IsNull(_),
+                expr,
+            } => column_stats(desc, stats, expr).map_or(true, |col| col.lower.is_some()),
+            _ => true,
+        },
+        MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+            // Normalize to `<column> <func> <literal>`.
+            let (col, lit, func) = match (expr1.as_literal(), expr2.as_literal()) {
+                (None, Some(Ok(lit))) => (expr1, lit, func.clone()),
+                (Some(Ok(lit)), None) => {
+                    let func = match func {
+                        BinaryFunc::Lt => BinaryFunc::Gt,
+                        BinaryFunc::Lte => BinaryFunc::Gte,
+                        BinaryFunc::Gt => BinaryFunc::Lt,
+                        BinaryFunc::Gte => BinaryFunc::Lte,
+                        x => x.clone(),
+                    };
+                    (expr2, lit, func)
+                }
+                _ => return true,
+            };
+            let col = match column_stats(desc, stats, col) {
+                Some(col) => col,
+                None => return true,
+            };
+            let lit = match datum_stats_value(lit) {
+                Some(lit) => lit,
+                None => return true,
+            };
+            let (lower, upper) = match (&col.lower, &col.upper) {
+                (Some(lower), Some(upper)) => (lower, upper),
+                // Every value in the column is null, and comparisons with null
+                // are never true.
+                _ => {
+                    return !matches!(
+                        func,
+                        BinaryFunc::Eq
+                            | BinaryFunc::Lt
+                            | BinaryFunc::Lte
+                            | BinaryFunc::Gt
+                            | BinaryFunc::Gte
+                    )
+                }
+            };
+            if std::mem::discriminant(lower) != std::mem::discriminant(&lit) {
+                return true;
+            }
+            match func {
+                BinaryFunc::Eq => *lower <= lit && lit <= *upper,
+                BinaryFunc::Lt => *lower < lit,
+                BinaryFunc::Lte => *lower <= lit,
+                BinaryFunc::Gt => *upper > lit,
+                BinaryFunc::Gte => *upper >= lit,
+                _ => true,
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Returns the stats of the column referenced by `expr`, if it is a column
+/// reference and stats are available for it.
+fn column_stats<'a>(
+    desc: &RelationDesc,
+    stats: &'a StructStats,
+    expr: &MirScalarExpr,
+) -> Option<&'a ColumnStats> {
+    let col = expr.as_column()?;
+    if col >= desc.arity() {
+        return None;
+    }
+    stats.col(desc.get_name(col).as_str())
+}
+
 pub fn decode_and_mfp<G, YFn>(
     fetched: &Stream<G, FetchedPart<SourceData, (), Timestamp, Diff>>,
     name: &str,
     until: Antichain<Timestamp>,
+    layout: Option<CollectionLayout>,
     mut map_filter_project: Option<&mut MfpPlan>,
     yield_fn: YFn,
 ) -> Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>
@@ -162,6 +298,9 @@ where
     // Re-used state for processing and building rows.
     let mut datum_vec = mz_repr::DatumVec::new();
     let mut row_builder = Row::default();
+    let mut layout_row_builder = Row::default();
+
+    let layout = layout.map(|layout| layout.converter());
 
     // Extract the MFP if it exists; leave behind an identity MFP in that case.
     let map_filter_project = map_filter_project.as_mut().map(|mfp| mfp.take());
@@ -196,9 +335,11 @@ where
                     start_time,
                     &yield_fn,
                     &until,
+                    layout.as_ref(),
                     map_filter_project.as_ref(),
                     &mut datum_vec,
                     &mut row_builder,
+                    &mut layout_row_builder,
                     &mut handle,
                 );
                 if done {
@@ -231,9 +372,11 @@ impl PendingWork {
         start_time: Instant,
         yield_fn: YFn,
         until: &Antichain<Timestamp>,
+        layout: Option<&LayoutConverter>,
         map_filter_project: Option<&MfpPlan>,
         datum_vec: &mut DatumVec,
         row_builder: &mut Row,
+        layout_row_builder: &mut Row,
         output: &mut ConsolidateBuffer<Timestamp, Result<Row, DataflowError>, Diff, P>,
     ) -> bool
     where
@@ -246,6 +389,10 @@ impl PendingWork {
             }
             match (key, val) {
                 (Ok(SourceData(Ok(row))), Ok(())) => {
+                    let row = match layout {
+                        Some(layout) => layout.to_logical(row, datum_vec, layout_row_builder),
+                        None => row,
+                    };
                     if let Some(mfp) = map_filter_project {
                         let arena = mz_repr::RowArena::new();
                         let mut datums_local = datum_vec.borrow_with(&row);
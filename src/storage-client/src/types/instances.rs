@@ -23,6 +23,9 @@ include!(concat!(
     "/mz_storage_client.types.instances.rs"
 ));
 
+/// Identifier of a replica of a storage instance.
+pub type ReplicaId = u64;
+
 /// Identifier of a storage instance.
 #[derive(
     Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
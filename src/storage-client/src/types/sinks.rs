@@ -137,6 +137,12 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<MetadataFilled, mz_repr:
 pub enum SinkEnvelope {
     Debezium,
     Upsert,
+    /// Emits each row that is added to the sinked relation as a message.
+    /// Retractions are an error.
+    None,
+    /// Emits updates and progress statements in the CDCv2 format, which
+    /// `ENVELOPE MATERIALIZE` sources can ingest losslessly.
+    CdcV2,
 }
 
 impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
@@ -146,6 +152,8 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
             kind: Some(match self {
                 SinkEnvelope::Debezium => Kind::Debezium(()),
                 SinkEnvelope::Upsert => Kind::Upsert(()),
+                SinkEnvelope::None => Kind::None(()),
+                SinkEnvelope::CdcV2 => Kind::CdcV2(()),
             }),
         }
     }
@@ -158,6 +166,8 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
         Ok(match kind {
             Kind::Debezium(()) => SinkEnvelope::Debezium,
             Kind::Upsert(()) => SinkEnvelope::Upsert,
+            Kind::None(()) => SinkEnvelope::None,
+            Kind::CdcV2(()) => SinkEnvelope::CdcV2,
         })
     }
 }
@@ -25,6 +25,7 @@ use globset::{Glob, GlobBuilder};
 use itertools::Itertools;
 use mz_persist_types::codec_impls::{TodoSchema, UnitSchema};
 use mz_persist_types::columnar::Schema;
+use mz_persist_types::stats::{StatsBuilder, StructStats};
 use once_cell::sync::Lazy;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
@@ -44,7 +45,9 @@ use mz_persist_client::write::WriteHandle;
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::numeric::{Numeric, NumericMaxScale};
-use mz_repr::{ColumnType, Datum, Diff, GlobalId, RelationDesc, RelationType, Row, ScalarType};
+use mz_repr::{
+    ColumnType, Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowStatsBuilder, ScalarType,
+};
 use mz_timely_util::order::{Interval, Partitioned, RangeBound};
 
 use crate::controller::{CollectionMetadata, ResumptionFrontierCalculator};
@@ -107,6 +110,8 @@ impl<T: Timestamp + Lattice + Codec64> ResumptionFrontierCalculator<T>
                 // The status shard only contains non-definite status updates
                 status_shard: _,
                 relation_desc,
+                // Sources don't change their schema.
+                layout: _,
             } = &export.storage_metadata;
             let handle = client_cache
                 .open(persist_location.clone())
@@ -139,6 +144,7 @@ impl<T: Timestamp + Lattice + Codec64> ResumptionFrontierCalculator<T>
             // The status shard only contains non-definite status updates
             status_shard: _,
             relation_desc: _,
+            layout: _,
         } = &self.ingestion_metadata;
         let remap_handle = client_cache
             .open(persist_location.clone())
@@ -615,6 +621,9 @@ pub enum IncludedColumnSource {
     Timestamp,
     Topic,
     Headers,
+    ObjectKey,
+    LineNumber,
+    Lsn,
 }
 
 impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
@@ -627,6 +636,9 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
                 IncludedColumnSource::Timestamp => Kind::Timestamp(()),
                 IncludedColumnSource::Topic => Kind::Topic(()),
                 IncludedColumnSource::Headers => Kind::Headers(()),
+                IncludedColumnSource::ObjectKey => Kind::ObjectKey(()),
+                IncludedColumnSource::LineNumber => Kind::LineNumber(()),
+                IncludedColumnSource::Lsn => Kind::Lsn(()),
             }),
         }
     }
@@ -642,6 +654,9 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
             Kind::Timestamp(()) => IncludedColumnSource::Timestamp,
             Kind::Topic(()) => IncludedColumnSource::Topic,
             Kind::Headers(()) => IncludedColumnSource::Headers,
+            Kind::ObjectKey(()) => IncludedColumnSource::ObjectKey,
+            Kind::LineNumber(()) => IncludedColumnSource::LineNumber,
+            Kind::Lsn(()) => IncludedColumnSource::Lsn,
         })
     }
 }
@@ -1715,107 +1730,215 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection {
 }
 
 impl GenericSourceConnection {
-    /// Returns the name and type of each additional metadata column that
-    /// Materialize will automatically append to the source's inherent columns.
+    /// Reports whether this source can be ingested by several replicas at
+    /// once.
     ///
-    /// Presently, each source type exposes precisely one metadata column that
-    /// corresponds to some source-specific record counter. For example, file
-    /// sources use a line number, while Kafka sources use a topic offset.
+    /// Every replica of a storage instance ingests its sources independently
+    /// and writes to the same shards, which is only correct if all replicas
+    /// assign the same offsets to the same data. Sources that number messages
+    /// in the order they happen to arrive in cannot uphold this, and Postgres
+    /// sources cannot either, as only one client can consume a replication
+    /// slot at a time.
+    pub fn supports_active_replication(&self) -> bool {
+        match self {
+            GenericSourceConnection::Kafka(_) | GenericSourceConnection::LoadGenerator(_) => true,
+            GenericSourceConnection::Kinesis(_)
+            | GenericSourceConnection::Postgres(_)
+            | GenericSourceConnection::S3(_)
+            | GenericSourceConnection::TestScript(_) => false,
+        }
+    }
+
+    /// Returns the pieces of metadata that this type of source is able to
+    /// expose as additional columns, via `INCLUDE`.
     ///
-    /// The columns declared here must be kept in sync with the actual source
+    /// The metadata declared here must be kept in sync with the actual source
     /// implementations that produce these columns.
-    pub fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+    pub fn supported_metadata_columns(&self) -> &'static [IncludedColumnSource] {
         match self {
-            Self::Kafka(KafkaSourceConnection {
-                include_partition: part,
-                include_timestamp: time,
-                include_topic: topic,
-                include_offset: offset,
-                include_headers: headers,
-                ..
-            }) => {
-                let mut items = BTreeMap::new();
-                for (include, ty) in [
-                    (offset, ScalarType::UInt64),
-                    (part, ScalarType::Int32),
-                    (time, ScalarType::Timestamp),
-                    (topic, ScalarType::String),
-                    (
-                        headers,
-                        ScalarType::List {
-                            element_type: Box::new(ScalarType::Record {
-                                fields: vec![
-                                    (
-                                        "key".into(),
-                                        ColumnType {
-                                            nullable: false,
-                                            scalar_type: ScalarType::String,
-                                        },
-                                    ),
-                                    (
-                                        "value".into(),
-                                        ColumnType {
-                                            nullable: false,
-                                            scalar_type: ScalarType::Bytes,
-                                        },
-                                    ),
-                                ],
-                                custom_id: None,
-                            }),
-                            custom_id: None,
-                        },
-                    ),
-                ] {
-                    if let Some(include) = include {
-                        items.insert(include.pos + 1, (&*include.name, ty.nullable(false)));
-                    }
-                }
+            Self::Kafka(_) => &[
+                IncludedColumnSource::Partition,
+                IncludedColumnSource::Offset,
+                IncludedColumnSource::Timestamp,
+                IncludedColumnSource::Topic,
+                IncludedColumnSource::Headers,
+            ],
+            Self::S3(_) => &[
+                IncludedColumnSource::ObjectKey,
+                IncludedColumnSource::LineNumber,
+            ],
+            Self::Postgres(_) => &[IncludedColumnSource::Lsn, IncludedColumnSource::Timestamp],
+            Self::LoadGenerator(_) => &[IncludedColumnSource::Offset],
+            Self::Kinesis(_) | Self::TestScript(_) => &[],
+        }
+    }
 
-                items.into_values().collect()
+    /// Configures the connection to append the metadata column described by
+    /// `column` to its output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` is not among the connection's
+    /// [`supported_metadata_columns`](Self::supported_metadata_columns).
+    pub fn include_metadata_column(
+        &mut self,
+        source: IncludedColumnSource,
+        column: IncludedColumnPos,
+    ) {
+        let include = match (self, source) {
+            (Self::Kafka(kafka), IncludedColumnSource::Partition) => &mut kafka.include_partition,
+            (Self::Kafka(kafka), IncludedColumnSource::Offset) => &mut kafka.include_offset,
+            (Self::Kafka(kafka), IncludedColumnSource::Timestamp) => &mut kafka.include_timestamp,
+            (Self::Kafka(kafka), IncludedColumnSource::Topic) => &mut kafka.include_topic,
+            (Self::Kafka(kafka), IncludedColumnSource::Headers) => &mut kafka.include_headers,
+            (Self::S3(s3), IncludedColumnSource::ObjectKey) => &mut s3.include_object_key,
+            (Self::S3(s3), IncludedColumnSource::LineNumber) => &mut s3.include_line_number,
+            (Self::Postgres(pg), IncludedColumnSource::Lsn) => &mut pg.include_lsn,
+            (Self::Postgres(pg), IncludedColumnSource::Timestamp) => &mut pg.include_timestamp,
+            (Self::LoadGenerator(generator), IncludedColumnSource::Offset) => {
+                &mut generator.include_offset
             }
-            Self::Kinesis(_) => vec![],
-            Self::S3(_) => vec![],
-            Self::Postgres(_) => vec![],
-            Self::LoadGenerator(_) => vec![],
-            Self::TestScript(_) => vec![],
-        }
+            (connection, source) => panic!(
+                "{} sources cannot include {:?} metadata",
+                connection.name(),
+                source
+            ),
+        };
+        *include = Some(column);
     }
 
-    pub fn metadata_column_types(&self) -> Vec<IncludedColumnSource> {
-        match self {
-            GenericSourceConnection::Kafka(KafkaSourceConnection {
-                include_partition: part,
-                include_timestamp: time,
-                include_topic: topic,
-                include_offset: offset,
-                include_headers: headers,
+    /// Returns each metadata column that the connection has been configured to
+    /// include, along with the piece of metadata it corresponds to and its
+    /// type, in the order the columns were declared in SQL.
+    fn included_columns(&self) -> Vec<(&IncludedColumnPos, IncludedColumnSource, ColumnType)> {
+        let included = match self {
+            Self::Kafka(KafkaSourceConnection {
+                include_partition,
+                include_timestamp,
+                include_topic,
+                include_offset,
+                include_headers,
                 ..
-            }) => {
-                // create a sorted list of column types based on the order they were declared in sql
-                // TODO: should key be included in the sorted list? Breaking change, and it's
-                // already special (it commonly multiple columns embedded in it).
-                let mut items = BTreeMap::new();
-                for (include, ty) in [
-                    (offset, IncludedColumnSource::Offset),
-                    (part, IncludedColumnSource::Partition),
-                    (time, IncludedColumnSource::Timestamp),
-                    (topic, IncludedColumnSource::Topic),
-                    (headers, IncludedColumnSource::Headers),
-                ] {
-                    if let Some(include) = include {
-                        items.insert(include.pos, ty);
+            }) => vec![
+                (
+                    include_offset,
+                    IncludedColumnSource::Offset,
+                    ScalarType::UInt64.nullable(false),
+                ),
+                (
+                    include_partition,
+                    IncludedColumnSource::Partition,
+                    ScalarType::Int32.nullable(false),
+                ),
+                (
+                    include_timestamp,
+                    IncludedColumnSource::Timestamp,
+                    ScalarType::Timestamp.nullable(false),
+                ),
+                (
+                    include_topic,
+                    IncludedColumnSource::Topic,
+                    ScalarType::String.nullable(false),
+                ),
+                (
+                    include_headers,
+                    IncludedColumnSource::Headers,
+                    ScalarType::List {
+                        element_type: Box::new(ScalarType::Record {
+                            fields: vec![
+                                (
+                                    "key".into(),
+                                    ColumnType {
+                                        nullable: false,
+                                        scalar_type: ScalarType::String,
+                                    },
+                                ),
+                                (
+                                    "value".into(),
+                                    ColumnType {
+                                        nullable: false,
+                                        scalar_type: ScalarType::Bytes,
+                                    },
+                                ),
+                            ],
+                            custom_id: None,
+                        }),
+                        custom_id: None,
                     }
-                }
+                    .nullable(false),
+                ),
+            ],
+            Self::S3(S3SourceConnection {
+                include_object_key,
+                include_line_number,
+                ..
+            }) => vec![
+                (
+                    include_object_key,
+                    IncludedColumnSource::ObjectKey,
+                    ScalarType::String.nullable(false),
+                ),
+                (
+                    include_line_number,
+                    IncludedColumnSource::LineNumber,
+                    ScalarType::UInt64.nullable(false),
+                ),
+            ],
+            Self::Postgres(PostgresSourceConnection {
+                include_lsn,
+                include_timestamp,
+                ..
+            }) => vec![
+                (
+                    include_lsn,
+                    IncludedColumnSource::Lsn,
+                    ScalarType::UInt64.nullable(false),
+                ),
+                // Rows from the initial snapshot were not committed by any
+                // transaction we observed, so they have no commit timestamp.
+                (
+                    include_timestamp,
+                    IncludedColumnSource::Timestamp,
+                    ScalarType::Timestamp.nullable(true),
+                ),
+            ],
+            Self::LoadGenerator(LoadGeneratorSourceConnection { include_offset, .. }) => vec![(
+                include_offset,
+                IncludedColumnSource::Offset,
+                ScalarType::UInt64.nullable(false),
+            )],
+            Self::Kinesis(_) | Self::TestScript(_) => vec![],
+        };
 
-                items.into_values().collect()
-            }
+        // TODO: should key be included in the sorted list? Breaking change, and it's
+        // already special (it commonly multiple columns embedded in it).
+        let items: BTreeMap<_, _> = included
+            .into_iter()
+            .filter_map(|(include, source, ty)| {
+                include
+                    .as_ref()
+                    .map(|include| (include.pos, (include, source, ty)))
+            })
+            .collect();
+        items.into_values().collect()
+    }
 
-            GenericSourceConnection::Kinesis(_)
-            | GenericSourceConnection::S3(_)
-            | GenericSourceConnection::Postgres(_)
-            | GenericSourceConnection::LoadGenerator(_)
-            | GenericSourceConnection::TestScript(_) => Vec::new(),
-        }
+    /// Returns the name and type of each additional metadata column that
+    /// Materialize will automatically append to the source's inherent columns.
+    pub fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        self.included_columns()
+            .into_iter()
+            .map(|(include, _source, ty)| (&*include.name, ty))
+            .collect()
+    }
+
+    /// Returns the piece of metadata that each of the
+    /// [`metadata_columns`](Self::metadata_columns) corresponds to.
+    pub fn metadata_column_types(&self) -> Vec<IncludedColumnSource> {
+        self.included_columns()
+            .into_iter()
+            .map(|(_include, source, _ty)| source)
+            .collect()
     }
 
     /// Returns the name of the external source connection.
@@ -1902,6 +2025,12 @@ pub struct PostgresSourceConnection {
     pub table_casts: BTreeMap<usize, Vec<MirScalarExpr>>,
     pub publication: String,
     pub publication_details: PostgresSourcePublicationDetails,
+    /// If present, include the LSN of each change's commit as an output
+    /// column of each subsource with the given name.
+    pub include_lsn: Option<IncludedColumnPos>,
+    /// If present, include the timestamp of each change's commit as an
+    /// output column of each subsource with the given name.
+    pub include_timestamp: Option<IncludedColumnPos>,
 }
 
 pub static PG_PROGRESS_DESC: Lazy<RelationDesc> =
@@ -1922,14 +2051,26 @@ impl Arbitrary for PostgresSourceConnection {
             ),
             any::<String>(),
             any::<PostgresSourcePublicationDetails>(),
+            any::<Option<IncludedColumnPos>>(),
+            any::<Option<IncludedColumnPos>>(),
         )
             .prop_map(
-                |(connection, connection_id, table_casts, publication, details)| Self {
+                |(
+                    connection,
+                    connection_id,
+                    table_casts,
+                    publication,
+                    details,
+                    include_lsn,
+                    include_timestamp,
+                )| Self {
                     connection,
                     connection_id,
                     table_casts,
                     publication,
                     publication_details: details,
+                    include_lsn,
+                    include_timestamp,
                 },
             )
             .boxed()
@@ -1965,6 +2106,8 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
             details: Some(self.publication_details.into_proto()),
             table_casts,
             table_cast_pos,
+            include_lsn: self.include_lsn.into_proto(),
+            include_timestamp: self.include_timestamp.into_proto(),
         }
     }
 
@@ -2006,6 +2149,8 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
                 .details
                 .into_rust_if_some("ProtoPostgresSourceConnection::details")?,
             table_casts,
+            include_lsn: proto.include_lsn.into_rust()?,
+            include_timestamp: proto.include_timestamp.into_rust()?,
         })
     }
 }
@@ -2040,6 +2185,8 @@ impl RustType<ProtoPostgresSourcePublicationDetails> for PostgresSourcePublicati
 pub struct LoadGeneratorSourceConnection {
     pub load_generator: LoadGenerator,
     pub tick_micros: Option<u64>,
+    /// The offset of the tick that generated each row.
+    pub include_offset: Option<IncludedColumnPos>,
 }
 
 impl SourceConnection for LoadGeneratorSourceConnection {
@@ -2302,6 +2449,7 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                 LoadGenerator::Datums => ProtoGenerator::Datums(()),
             }),
             tick_micros: self.tick_micros,
+            include_offset: self.include_offset.into_proto(),
         }
     }
 
@@ -2329,6 +2477,7 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                 ProtoGenerator::Datums(()) => LoadGenerator::Datums,
             },
             tick_micros: proto.tick_micros,
+            include_offset: proto.include_offset.into_rust()?,
         })
     }
 }
@@ -2368,6 +2517,12 @@ pub struct S3SourceConnection {
     pub pattern: Option<Glob>,
     pub aws: AwsConfig,
     pub compression: Compression,
+    /// If present, include the key of the object each record was read from
+    /// as an output column of the source with the given name.
+    pub include_object_key: Option<IncludedColumnPos>,
+    /// If present, include the line number of each record within its object
+    /// as an output column of the source with the given name.
+    pub include_line_number: Option<IncludedColumnPos>,
 }
 
 impl SourceConnection for S3SourceConnection {
@@ -2401,16 +2556,28 @@ impl Arbitrary for S3SourceConnection {
             proptest::option::of(any_glob()),
             any::<AwsConfig>(),
             any::<Compression>(),
+            any::<Option<IncludedColumnPos>>(),
+            any::<Option<IncludedColumnPos>>(),
         )
-            .prop_map(|(connection_id, key_sources, pattern, aws, compression)| {
-                S3SourceConnection {
+            .prop_map(
+                |(
                     connection_id,
                     key_sources,
                     pattern,
                     aws,
                     compression,
-                }
-            })
+                    include_object_key,
+                    include_line_number,
+                )| S3SourceConnection {
+                    connection_id,
+                    key_sources,
+                    pattern,
+                    aws,
+                    compression,
+                    include_object_key,
+                    include_line_number,
+                },
+            )
             .boxed()
     }
 }
@@ -2423,6 +2590,8 @@ impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
             pattern: self.pattern.as_ref().map(|g| g.glob().into()),
             aws: Some(self.aws.into_proto()),
             compression: Some(self.compression.into_proto()),
+            include_object_key: self.include_object_key.into_proto(),
+            include_line_number: self.include_line_number.into_proto(),
         }
     }
 
@@ -2447,6 +2616,8 @@ impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
             compression: proto
                 .compression
                 .into_rust_if_some("ProtoS3SourceConnection::compression")?,
+            include_object_key: proto.include_object_key.into_rust()?,
+            include_line_number: proto.include_line_number.into_rust()?,
         })
     }
 }
@@ -2569,6 +2740,42 @@ impl Schema<SourceData> for RelationDesc {
     ) -> Result<Self::Encoder<'a>, String> {
         panic!("TODO")
     }
+
+    fn stats_builder(&self) -> Option<Box<dyn StatsBuilder<SourceData>>> {
+        Some(Box::new(SourceDataStatsBuilder {
+            rows: RowStatsBuilder::new(self),
+            errs: 0,
+        }))
+    }
+}
+
+/// A [StatsBuilder] for [SourceData].
+///
+/// Errors can't be described by the column stats of the rows, so if a part
+/// contains any errors we drop the column stats entirely. This ensures that a
+/// reader never skips a part that contains errors it would have to emit.
+#[derive(Debug)]
+struct SourceDataStatsBuilder {
+    rows: RowStatsBuilder,
+    errs: usize,
+}
+
+impl StatsBuilder<SourceData> for SourceDataStatsBuilder {
+    fn push(&mut self, val: &SourceData) {
+        match &val.0 {
+            Ok(row) => self.rows.push_row(row),
+            Err(_) => self.errs += 1,
+        }
+    }
+
+    fn finish(self: Box<Self>) -> StructStats {
+        let mut stats = self.rows.finish_row_stats();
+        stats.len += self.errs;
+        if self.errs > 0 {
+            stats.cols.clear();
+        }
+        stats
+    }
 }
 
 /// A `SourceToken` manages interest in a source.
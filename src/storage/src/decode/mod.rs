@@ -47,7 +47,7 @@ use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
 use self::metrics::DecodeMetrics;
 use self::protobuf::ProtobufDecoderState;
-use crate::source::types::{DecodeResult, SourceOutput};
+use crate::source::types::{DecodeResult, SourceMessageMetadata, SourceOutput};
 
 mod avro;
 mod csv;
@@ -403,7 +403,7 @@ where
                     position,
                     upstream_time_millis,
                     partition,
-                    headers,
+                    metadata,
                     diff: (),
                 } in data.iter()
                 {
@@ -441,7 +441,8 @@ where
                             partition.clone(),
                             *position,
                             *upstream_time_millis,
-                            headers.as_deref(),
+                            None,
+                            metadata,
                         ),
                     });
                 }
@@ -500,14 +501,15 @@ where
     // We therefore ignore it, and keep track ourselves of how many records we've seen (for filling in `mz_line_no`, etc).
     // Historically, non-delimited sources have their offset start at 1
     let mut n_seen = 1..;
+    // The line number of each record within the object it was read from. A `None` value marks the
+    // end of an object, after which numbering starts over.
+    let mut line_numbers = 1..;
     let results = stream.unary_frontier(Pipeline, &op_name, move |_, _| {
         let metadata_items = metadata_items;
         move |input, output| {
             let mut n_errors = 0;
             let mut n_successes = 0;
             input.for_each(|cap, data| {
-                // Currently Kafka is the only kind of source that can have metadata, and it is
-                // always delimited, so we will never have metadata in `render_decode`
                 let mut session = output.session(&cap);
                 for SourceOutput {
                     key: _,
@@ -515,7 +517,7 @@ where
                     position: _,
                     upstream_time_millis,
                     partition,
-                    headers,
+                    metadata,
                     diff: (),
                 } in data.iter()
                 {
@@ -531,6 +533,8 @@ where
                                 )));
                             }
                             value_buf.clear();
+                            let line_number = line_numbers.next().unwrap();
+                            line_numbers = 1..;
 
                             match result.transpose() {
                                 None => continue,
@@ -547,7 +551,8 @@ where
                                         partition.clone(),
                                         position.into(),
                                         *upstream_time_millis,
-                                        headers.as_deref(),
+                                        Some(line_number),
+                                        metadata,
                                     );
 
                                     session.give(DecodeResult {
@@ -608,12 +613,14 @@ where
                         }
                         // `RangeFrom` `Iterator`'s never end
                         let position = n_seen.next().unwrap();
+                        let line_number = line_numbers.next().unwrap();
                         let metadata = to_metadata_row(
                             &metadata_items,
                             partition.clone(),
                             position.into(),
                             *upstream_time_millis,
-                            headers.as_deref(),
+                            Some(line_number),
+                            metadata,
                         );
 
                         if value_bytes_remaining.is_empty() {
@@ -663,65 +670,77 @@ where
     (results, None)
 }
 
-fn to_metadata_row(
+/// Packs the requested metadata items for a record into a row, in order.
+///
+/// The `line_number` is the position of the record within the object it was read from, and is
+/// only known to sources that must be split into records by the decoder.
+pub(crate) fn to_metadata_row(
     metadata_items: &[IncludedColumnSource],
     partition: PartitionId,
     position: MzOffset,
     upstream_time_millis: Option<i64>,
-    headers: Option<&[(String, Option<Vec<u8>>)]>,
+    line_number: Option<u64>,
+    metadata: &SourceMessageMetadata,
 ) -> Row {
     let position = position.offset;
     let mut row = Row::default();
     let mut packer = row.packer();
-    match partition {
-        PartitionId::Kafka(partition) => {
-            for item in metadata_items.iter() {
-                match item {
-                    IncludedColumnSource::Partition => packer.push(Datum::from(partition)),
-                    IncludedColumnSource::Offset => packer.push(Datum::UInt64(position)),
-                    IncludedColumnSource::Timestamp => {
-                        let ts =
-                            upstream_time_millis.expect("kafka sources always have upstream_time");
-
-                        let d: Datum = NaiveDateTime::from_timestamp_millis(ts)
-                            .and_then(|dt| {
-                                let ct: Option<CheckedTimestamp<NaiveDateTime>> =
-                                    dt.try_into().ok();
-                                ct
-                            })
-                            .into();
-                        packer.push(d)
-                    }
-                    IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
-                    IncludedColumnSource::Headers => {
-                        packer.push_list_with(|r| {
-                            // If the source asked for headers, but we didn't get any, we still
-                            // want to run the `push_dict_with`, to produce an empty map value
-                            //
-                            // This is a `BTreeMap`, so the `push_dict_with` ordering invariant is
-                            // upheld
-                            if let Some(headers) = headers {
-                                for (k, v) in headers {
-                                    match v {
-                                        Some(v) => r.push_list_with(|record_row| {
-                                            record_row.push(Datum::String(k));
-                                            record_row.push(Datum::Bytes(v));
-                                        }),
-                                        None => r.push_list_with(|record_row| {
-                                            record_row.push(Datum::String(k));
-                                            record_row.push(Datum::Null);
-                                        }),
-                                    }
-                                }
+    for item in metadata_items.iter() {
+        match item {
+            IncludedColumnSource::Partition => match partition {
+                PartitionId::Kafka(partition) => packer.push(Datum::from(partition)),
+                PartitionId::None => unreachable!("only Kafka sources have partitions"),
+            },
+            // For Kafka sources, the position is the offset of the message, and for Postgres
+            // sources it is the LSN of the commit of the change.
+            IncludedColumnSource::Offset | IncludedColumnSource::Lsn => {
+                packer.push(Datum::UInt64(position))
+            }
+            IncludedColumnSource::Timestamp => {
+                let d: Datum = upstream_time_millis
+                    .and_then(NaiveDateTime::from_timestamp_millis)
+                    .and_then(|dt| {
+                        let ct: Option<CheckedTimestamp<NaiveDateTime>> = dt.try_into().ok();
+                        ct
+                    })
+                    .into();
+                packer.push(d)
+            }
+            IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
+            IncludedColumnSource::Headers => {
+                packer.push_list_with(|r| {
+                    // If the source asked for headers, but we didn't get any, we still
+                    // want to run the `push_dict_with`, to produce an empty map value
+                    //
+                    // This is a `BTreeMap`, so the `push_dict_with` ordering invariant is
+                    // upheld
+                    if let Some(headers) = &metadata.headers {
+                        for (k, v) in headers {
+                            match v {
+                                Some(v) => r.push_list_with(|record_row| {
+                                    record_row.push(Datum::String(k));
+                                    record_row.push(Datum::Bytes(v));
+                                }),
+                                None => r.push_list_with(|record_row| {
+                                    record_row.push(Datum::String(k));
+                                    record_row.push(Datum::Null);
+                                }),
                             }
-                        });
+                        }
                     }
-                }
+                });
             }
-        }
-        PartitionId::None => {
-            if !metadata_items.is_empty() {
-                unreachable!("Only Kafka supports metadata items");
+            IncludedColumnSource::ObjectKey => {
+                let object_key = metadata
+                    .object_key
+                    .as_deref()
+                    .expect("sources that include object keys always pass them along");
+                packer.push(Datum::String(object_key))
+            }
+            IncludedColumnSource::LineNumber => {
+                let line_number = line_number
+                    .expect("sources that include line numbers are always split by the decoder");
+                packer.push(Datum::UInt64(line_number))
             }
         }
     }
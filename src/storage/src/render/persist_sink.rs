@@ -199,34 +199,45 @@ where
 
                     // If the frontier has advanced, we need to finalize data being written to persist
                     if PartialOrder::less_than(&*current_upper.borrow(), &input_upper) {
+                        // `current_upper` tracks the last known upper. Other replicas of this
+                        // source write the same data to the same shard, so the shard's actual
+                        // upper may be ahead of it. Every append below is therefore
+                        // idempotent: if another replica has already written a timestamp, we
+                        // skip ahead to the upper it left the shard at.
+                        let mut expected_upper = current_upper.borrow().clone();
+
                         // We always append, even in case we don't have any updates, because appending
                         // also advances the frontier.
                         if finalized_timestamps.is_empty() {
-                            let expected_upper = current_upper.borrow().clone();
-                            write
-                                .append(
-                                    Vec::<((SourceData, ()), Timestamp, Diff)>::new(),
-                                    expected_upper,
-                                    input_upper.clone(),
-                                )
-                                .await
-                                .expect("cannot append updates")
-                                .expect("invalid/outdated upper");
+                            while PartialOrder::less_than(&expected_upper, &input_upper) {
+                                let result = write
+                                    .compare_and_append(
+                                        Vec::<((SourceData, ()), Timestamp, Diff)>::new(),
+                                        expected_upper.clone(),
+                                        input_upper.clone(),
+                                    )
+                                    .await
+                                    .expect("cannot append updates");
+                                match result {
+                                    Ok(()) => expected_upper = input_upper.clone(),
+                                    Err(mismatch) => expected_upper = mismatch.current,
+                                }
+                            }
 
                             metrics
                                 .progress
-                                .set(mz_persist_client::metrics::encode_ts_metric(&input_upper));
+                                .set(mz_persist_client::metrics::encode_ts_metric(
+                                    &expected_upper,
+                                ));
 
-                            source_statistics.update_snapshot_committed(&input_upper);
+                            source_statistics.update_snapshot_committed(&expected_upper);
 
                             // advance our stashed frontier
-                            *current_upper.borrow_mut() = input_upper.clone();
+                            *current_upper.borrow_mut() = expected_upper;
                             // wait for more data or a new input frontier
                             continue;
                         }
 
-                        // `current_upper` tracks the last known upper
-                        let mut expected_upper = current_upper.borrow().clone();
                         let finalized_batch_count = finalized_timestamps.len();
 
                         for (i, ts) in finalized_timestamps.into_iter().enumerate() {
@@ -250,40 +261,76 @@ where
                                 .await
                                 .expect("invalid usage");
 
-                            write
-                                .compare_and_append_batch(
-                                    &mut [&mut batch],
-                                    expected_upper,
-                                    new_upper.clone(),
-                                )
-                                .await
-                                .expect("cannot append updates")
-                                .expect("invalid/outdated upper");
+                            let mut appended = false;
+                            while PartialOrder::less_than(&expected_upper, &new_upper) {
+                                let result = if expected_upper.less_equal(&ts) {
+                                    write
+                                        .compare_and_append_batch(
+                                            &mut [&mut batch],
+                                            expected_upper.clone(),
+                                            new_upper.clone(),
+                                        )
+                                        .await
+                                } else {
+                                    // Another replica has already written the updates at
+                                    // `ts`, but has not yet advanced the shard to `new_upper`.
+                                    write
+                                        .compare_and_append(
+                                            Vec::<((SourceData, ()), Timestamp, Diff)>::new(),
+                                            expected_upper.clone(),
+                                            new_upper.clone(),
+                                        )
+                                        .await
+                                };
+                                match result.expect("cannot append updates") {
+                                    Ok(()) => {
+                                        appended = expected_upper.less_equal(&ts);
+                                        expected_upper = new_upper.clone();
+                                    }
+                                    Err(mismatch) => expected_upper = mismatch.current,
+                                }
+                            }
+                            if !appended {
+                                batch.delete().await;
+                            }
 
                             source_statistics.inc_updates_committed_by(
                                 batch_builder.inserts + batch_builder.retractions,
                             );
-                            source_statistics.update_snapshot_committed(&new_upper);
-
-                            metrics.processed_batches.inc();
-                            metrics.row_inserts.inc_by(batch_builder.inserts);
-                            metrics.row_retractions.inc_by(batch_builder.retractions);
-                            metrics.error_inserts.inc_by(batch_builder.error_inserts);
-                            metrics
-                                .error_retractions
-                                .inc_by(batch_builder.error_retractions);
+                            source_statistics.update_snapshot_committed(&expected_upper);
+
+                            if appended {
+                                metrics.processed_batches.inc();
+                                metrics.row_inserts.inc_by(batch_builder.inserts);
+                                metrics.row_retractions.inc_by(batch_builder.retractions);
+                                metrics.error_inserts.inc_by(batch_builder.error_inserts);
+                                metrics
+                                    .error_retractions
+                                    .inc_by(batch_builder.error_retractions);
+                            }
                             metrics
                                 .progress
-                                .set(mz_persist_client::metrics::encode_ts_metric(&new_upper));
-
-                            // next `expected_upper` is the one we just successfully appended
-                            expected_upper = new_upper;
+                                .set(mz_persist_client::metrics::encode_ts_metric(
+                                    &expected_upper,
+                                ));
                         }
                         // advance our stashed frontier
-                        *current_upper.borrow_mut() = input_upper.clone();
+                        *current_upper.borrow_mut() = expected_upper;
                     } else {
-                        // We cannot have updates without the frontier advancing
-                        assert!(finalized_timestamps.is_empty());
+                        // Another replica has already advanced the shard past the finalized
+                        // timestamps, and so has already written their updates.
+                        for ts in finalized_timestamps {
+                            let batch_builder = stashed_batches
+                                .remove(&ts)
+                                .expect("batch for timestamp to still be there");
+                            batch_builder
+                                .builder
+                                .finish(Antichain::from_elem(ts.step_forward()))
+                                .await
+                                .expect("invalid usage")
+                                .delete()
+                                .await;
+                        }
                     }
                 }
             }
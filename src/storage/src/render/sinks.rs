@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
 use differential_dataflow::{AsCollection, Collection, Hashable};
+use timely::dataflow::operators::Map;
 use timely::dataflow::Scope;
 use tracing::warn;
 
@@ -172,6 +173,10 @@ where
     //   It then renders those as Avro.
     // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
     //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
+    // * "None" consolidates the stream and passes its updates through. The sink reports an error
+    //   if it encounters a retraction.
+    // * "CdcV2" consolidates the stream and packs the time and diff of each update into its row,
+    //   so that each update can be rendered as a CDCv2 update.
     let collection = match sink.envelope {
         Some(SinkEnvelope::Debezium) => {
             let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
@@ -215,6 +220,34 @@ where
             });
             collection
         }
+        Some(SinkEnvelope::None) => {
+            let consolidated = keyed.consolidate();
+
+            // if there is no user-specified key, remove the synthetic
+            // distribution key again
+            let user_key_indices = sink_render.get_key_indices();
+            if user_key_indices.is_some() {
+                consolidated.map(|(key, value)| (key, Some(value)))
+            } else {
+                consolidated.map(|(_key, value)| (None, Some(value)))
+            }
+        }
+        Some(SinkEnvelope::CdcV2) => {
+            let mut row_buf = Row::default();
+            keyed
+                .consolidate()
+                .inner
+                .map(move |((_key, value), time, diff)| {
+                    {
+                        let mut packer = row_buf.packer();
+                        packer.extend_by_row(&value);
+                        packer.push(Datum::MzTimestamp(time));
+                        packer.push(Datum::Int64(diff));
+                    }
+                    ((None, Some(row_buf.clone())), time, 1)
+                })
+                .as_collection()
+        }
         None => keyed.map(|(key, value)| (key, Some(value))),
     };
 
@@ -29,7 +29,7 @@ use mz_storage_client::types::errors::{DataflowError, DecodeError, EnvelopeError
 use mz_storage_client::types::sources::{encoding::*, *};
 use mz_timely_util::operator::{CollectionExt, StreamExt};
 
-use crate::decode::{render_decode, render_decode_cdcv2, render_decode_delimited};
+use crate::decode::{render_decode, render_decode_cdcv2, render_decode_delimited, to_metadata_row};
 use crate::source::types::{DecodeResult, SourceOutput};
 use crate::source::{self, DelimitedValueSourceConnection, RawSourceCreationConfig};
 
@@ -326,13 +326,20 @@ where
                     &storage_state.connection_context,
                 ),
                 SourceType::Row(source) => (
-                    source.map(|r| DecodeResult {
+                    source.map(move |r| DecodeResult {
                         key: None,
                         value: Some(Ok((r.value, r.diff))),
                         position: r.position,
                         upstream_time_millis: r.upstream_time_millis,
+                        metadata: to_metadata_row(
+                            &metadata_columns,
+                            r.partition.clone(),
+                            r.position,
+                            r.upstream_time_millis,
+                            None,
+                            &r.metadata,
+                        ),
                         partition: r.partition,
-                        metadata: Row::default(),
                     }),
                     None,
                 ),
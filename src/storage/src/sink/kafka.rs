@@ -43,7 +43,7 @@ use tokio::runtime::Handle as TokioHandle;
 use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, error, info, warn};
 
-use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator};
+use mz_interchange::avro::{cdc_v2, AvroEncoder, AvroSchemaGenerator};
 use mz_interchange::encode::Encode;
 use mz_interchange::json::JsonEncoder;
 use mz_kafka_util::client::{BrokerRewritingClientContext, MzClientContext};
@@ -391,6 +391,10 @@ struct KafkaSinkState {
     internal_cmd_tx: Rc<RefCell<dyn InternalCommandSender>>,
     gate_ts: Rc<Cell<Option<Timestamp>>>,
 
+    /// For `ENVELOPE MATERIALIZE` sinks, the state of the CDCv2 progress
+    /// statements written to the sink topic.
+    cdc_v2_progress: Option<CdcV2Progress>,
+
     /// Timestamp of the latest progress record that was written out to Kafka.
     latest_progress_ts: Timestamp,
 
@@ -405,6 +409,19 @@ struct KafkaSinkState {
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
 }
 
+/// The CDCv2 progress statements written to the topic of an `ENVELOPE
+/// MATERIALIZE` sink.
+///
+/// Each progress statement is written in the same transaction as the progress
+/// record of the same timestamp, so after a restart the gate timestamp
+/// determines the upper of the latest progress statement.
+struct CdcV2Progress {
+    encoder: cdc_v2::Encoder,
+    /// The upper of the latest progress statement, which is the lower of the
+    /// next one.
+    upper: Cell<Timestamp>,
+}
+
 struct SinkConsumerContext {
     status_tx: mpsc::Sender<SinkStatus>,
 }
@@ -433,6 +450,7 @@ impl KafkaSinkState {
         connection_context: &ConnectionContext,
         gate_ts: Rc<Cell<Option<Timestamp>>>,
         internal_cmd_tx: Rc<RefCell<dyn InternalCommandSender>>,
+        cdc_v2_encoder: Option<cdc_v2::Encoder>,
     ) -> Self {
         let metrics = Arc::new(SinkMetrics::new(
             metrics,
@@ -532,6 +550,10 @@ impl KafkaSinkState {
             healthchecker,
             internal_cmd_tx,
             gate_ts,
+            cdc_v2_progress: cdc_v2_encoder.map(|encoder| CdcV2Progress {
+                encoder,
+                upper: Cell::new(Timestamp::minimum()),
+            }),
             latest_progress_ts: Timestamp::minimum(),
             write_frontier,
         }
@@ -781,6 +803,21 @@ impl KafkaSinkState {
         self.send(record).await
     }
 
+    /// For `ENVELOPE MATERIALIZE` sinks, writes a CDCv2 progress statement to
+    /// the sink topic that advances its upper to `upper`. `counts` must
+    /// contain the number of updates written at each time in between.
+    ///
+    /// Does nothing for other sinks.
+    async fn send_cdc_v2_progress(&self, upper: Timestamp, counts: &[(Timestamp, usize)]) {
+        let Some(progress) = &self.cdc_v2_progress else {
+            return;
+        };
+        let lower = progress.upper.replace(upper);
+        let encoded = progress.encoder.encode_progress(&[lower], &[upper], counts);
+        let record = BaseRecord::<(), _>::to(&self.topic).payload(&encoded);
+        self.send(record).await
+    }
+
     /// Asserts that the write frontier has not yet advanced beyond `t`.
     fn assert_progress(&self, ts: &Timestamp) {
         assert!(self.write_frontier.borrow().less_equal(ts));
@@ -869,6 +906,8 @@ impl KafkaSinkState {
                     "{}: sending progress for gate ts: {:?}",
                     &self.name, min_frontier
                 );
+                self.send_cdc_v2_progress(min_frontier.step_forward(), &[])
+                    .await;
                 self.send_progress_record(min_frontier).await;
 
                 self.halt_on_err(
@@ -909,6 +948,18 @@ impl KafkaSinkState {
         }
     }
 
+    /// Report a SinkStatus::Failed and then stop the sink for good. Unlike
+    /// [`Self::halt_on_err`], this is for errors that restarting the sink can't
+    /// resolve.
+    pub async fn fail<T>(&self, msg: impl ToString) -> T {
+        self.update_status(SinkStatus::Failed(msg.to_string()))
+            .await;
+
+        // Make sure to never return, preventing the sink from writing out
+        // anything past the failure.
+        future::pending().await
+    }
+
     /// Report a SinkStatus::Stalled and then halt with the same message.
     pub async fn halt_on_err<T>(&self, result: Result<T, impl ToString + Debug>) -> T {
         match result {
@@ -967,7 +1018,26 @@ where
         .map(|(desc, _indices)| desc.clone());
     let value_desc = connection.value_desc.clone();
 
+    let mut cdc_v2_encoder = None;
     let encoded_stream = match connection.published_schema_info {
+        Some(PublishedSchemaInfo {
+            key_schema_id: _,
+            value_schema_id,
+        }) if envelope == Some(SinkEnvelope::CdcV2) => {
+            let new_encoder = || {
+                cdc_v2::Encoder::new(value_desc.clone(), value_schema_id)
+                    .expect("avro schema validated")
+            };
+            cdc_v2_encoder = Some(new_encoder());
+            encode_stream(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                new_encoder(),
+                connection.fuel,
+                name.clone(),
+            )
+        }
         Some(PublishedSchemaInfo {
             key_schema_id,
             value_schema_id,
@@ -1020,6 +1090,7 @@ where
         connection_context,
         healthchecker_args,
         internal_cmd_tx,
+        cdc_v2_encoder,
     )
 }
 
@@ -1034,6 +1105,9 @@ where
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnection`] will be discarded without producing them.
+///
+/// If given a CDCv2 encoder, each transaction additionally writes a CDCv2
+/// progress statement to the sink topic.
 pub fn produce_to_kafka<G>(
     stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
     id: GlobalId,
@@ -1047,6 +1121,7 @@ pub fn produce_to_kafka<G>(
     connection_context: &ConnectionContext,
     healthchecker_args: HealthcheckerArgs,
     internal_cmd_tx: Rc<RefCell<dyn InternalCommandSender>>,
+    cdc_v2_encoder: Option<cdc_v2::Encoder>,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -1064,6 +1139,7 @@ where
         connection_context,
         Rc::clone(&shared_gate_ts),
         internal_cmd_tx,
+        cdc_v2_encoder,
     );
 
     let mut vector = Vec::new();
@@ -1125,6 +1201,9 @@ where
                 gate
             );
             s.maybe_update_progress(&gate);
+            if let Some(progress) = &s.cdc_v2_progress {
+                progress.upper.set(gate.step_forward());
+            }
         }
 
         s.update_status(SinkStatus::Running).await;
@@ -1153,8 +1232,20 @@ where
                             // Explicitly refuse to send no-op records
                             continue;
                         };
-                        let count =
-                            usize::try_from(diff).expect("can't sink negative multiplicities");
+                        // Only `ENVELOPE NONE` sinks pass retractions through
+                        // their envelope, and they don't support them. Restarting
+                        // the sink would only encounter the retraction again.
+                        let count = match usize::try_from(diff) {
+                            Ok(count) => count,
+                            Err(_) => {
+                                s.fail(format!(
+                                    "ENVELOPE NONE sink received a retraction at time {}; \
+                                     the sinked relation must be append-only",
+                                    time
+                                ))
+                                .await
+                            }
+                        };
 
                         let rows = s.pending_rows.entry(time).or_default();
                         rows.push(EncodedRow { key, value, count });
@@ -1229,6 +1320,12 @@ where
                             }
                         }
 
+                        // We don't count this statement as part of the message count in
+                        // user-facing statistics.
+                        let updates: usize = rows.iter().map(|row| row.count).sum();
+                        s.send_cdc_v2_progress(ts.step_forward(), &[(*ts, updates)])
+                            .await;
+
                         // Flush to make sure that errored messages have been properly retried before
                         // sending progress records and commit transactions.
                         s.flush().await;
@@ -97,7 +97,7 @@ where
                     key: _,
                     value,
                     upstream_time_millis,
-                    headers,
+                    metadata,
                 }),
                 cap,
                 diff,
@@ -107,7 +107,7 @@ where
                     key: None,
                     value,
                     upstream_time_millis,
-                    headers,
+                    metadata,
                 }),
                 cap,
                 diff,
@@ -159,7 +159,7 @@ impl SourceReader for LoadGeneratorSourceReader {
             upstream_time_millis: None,
             key: (),
             value,
-            headers: None,
+            metadata: Default::default(),
         };
         let cap = self.data_capability.delayed(&self.offset);
         let next_ts = self.offset + 1;
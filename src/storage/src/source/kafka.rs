@@ -41,7 +41,7 @@ use mz_timely_util::order::Partitioned;
 use crate::source::commit::LogCommitter;
 
 use crate::source::source_reader_pipeline::HealthStatus;
-use crate::source::types::{OffsetCommitter, SourceConnectionBuilder};
+use crate::source::types::{OffsetCommitter, SourceConnectionBuilder, SourceMessageMetadata};
 use crate::source::{
     NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
 };
@@ -815,7 +815,10 @@ fn construct_source_message(
         upstream_time_millis: msg.timestamp().to_millis(),
         key: msg.key().map(|k| k.to_vec()),
         value: msg.payload().map(|p| p.to_vec()),
-        headers,
+        metadata: SourceMessageMetadata {
+            headers,
+            ..Default::default()
+        },
     };
     (msg, (pid, offset.into()))
 }
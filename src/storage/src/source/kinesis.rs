@@ -316,7 +316,7 @@ impl SourceReader for KinesisSourceReader {
                             upstream_time_millis: None,
                             key: (),
                             value: Some(data),
-                            headers: None,
+                            metadata: Default::default(),
                         };
                         self.buffered_messages.push_back((source_message, ts));
                     }
@@ -60,6 +60,17 @@ mod metrics;
 /// Postgres epoch is 2000-01-01T00:00:00Z
 static PG_EPOCH: Lazy<SystemTime> = Lazy::new(|| UNIX_EPOCH + Duration::from_secs(946_684_800));
 
+/// Converts a commit timestamp from the replication stream, in microseconds since the Postgres
+/// epoch, into milliseconds since the Unix epoch.
+fn commit_time_millis(pg_micros: i64) -> i64 {
+    let pg_epoch_millis = PG_EPOCH
+        .duration_since(UNIX_EPOCH)
+        .expect("Postgres epoch is after the Unix epoch")
+        .as_millis();
+    i64::try_from(pg_epoch_millis).expect("Postgres epoch fits in i64")
+        + pg_micros.div_euclid(1_000)
+}
+
 /// How often a status update message should be sent to the server
 static FEEDBACK_INTERVAL: Duration = Duration::from_secs(30);
 
@@ -190,6 +201,8 @@ enum InternalMessage {
         output: usize,
         value: Row,
         lsn: PgLsn,
+        /// The time the change was committed upstream, as milliseconds since the Unix epoch
+        upstream_time_millis: Option<i64>,
         diff: Diff,
         end: bool,
     },
@@ -245,6 +258,8 @@ struct PostgresTaskInfo {
     row_sender: RowSender,
     sender: Sender<InternalMessage>,
     resume_lsn: Arc<AtomicU64>,
+    /// Whether the source includes the LSN or timestamp of each change's commit in its output
+    include_commit_metadata: bool,
 }
 
 impl SourceConnectionBuilder for PostgresSourceConnection {
@@ -321,6 +336,8 @@ impl SourceConnectionBuilder for PostgresSourceConnection {
                 row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
                 sender: dataflow_tx,
                 resume_lsn: Arc::clone(&resume_lsn),
+                include_commit_metadata: self.include_lsn.is_some()
+                    || self.include_timestamp.is_some(),
             };
 
             task::spawn(
@@ -368,15 +385,16 @@ impl SourceReader for PostgresSourceReader {
                 value,
                 diff,
                 lsn,
+                upstream_time_millis,
                 end,
             })) => {
                 self.last_lsn = lsn;
                 let msg = SourceMessage {
                     output,
-                    upstream_time_millis: None,
+                    upstream_time_millis,
                     key: (),
                     value,
-                    headers: None,
+                    metadata: Default::default(),
                 };
 
                 let ts = lsn.into();
@@ -591,7 +609,7 @@ async fn postgres_replication_loop_inner(
             };
             task_info
                 .row_sender
-                .send_row(output, row, slot_lsn, 1)
+                .send_row(output, row, slot_lsn, None, 1)
                 .await;
         }
 
@@ -629,13 +647,14 @@ async fn postgres_replication_loop_inner(
 
             while let Some(event) = replication_stream.next().await {
                 match event {
-                    Ok(Event::Message(lsn, (output, row, diff))) => {
+                    Ok(Event::Message(lsn, (output, row, diff, _commit_time))) => {
                         // Here we ignore the lsn that this row actually happened at and we
-                        // forcefully emit it at the slot_lsn with a negated diff.
+                        // forcefully emit it at the slot_lsn with a negated diff. Like the
+                        // snapshot rows that it corrects, the row has no commit time.
                         if lsn <= snapshot_lsn {
                             task_info
                                 .row_sender
-                                .send_row(output, row, slot_lsn, -diff)
+                                .send_row(output, row, slot_lsn, None, -diff)
                                 .await;
                         }
                     }
@@ -680,8 +699,19 @@ async fn postgres_replication_loop_inner(
     // a way to encode this in the type signature
     while let Some(event) = replication_stream.next().await.transpose()? {
         match event {
-            Event::Message(lsn, (output, row, diff)) => {
-                task_info.row_sender.send_row(output, row, lsn, diff).await;
+            Event::Message(lsn, (output, row, diff, commit_time)) => {
+                // Retracting a row requires knowing the commit metadata it was inserted with,
+                // which we do not track.
+                if diff < 0 && task_info.include_commit_metadata {
+                    return Err(ReplicationError::Definite(anyhow!(
+                        "postgres sources that INCLUDE LSN or TIMESTAMP only support tables \
+                         that receive INSERTs, but saw an UPDATE or DELETE"
+                    )));
+                }
+                task_info
+                    .row_sender
+                    .send_row(output, row, lsn, Some(commit_time), diff)
+                    .await;
             }
             Event::Progress([lsn]) => {
                 // The lsn passed to `START_REPLICATION_SLOT` produces all transactions that
@@ -701,6 +731,7 @@ struct RowMessage {
     output_index: usize,
     row: Row,
     lsn: PgLsn,
+    upstream_time_millis: Option<i64>,
     diff: i64,
 }
 
@@ -727,23 +758,24 @@ impl RowSender {
     }
 
     /// Send a triplet for the specific output
-    pub async fn send_row(&mut self, output_index: usize, row: Row, lsn: PgLsn, diff: Diff) {
+    pub async fn send_row(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        lsn: PgLsn,
+        upstream_time_millis: Option<i64>,
+        diff: Diff,
+    ) {
         if let Some(buffered) = self.buffered_message.take() {
             assert_eq!(buffered.lsn, lsn);
-            self.send_row_inner(
-                buffered.output_index,
-                buffered.row,
-                buffered.lsn,
-                buffered.diff,
-                false,
-            )
-            .await;
+            self.send_row_inner(buffered, false).await;
         }
 
         self.buffered_message = Some(RowMessage {
             output_index,
             row,
             lsn,
+            upstream_time_millis,
             diff,
         });
     }
@@ -754,27 +786,28 @@ impl RowSender {
     pub async fn close_lsn(&mut self, lsn: PgLsn) {
         if let Some(buffered) = self.buffered_message.take() {
             assert!(buffered.lsn <= lsn);
-            self.send_row_inner(
-                buffered.output_index,
-                buffered.row,
-                buffered.lsn,
-                buffered.diff,
-                true,
-            )
-            .await;
+            self.send_row_inner(buffered, true).await;
         }
     }
 
-    async fn send_row_inner(&self, output: usize, row: Row, lsn: PgLsn, diff: i64, end: bool) {
+    async fn send_row_inner(&self, message: RowMessage, end: bool) {
+        let RowMessage {
+            output_index,
+            row,
+            lsn,
+            upstream_time_millis,
+            diff,
+        } = message;
         // a closed receiver means the source has been shutdown
         // (dropped or the process is dying), so just continue on
         // without activation
         if let Ok(_) = self
             .sender
             .send(InternalMessage::Value {
-                output,
+                output: output_index,
                 value: row,
                 lsn,
+                upstream_time_millis,
                 diff,
                 end,
             })
@@ -950,7 +983,7 @@ async fn produce_replication<'a>(
     committed_lsn: Arc<AtomicU64>,
     metrics: &'a PgSourceMetrics,
     source_tables: &'a BTreeMap<u32, SourceTable>,
-) -> impl Stream<Item = Result<Event<[PgLsn; 1], (usize, Row, Diff)>, ReplicationError>> + 'a {
+) -> impl Stream<Item = Result<Event<[PgLsn; 1], (usize, Row, Diff, i64)>, ReplicationError>> + 'a {
     use ReplicationError::*;
     use ReplicationMessage::*;
     async_stream::try_stream!({
@@ -1097,12 +1130,19 @@ async fn produce_replication<'a>(
                             last_data_message = Instant::now();
                             metrics.transactions.inc();
                             last_commit_lsn = PgLsn::from(commit.end_lsn());
+                            let commit_time = commit_time_millis(commit.timestamp());
 
                             for (output, row) in deletes.drain(..) {
-                                yield Event::Message(last_commit_lsn, (output, row, -1));
+                                yield Event::Message(
+                                    last_commit_lsn,
+                                    (output, row, -1, commit_time),
+                                );
                             }
                             for (output, row) in inserts.drain(..) {
-                                yield Event::Message(last_commit_lsn, (output, row, 1));
+                                yield Event::Message(
+                                    last_commit_lsn,
+                                    (output, row, 1, commit_time),
+                                );
                             }
                             yield Event::Progress([PgLsn::from(u64::from(last_commit_lsn) + 1)]);
                             metrics.lsn.set(last_commit_lsn.into());
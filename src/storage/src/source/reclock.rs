@@ -660,6 +660,7 @@ mod tests {
             data_shard: ShardId::new(),
             status_shard: None,
             relation_desc: RelationDesc::empty(),
+            layout: None,
         };
 
         let clock_stream = futures::stream::iter((0..).map(|seconds| {
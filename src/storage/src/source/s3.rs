@@ -66,7 +66,7 @@ use mz_storage_client::types::sources::{
 use self::metrics::{BucketMetrics, ScanBucketMetrics};
 use self::notifications::{Event, EventType, TestEvent};
 use crate::source::commit::LogCommitter;
-use crate::source::types::SourceConnectionBuilder;
+use crate::source::types::{SourceConnectionBuilder, SourceMessageMetadata};
 use crate::source::{
     NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
 };
@@ -77,6 +77,8 @@ mod metrics;
 mod notifications;
 
 struct InternalMessage {
+    /// The key of the object the record was read from
+    object_key: Arc<str>,
     record: Option<Vec<u8>>,
 }
 /// Size of data chunks we send to dataflow
@@ -103,6 +105,8 @@ pub struct S3SourceReader {
     // S3 sources support single-threaded ingestion only, so only one of the
     // `S3SourceReader`s will actually produce data.
     active_read_worker: bool,
+    /// Whether to pass the key of each record's object along with the record
+    include_object_key: bool,
 }
 
 /// Current dataflow status
@@ -743,11 +747,12 @@ async fn download_object(
         Err(err) => return Err(DownloadError::Failed { err }),
     };
 
+    let object_key: Arc<str> = Arc::from(key);
     let mut download_result = match compression {
-        Compression::None => read_object_chunked(source_id, reader, tx).await,
+        Compression::None => read_object_chunked(source_id, &object_key, reader, tx).await,
         Compression::Gzip => {
             let decoder = GzipDecoder::new(reader);
-            read_object_chunked(source_id, decoder, tx).await
+            read_object_chunked(source_id, &object_key, decoder, tx).await
         }
     };
 
@@ -757,7 +762,10 @@ async fn download_object(
     );
 
     if download_result.is_ok() {
-        let sent = tx.send(Ok(InternalMessage { record: None }));
+        let sent = tx.send(Ok(InternalMessage {
+            object_key,
+            record: None,
+        }));
         if sent.await.is_err() {
             download_result = Err(DownloadError::SendFailed);
         }
@@ -768,6 +776,7 @@ async fn download_object(
 
 async fn read_object_chunked<R>(
     source_id: &str,
+    object_key: &Arc<str>,
     reader: R,
     tx: &Sender<Result<InternalMessage, S3Error>>,
 ) -> Result<DownloadMetricUpdate, DownloadError>
@@ -785,6 +794,7 @@ where
                 chunks += 1;
                 if tx
                     .send(Ok(InternalMessage {
+                        object_key: Arc::clone(object_key),
                         // ReaderStream return's `None` if the underlying `AsyncRead`
                         // gives out 0 bytes, so the chunk is always !empty.
                         // See https://github.com/tokio-rs/tokio/blob/e8f19e771f501408427f7f9ee6ba4f54b2d4094c/tokio-util/src/io/reader_stream.rs#L102-L108
@@ -920,6 +930,7 @@ impl SourceConnectionBuilder for S3SourceConnection {
                 upper_capability,
                 offset: S3Offset(0),
                 active_read_worker,
+                include_object_key: self.include_object_key.is_some(),
             },
             LogCommitter {
                 source_id,
@@ -942,14 +953,17 @@ impl SourceReader for S3SourceReader {
         }
 
         match self.receiver_stream.recv().now_or_never() {
-            Some(Some(Ok(InternalMessage { record }))) => {
+            Some(Some(Ok(InternalMessage { object_key, record }))) => {
                 self.offset += 1;
                 let msg = SourceMessage {
                     output: 0,
                     upstream_time_millis: None,
                     key: (),
                     value: record,
-                    headers: None,
+                    metadata: SourceMessageMetadata {
+                        object_key: self.include_object_key.then(|| object_key.to_string()),
+                        ..Default::default()
+                    },
                 };
                 let ts = MzOffset::from(self.offset);
                 let cap = self.data_capability.delayed(&ts);
@@ -1225,7 +1225,7 @@ fn handle_message<K, V, T, D>(
                     offset,
                     message.upstream_time_millis,
                     partition.clone(),
-                    message.headers,
+                    message.metadata,
                     diff,
                 )),
             )
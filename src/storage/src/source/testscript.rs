@@ -117,7 +117,7 @@ impl SourceReader for TestScriptSourceReader {
                         upstream_time_millis: None,
                         key: key.map(|k| k.into_bytes()),
                         value: Some(value.into_bytes()),
-                        headers: None,
+                        metadata: Default::default(),
                     });
                     let ts = MzOffset::from(offset);
                     let cap = self.data_capability.delayed(&ts);
@@ -199,9 +199,21 @@ pub struct SourceMessage<Key, Value> {
     pub key: Key,
     /// The message value
     pub value: Value,
+    /// Source-specific metadata about the message
+    pub metadata: SourceMessageMetadata,
+}
+
+/// Source-specific metadata about a message that sources pass along, beyond its position and
+/// upstream time, so that it can be exposed as `INCLUDE`d columns. Sources only fill in the pieces
+/// of metadata that they support and that they are configured to include.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SourceMessageMetadata {
     /// Headers, if the source is configured to pass them along. If it is, but there are none, it
     /// passes `Some([])`
     pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+    /// The key of the object that the message was read from, if the source is configured to pass
+    /// it along
+    pub object_key: Option<String>,
 }
 
 /// A record produced by a source
@@ -223,9 +235,8 @@ where
     pub upstream_time_millis: Option<i64>,
     /// The partition of this message, present iff the partition comes from Kafka
     pub partition: PartitionId,
-    /// Headers, if the source is configured to pass them along. If it is, but there are none, it
-    /// passes `Some([])`
-    pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+    /// Source-specific metadata about the record
+    pub metadata: SourceMessageMetadata,
 
     /// Indicator for what the differential `diff` value
     /// for this decoded message should be
@@ -244,7 +255,7 @@ where
         position: MzOffset,
         upstream_time_millis: Option<i64>,
         partition: PartitionId,
-        headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+        metadata: SourceMessageMetadata,
         diff: D,
     ) -> SourceOutput<K, V, D> {
         SourceOutput {
@@ -253,7 +264,7 @@ where
             position,
             upstream_time_millis,
             partition,
-            headers,
+            metadata,
             diff,
         }
     }
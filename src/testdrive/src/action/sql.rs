@@ -69,6 +69,8 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &mut State) -> Result<ControlFl
         | CreateRole(_)
         | AlterObjectRename(_)
         | AlterIndex(_)
+        | AlterTable(_)
+        | AlterMaterializedView(_)
         | Discard(_)
         | DropDatabase(_)
         | DropObjects(_)
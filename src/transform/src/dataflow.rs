@@ -20,12 +20,12 @@ use mz_compute_client::types::dataflows::DataflowDesc;
 use mz_expr::visit::Visit;
 use mz_expr::{CollectionPlan, Id, LocalId, MapFilterProject, MirRelationExpr};
 
-use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, TransformError};
+use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, StatisticsOracle, TransformError};
 
 /// Optimizes the implementation of each dataflow.
 ///
 /// Inlines views, performs a full optimization pass including physical
-/// planning using the supplied indexes, propagates filtering and projection
+/// planning using the supplied indexes and statistics, propagates filtering and projection
 /// information to dataflow sources and lifts monotonicity information.
 #[tracing::instrument(
     target = "optimizer",
@@ -36,12 +36,13 @@ use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, TransformError};
 pub fn optimize_dataflow(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
+    stats: &dyn StatisticsOracle,
 ) -> Result<(), TransformError> {
     // Inline views that are used in only one other view.
     inline_views(dataflow)?;
 
     // Logical optimization pass after view inlining
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::logical_optimizer())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::logical_optimizer())?;
 
     optimize_dataflow_filters(dataflow)?;
     // TODO: when the linear operator contract ensures that propagated
@@ -54,10 +55,10 @@ pub fn optimize_dataflow(
 
     // A smaller logical optimization pass after projections and filters are
     // pushed down across views.
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::logical_cleanup_pass())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::logical_cleanup_pass())?;
 
     // Physical optimization pass
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::physical_optimizer())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::physical_optimizer())?;
 
     optimize_dataflow_monotonic(dataflow)?;
 
@@ -166,7 +167,7 @@ fn inline_views(dataflow: &mut DataflowDesc) -> Result<(), TransformError> {
 }
 
 /// Performs either the logical or the physical optimization pass on the
-/// dataflow using the supplied set of indexes.
+/// dataflow using the supplied set of indexes and statistics.
 #[tracing::instrument(
     target = "optimizer",
     level = "debug",
@@ -176,6 +177,7 @@ fn inline_views(dataflow: &mut DataflowDesc) -> Result<(), TransformError> {
 fn optimize_dataflow_relations(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
+    stats: &dyn StatisticsOracle,
     optimizer: &Optimizer,
 ) -> Result<(), TransformError> {
     // Re-optimize each dataflow
@@ -184,7 +186,7 @@ fn optimize_dataflow_relations(
     // add indexes imperatively to `DataflowDesc`.
     for object in dataflow.objects_to_build.iter_mut() {
         // Re-run all optimizations on the composite views.
-        optimizer.transform(object.plan.as_inner_mut(), indexes)?;
+        optimizer.transform(object.plan.as_inner_mut(), indexes, stats)?;
     }
 
     mz_repr::explain::trace_plan(dataflow);
@@ -180,6 +180,7 @@ impl Demand {
                     inputs,
                     equivalences,
                     implementation: _,
+                    hints: _,
                 } => {
                     let input_mapper = JoinInputMapper::new(inputs);
 
@@ -23,7 +23,7 @@
 
 use crate::{TransformArgs, TransformError};
 use mz_expr::visit::Visit;
-use mz_expr::{MirRelationExpr, MirScalarExpr};
+use mz_expr::{JoinHints, MirRelationExpr, MirScalarExpr};
 use mz_repr::RelationType;
 
 /// Fuses multiple `Join` operators into one `Join` operator.
@@ -57,10 +57,11 @@ impl Join {
         if let MirRelationExpr::Join {
             inputs,
             equivalences,
+            hints,
             ..
         } = relation
         {
-            let mut join_builder = JoinBuilder::new(equivalences);
+            let mut join_builder = JoinBuilder::new(equivalences, hints);
 
             // We scan through each input, digesting any joins that we find and updating their equivalence classes.
             // We retain any existing equivalence classes, as they are already with respect to the cross product.
@@ -69,20 +70,27 @@ impl Join {
                     MirRelationExpr::Join {
                         inputs,
                         equivalences,
+                        hints,
                         ..
                     } => {
                         // Merge the inputs into the new join being built.
-                        join_builder.add_subjoin(inputs, equivalences, None)?;
+                        join_builder.add_subjoin(inputs, equivalences, hints, None)?;
                     }
                     MirRelationExpr::Filter { input, predicates } => {
                         if let MirRelationExpr::Join {
                             inputs,
                             equivalences,
+                            hints,
                             ..
                         } = *input
                         {
                             // Merge the inputs and the predicates into the new join being built.
-                            join_builder.add_subjoin(inputs, equivalences, Some(predicates))?;
+                            join_builder.add_subjoin(
+                                inputs,
+                                equivalences,
+                                hints,
+                                Some(predicates),
+                            )?;
                         } else {
                             // Retain the input.
                             let input = input.filter(predicates);
@@ -109,15 +117,18 @@ struct JoinBuilder {
     num_columns: usize,
     /// Predicates that will be evaluated on top of the join, if any.
     predicates: Vec<MirScalarExpr>,
+    /// Hints of the fused joins, with those of outer joins taking precedence.
+    hints: JoinHints,
 }
 
 impl JoinBuilder {
-    fn new(equivalences: &mut Vec<Vec<MirScalarExpr>>) -> Self {
+    fn new(equivalences: &mut Vec<Vec<MirScalarExpr>>, hints: &mut JoinHints) -> Self {
         Self {
             inputs: Vec::new(),
             equivalences: equivalences.drain(..).collect(),
             num_columns: 0,
             predicates: Vec::new(),
+            hints: std::mem::take(hints),
         }
     }
 
@@ -134,11 +145,14 @@ impl JoinBuilder {
         &mut self,
         inputs: I,
         mut equivalences: Vec<Vec<MirScalarExpr>>,
+        hints: JoinHints,
         predicates: Option<Vec<MirScalarExpr>>,
     ) -> Result<(), TransformError>
     where
         I: IntoIterator<Item = MirRelationExpr>,
     {
+        self.hints.merge(hints);
+
         // Update and push all of the variables.
         for mut equivalence in equivalences.drain(..) {
             for expr in equivalence.iter_mut() {
@@ -189,6 +203,7 @@ impl JoinBuilder {
                 inputs: self.inputs,
                 equivalences: self.equivalences,
                 implementation: mz_expr::JoinImplementation::Unimplemented,
+                hints: self.hints,
             },
         };
 
@@ -46,6 +46,7 @@ impl TopK {
             limit,
             offset,
             monotonic,
+            expected_group_size,
         } = relation
         {
             while let MirRelationExpr::TopK {
@@ -55,6 +56,7 @@ impl TopK {
                 limit: inner_limit,
                 offset: inner_offset,
                 monotonic: inner_monotonic,
+                expected_group_size: inner_expected_group_size,
             } = &mut **input
             {
                 // We can fuse two chained TopK operators as long as they share the
@@ -88,6 +90,8 @@ impl TopK {
 
                     *offset += *inner_offset;
                     *monotonic = *inner_monotonic;
+                    // The fused operator groups the input of the inner one.
+                    *expected_group_size = *inner_expected_group_size;
                     **input = inner_input.take_dangerous();
                 } else {
                     break;
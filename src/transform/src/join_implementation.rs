@@ -21,12 +21,14 @@ use std::collections::BTreeMap;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::IndexedFilter;
 use mz_expr::{
-    FilterCharacteristics, Id, JoinInputCharacteristics, JoinInputMapper, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+    FilterCharacteristics, Id, JoinAlgorithm, JoinInputCharacteristics, JoinInputMapper,
+    MapFilterProject, MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
 };
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
+use mz_ore::str::StrExt;
 
 use self::index_map::IndexMap;
+pub(crate) use self::statistics_map::{InputStatistics, StatisticsMap};
 use crate::predicate_pushdown::PredicatePushdown;
 use crate::{TransformArgs, TransformError};
 
@@ -68,7 +70,11 @@ impl crate::Transform for JoinImplementation {
         relation: &mut MirRelationExpr,
         args: TransformArgs,
     ) -> Result<(), TransformError> {
-        let result = self.action_recursive(relation, &mut IndexMap::new(args.indexes));
+        let result = self.action_recursive(
+            relation,
+            &mut IndexMap::new(args.indexes),
+            &mut StatisticsMap::new(args.stats),
+        );
         mz_repr::explain::trace_plan(&*relation);
         result
     }
@@ -78,14 +84,17 @@ impl JoinImplementation {
     /// Pre-order visitor for each `MirRelationExpr` to find join operators.
     ///
     /// This method accumulates state about let-bound arrangements, so that
-    /// join operators can more accurately assess their available arrangements.
+    /// join operators can more accurately assess their available arrangements,
+    /// and about the collections that let bindings read, so that join operators
+    /// can use statistics about them.
     pub fn action_recursive(
         &self,
         relation: &mut MirRelationExpr,
         indexes: &mut IndexMap,
+        stats: &mut StatisticsMap,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Let { id, value, body } = relation {
-            self.action_recursive(value, indexes)?;
+            self.action_recursive(value, indexes, stats)?;
             match &**value {
                 MirRelationExpr::ArrangeBy { keys, .. } => {
                     for key in keys {
@@ -100,13 +109,15 @@ impl JoinImplementation {
                 }
                 _ => {}
             }
-            self.action_recursive(body, indexes)?;
+            stats.add_local(*id, value);
+            self.action_recursive(body, indexes, stats)?;
             indexes.remove_local(*id);
+            stats.remove_local(*id);
             Ok(())
         } else {
             let (mfp, mfp_input) = MapFilterProject::extract_non_errors_from_expr_ref_mut(relation);
-            mfp_input.try_visit_mut_children(|e| self.action_recursive(e, indexes))?;
-            self.action(mfp_input, mfp, indexes)?;
+            mfp_input.try_visit_mut_children(|e| self.action_recursive(e, indexes, stats))?;
+            self.action(mfp_input, mfp, indexes, stats)?;
             Ok(())
         }
     }
@@ -117,11 +128,13 @@ impl JoinImplementation {
         relation: &mut MirRelationExpr,
         mfp_above: MapFilterProject,
         indexes: &IndexMap,
+        stats: &StatisticsMap,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Join {
             inputs,
             equivalences,
             implementation,
+            hints,
         } = relation
         {
             let inputs_len = inputs.len();
@@ -162,6 +175,32 @@ impl JoinImplementation {
                     .map(|typ| typ.keys)
                     .collect::<Vec<_>>();
                 let mut available_arrangements = vec![Vec::new(); inputs.len()];
+
+                // A hinted index must be available, e.g., exist on the cluster the
+                // join runs on, rather than be silently replaced by other arrangements.
+                for index in hints.indexes.iter() {
+                    if !indexes
+                        .get(Id::Global(index.on_id))
+                        .any(|key| key == index.key.as_slice())
+                    {
+                        return Err(TransformError::InvalidHint(format!(
+                            "JOIN INDEX refers to {}, which is not available in the cluster",
+                            index.name.quoted()
+                        )));
+                    }
+                }
+
+                // The keys of the indexes on `id` that the join may use. If the hints name
+                // indexes on a global collection, they are the only ones we consider for it.
+                let hinted_indexes = |id: Id| -> Vec<Vec<MirScalarExpr>> {
+                    let mut keys = indexes.get(id).map(|key| key.to_vec()).collect::<Vec<_>>();
+                    if let Id::Global(id) = id {
+                        if let Some(hinted) = hints.index_keys(id) {
+                            keys.retain(|key| hinted.contains(&key.as_slice()));
+                        }
+                    }
+                    keys
+                };
                 let mut filters = Vec::new();
 
                 // We figure out what predicates from mfp_above could be pushed to which input.
@@ -232,15 +271,13 @@ impl JoinImplementation {
                     // Collect available arrangements on this input.
                     match input {
                         MirRelationExpr::Get { id, typ: _ } => {
-                            available_arrangements[index]
-                                .extend(indexes.get(*id).map(|key| key.to_vec()));
+                            available_arrangements[index].extend(hinted_indexes(*id));
                         }
                         MirRelationExpr::ArrangeBy { input, keys } => {
                             // We may use any presented arrangement keys.
                             available_arrangements[index].extend(keys.clone());
                             if let MirRelationExpr::Get { id, typ: _ } = &**input {
-                                available_arrangements[index]
-                                    .extend(indexes.get(*id).map(|key| key.to_vec()));
+                                available_arrangements[index].extend(hinted_indexes(*id));
                             }
                         }
                         MirRelationExpr::Reduce { group_key, .. } => {
@@ -293,15 +330,48 @@ impl JoinImplementation {
                     });
                 }
 
+                // Gather statistics about the inputs, but only if there are statistics about
+                // all of them: otherwise inputs without statistics would compare as the worst
+                // candidates, no matter their actual sizes.
+                let statistics = inputs
+                    .iter()
+                    .map(|input| stats.get(input))
+                    .collect::<Option<Vec<_>>>();
+
+                // Translate the hinted join order into input positions. A hinted
+                // collection that several inputs read, e.g., in a self-join, does not
+                // identify an input, so we reject it rather than guess.
+                let mut order_hint = Vec::new();
+                for id in hints.order.iter() {
+                    let mut positions = (0..inputs_len)
+                        .filter(|position| stats.collection_id(&inputs[*position]) == Some(*id));
+                    if let Some(position) = positions.next() {
+                        if positions.next().is_some() {
+                            return Err(TransformError::InvalidHint(format!(
+                                "JOIN ORDER refers to a relation that the join reads more than \
+                                 once: {}",
+                                id
+                            )));
+                        }
+                        order_hint.push(position);
+                    }
+                }
+                let algorithm = hints.algorithm;
+
                 // Determine if we can perform delta queries with the existing arrangements.
                 // We could defer the execution if we are sure we know we want one input,
                 // but we could imagine wanting the best from each and then comparing the two.
+                // If the hints ask for a delta query, we are willing to create any missing
+                // arrangements for it.
                 let delta_query_plan = delta_queries::plan(
                     relation,
                     &input_mapper,
                     &available_arrangements,
                     &unique_keys,
                     &filters,
+                    statistics.as_deref(),
+                    &order_hint,
+                    algorithm == Some(JoinAlgorithm::Delta),
                 );
                 let differential_plan = differential::plan(
                     relation,
@@ -309,13 +379,17 @@ impl JoinImplementation {
                     &available_arrangements,
                     &unique_keys,
                     &filters,
+                    statistics.as_deref(),
+                    &order_hint,
                 );
 
-                // Employ delta join plans only for multi-way joins of at least three inputs.
-                *relation = if inputs_len > 2 {
-                    delta_query_plan.or(differential_plan)
-                } else {
-                    differential_plan
+                // Employ delta join plans only for multi-way joins of at least three inputs,
+                // unless the hints say otherwise.
+                *relation = match algorithm {
+                    Some(JoinAlgorithm::Delta) => delta_query_plan.or(differential_plan),
+                    Some(JoinAlgorithm::Differential) => differential_plan,
+                    None if inputs_len > 2 => delta_query_plan.or(differential_plan),
+                    None => differential_plan,
                 }
                 .expect("Failed to produce a join plan");
             }
@@ -375,12 +449,158 @@ mod index_map {
     }
 }
 
+mod statistics_map {
+    use std::collections::BTreeMap;
+
+    use mz_expr::{Id, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr};
+    use mz_repr::GlobalId;
+
+    use crate::StatisticsOracle;
+
+    /// Keeps track of the global collections read by local bindings while
+    /// descending a `MirRelationExpr`, so that statistics about those
+    /// collections can inform the ordering of joins.
+    #[derive(Debug)]
+    pub struct StatisticsMap<'a> {
+        local: BTreeMap<LocalId, BaseCollection>,
+        global: &'a dyn StatisticsOracle,
+    }
+
+    /// A global collection read by some expression, along with, for each
+    /// column of the expression, the column of the collection it projects, if
+    /// any.
+    #[derive(Clone, Debug)]
+    struct BaseCollection {
+        id: GlobalId,
+        columns: Vec<Option<usize>>,
+    }
+
+    impl<'a> StatisticsMap<'a> {
+        /// Creates a new statistics map with knowledge of the provided global
+        /// statistics.
+        pub fn new(global: &'a dyn StatisticsOracle) -> StatisticsMap<'a> {
+            StatisticsMap {
+                local: BTreeMap::new(),
+                global,
+            }
+        }
+
+        /// Records the collection read by `value`, if any, as the collection
+        /// read by the local binding `id`.
+        pub fn add_local(&mut self, id: LocalId, value: &MirRelationExpr) {
+            if let Some(base) = self.resolve(value) {
+                self.local.insert(id, base);
+            }
+        }
+
+        /// Forgets the collection read by the specified local binding.
+        pub fn remove_local(&mut self, id: LocalId) {
+            self.local.remove(&id);
+        }
+
+        /// Returns the ID of the global collection that `input` reads, if any.
+        pub fn collection_id(&self, input: &MirRelationExpr) -> Option<GlobalId> {
+            self.resolve(input).map(|base| base.id)
+        }
+
+        /// Returns statistics about the join input `input`, if it reads a
+        /// global collection whose cardinality can be estimated.
+        pub fn get(&self, input: &MirRelationExpr) -> Option<InputStatistics<'a>> {
+            let BaseCollection { id, columns } = self.resolve(input)?;
+            let cardinality = self.global.cardinality_estimate(id)?;
+            Some(InputStatistics {
+                id,
+                columns,
+                cardinality,
+                oracle: self.global,
+            })
+        }
+
+        /// Looks through maps, filters, projections and arrangements to find the
+        /// collection that `expr` reads.
+        fn resolve(&self, expr: &MirRelationExpr) -> Option<BaseCollection> {
+            let (mfp, input) = MapFilterProject::extract_non_errors_from_expr(expr);
+            let base = match input {
+                MirRelationExpr::Get {
+                    id: Id::Global(id),
+                    typ,
+                } => BaseCollection {
+                    id: *id,
+                    columns: (0..typ.arity()).map(Some).collect(),
+                },
+                MirRelationExpr::Get {
+                    id: Id::Local(id), ..
+                } => self.local.get(id)?.clone(),
+                MirRelationExpr::ArrangeBy { input, .. } => self.resolve(input)?,
+                _ => return None,
+            };
+            let (_, _, project) = mfp.as_map_filter_project();
+            Some(BaseCollection {
+                id: base.id,
+                // Columns past the input's arity are the results of maps.
+                columns: project
+                    .into_iter()
+                    .map(|c| base.columns.get(c).copied().flatten())
+                    .collect(),
+            })
+        }
+    }
+
+    /// Statistics about a join input that reads a global collection.
+    #[derive(Clone, Debug)]
+    pub struct InputStatistics<'a> {
+        id: GlobalId,
+        columns: Vec<Option<usize>>,
+        /// An upper bound on the number of records in the input.
+        cardinality: usize,
+        oracle: &'a dyn StatisticsOracle,
+    }
+
+    impl InputStatistics<'_> {
+        /// Estimates how many records of the input each record that is joined
+        /// with it on `key` matches.
+        ///
+        /// This is the cardinality of the input divided by the number of distinct
+        /// values of `key`. Key expressions that are not plain columns are ignored,
+        /// and if the number of distinct values is unknown we assume that every
+        /// record matches, both of which can only overestimate the result.
+        ///
+        /// The number of distinct values reported by the oracle may itself be an
+        /// overestimate, e.g., when it is derived from the bounds of the columns of
+        /// `key` rather than from their values. In that case the result is an
+        /// underestimate for inputs whose keys are sparse or skewed, so it is only
+        /// a heuristic for ordering joins, not a bound.
+        pub fn fanout(&self, key: &[MirScalarExpr], is_unique: bool) -> usize {
+            if is_unique {
+                return std::cmp::min(1, self.cardinality);
+            }
+            let mut columns = key
+                .iter()
+                .filter_map(|k| k.as_column())
+                .filter_map(|c| self.columns.get(c).copied().flatten())
+                .collect::<Vec<_>>();
+            columns.sort();
+            columns.dedup();
+            let distinct = if columns.is_empty() {
+                1
+            } else {
+                self.oracle
+                    .distinct_estimate(self.id, &columns)
+                    .unwrap_or(1)
+                    .clamp(1, std::cmp::max(1, self.cardinality))
+            };
+            (self.cardinality + distinct - 1) / distinct
+        }
+    }
+}
+
 mod delta_queries {
 
     use mz_expr::{
         FilterCharacteristics, JoinImplementation, JoinInputMapper, MirRelationExpr, MirScalarExpr,
     };
 
+    use crate::join_implementation::InputStatistics;
     use crate::TransformError;
 
     /// Creates a delta query plan, and any predicates that need to be lifted.
@@ -393,6 +613,9 @@ mod delta_queries {
         available: &[Vec<Vec<MirScalarExpr>>],
         unique_keys: &[Vec<Vec<usize>>],
         filters: &[FilterCharacteristics],
+        statistics: Option<&[InputStatistics]>,
+        order_hint: &[usize],
+        create_arrangements: bool,
     ) -> Result<MirRelationExpr, TransformError> {
         let mut new_join = join.clone();
 
@@ -400,6 +623,7 @@ mod delta_queries {
             inputs,
             equivalences,
             implementation,
+            ..
         } = &mut new_join
         {
             if inputs.len() < 2 {
@@ -413,9 +637,32 @@ mod delta_queries {
                 )));
             }
 
+            // If asked to, consider arranging each input by any expression that it is
+            // equated with, so that every input can be looked up.
+            let mut arrangeable = available.to_vec();
+            if create_arrangements {
+                for equivalence in equivalences.iter() {
+                    for expr in equivalence.iter() {
+                        if let Some(input) = input_mapper.single_input(expr) {
+                            let key = vec![input_mapper.map_expr_to_local(expr.clone())];
+                            if !arrangeable[input].contains(&key) {
+                                arrangeable[input].push(key);
+                            }
+                        }
+                    }
+                }
+            }
+
             // Determine a viable order for each relation, or return `Err` if none found.
-            let orders =
-                super::optimize_orders(equivalences, available, unique_keys, filters, input_mapper);
+            let orders = super::optimize_orders(
+                equivalences,
+                &arrangeable,
+                unique_keys,
+                filters,
+                statistics,
+                input_mapper,
+                order_hint,
+            );
 
             // A viable delta query requires that, for every order,
             // there is an arrangement for every input except for
@@ -459,7 +706,9 @@ mod delta_queries {
 }
 
 mod differential {
-    use crate::join_implementation::{FilterCharacteristics, JoinInputCharacteristics};
+    use crate::join_implementation::{
+        FilterCharacteristics, InputStatistics, JoinInputCharacteristics,
+    };
     use itertools::Itertools;
     use mz_expr::{JoinImplementation, JoinInputMapper, MirRelationExpr, MirScalarExpr};
     use mz_ore::soft_assert;
@@ -473,6 +722,8 @@ mod differential {
         available: &[Vec<Vec<MirScalarExpr>>],
         unique_keys: &[Vec<Vec<usize>>],
         filters: &[FilterCharacteristics],
+        statistics: Option<&[InputStatistics]>,
+        order_hint: &[usize],
     ) -> Result<MirRelationExpr, TransformError> {
         let mut new_join = join.clone();
 
@@ -480,6 +731,7 @@ mod differential {
             inputs,
             equivalences,
             implementation,
+            ..
         } = &mut new_join
         {
             // We prefer a starting point based on the characteristics of the other input arrangements.
@@ -487,8 +739,19 @@ mod differential {
             // Important, we should choose something stable under re-ordering, to converge under fixed
             // point iteration; we choose to start with the first input optimizing our criteria, which
             // should remain stable even when promoted to the first position.
-            let mut orders =
-                super::optimize_orders(equivalences, available, unique_keys, filters, input_mapper);
+            let mut orders = super::optimize_orders(
+                equivalences,
+                available,
+                unique_keys,
+                filters,
+                statistics,
+                input_mapper,
+                order_hint,
+            );
+            // A hinted order also determines the starting input.
+            if let Some(start) = order_hint.first() {
+                orders.retain(|order| order[0].2 == *start);
+            }
 
             // Inside each order, we take the `FilterCharacteristics` from each element, and OR it
             // to every other element to the right. This is because we are gonna be looking for the
@@ -736,11 +999,20 @@ fn optimize_orders(
     available: &[Vec<Vec<MirScalarExpr>>],
     unique_keys: &[Vec<Vec<usize>>],
     filters: &[FilterCharacteristics],
+    statistics: Option<&[InputStatistics]>,
     input_mapper: &JoinInputMapper,
+    order_hint: &[usize],
 ) -> Vec<Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>> {
-    let mut orderer = Orderer::new(equivalences, available, unique_keys, filters, input_mapper);
+    let mut orderer = Orderer::new(
+        equivalences,
+        available,
+        unique_keys,
+        filters,
+        statistics,
+        input_mapper,
+    );
     (0..available.len())
-        .map(move |i| orderer.optimize_order_for(i))
+        .map(move |i| orderer.optimize_order_for(i, order_hint))
         .collect::<Vec<_>>()
 }
 
@@ -750,6 +1022,7 @@ struct Orderer<'a> {
     arrangements: &'a [Vec<Vec<MirScalarExpr>>],
     unique_keys: &'a [Vec<Vec<usize>>],
     filters: &'a [FilterCharacteristics],
+    statistics: Option<&'a [InputStatistics<'a>]>,
     input_mapper: &'a JoinInputMapper,
     reverse_equivalences: Vec<Vec<(usize, usize)>>,
     unique_arrangement: Vec<Vec<bool>>,
@@ -769,6 +1042,7 @@ impl<'a> Orderer<'a> {
         arrangements: &'a [Vec<Vec<MirScalarExpr>>],
         unique_keys: &'a [Vec<Vec<usize>>],
         filters: &'a [FilterCharacteristics],
+        statistics: Option<&'a [InputStatistics<'a>]>,
         input_mapper: &'a JoinInputMapper,
     ) -> Self {
         let inputs = arrangements.len();
@@ -804,6 +1078,7 @@ impl<'a> Orderer<'a> {
             arrangements,
             unique_keys,
             filters,
+            statistics,
             input_mapper,
             reverse_equivalences,
             unique_arrangement,
@@ -816,9 +1091,13 @@ impl<'a> Orderer<'a> {
         }
     }
 
+    /// Orders the inputs starting from `start`, followed by the inputs in
+    /// `order_hint`, in that order, and then by the remaining inputs in
+    /// decreasing priority.
     fn optimize_order_for(
         &mut self,
         start: usize,
+        order_hint: &[usize],
     ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
         self.order.clear();
         self.priority_queue.clear();
@@ -842,6 +1121,7 @@ impl<'a> Orderer<'a> {
                 self.priority_queue.push((
                     JoinInputCharacteristics::new(
                         is_unique,
+                        self.fanout(input, &[], is_unique),
                         0,
                         true,
                         self.filters[input].clone(),
@@ -854,6 +1134,7 @@ impl<'a> Orderer<'a> {
                 self.priority_queue.push((
                     JoinInputCharacteristics::new(
                         is_unique,
+                        self.fanout(input, &[], is_unique),
                         0,
                         false,
                         self.filters[input].clone(),
@@ -867,6 +1148,23 @@ impl<'a> Orderer<'a> {
 
         if self.inputs > 1 {
             self.order_input(start);
+            for input in order_hint.iter().copied() {
+                if !self.placed[input] {
+                    // Take the best candidate for `input`, and put back the others. There
+                    // is always at least one candidate for an input that is not placed yet,
+                    // as every input can be cross joined.
+                    let mut others = Vec::new();
+                    while let Some((characteristics, key, candidate)) = self.priority_queue.pop() {
+                        if candidate == input {
+                            self.order.push((characteristics, key, input));
+                            self.order_input(input);
+                            break;
+                        }
+                        others.push((characteristics, key, candidate));
+                    }
+                    self.priority_queue.extend(others);
+                }
+            }
             while self.order.len() < self.inputs - 1 {
                 let (characteristics, key, input) = self.priority_queue.pop().unwrap();
                 // put the tuple into `self.order` unless the tuple with the same
@@ -884,7 +1182,14 @@ impl<'a> Orderer<'a> {
         // calculate characteristics of an arrangement, if any on the starting input
         // by default, there is no arrangement on the starting input
         let mut start_tuple = (
-            JoinInputCharacteristics::new(false, 0, false, self.filters[start].clone(), start),
+            JoinInputCharacteristics::new(
+                false,
+                self.fanout(start, &[], false),
+                0,
+                false,
+                self.filters[start].clone(),
+                start,
+            ),
             vec![],
             start,
         );
@@ -911,6 +1216,7 @@ impl<'a> Orderer<'a> {
                     start_tuple = (
                         JoinInputCharacteristics::new(
                             is_unique,
+                            self.fanout(start, &candidate_start_key, is_unique),
                             candidate_start_key.len(),
                             true,
                             self.filters[start].clone(),
@@ -927,6 +1233,14 @@ impl<'a> Orderer<'a> {
         std::mem::replace(&mut self.order, Vec::new())
     }
 
+    /// Estimates how many records of `input` each record of the inputs placed
+    /// before it matches, when joined on `key`, if there are statistics about
+    /// the inputs.
+    fn fanout(&self, input: usize, key: &[MirScalarExpr], is_unique: bool) -> Option<usize> {
+        self.statistics
+            .map(|statistics| statistics[input].fanout(key, is_unique))
+    }
+
     /// Introduces a specific input and keys to the order, along with its characteristics.
     ///
     /// This method places a next element in the order, and updates the associated state
@@ -982,9 +1296,11 @@ impl<'a> Orderer<'a> {
                                             self.arrangement_active[rel].push(pos);
                                             // TODO: This could be pre-computed, as it is independent of the order.
                                             let is_unique = self.unique_arrangement[rel][pos];
+                                            let fanout = self.fanout(rel, keys, is_unique);
                                             self.priority_queue.push((
                                                 JoinInputCharacteristics::new(
                                                     is_unique,
+                                                    fanout,
                                                     keys.len(),
                                                     true,
                                                     self.filters[rel].clone(),
@@ -1004,6 +1320,7 @@ impl<'a> Orderer<'a> {
                                 self.priority_queue.push((
                                     JoinInputCharacteristics::new(
                                         is_unique,
+                                        self.fanout(rel, &self.bound[rel], is_unique),
                                         self.bound[rel].len(),
                                         false,
                                         self.filters[rel].clone(),
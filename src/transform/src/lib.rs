@@ -119,6 +119,7 @@ pub mod projection_extraction;
 pub mod projection_lifting;
 pub mod projection_pushdown;
 pub mod reduce_elision;
+pub mod reduce_group_size;
 pub mod reduction_pushdown;
 pub mod redundant_join;
 pub mod semijoin_idempotence;
@@ -138,6 +139,8 @@ extern crate num_derive;
 pub struct TransformArgs<'a> {
     /// The indexes accessible.
     pub indexes: &'a dyn IndexOracle,
+    /// Statistics about the collections accessible.
+    pub stats: &'a dyn StatisticsOracle,
 }
 
 /// Types capable of transforming relation expressions.
@@ -172,6 +175,8 @@ pub enum TransformError {
     LetRecUnsupported,
     /// A reference to an apparently unbound identifier.
     IdentifierMissing(mz_expr::LocalId),
+    /// A query hint that cannot be honored.
+    InvalidHint(String),
 }
 
 impl fmt::Display for TransformError {
@@ -182,6 +187,7 @@ impl fmt::Display for TransformError {
             TransformError::IdentifierMissing(i) => {
                 write!(f, "apparently unbound identifier: {:?}", i)
             }
+            TransformError::InvalidHint(msg) => write!(f, "invalid query hint: {}", msg),
         }
     }
 }
@@ -220,6 +226,44 @@ impl IndexOracle for EmptyIndexOracle {
     }
 }
 
+/// A trait for a type that can estimate the sizes of collections.
+pub trait StatisticsOracle: fmt::Debug {
+    /// Returns an estimate of the number of records in the identified
+    /// collection, or `None` if no estimate is available.
+    fn cardinality_estimate(&self, id: GlobalId) -> Option<usize>;
+
+    /// Returns an estimate of the number of distinct values of the columns
+    /// `key` in the identified collection, or `None` if no estimate is
+    /// available.
+    ///
+    /// The estimate may exceed the actual number of distinct values, so
+    /// estimates derived from it are heuristics rather than bounds.
+    fn distinct_estimate(&self, _id: GlobalId, _key: &[usize]) -> Option<usize> {
+        None
+    }
+
+    /// Reports whether the plans optimized with these estimates are one-shot,
+    /// i.e., discarded before the estimates become stale.
+    ///
+    /// Transforms that tune how a plan is rendered to the estimates, rather
+    /// than just choosing between equivalent plans, only do so for one-shot
+    /// plans: the plans of indexes and materialized views are maintained
+    /// indefinitely, and would keep their tuning as their inputs change.
+    fn one_shot(&self) -> bool {
+        false
+    }
+}
+
+/// A [`StatisticsOracle`] that knows nothing about any collection.
+#[derive(Debug)]
+pub struct EmptyStatisticsOracle;
+
+impl StatisticsOracle for EmptyStatisticsOracle {
+    fn cardinality_estimate(&self, _: GlobalId) -> Option<usize> {
+        None
+    }
+}
+
 /// A sequence of transformations iterated some number of times.
 #[derive(Debug)]
 pub struct Fixpoint {
@@ -271,6 +315,7 @@ impl Transform for Fixpoint {
                                 relation,
                                 TransformArgs {
                                     indexes: args.indexes,
+                                    stats: args.stats,
                                 },
                             )?;
                         }
@@ -296,6 +341,7 @@ impl Transform for Fixpoint {
                     relation,
                     TransformArgs {
                         indexes: args.indexes,
+                        stats: args.stats,
                     },
                 )?;
             }
@@ -372,6 +418,7 @@ impl Transform for FuseAndCollapse {
                 relation,
                 TransformArgs {
                     indexes: args.indexes,
+                    stats: args.stats,
                 },
             )?;
         }
@@ -483,6 +530,9 @@ impl Optimizer {
                     Box::new(crate::literal_lifting::LiteralLifting::default()),
                 ],
             }),
+            // Estimates the group sizes of reductions from statistics, now
+            // that the inputs of reductions have settled.
+            Box::new(crate::reduce_group_size::ReduceGroupSizeEstimation::default()),
             Box::new(crate::canonicalize_mfp::CanonicalizeMfp),
             // Identifies common relation subexpressions.
             Box::new(crate::cse::relation_cse::RelationCSE::new(false)),
@@ -549,7 +599,8 @@ impl Optimizer {
         &self,
         mut relation: MirRelationExpr,
     ) -> Result<mz_expr::OptimizedMirRelationExpr, TransformError> {
-        let transform_result = self.transform(&mut relation, &EmptyIndexOracle);
+        let transform_result =
+            self.transform(&mut relation, &EmptyIndexOracle, &EmptyStatisticsOracle);
         match transform_result {
             Ok(_) => {
                 mz_repr::explain::trace_plan(&relation);
@@ -566,7 +617,8 @@ impl Optimizer {
         }
     }
 
-    /// Optimizes the supplied relation expression in place, using available arrangements.
+    /// Optimizes the supplied relation expression in place, using available arrangements
+    /// and collection statistics.
     ///
     /// This method should only be called with non-empty `indexes` when optimizing a dataflow,
     /// as the optimizations may lock in the use of arrangements that may cease to exist.
@@ -574,11 +626,12 @@ impl Optimizer {
         &self,
         relation: &mut MirRelationExpr,
         indexes: &dyn IndexOracle,
+        stats: &dyn StatisticsOracle,
     ) -> Result<(), TransformError> {
         let recursive = relation.is_recursive();
         for transform in self.transforms.iter() {
             if transform.recursion_safe() || !recursive {
-                transform.transform(relation, TransformArgs { indexes })?;
+                transform.transform(relation, TransformArgs { indexes, stats })?;
             }
         }
 
@@ -414,6 +414,7 @@ impl LiteralLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    hints: _,
                 } => {
                     if !matches!(implementation, IndexedFilter(..)) {
                         // before lifting, save the original shape of the inputs
@@ -615,6 +616,7 @@ impl LiteralLifting {
                     limit: _,
                     offset: _,
                     monotonic: _,
+                    expected_group_size: _,
                 } => {
                     let literals = self.action(input, gets)?;
                     if !literals.is_empty() {
@@ -176,6 +176,7 @@ impl ProjectionLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    hints: _,
                 } => {
                     for input in inputs.iter_mut() {
                         self.action(input, gets)?;
@@ -245,6 +246,7 @@ impl ProjectionLifting {
                     limit,
                     offset,
                     monotonic: _,
+                    expected_group_size,
                 } => {
                     self.action(input, gets)?;
                     if let MirRelationExpr::Project {
@@ -265,6 +267,7 @@ impl ProjectionLifting {
                                 order_key.clone(),
                                 limit.clone(),
                                 offset.clone(),
+                                expected_group_size.clone(),
                             )
                             .project(outputs.clone());
                     }
@@ -0,0 +1,121 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Estimates the expected group size of `Reduce` operators from statistics.
+//!
+//! Hierarchical aggregations (`MIN` and `MAX`) are rendered as a tower of
+//! reductions over progressively fewer buckets, whose height is chosen from
+//! the number of records expected in each group. Without an
+//! `EXPECTED GROUP SIZE` hint, rendering has to plan for very large groups,
+//! which wastes memory on groups that are small. This transform fills in the
+//! expected group size of `Reduce` operators that lack a hint, using the
+//! cardinality of the collection they read and the number of distinct values
+//! of their grouping key.
+//!
+//! Estimates are only applied to one-shot plans (see
+//! [`StatisticsOracle::one_shot`]). An estimate that is too small leaves
+//! groups that outgrow it with too few layers, so the plans of indexes and
+//! materialized views, which outlive the statistics they were planned with,
+//! keep planning for large groups unless they are hinted otherwise.
+//!
+//! [`StatisticsOracle::one_shot`]: crate::StatisticsOracle::one_shot
+
+use mz_expr::visit::VisitChildren;
+use mz_expr::{MirRelationExpr, RECURSION_LIMIT};
+use mz_ore::stack::{CheckedRecursion, RecursionGuard};
+
+use crate::join_implementation::StatisticsMap;
+use crate::{TransformArgs, TransformError};
+
+/// Estimates the expected group size of `Reduce` operators from statistics.
+#[derive(Debug)]
+pub struct ReduceGroupSizeEstimation {
+    recursion_guard: RecursionGuard,
+}
+
+impl Default for ReduceGroupSizeEstimation {
+    /// Construct a new [`ReduceGroupSizeEstimation`] where `recursion_guard`
+    /// is initialized with [`RECURSION_LIMIT`] as limit.
+    fn default() -> ReduceGroupSizeEstimation {
+        ReduceGroupSizeEstimation {
+            recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
+        }
+    }
+}
+
+impl CheckedRecursion for ReduceGroupSizeEstimation {
+    fn recursion_guard(&self) -> &RecursionGuard {
+        &self.recursion_guard
+    }
+}
+
+impl crate::Transform for ReduceGroupSizeEstimation {
+    fn recursion_safe(&self) -> bool {
+        true
+    }
+
+    #[tracing::instrument(
+        target = "optimizer"
+        level = "trace",
+        skip_all,
+        fields(path.segment = "reduce_group_size_estimation")
+    )]
+    fn transform(
+        &self,
+        relation: &mut MirRelationExpr,
+        args: TransformArgs,
+    ) -> Result<(), TransformError> {
+        let result = if args.stats.one_shot() {
+            self.action(relation, &mut StatisticsMap::new(args.stats))
+        } else {
+            Ok(())
+        };
+        mz_repr::explain::trace_plan(&*relation);
+        result
+    }
+}
+
+impl ReduceGroupSizeEstimation {
+    /// Sets the expected group size of each `Reduce` in `relation` that has
+    /// aggregates but no expected group size, if its input reads a collection
+    /// with known statistics.
+    pub fn action(
+        &self,
+        relation: &mut MirRelationExpr,
+        stats: &mut StatisticsMap,
+    ) -> Result<(), TransformError> {
+        self.checked_recur(|_| {
+            if let MirRelationExpr::Let { id, value, body } = relation {
+                self.action(value, stats)?;
+                stats.add_local(*id, value);
+                self.action(body, stats)?;
+                stats.remove_local(*id);
+                return Ok(());
+            }
+
+            relation.try_visit_mut_children(|e| self.action(e, stats))?;
+
+            if let MirRelationExpr::Reduce {
+                input,
+                group_key,
+                aggregates,
+                monotonic: _,
+                expected_group_size,
+            } = relation
+            {
+                if expected_group_size.is_none() && !aggregates.is_empty() {
+                    if let Some(input_stats) = stats.get(input) {
+                        *expected_group_size = Some(input_stats.fanout(group_key, false));
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
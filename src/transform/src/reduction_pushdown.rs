@@ -52,7 +52,7 @@ use std::iter::FromIterator;
 
 use crate::TransformArgs;
 use mz_expr::visit::Visit;
-use mz_expr::{AggregateExpr, JoinInputMapper, MirRelationExpr, MirScalarExpr};
+use mz_expr::{AggregateExpr, JoinHints, JoinInputMapper, MirRelationExpr, MirScalarExpr};
 
 /// Pushes Reduce operators toward sources.
 #[derive(Debug)]
@@ -139,11 +139,13 @@ impl ReductionPushdown {
                 inputs,
                 equivalences,
                 implementation: _,
+                hints,
             } = &mut **input
             {
                 if let Some(new_relation_expr) = try_push_reduce_through_join(
                     inputs,
                     equivalences,
+                    hints,
                     group_key,
                     aggregates,
                     *monotonic,
@@ -160,6 +162,7 @@ impl ReductionPushdown {
 fn try_push_reduce_through_join(
     inputs: &Vec<MirRelationExpr>,
     equivalences: &Vec<Vec<MirScalarExpr>>,
+    hints: &JoinHints,
     group_key: &Vec<MirScalarExpr>,
     aggregates: &Vec<AggregateExpr>,
     monotonic: bool,
@@ -337,7 +340,11 @@ fn try_push_reduce_through_join(
         .map(|(idx, col)| new_join_mapper.map_column_to_global(col, idx))
         .collect::<Vec<_>>();
 
-    Some(MirRelationExpr::join_scalars(new_inputs, new_equivalences).project(new_projection))
+    Some(
+        MirRelationExpr::join_scalars(new_inputs, new_equivalences)
+            .with_join_hints(hints.clone())
+            .project(new_projection),
+    )
 }
 
 /// Returns None if `expr` does not belong to exactly one component.
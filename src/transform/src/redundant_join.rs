@@ -115,6 +115,7 @@ impl RedundantJoin {
                     inputs,
                     equivalences,
                     implementation,
+                    hints: _,
                 } => {
                     // This logic first applies what it has learned about its input provenance,
                     // and if it finds a redundant join input it removes it. In that case, it
@@ -99,7 +99,7 @@ mod tests {
     use mz_repr::explain::{Explain, ExplainConfig, ExplainFormat, UsedIndexes};
     use mz_repr::GlobalId;
     use mz_transform::dataflow::{optimize_dataflow_demand_inner, optimize_dataflow_filters_inner};
-    use mz_transform::{EmptyIndexOracle, Optimizer, Transform, TransformArgs};
+    use mz_transform::{EmptyIndexOracle, Optimizer, StatisticsOracle, Transform, TransformArgs};
     use proc_macro2::TokenTree;
 
     use super::explain::Explainable;
@@ -107,6 +107,8 @@ mod tests {
     // Global options
     const IN: &str = "in";
     const FORMAT: &str = "format";
+    const CARDINALITY: &str = "cardinality";
+    const DURABLE: &str = "durable";
     // Values that can be supplied for global options
     const JSON: &str = "json";
     const TEST: &str = "test";
@@ -156,6 +158,44 @@ mod tests {
         }
     }
 
+    /// A [StatisticsOracle] that knows the cardinalities of sources.
+    #[derive(Debug, Default)]
+    struct TestStatisticsOracle {
+        cardinalities: BTreeMap<GlobalId, usize>,
+        durable: bool,
+    }
+
+    impl StatisticsOracle for TestStatisticsOracle {
+        fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
+            self.cardinalities.get(&id).copied()
+        }
+
+        fn one_shot(&self) -> bool {
+            !self.durable
+        }
+    }
+
+    /// Parses the cardinalities of sources from `args[cardinality]`, which
+    /// lists them as `<source>:<cardinality>`. The statistics are for a
+    /// one-shot plan unless `args[durable]` is given.
+    fn get_statistics(
+        cat: &TestCatalog,
+        args: &HashMap<String, Vec<String>>,
+    ) -> Result<TestStatisticsOracle, Error> {
+        let mut stats = TestStatisticsOracle::default();
+        for spec in args.get(CARDINALITY).into_iter().flatten() {
+            let (name, cardinality) = spec
+                .split_once(':')
+                .ok_or_else(|| anyhow!("invalid cardinality: {}", spec))?;
+            let id = cat
+                .get_source_id(name)
+                .ok_or_else(|| anyhow!("unknown source: {}", name))?;
+            stats.cardinalities.insert(id, cardinality.parse()?);
+        }
+        stats.durable = args.contains_key(DURABLE);
+        Ok(stats)
+    }
+
     // Converts string to MirRelationExpr. `args[in]` specifies which input
     // format is being used.
     fn parse_relation(
@@ -225,11 +265,13 @@ mod tests {
         test_type: TestType,
     ) -> Result<String, Error> {
         let mut rel = parse_relation(s, cat, args)?;
+        let stats = get_statistics(cat, args)?;
         for t in args.get("apply").cloned().unwrap_or_else(Vec::new).iter() {
             get_transform(t)?.transform(
                 &mut rel,
                 TransformArgs {
                     indexes: &EmptyIndexOracle,
+                    stats: &stats,
                 },
             )?;
         }
@@ -243,6 +285,7 @@ mod tests {
                         &mut rel,
                         TransformArgs {
                             indexes: &EmptyIndexOracle,
+                            stats: &stats,
                         },
                     )?;
                 }
@@ -266,6 +309,7 @@ mod tests {
                             &mut rel,
                             TransformArgs {
                                 indexes: &EmptyIndexOracle,
+                                stats: &stats,
                             },
                         )?;
 
@@ -358,6 +402,9 @@ mod tests {
                 mz_transform::reduction_pushdown::ReductionPushdown,
             )),
             "ReduceElision" => Ok(Box::new(mz_transform::reduce_elision::ReduceElision)),
+            "ReduceGroupSizeEstimation" => Ok(Box::new(
+                mz_transform::reduce_group_size::ReduceGroupSizeEstimation::default(),
+            )),
             "RedundantJoin" => Ok(Box::new(
                 mz_transform::redundant_join::RedundantJoin::default(),
             )),